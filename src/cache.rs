@@ -0,0 +1,381 @@
+use crate::languages::{Language, LanguageConfig};
+use crate::library::Library;
+use std::env;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::UNIX_EPOCH;
+
+const CACHE_DIRECTORY_NAME: &str = "daipendency";
+const UNVERSIONED_ENTRY_NAME: &str = "unversioned";
+/// Directories that never contain the sources of the library itself.
+const IGNORED_DIRECTORY_NAMES: [&str; 2] = ["node_modules", "target"];
+const FNV_OFFSET_BASIS: u64 = 0xcbf29ce484222325;
+const FNV_PRIME: u64 = 0x100000001b3;
+
+/// An on-disk cache of extracted libraries.
+///
+/// Entries are keyed by the language, name and version of the library and a checksum of the
+/// paths, sizes and modification times of its sources, and they are discarded when Daipendency
+/// itself is upgraded.
+#[derive(Debug, Clone)]
+pub struct Cache {
+    directory: PathBuf,
+}
+
+/// What identifies a library in the cache.
+pub(crate) struct CacheKey<'a> {
+    pub language: Language,
+    pub name: &'a str,
+    pub version: Option<&'a str>,
+    pub checksum: u64,
+}
+
+impl Cache {
+    pub fn new(directory: PathBuf) -> Self {
+        Self { directory }
+    }
+
+    /// Get the cache under `$XDG_CACHE_HOME/daipendency` or `~/.cache/daipendency`.
+    pub fn get_default() -> Option<Self> {
+        let cache_home = env::var_os("XDG_CACHE_HOME")
+            .filter(|path| !path.is_empty())
+            .map(PathBuf::from)
+            .or_else(|| env::var_os("HOME").map(|home| PathBuf::from(home).join(".cache")))?;
+        Some(Self::new(cache_home.join(CACHE_DIRECTORY_NAME)))
+    }
+
+    pub fn get_directory(&self) -> &Path {
+        &self.directory
+    }
+
+    /// Delete every entry in the cache.
+    pub fn clear(&self) -> io::Result<()> {
+        match fs::remove_dir_all(&self.directory) {
+            Err(error) if error.kind() == io::ErrorKind::NotFound => Ok(()),
+            result => result,
+        }
+    }
+
+    pub(crate) fn get(&self, key: &CacheKey) -> Option<Library> {
+        let entry = fs::read_to_string(self.get_entry_path(key)).ok()?;
+        serde_json::from_str(&entry).ok()
+    }
+
+    pub(crate) fn put(&self, key: &CacheKey, library: &Library) -> io::Result<()> {
+        let entry_path = self.get_entry_path(key);
+        let entry_directory = entry_path.parent().expect("Entries are in subdirectories");
+        fs::create_dir_all(entry_directory)?;
+        // Write to a temporary file first so that concurrent readers never see partial entries.
+        let temporary_path = entry_path.with_extension(format!("{}.tmp", std::process::id()));
+        let entry = serde_json::to_vec(library).map_err(io::Error::other)?;
+        fs::write(&temporary_path, entry)?;
+        fs::rename(temporary_path, entry_path)
+    }
+
+    fn get_entry_path(&self, key: &CacheKey) -> PathBuf {
+        self.directory
+            .join(env!("CARGO_PKG_VERSION"))
+            .join(LanguageConfig::get_from_language(key.language).name)
+            .join(sanitise_file_name(key.name))
+            .join(format!(
+                "{}-{:016x}.json",
+                sanitise_file_name(key.version.unwrap_or(UNVERSIONED_ENTRY_NAME)),
+                key.checksum
+            ))
+    }
+}
+
+fn sanitise_file_name(name: &str) -> String {
+    name.replace(['/', '\\', ':'], "_")
+}
+
+/// Compute a checksum of the paths, sizes and modification times of the files in the library at
+/// `path`, including those under its `entry_point` if it lies elsewhere (e.g. the package next to
+/// a Python `.dist-info` directory).
+///
+/// Hidden directories and those holding dependencies or build artefacts are skipped.
+pub(crate) fn compute_checksum(path: &Path, entry_point: &Path) -> io::Result<u64> {
+    let mut roots = vec![path.to_path_buf()];
+    if !entry_point.starts_with(path) {
+        roots.push(get_source_root(path, entry_point));
+    }
+
+    let mut checksum = FNV_OFFSET_BASIS;
+    for root in roots {
+        let mut file_paths = Vec::new();
+        match root.is_dir() {
+            true => collect_file_paths(&root, &mut file_paths)?,
+            false => file_paths.push(root.clone()),
+        }
+        file_paths.sort();
+        for file_path in file_paths {
+            let metadata = fs::metadata(&file_path)?;
+            let modification_time = metadata
+                .modified()?
+                .duration_since(UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_nanos();
+            for bytes in [
+                file_path.to_string_lossy().as_bytes(),
+                &[0],
+                &metadata.len().to_le_bytes(),
+                &modification_time.to_le_bytes(),
+            ] {
+                for byte in bytes {
+                    checksum = (checksum ^ u64::from(*byte)).wrapping_mul(FNV_PRIME);
+                }
+            }
+        }
+    }
+    Ok(checksum)
+}
+
+/// Get the directory holding the sources under `entry_point`, or the entry point itself if it's
+/// a file next to the library at `path`, such as a single-module Python distribution.
+fn get_source_root(path: &Path, entry_point: &Path) -> PathBuf {
+    match entry_point.parent() {
+        Some(parent) if entry_point.is_file() && !path.starts_with(parent) => parent.to_path_buf(),
+        _ => entry_point.to_path_buf(),
+    }
+}
+
+fn collect_file_paths(directory: &Path, file_paths: &mut Vec<PathBuf>) -> io::Result<()> {
+    for entry in fs::read_dir(directory)? {
+        let entry = entry?;
+        let file_name = entry.file_name();
+        let file_name = file_name.to_string_lossy();
+        let file_type = entry.file_type()?;
+        if file_type.is_dir() {
+            if !file_name.starts_with('.') && !IGNORED_DIRECTORY_NAMES.contains(&&*file_name) {
+                collect_file_paths(&entry.path(), file_paths)?;
+            }
+        } else if file_type.is_file() {
+            file_paths.push(entry.path());
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::languages::DISCOVERY_ORDER;
+    use daipendency_extractor::{Namespace, Symbol};
+    use daipendency_testing::tempdir::TempDir;
+
+    const STUB_NAME: &str = "@scope/lib";
+    const STUB_VERSION: &str = "1.0.0";
+
+    fn create_key(checksum: u64) -> CacheKey<'static> {
+        CacheKey {
+            language: Language::TypeScript,
+            name: STUB_NAME,
+            version: Some(STUB_VERSION),
+            checksum,
+        }
+    }
+
+    fn create_library() -> Library {
+        Library {
+            name: STUB_NAME.to_string(),
+            version: Some(STUB_VERSION.to_string()),
+            documentation: "Docs".to_string(),
+            namespaces: vec![Namespace {
+                name: STUB_NAME.to_string(),
+                symbols: vec![Symbol {
+                    name: "f".to_string(),
+                    source_code: "export function f(): void;".to_string(),
+                }],
+                doc_comment: Some("/** Namespace */".to_string()),
+            }],
+            language: Language::TypeScript,
+            features: None,
+            target: None,
+        }
+    }
+
+    mod entries {
+        use super::*;
+
+        #[test]
+        fn round_trip() {
+            let temp_dir = TempDir::new();
+            let cache = Cache::new(temp_dir.path.clone());
+
+            cache.put(&create_key(1), &create_library()).unwrap();
+            let library = cache.get(&create_key(1)).unwrap();
+
+            let expected_library = create_library();
+            assert_eq!(library.name, expected_library.name);
+            assert_eq!(library.version, expected_library.version);
+            assert_eq!(library.documentation, expected_library.documentation);
+            assert_eq!(library.language, expected_library.language);
+            assert_eq!(
+                library.namespaces[0].symbols,
+                expected_library.namespaces[0].symbols
+            );
+            assert_eq!(
+                library.namespaces[0].doc_comment,
+                expected_library.namespaces[0].doc_comment
+            );
+        }
+
+        #[test]
+        fn every_language() {
+            let temp_dir = TempDir::new();
+            let cache = Cache::new(temp_dir.path.clone());
+
+            for language in DISCOVERY_ORDER {
+                let key = CacheKey {
+                    language,
+                    ..create_key(1)
+                };
+                let library = Library {
+                    language,
+                    ..create_library()
+                };
+
+                cache.put(&key, &library).unwrap();
+
+                assert_eq!(cache.get(&key).unwrap().language, language);
+            }
+        }
+
+        #[test]
+        fn different_checksum() {
+            let temp_dir = TempDir::new();
+            let cache = Cache::new(temp_dir.path.clone());
+
+            cache.put(&create_key(1), &create_library()).unwrap();
+
+            assert!(cache.get(&create_key(2)).is_none());
+        }
+
+        #[test]
+        fn corrupted_entry() {
+            let temp_dir = TempDir::new();
+            let cache = Cache::new(temp_dir.path.clone());
+            cache.put(&create_key(1), &create_library()).unwrap();
+            fs::write(cache.get_entry_path(&create_key(1)), "{").unwrap();
+
+            assert!(cache.get(&create_key(1)).is_none());
+        }
+
+        #[test]
+        fn clear() {
+            let temp_dir = TempDir::new();
+            let cache = Cache::new(temp_dir.path.join("cache"));
+            cache.put(&create_key(1), &create_library()).unwrap();
+
+            cache.clear().unwrap();
+
+            assert!(cache.get(&create_key(1)).is_none());
+            assert!(!cache.get_directory().exists());
+        }
+
+        #[test]
+        fn clear_missing_directory() {
+            let temp_dir = TempDir::new();
+            let cache = Cache::new(temp_dir.path.join("cache"));
+
+            assert!(cache.clear().is_ok());
+        }
+    }
+
+    mod checksum {
+        use super::*;
+
+        fn compute(temp_dir: &TempDir) -> u64 {
+            compute_checksum(&temp_dir.path, &temp_dir.path.join("src/lib.rs")).unwrap()
+        }
+
+        #[test]
+        fn unchanged_files() {
+            let temp_dir = TempDir::new();
+            temp_dir.create_file("src/lib.rs", "pub fn f() {}").unwrap();
+
+            assert_eq!(compute(&temp_dir), compute(&temp_dir));
+        }
+
+        #[test]
+        fn changed_file() {
+            let temp_dir = TempDir::new();
+            temp_dir.create_file("src/lib.rs", "pub fn f() {}").unwrap();
+            let checksum = compute(&temp_dir);
+
+            temp_dir
+                .create_file("src/lib.rs", "pub fn f() {}\npub fn g() {}")
+                .unwrap();
+
+            assert_ne!(compute(&temp_dir), checksum);
+        }
+
+        #[test]
+        fn added_file() {
+            let temp_dir = TempDir::new();
+            temp_dir.create_file("src/lib.rs", "pub fn f() {}").unwrap();
+            let checksum = compute(&temp_dir);
+
+            temp_dir.create_file("src/other.rs", "").unwrap();
+
+            assert_ne!(compute(&temp_dir), checksum);
+        }
+
+        #[test]
+        fn ignored_directories() {
+            let temp_dir = TempDir::new();
+            temp_dir.create_file("src/lib.rs", "").unwrap();
+            let checksum = compute(&temp_dir);
+
+            temp_dir
+                .create_file("node_modules/dep/index.js", "")
+                .unwrap();
+            temp_dir.create_file(".git/HEAD", "").unwrap();
+
+            assert_eq!(compute(&temp_dir), checksum);
+        }
+
+        #[test]
+        fn package_outside_library() {
+            let temp_dir = TempDir::new();
+            temp_dir
+                .create_file("lib-1.0.dist-info/METADATA", "Name: lib")
+                .unwrap();
+            temp_dir.create_file("lib/__init__.py", "").unwrap();
+            let library_path = temp_dir.path.join("lib-1.0.dist-info");
+            let entry_point = temp_dir.path.join("lib/__init__.py");
+            let checksum = compute_checksum(&library_path, &entry_point).unwrap();
+
+            temp_dir.create_file("lib/client.py", "").unwrap();
+
+            assert_ne!(
+                compute_checksum(&library_path, &entry_point).unwrap(),
+                checksum
+            );
+        }
+
+        #[test]
+        fn module_outside_library() {
+            let temp_dir = TempDir::new();
+            temp_dir
+                .create_file("six-1.0.dist-info/METADATA", "Name: six")
+                .unwrap();
+            temp_dir.create_file("six.py", "").unwrap();
+            let library_path = temp_dir.path.join("six-1.0.dist-info");
+            let entry_point = temp_dir.path.join("six.py");
+            let checksum = compute_checksum(&library_path, &entry_point).unwrap();
+
+            temp_dir.create_file("other.py", "").unwrap();
+            assert_eq!(
+                compute_checksum(&library_path, &entry_point).unwrap(),
+                checksum
+            );
+            temp_dir.create_file("six.py", "import os").unwrap();
+            assert_ne!(
+                compute_checksum(&library_path, &entry_point).unwrap(),
+                checksum
+            );
+        }
+    }
+}
@@ -0,0 +1,43 @@
+use super::Command;
+use bpaf::{parsers::ParseCommand, *};
+
+fn make_clear_subcommand() -> ParseCommand<Command> {
+    pure(Command::ClearCache)
+        .to_options()
+        .descr("Delete every entry in the cache")
+        .command("clear")
+}
+
+pub fn make_cache_subcommand() -> ParseCommand<Command> {
+    let clear = make_clear_subcommand();
+
+    construct!([clear])
+        .to_options()
+        .descr("Manage the cache of extracted libraries")
+        .command("cache")
+}
+
+#[cfg(test)]
+mod tests {
+    use assertables::assert_matches;
+
+    use super::*;
+
+    #[test]
+    fn test_cache_clear() {
+        let parser = make_cache_subcommand().to_options();
+
+        let result = parser.run_inner(&["cache", "clear"]);
+
+        assert_matches!(result, Ok(Command::ClearCache));
+    }
+
+    #[test]
+    fn test_cache_without_action() {
+        let parser = make_cache_subcommand().to_options();
+
+        let result = parser.run_inner(&["cache"]);
+
+        assert!(result.is_err());
+    }
+}
@@ -0,0 +1,92 @@
+use super::{
+    make_feature_selection_option, make_filter_option, make_format_option, make_language_option,
+    make_no_cache_option, make_target_option, Command, LibrarySource,
+};
+use bpaf::{parsers::ParseCommand, *};
+
+pub fn make_diff_subcommand() -> ParseCommand<Command> {
+    let language = make_language_option();
+    let format = make_format_option();
+    let no_cache = make_no_cache_option();
+    let filter = make_filter_option();
+    let features = make_feature_selection_option();
+    let target = make_target_option();
+    let check = long("check")
+        .help("Exit unsuccessfully if the version bump is smaller than the API changes require")
+        .switch();
+    let old = positional::<LibrarySource>("OLD")
+        .help("Path to the old version of the library, or NAME@VERSION to load it from the Cargo registry cache");
+    let new = positional::<LibrarySource>("NEW")
+        .help("Path to the new version of the library, or NAME@VERSION to load it from the Cargo registry cache");
+
+    construct!(Command::Diff {
+        language,
+        format,
+        no_cache,
+        filter,
+        features,
+        target,
+        check,
+        old,
+        new,
+    })
+    .to_options()
+    .descr("Compare the public APIs of two versions of a library")
+    .command("diff")
+}
+
+#[cfg(test)]
+mod tests {
+    use assertables::assert_matches;
+    use std::path::Path;
+
+    use super::*;
+
+    #[test]
+    fn test_diff_paths() {
+        let parser = make_diff_subcommand().to_options();
+
+        let result = parser.run_inner(&["diff", "/old", "/new"]);
+
+        assert_matches!(result.unwrap(),
+            Command::Diff {
+                old: LibrarySource::Path(old),
+                new: LibrarySource::Path(new),
+                ..
+            } if old == Path::new("/old") && new == Path::new("/new")
+        );
+    }
+
+    #[test]
+    fn test_diff_registry_crates() {
+        let parser = make_diff_subcommand().to_options();
+
+        let result = parser.run_inner(&["diff", "serde@1.0.100", "./serde"]);
+
+        assert_matches!(result.unwrap(),
+            Command::Diff {
+                old: LibrarySource::Registry { name, version },
+                new: LibrarySource::Path(_),
+                ..
+            } if name == "serde" && version == "1.0.100"
+        );
+    }
+
+    #[test]
+    fn test_diff_with_check() {
+        let parser = make_diff_subcommand().to_options();
+
+        let result = parser.run_inner(&["diff", "/old", "/new", "--check"]);
+
+        assert_matches!(result.unwrap(), Command::Diff { check: true, .. });
+    }
+
+    #[test]
+    fn test_diff_without_new_version() {
+        let parser = make_diff_subcommand().to_options();
+
+        let result = parser.run_inner(&["diff", "/old"]);
+
+        assert!(result.is_err());
+    }
+}
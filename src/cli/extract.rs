@@ -1,4 +1,7 @@
-use super::{make_language_option, Command};
+use super::{
+    make_feature_selection_option, make_filter_option, make_format_option, make_formatting_options,
+    make_language_option, make_no_cache_option, make_package_option, make_target_option, Command,
+};
 use bpaf::{parsers::ParseCommand, *};
 use std::path::PathBuf;
 
@@ -8,12 +11,29 @@ fn make_path_arg() -> impl Parser<PathBuf> {
 
 pub fn make_extract_subcommand() -> ParseCommand<Command> {
     let language = make_language_option();
+    let format = make_format_option();
+    let formatting = make_formatting_options();
+    let no_cache = make_no_cache_option();
+    let filter = make_filter_option();
+    let features = make_feature_selection_option();
+    let target = make_target_option();
     let path = make_path_arg();
-
-    construct!(Command::Extract { language, path })
-        .to_options()
-        .descr("Extract and document dependencies from a project")
-        .command("extract")
+    let package = make_package_option();
+
+    construct!(Command::Extract {
+        language,
+        format,
+        formatting,
+        no_cache,
+        filter,
+        features,
+        target,
+        package,
+        path,
+    })
+    .to_options()
+    .descr("Extract and document dependencies from a project")
+    .command("extract")
 }
 
 #[cfg(test)]
@@ -21,6 +41,7 @@ mod tests {
     use daipendency::Language;
 
     use super::*;
+    use crate::cli::OutputFormat;
 
     #[test]
     fn test_parse_valid_path() {
@@ -30,9 +51,15 @@ mod tests {
 
         assert!(result.is_ok());
         match result.unwrap() {
-            Command::Extract { path, language } => {
+            Command::Extract {
+                path,
+                language,
+                no_cache,
+                ..
+            } => {
                 assert_eq!(path, PathBuf::from("/some/path"));
                 assert!(language.is_none());
+                assert!(!no_cache);
             }
             _ => panic!("Expected Extract command"),
         }
@@ -46,7 +73,7 @@ mod tests {
 
         assert!(result.is_ok());
         match result.unwrap() {
-            Command::Extract { path, language } => {
+            Command::Extract { path, language, .. } => {
                 assert_eq!(path, PathBuf::from("/some/path"));
                 assert_eq!(language, Some(Language::Rust));
             }
@@ -63,6 +90,107 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[test]
+    fn test_parse_with_format() {
+        let parser = make_extract_subcommand().to_options();
+
+        let result = parser.run_inner(&["extract", "/some/path", "--format", "json"]);
+
+        assert!(result.is_ok());
+        match result.unwrap() {
+            Command::Extract { format, .. } => assert_eq!(format, OutputFormat::Json),
+            _ => panic!("Expected Extract command"),
+        }
+    }
+
+    #[test]
+    fn test_parse_with_max_tokens() {
+        let parser = make_extract_subcommand().to_options();
+
+        let result = parser.run_inner(&["extract", "/some/path", "--max-tokens", "2000"]);
+
+        assert!(result.is_ok());
+        match result.unwrap() {
+            Command::Extract { formatting, .. } => assert_eq!(formatting.max_tokens, Some(2000)),
+            _ => panic!("Expected Extract command"),
+        }
+    }
+
+    #[test]
+    fn test_parse_with_group_by_kind() {
+        let parser = make_extract_subcommand().to_options();
+
+        let result = parser.run_inner(&["extract", "/some/path", "--group-by-kind"]);
+
+        assert!(result.is_ok());
+        match result.unwrap() {
+            Command::Extract { formatting, .. } => assert!(formatting.group_by_kind),
+            _ => panic!("Expected Extract command"),
+        }
+    }
+
+    #[test]
+    fn test_parse_with_table_of_contents() {
+        let parser = make_extract_subcommand().to_options();
+
+        let result = parser.run_inner(&["extract", "/some/path", "--table-of-contents"]);
+
+        assert!(result.is_ok());
+        match result.unwrap() {
+            Command::Extract { formatting, .. } => assert!(formatting.table_of_contents),
+            _ => panic!("Expected Extract command"),
+        }
+    }
+
+    #[test]
+    fn test_parse_with_no_cache() {
+        let parser = make_extract_subcommand().to_options();
+
+        let result = parser.run_inner(&["extract", "/some/path", "--no-cache"]);
+
+        assert!(result.is_ok());
+        match result.unwrap() {
+            Command::Extract { no_cache, .. } => assert!(no_cache),
+            _ => panic!("Expected Extract command"),
+        }
+    }
+
+    #[test]
+    fn test_parse_with_invalid_format() {
+        let parser = make_extract_subcommand().to_options();
+
+        let result = parser.run_inner(&["extract", "/some/path", "--format", "yaml"]);
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_parse_with_filter() {
+        let parser = make_extract_subcommand().to_options();
+
+        let result = parser.run_inner(&[
+            "extract",
+            "/some/path",
+            "--include",
+            "tokio::sync::*",
+            "--include",
+            "tokio::net::*",
+            "--exclude",
+            "*::Error",
+        ]);
+
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn test_parse_with_invalid_filter() {
+        let parser = make_extract_subcommand().to_options();
+
+        let result = parser.run_inner(&["extract", "/some/path", "--include", "tokio::[sync"]);
+
+        assert!(result.is_err());
+    }
+
     #[test]
     fn test_parse_without_path() {
         let parser = make_extract_subcommand().to_options();
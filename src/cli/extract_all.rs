@@ -0,0 +1,98 @@
+use super::{
+    make_dependant_option, make_format_option, make_formatting_options, make_language_option,
+    make_no_cache_option, make_package_option, Command,
+};
+use bpaf::{parsers::ParseCommand, *};
+use std::path::PathBuf;
+
+fn make_output_dir_option() -> impl Parser<Option<PathBuf>> {
+    long("output-dir")
+        .help("Directory to write one document per dependency to, instead of standard output")
+        .argument("DIR")
+        .optional()
+}
+
+fn make_depth_option() -> impl Parser<usize> {
+    long("depth")
+        .help("How many levels of transitive dependencies to extract, where 1 means direct dependencies only")
+        .argument("N")
+        .fallback(1)
+}
+
+pub fn make_extract_all_subcommand() -> ParseCommand<Command> {
+    let dependant = make_dependant_option();
+    let language = make_language_option();
+    let format = make_format_option();
+    let formatting = make_formatting_options();
+    let no_cache = make_no_cache_option();
+    let output_dir = make_output_dir_option();
+    let depth = make_depth_option();
+    let package = make_package_option();
+
+    construct!(Command::ExtractAll {
+        dependant,
+        language,
+        format,
+        formatting,
+        no_cache,
+        output_dir,
+        depth,
+        package,
+    })
+    .to_options()
+    .descr("Extract every dependency declared in the manifest of a project")
+    .command("extract-all")
+}
+
+#[cfg(test)]
+mod tests {
+    use std::env::current_dir;
+    use std::path::Path;
+
+    use assertables::assert_matches;
+
+    use super::*;
+
+    #[test]
+    fn test_extract_all_defaults() {
+        let parser = make_extract_all_subcommand().to_options();
+
+        let result = parser.run_inner(&["extract-all"]);
+
+        assert!(result.is_ok());
+        assert_matches!(result.unwrap(),
+            Command::ExtractAll {
+                dependant,
+                language: None,
+                output_dir: None,
+                depth: 1,
+                ..
+            } if dependant == current_dir().unwrap()
+        );
+    }
+
+    #[test]
+    fn test_extract_all_with_output_dir() {
+        let parser = make_extract_all_subcommand().to_options();
+
+        let result = parser.run_inner(&["extract-all", "--output-dir", "/some/docs"]);
+
+        assert!(result.is_ok());
+        assert_matches!(result.unwrap(),
+            Command::ExtractAll {
+                output_dir: Some(output_dir),
+                ..
+            } if output_dir == Path::new("/some/docs")
+        );
+    }
+
+    #[test]
+    fn test_extract_all_with_depth() {
+        let parser = make_extract_all_subcommand().to_options();
+
+        let result = parser.run_inner(&["extract-all", "--depth", "3"]);
+
+        assert!(result.is_ok());
+        assert_matches!(result.unwrap(), Command::ExtractAll { depth: 3, .. });
+    }
+}
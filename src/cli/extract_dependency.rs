@@ -1,18 +1,28 @@
-use super::{make_language_option, Command};
+use super::{
+    make_dependant_option, make_feature_selection_option, make_filter_option, make_format_option,
+    make_formatting_options, make_language_option, make_no_cache_option, make_package_option,
+    make_target_option, Command, DependencySource,
+};
 use bpaf::{parsers::ParseCommand, *};
-use std::env::current_dir;
 
 pub fn make_extract_dep_subcommand() -> ParseCommand<Command> {
-    let dependant = long("dependant")
-        .help("Path to the dependant project")
-        .argument("PATH")
-        .fallback_with(current_dir);
+    let source = make_dependency_source_options();
     let dependency = positional("DEPENDENCY").help("Name of the dependency to extract");
-    let language = make_language_option();
+    let format = make_format_option();
+    let formatting = make_formatting_options();
+    let no_cache = make_no_cache_option();
+    let filter = make_filter_option();
+    let features = make_feature_selection_option();
+    let target = make_target_option();
 
     construct!(Command::ExtractDep {
-        dependant,
-        language,
+        source,
+        format,
+        formatting,
+        no_cache,
+        filter,
+        features,
+        target,
         dependency,
     })
     .to_options()
@@ -20,13 +30,34 @@ pub fn make_extract_dep_subcommand() -> ParseCommand<Command> {
     .command("extract-dep")
 }
 
+/// `--version` loads the crate from the registry, so it can't be combined with the dependant.
+fn make_dependency_source_options() -> impl Parser<DependencySource> {
+    let version = long("version")
+        .help("Version of the crate to load from the local Cargo registry cache, instead of using a dependant")
+        .argument("VERSION");
+    let registry = construct!(DependencySource::Registry { version });
+
+    let path = make_dependant_option();
+    let package = make_package_option();
+    let language = make_language_option();
+    let dependant = construct!(DependencySource::Dependant {
+        path,
+        package,
+        language
+    });
+
+    construct!([registry, dependant])
+}
+
 #[cfg(test)]
 mod tests {
+    use std::env::current_dir;
     use std::path::PathBuf;
 
     use assertables::assert_matches;
 
     use super::*;
+    use crate::cli::OutputFormat;
 
     #[test]
     fn test_extract_dep_with_dependency() {
@@ -38,13 +69,14 @@ mod tests {
         assert_matches!(result.unwrap(),
             Command::ExtractDep {
                 dependency,
-                dependant,
-                language: None,
-            } if dependency == "my-dep" && dependant == current_dir().unwrap()
+                source: DependencySource::Dependant { path, language: None, .. },
+                ..
+            } if dependency == "my-dep" && path == current_dir().unwrap()
         );
     }
 
     #[test]
+    #[allow(clippy::cmp_owned)]
     fn test_extract_dep_with_dependant() {
         let parser = make_extract_dep_subcommand().to_options();
 
@@ -54,12 +86,73 @@ mod tests {
         assert_matches!(result.unwrap(),
             Command::ExtractDep {
                 dependency,
-                dependant,
-                language: None,
-            } if dependency == "my-dep" && dependant == PathBuf::from("/some/path")
+                source: DependencySource::Dependant { path, language: None, .. },
+                ..
+            } if dependency == "my-dep" && path == PathBuf::from("/some/path")
+        );
+    }
+
+    #[test]
+    fn test_extract_dep_with_format() {
+        let parser = make_extract_dep_subcommand().to_options();
+
+        let result = parser.run_inner(&["extract-dep", "my-dep", "--format", "json"]);
+
+        assert!(result.is_ok());
+        assert_matches!(
+            result.unwrap(),
+            Command::ExtractDep {
+                format: OutputFormat::Json,
+                ..
+            }
         );
     }
 
+    #[test]
+    fn test_extract_dep_with_filter() {
+        let parser = make_extract_dep_subcommand().to_options();
+
+        let result = parser.run_inner(&["extract-dep", "my-dep", "--exclude", "*::Error"]);
+
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn test_extract_dep_with_version() {
+        let parser = make_extract_dep_subcommand().to_options();
+
+        let result = parser.run_inner(&["extract-dep", "serde", "--version", "1.0.217"]);
+
+        assert_matches!(result.unwrap(),
+            Command::ExtractDep {
+                dependency,
+                source: DependencySource::Registry { version },
+                ..
+            } if dependency == "serde" && version == "1.0.217"
+        );
+    }
+
+    #[test]
+    fn test_extract_dep_with_version_and_dependant_options() {
+        let parser = make_extract_dep_subcommand().to_options();
+
+        for option in [
+            ["--language", "rust"],
+            ["--dependant", "/some/path"],
+            ["--package", "app-core"],
+        ] {
+            let args = [
+                &["extract-dep", "serde", "--version", "1.0.217"],
+                &option[..],
+            ]
+            .concat();
+
+            let result = parser.run_inner(args.as_slice());
+
+            assert!(result.is_err(), "{} was accepted", option[0]);
+        }
+    }
+
     #[test]
     fn test_extract_dep_without_dependency() {
         let parser = make_extract_dep_subcommand().to_options();
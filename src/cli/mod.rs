@@ -1,12 +1,32 @@
 use bpaf::*;
-use daipendency::Language;
-use std::path::PathBuf;
+use daipendency::{FeatureSelection, FormattingOptions, Language, SymbolFilter, SymbolKind};
+use std::convert::Infallible;
+use std::env::current_dir;
+use std::ffi::OsString;
+use std::path::{PathBuf, MAIN_SEPARATOR};
+use std::str::FromStr;
 
+mod cache;
+mod diff;
 mod extract;
+mod extract_all;
 mod extract_dependency;
+mod serve_mcp;
 
+use cache::make_cache_subcommand;
+use diff::make_diff_subcommand;
 use extract::make_extract_subcommand;
+use extract_all::make_extract_all_subcommand;
 use extract_dependency::make_extract_dep_subcommand;
+use serve_mcp::make_serve_mcp_subcommand;
+
+/// The parsed command line
+#[derive(Debug, Clone)]
+pub struct Cli {
+    /// How to report errors on standard error
+    pub error_format: ErrorFormat,
+    pub command: Command,
+}
 
 #[derive(Debug, Clone)]
 pub enum Command {
@@ -14,29 +34,260 @@ pub enum Command {
     Extract {
         /// Path to the project or file
         path: PathBuf,
+        /// Member of the workspace at the path to use, if it is a workspace
+        package: Option<String>,
         /// Programming language to use
         language: Option<Language>,
+        /// Output format
+        format: OutputFormat,
+        /// Options for the output format
+        formatting: FormattingOptions,
+        /// Whether to extract the library again instead of using the cache
+        no_cache: bool,
+        /// Namespaces and symbols to keep
+        filter: SymbolFilter,
+        /// Cargo features to enable, if any were selected
+        features: Option<FeatureSelection>,
+        /// Target triple to keep the platform-specific items of, if any
+        target: Option<String>,
     },
     /// Extract a specific dependency
     ExtractDep {
+        /// Where to find the dependency
+        source: DependencySource,
+        /// Output format
+        format: OutputFormat,
+        /// Options for the output format
+        formatting: FormattingOptions,
+        /// Whether to extract the library again instead of using the cache
+        no_cache: bool,
+        /// Name of the dependency to extract
+        dependency: String,
+        /// Namespaces and symbols to keep
+        filter: SymbolFilter,
+        /// Cargo features to enable, if any were selected
+        features: Option<FeatureSelection>,
+        /// Target triple to keep the platform-specific items of, if any
+        target: Option<String>,
+    },
+    /// Extract every dependency of a project
+    ExtractAll {
         /// Path to the dependant project
         dependant: PathBuf,
+        /// Member of the workspace at the dependant path to use, if it is a workspace
+        package: Option<String>,
+        /// Programming language to use
+        language: Option<Language>,
+        /// Output format
+        format: OutputFormat,
+        /// Options for the output format
+        formatting: FormattingOptions,
+        /// Whether to extract the library again instead of using the cache
+        no_cache: bool,
+        /// Directory to write one document per dependency to, instead of standard output
+        output_dir: Option<PathBuf>,
+        /// How many levels of transitive dependencies to extract
+        depth: usize,
+    },
+    /// Compare the public APIs of two versions of a library
+    Diff {
+        /// The old version of the library
+        old: LibrarySource,
+        /// The new version of the library
+        new: LibrarySource,
+        /// Programming language to use
+        language: Option<Language>,
+        /// Output format
+        format: OutputFormat,
+        /// Whether to extract the libraries again instead of using the cache
+        no_cache: bool,
+        /// Namespaces and symbols to compare
+        filter: SymbolFilter,
+        /// Cargo features to enable, if any were selected
+        features: Option<FeatureSelection>,
+        /// Target triple to keep the platform-specific items of, if any
+        target: Option<String>,
+        /// Whether to fail if the version bump is smaller than the changes require
+        check: bool,
+    },
+    /// Serve the Model Context Protocol over stdio
+    ServeMcp {
+        /// Whether to extract libraries again instead of using the cache
+        no_cache: bool,
+    },
+    /// Delete every entry in the cache
+    ClearCache,
+}
+
+/// The format in which to output the documentation
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OutputFormat {
+    Markdown,
+    Json,
+}
+
+impl OutputFormat {
+    pub fn get_file_extension(&self) -> &'static str {
+        match self {
+            Self::Markdown => "md",
+            Self::Json => "json",
+        }
+    }
+}
+
+impl FromStr for OutputFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "markdown" => Ok(Self::Markdown),
+            "json" => Ok(Self::Json),
+            _ => Err(format!("Unknown format '{}'", s)),
+        }
+    }
+}
+
+/// Where to find the dependency to extract
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DependencySource {
+    /// A dependency declared by a dependant project
+    Dependant {
+        /// Path to the dependant project
+        path: PathBuf,
+        /// Member of the workspace at the dependant path to use, if it is a workspace
+        package: Option<String>,
         /// Programming language to use
         language: Option<Language>,
-        /// Name of the dependency to extract
-        dependency: String,
     },
+    /// A crate in the local Cargo registry cache, regardless of any dependant
+    Registry { version: String },
+}
+
+/// Where to load a library from
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LibrarySource {
+    Path(PathBuf),
+    /// A crate in the local Cargo registry cache
+    Registry {
+        name: String,
+        version: String,
+    },
+}
+
+impl FromStr for LibrarySource {
+    type Err = Infallible;
+
+    /// Parse `NAME@VERSION` as a registry crate and anything else, including any value with a
+    /// path separator, as a path.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let is_path = s.contains(['/', MAIN_SEPARATOR]);
+        match s.split_once('@') {
+            Some((name, version)) if !is_path && !name.is_empty() && !version.is_empty() => {
+                Ok(Self::Registry {
+                    name: name.to_string(),
+                    version: version.to_string(),
+                })
+            }
+            _ => Ok(Self::Path(PathBuf::from(s))),
+        }
+    }
+}
+
+/// The format in which to report errors
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ErrorFormat {
+    Text,
+    Json,
+}
+
+impl FromStr for ErrorFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "text" => Ok(Self::Text),
+            "json" => Ok(Self::Json),
+            _ => Err(format!("Unknown error format '{}'", s)),
+        }
+    }
+}
+
+const ERROR_FORMAT_OPTION: &str = "--error-format";
+
+/// Move `--error-format` to the front of the arguments, so that it can also follow the subcommand.
+pub fn hoist_error_format(args: Vec<OsString>) -> Vec<OsString> {
+    let mut hoisted_args = Vec::new();
+    let mut other_args = Vec::new();
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        if arg == "--" {
+            other_args.push(arg);
+            other_args.extend(args.by_ref());
+        } else if arg == ERROR_FORMAT_OPTION {
+            hoisted_args.push(arg);
+            hoisted_args.extend(args.next());
+        } else if get_inline_error_format(&arg).is_some() {
+            hoisted_args.push(arg);
+        } else {
+            other_args.push(arg);
+        }
+    }
+    hoisted_args.extend(other_args);
+    hoisted_args
+}
+
+/// Get the error format requested in the arguments, even if the arguments are otherwise invalid.
+pub fn get_error_format(args: &[OsString]) -> ErrorFormat {
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        let value = if arg == ERROR_FORMAT_OPTION {
+            args.next().and_then(|value| value.to_str())
+        } else {
+            get_inline_error_format(arg)
+        };
+        if let Some(value) = value {
+            return value.parse().unwrap_or(ErrorFormat::Text);
+        }
+        if arg == "--" {
+            break;
+        }
+    }
+    ErrorFormat::Text
 }
 
-pub fn make_command_parser() -> OptionParser<Command> {
+fn get_inline_error_format(arg: &OsString) -> Option<&str> {
+    arg.to_str()?
+        .strip_prefix(ERROR_FORMAT_OPTION)?
+        .strip_prefix('=')
+}
+
+pub fn make_command_parser() -> OptionParser<Cli> {
+    let error_format = long("error-format")
+        .help("Format of the errors on standard error: text (default) or json")
+        .argument("FORMAT")
+        .fallback(ErrorFormat::Text);
+
     let extract = make_extract_subcommand();
 
     let extract_dep = make_extract_dep_subcommand();
 
-    construct!([extract, extract_dep])
-        .to_options()
-        .descr("A tool for extracting and documenting dependencies")
-        .header("daipendency")
+    let extract_all = make_extract_all_subcommand();
+
+    let diff = make_diff_subcommand();
+
+    let serve_mcp = make_serve_mcp_subcommand();
+
+    let cache = make_cache_subcommand();
+
+    let command = construct!([extract, extract_dep, extract_all, diff, serve_mcp, cache]);
+
+    construct!(Cli {
+        error_format,
+        command
+    })
+    .to_options()
+    .descr("A tool for extracting and documenting dependencies")
+    .header("daipendency")
 }
 
 fn make_language_option() -> impl Parser<Option<Language>> {
@@ -46,6 +297,105 @@ fn make_language_option() -> impl Parser<Option<Language>> {
         .optional()
 }
 
+fn make_dependant_option() -> impl Parser<PathBuf> {
+    long("dependant")
+        .help("Path to the dependant project")
+        .argument("PATH")
+        .fallback_with(current_dir)
+}
+
+fn make_package_option() -> impl Parser<Option<String>> {
+    long("package")
+        .short('p')
+        .help("Name of the workspace member to use when the path is a workspace root (e.g. a Cargo workspace)")
+        .argument("NAME")
+        .optional()
+}
+
+fn make_target_option() -> impl Parser<Option<String>> {
+    long("target")
+        .help("Target triple whose platform-specific items to keep (e.g. x86_64-unknown-linux-gnu)")
+        .argument("TRIPLE")
+        .optional()
+}
+
+fn make_format_option() -> impl Parser<OutputFormat> {
+    long("format")
+        .help("Output format: markdown (default) or json")
+        .argument("FORMAT")
+        .fallback(OutputFormat::Markdown)
+}
+
+fn make_no_cache_option() -> impl Parser<bool> {
+    long("no-cache")
+        .help("Extract the libraries again instead of reusing the results of previous extractions")
+        .switch()
+}
+
+fn make_formatting_options() -> impl Parser<FormattingOptions> {
+    let max_tokens = long("max-tokens")
+        .help("Approximate maximum number of tokens in the output, omitting the least important content")
+        .argument::<usize>("N")
+        .optional();
+    let group_by_kind = long("group-by-kind")
+        .help("Split the symbols in each namespace of the Markdown output into a section per kind")
+        .switch();
+    let table_of_contents = long("table-of-contents")
+        .help("Start the API in the Markdown output with links to each namespace and an index of the symbols")
+        .switch();
+    construct!(FormattingOptions {
+        max_tokens,
+        group_by_kind,
+        table_of_contents
+    })
+}
+
+fn make_feature_selection_option() -> impl Parser<Option<FeatureSelection>> {
+    let features = long("features")
+        .short('F')
+        .help("Comma- or space-separated list of Cargo features to enable")
+        .argument::<String>("FEATURES")
+        .many()
+        .map(|lists| {
+            lists
+                .iter()
+                .flat_map(|list| list.split([',', ' ']))
+                .filter(|feature| !feature.is_empty())
+                .map(String::from)
+                .collect::<Vec<_>>()
+        });
+    let all_features = long("all-features")
+        .help("Enable every Cargo feature")
+        .switch();
+    let no_default_features = long("no-default-features")
+        .help("Do not enable the default Cargo features")
+        .switch();
+    construct!(FeatureSelection {
+        features,
+        all_features,
+        no_default_features
+    })
+    .map(|selection| (selection != FeatureSelection::default()).then_some(selection))
+}
+
+fn make_filter_option() -> impl Parser<SymbolFilter> {
+    let include = long("include")
+        .help("Glob pattern of the namespaces or symbols to keep (e.g. tokio::sync::*)")
+        .argument::<String>("PATTERN")
+        .many();
+    let exclude = long("exclude")
+        .help("Glob pattern of the namespaces or symbols to drop (e.g. *::Error)")
+        .argument::<String>("PATTERN")
+        .many();
+    let kinds = long("kind")
+        .help("Kind of the symbols to keep: function, struct, class, enum, trait, interface, type_alias, constant, macro or module")
+        .argument::<SymbolKind>("KIND")
+        .many();
+    construct!(include, exclude, kinds).parse(|(include, exclude, kinds)| {
+        SymbolFilter::new(&include, &exclude).map(|filter| filter.with_kinds(&kinds))
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -57,10 +407,17 @@ mod tests {
         let result = parser.run_inner(&["extract", "/some/path"]);
 
         assert!(matches!(
-            result.unwrap(),
+            result.unwrap().command,
             Command::Extract {
                 path: _,
-                language: _
+                language: _,
+                format: OutputFormat::Markdown,
+                formatting: _,
+                no_cache: _,
+                filter: _,
+                features: None,
+                target: None,
+                package: None,
             }
         ));
     }
@@ -72,12 +429,216 @@ mod tests {
         let result = parser.run_inner(&["extract-dep", "my-dep"]);
 
         assert!(matches!(
-            result.unwrap(),
+            result.unwrap().command,
             Command::ExtractDep {
                 dependency: _,
-                dependant: _,
-                language: None,
+                source: DependencySource::Dependant {
+                    path: _,
+                    package: None,
+                    language: None,
+                },
+                format: OutputFormat::Markdown,
+                formatting: _,
+                no_cache: _,
+                filter: _,
+                features: None,
+                target: None,
             }
         ));
     }
+
+    #[test]
+    fn test_extract_all_command_registered() {
+        let parser = make_command_parser();
+
+        let result = parser.run_inner(&["extract-all"]);
+
+        assert!(matches!(
+            result.unwrap().command,
+            Command::ExtractAll { .. }
+        ));
+    }
+
+    #[test]
+    fn test_command_parser_invariants() {
+        make_command_parser().check_invariants(false);
+    }
+
+    #[test]
+    fn test_default_error_format() {
+        let parser = make_command_parser();
+
+        let result = parser.run_inner(&["extract-all"]);
+
+        assert_eq!(result.unwrap().error_format, ErrorFormat::Text);
+    }
+
+    #[test]
+    fn test_json_error_format() {
+        let parser = make_command_parser();
+
+        let result = parser.run_inner(&["--error-format", "json", "extract-all"]);
+
+        assert_eq!(result.unwrap().error_format, ErrorFormat::Json);
+    }
+
+    #[test]
+    fn test_unsupported_error_format() {
+        let parser = make_command_parser();
+
+        let result = parser.run_inner(&["--error-format", "yaml", "extract-all"]);
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_error_format_after_command() {
+        let parser = make_command_parser();
+        let args = hoist_error_format(to_os_strings(&["extract-all", "--error-format", "json"]));
+
+        let result = parser.run_inner(args.as_slice());
+
+        assert_eq!(result.unwrap().error_format, ErrorFormat::Json);
+    }
+
+    #[test]
+    fn test_inline_error_format_after_command() {
+        let parser = make_command_parser();
+        let args = hoist_error_format(to_os_strings(&["extract-all", "--error-format=json"]));
+
+        let result = parser.run_inner(args.as_slice());
+
+        assert_eq!(result.unwrap().error_format, ErrorFormat::Json);
+    }
+
+    #[test]
+    fn test_error_format_after_separator() {
+        let args = to_os_strings(&["extract", "--", "--error-format", "json"]);
+
+        let hoisted_args = hoist_error_format(args.clone());
+
+        assert_eq!(hoisted_args, args);
+        assert_eq!(get_error_format(&hoisted_args), ErrorFormat::Text);
+    }
+
+    #[test]
+    fn test_error_format_of_invalid_arguments() {
+        let args = to_os_strings(&["extract", "--nope", "--error-format", "json"]);
+
+        let error_format = get_error_format(&args);
+
+        assert_eq!(error_format, ErrorFormat::Json);
+    }
+
+    #[test]
+    fn test_unsupported_error_format_of_invalid_arguments() {
+        let args = to_os_strings(&["extract", "--error-format", "yaml"]);
+
+        let error_format = get_error_format(&args);
+
+        assert_eq!(error_format, ErrorFormat::Text);
+    }
+
+    fn to_os_strings(args: &[&str]) -> Vec<OsString> {
+        args.iter().map(OsString::from).collect()
+    }
+
+    #[test]
+    fn test_package_option() {
+        let parser = make_command_parser();
+
+        let result = parser.run_inner(&["extract-dep", "serde", "--package", "app-core"]);
+
+        assert!(matches!(
+            result.unwrap().command,
+            Command::ExtractDep {
+                source: DependencySource::Dependant { package: Some(package), .. },
+                ..
+            } if package == "app-core"
+        ));
+    }
+
+    #[test]
+    fn test_target_option() {
+        let parser = make_command_parser();
+
+        let result = parser.run_inner(&[
+            "extract",
+            "/some/path",
+            "--target",
+            "x86_64-pc-windows-msvc",
+        ]);
+
+        assert!(matches!(
+            result.unwrap().command,
+            Command::Extract { target: Some(target), .. } if target == "x86_64-pc-windows-msvc"
+        ));
+    }
+
+    #[test]
+    fn test_kind_option() {
+        let parser = make_command_parser();
+
+        let result = parser.run_inner(&["extract", "/some/path", "--kind", "trait"]);
+
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn test_unknown_kind_option() {
+        let parser = make_command_parser();
+
+        let result = parser.run_inner(&["extract", "/some/path", "--kind", "record"]);
+
+        assert!(result.is_err());
+    }
+
+    mod feature_selection {
+        use super::*;
+
+        fn parse_features(args: &[&str]) -> Option<FeatureSelection> {
+            let parser = make_feature_selection_option().to_options();
+            parser.run_inner(args).unwrap()
+        }
+
+        #[test]
+        fn no_selection() {
+            assert_eq!(parse_features(&[]), None);
+        }
+
+        #[test]
+        fn feature_lists() {
+            let selection = parse_features(&["--features", "rt,net", "-F", "sync fs"]);
+
+            assert_eq!(selection.unwrap().features, vec!["rt", "net", "sync", "fs"]);
+        }
+
+        #[test]
+        fn flags() {
+            let selection = parse_features(&["--all-features", "--no-default-features"]).unwrap();
+
+            assert!(selection.all_features);
+            assert!(selection.no_default_features);
+            assert!(selection.features.is_empty());
+        }
+    }
+
+    mod output_format {
+        use super::*;
+
+        #[test]
+        fn markdown() {
+            assert_eq!("markdown".parse(), Ok(OutputFormat::Markdown));
+        }
+
+        #[test]
+        fn json() {
+            assert_eq!("json".parse(), Ok(OutputFormat::Json));
+        }
+
+        #[test]
+        fn unsupported_format() {
+            assert!("yaml".parse::<OutputFormat>().is_err());
+        }
+    }
 }
@@ -0,0 +1,36 @@
+use super::{make_no_cache_option, Command};
+use bpaf::{parsers::ParseCommand, *};
+
+pub fn make_serve_mcp_subcommand() -> ParseCommand<Command> {
+    let no_cache = make_no_cache_option();
+
+    construct!(Command::ServeMcp { no_cache })
+        .to_options()
+        .descr("Serve the Model Context Protocol over standard input and output")
+        .command("serve-mcp")
+}
+
+#[cfg(test)]
+mod tests {
+    use assertables::assert_matches;
+
+    use super::*;
+
+    #[test]
+    fn test_serve_mcp() {
+        let parser = make_serve_mcp_subcommand().to_options();
+
+        let result = parser.run_inner(&["serve-mcp"]);
+
+        assert_matches!(result, Ok(Command::ServeMcp { no_cache: false }));
+    }
+
+    #[test]
+    fn test_serve_mcp_without_cache() {
+        let parser = make_serve_mcp_subcommand().to_options();
+
+        let result = parser.run_inner(&["serve-mcp", "--no-cache"]);
+
+        assert_matches!(result, Ok(Command::ServeMcp { no_cache: true }));
+    }
+}
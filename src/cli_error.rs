@@ -0,0 +1,288 @@
+use daipendency::{Language, LibraryError, SemverVerdict};
+use serde_json::{json, Value};
+use std::fmt;
+use std::io;
+
+/// The arguments could not be parsed.
+pub const USAGE_EXIT_CODE: u8 = 2;
+
+/// Error that makes the CLI exit unsuccessfully, each class with its own exit code.
+#[derive(Debug)]
+pub enum CliError {
+    /// The arguments could not be parsed.
+    Usage(String),
+    Library(LibraryError),
+    /// Some of the dependencies could not be extracted, but the others were output.
+    PartialFailure(Vec<(String, LibraryError)>),
+    Io(io::Error),
+    CacheUnavailable,
+    /// The API changes require a bigger version bump than the actual one.
+    InsufficientVersionBump(SemverVerdict),
+}
+
+impl CliError {
+    /// Get the exit code, as documented in the README's "Errors and exit codes" table.
+    ///
+    /// Codes 3 to 14 are the library errors in the order of `LibraryError`'s variants, and codes
+    /// 15 and 16 are the failures specific to the CLI.
+    pub fn get_exit_code(&self) -> u8 {
+        match self {
+            Self::Io(_) | Self::CacheUnavailable => 1,
+            Self::Usage(_) => USAGE_EXIT_CODE,
+            Self::Library(error) => match error {
+                LibraryError::UnknownLanguage => 3,
+                LibraryError::AmbiguousLanguage { .. } => 4,
+                LibraryError::MissingManifest(_) => 5,
+                LibraryError::MalformedManifest { .. } => 6,
+                LibraryError::WorkspaceRoot { .. } => 7,
+                LibraryError::UnknownWorkspaceMember { .. } => 8,
+                LibraryError::UnknownFeature(_) => 9,
+                LibraryError::UnknownTarget(_) => 10,
+                LibraryError::UnknownDependency(_) => 11,
+                LibraryError::DependencyRetrieval(_) => 12,
+                LibraryError::ParserInitialisation(_) => 13,
+                LibraryError::Extraction(_) => 14,
+                // `LibraryError` is non-exhaustive, but the CLI is built with every variant.
+                _ => 1,
+            },
+            Self::PartialFailure(_) => 15,
+            Self::InsufficientVersionBump(_) => 16,
+        }
+    }
+
+    pub fn get_kind(&self) -> &'static str {
+        match self {
+            Self::Usage(_) => "usage",
+            Self::Library(error) => get_library_error_kind(error),
+            Self::PartialFailure(_) => "partial_failure",
+            Self::Io(_) => "io",
+            Self::CacheUnavailable => "cache_unavailable",
+            Self::InsufficientVersionBump(_) => "insufficient_version_bump",
+        }
+    }
+
+    /// Render the error as the object printed with `--error-format json`.
+    pub fn to_json(&self) -> Value {
+        let mut object = json!({
+            "kind": self.get_kind(),
+            "message": self.to_string(),
+            "exit_code": self.get_exit_code(),
+        });
+        if let Self::Library(LibraryError::AmbiguousLanguage { candidates }) = self {
+            object["candidates"] = candidates.iter().map(Language::to_string).collect();
+        }
+        if let Self::Library(
+            LibraryError::WorkspaceRoot { members }
+            | LibraryError::UnknownWorkspaceMember { members, .. },
+        ) = self
+        {
+            object["members"] = json!(members);
+        }
+        if let Self::InsufficientVersionBump(verdict) = self {
+            object["required_bump"] = json!(verdict.required_bump.as_str());
+            object["actual_bump"] = json!(verdict.actual_bump.map(|bump| bump.as_str()));
+        }
+        if let Self::PartialFailure(failures) = self {
+            object["failures"] = failures
+                .iter()
+                .map(|(dependency, error)| {
+                    json!({
+                        "dependency": dependency,
+                        "kind": get_library_error_kind(error),
+                        "message": error.to_string(),
+                    })
+                })
+                .collect();
+        }
+        object
+    }
+}
+
+impl fmt::Display for CliError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Usage(message) => write!(f, "{}", message),
+            Self::Library(error @ LibraryError::AmbiguousLanguage { .. }) => {
+                write!(f, "{}. Pass --language to choose one", error)
+            }
+            Self::Library(error @ LibraryError::WorkspaceRoot { .. }) => {
+                write!(f, "{}. Pass --package to choose one", error)
+            }
+            Self::Library(error) => write!(f, "{}", error),
+            Self::PartialFailure(failures) => {
+                for (dependency, error) in failures {
+                    writeln!(f, "Failed to extract {}: {}", dependency, error)?;
+                }
+                write!(f, "Failed to extract {} dependencies", failures.len())
+            }
+            Self::Io(error) => write!(f, "{}", error),
+            Self::CacheUnavailable => write!(f, "Could not determine the cache directory"),
+            Self::InsufficientVersionBump(verdict) => write!(
+                f,
+                "The API changes require a {} version bump",
+                verdict.required_bump
+            ),
+        }
+    }
+}
+
+impl From<LibraryError> for CliError {
+    fn from(error: LibraryError) -> Self {
+        Self::Library(error)
+    }
+}
+
+impl From<io::Error> for CliError {
+    fn from(error: io::Error) -> Self {
+        Self::Io(error)
+    }
+}
+
+fn get_library_error_kind(error: &LibraryError) -> &'static str {
+    match error {
+        LibraryError::UnknownLanguage => "unknown_language",
+        LibraryError::AmbiguousLanguage { .. } => "ambiguous_language",
+        LibraryError::MissingManifest(_) => "missing_manifest",
+        LibraryError::MalformedManifest { .. } => "malformed_manifest",
+        LibraryError::WorkspaceRoot { .. } => "workspace_root",
+        LibraryError::UnknownWorkspaceMember { .. } => "unknown_workspace_member",
+        LibraryError::UnknownFeature(_) => "unknown_feature",
+        LibraryError::UnknownTarget(_) => "unknown_target",
+        LibraryError::UnknownDependency(_) => "unknown_dependency",
+        LibraryError::DependencyRetrieval(_) => "dependency_retrieval",
+        LibraryError::ParserInitialisation(_) => "parser_initialisation",
+        LibraryError::Extraction(_) => "extraction",
+        _ => "library",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use daipendency::SemverImpact;
+
+    #[test]
+    fn test_library_error_exit_code() {
+        let error = CliError::from(LibraryError::UnknownDependency("serde".to_string()));
+
+        assert_eq!(error.get_exit_code(), 11);
+        assert_eq!(error.get_kind(), "unknown_dependency");
+    }
+
+    #[test]
+    fn test_usage_error_json() {
+        let error = CliError::Usage("`--nope` is not expected in this context".to_string());
+
+        assert_eq!(
+            error.to_json(),
+            json!({
+                "kind": "usage",
+                "message": "`--nope` is not expected in this context",
+                "exit_code": 2,
+            })
+        );
+    }
+
+    #[test]
+    fn test_library_error_json() {
+        let error = CliError::from(LibraryError::UnknownDependency("serde".to_string()));
+
+        assert_eq!(
+            error.to_json(),
+            json!({
+                "kind": "unknown_dependency",
+                "message": "'serde' is not a dependency",
+                "exit_code": 11,
+            })
+        );
+    }
+
+    #[test]
+    fn test_ambiguous_language_json() {
+        let error = CliError::from(LibraryError::AmbiguousLanguage {
+            candidates: vec![Language::Rust, Language::Python],
+        });
+
+        let json = error.to_json();
+
+        assert_eq!(json["kind"], "ambiguous_language");
+        assert_eq!(json["exit_code"], 4);
+        assert_eq!(json["candidates"], json!(["rust", "python"]));
+        assert_eq!(
+            json["message"],
+            "Several languages recognised the library: rust, python. Pass --language to choose one"
+        );
+    }
+
+    #[test]
+    fn test_workspace_root_json() {
+        let error = CliError::from(LibraryError::WorkspaceRoot {
+            members: vec!["app-core".to_string(), "app-server".to_string()],
+        });
+
+        let json = error.to_json();
+
+        assert_eq!(json["kind"], "workspace_root");
+        assert_eq!(json["exit_code"], 7);
+        assert_eq!(json["members"], json!(["app-core", "app-server"]));
+        assert_eq!(
+            json["message"],
+            "The library is a workspace, so one of its members has to be chosen: \
+             app-core, app-server. Pass --package to choose one"
+        );
+    }
+
+    #[test]
+    fn test_partial_failure_json() {
+        let error = CliError::PartialFailure(vec![(
+            "left-pad".to_string(),
+            LibraryError::DependencyRetrieval("Not installed".to_string()),
+        )]);
+
+        let json = error.to_json();
+
+        assert_eq!(json["kind"], "partial_failure");
+        assert_eq!(json["exit_code"], 15);
+        assert_eq!(
+            json["failures"],
+            json!([{
+                "dependency": "left-pad",
+                "kind": "dependency_retrieval",
+                "message": "Failed to retrieve dependency: Not installed",
+            }])
+        );
+    }
+
+    #[test]
+    fn test_insufficient_version_bump_json() {
+        let error = CliError::InsufficientVersionBump(SemverVerdict {
+            required_bump: SemverImpact::Major,
+            actual_bump: Some(SemverImpact::Minor),
+        });
+
+        assert_eq!(
+            error.to_json(),
+            json!({
+                "kind": "insufficient_version_bump",
+                "message": "The API changes require a major version bump",
+                "exit_code": 16,
+                "required_bump": "major",
+                "actual_bump": "minor",
+            })
+        );
+    }
+
+    #[test]
+    fn test_partial_failure_message() {
+        let error = CliError::PartialFailure(vec![(
+            "left-pad".to_string(),
+            LibraryError::UnknownLanguage,
+        )]);
+
+        assert_eq!(
+            error.to_string(),
+            "Failed to extract left-pad: No supported language recognised the library\n\
+             Failed to extract 1 dependencies"
+        );
+    }
+}
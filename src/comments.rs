@@ -0,0 +1,82 @@
+/// Remove the `//` and `/* */` comments from `source_code`, including doc comments, as in Rust,
+/// Go, Java, Kotlin and ECMAScript. Block comments may be nested, as in Rust and Kotlin.
+pub fn strip_c_style_comments(source_code: &str) -> String {
+    let mut output = String::with_capacity(source_code.len());
+    let mut characters = source_code.chars().peekable();
+    while let Some(character) = characters.next() {
+        match (character, characters.peek()) {
+            ('/', Some('/')) => while characters.next_if(|next| *next != '\n').is_some() {},
+            ('/', Some('*')) => {
+                characters.next();
+                let mut depth = 1;
+                while depth > 0 {
+                    match (characters.next(), characters.peek()) {
+                        (Some('*'), Some('/')) => {
+                            characters.next();
+                            depth -= 1;
+                        }
+                        (Some('/'), Some('*')) => {
+                            characters.next();
+                            depth += 1;
+                        }
+                        (None, _) => break,
+                        _ => {}
+                    }
+                }
+            }
+            ('"', _) => {
+                output.push(character);
+                while let Some(next) = characters.next() {
+                    output.push(next);
+                    match next {
+                        '\\' => output.extend(characters.next()),
+                        '"' => break,
+                        _ => {}
+                    }
+                }
+            }
+            ('\'', _) => {
+                output.push(character);
+                // Keep character literals such as `'"'` intact, unlike lifetimes such as `'a`.
+                let mut lookahead = characters.clone();
+                let literal_length = match (lookahead.next(), lookahead.next(), lookahead.next()) {
+                    (Some('\\'), Some(_), Some('\'')) => 3,
+                    (Some(_), Some('\''), _) => 2,
+                    _ => 0,
+                };
+                output.extend(characters.by_ref().take(literal_length));
+            }
+            _ => output.push(character),
+        }
+    }
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn comment_markers_in_literals() {
+        assert_eq!(
+            strip_c_style_comments("const A: &str = \"// {\"; // }\nconst B: char = '\"'; /* } */"),
+            "const A: &str = \"// {\"; \nconst B: char = '\"'; "
+        );
+    }
+
+    #[test]
+    fn nested_block_comments() {
+        assert_eq!(
+            strip_c_style_comments("/* a /* b */ c */pub fn f();"),
+            "pub fn f();"
+        );
+    }
+
+    #[test]
+    fn lifetimes() {
+        assert_eq!(
+            strip_c_style_comments("pub fn f<'a>(x: &'a str) -> &'a str; // x"),
+            "pub fn f<'a>(x: &'a str) -> &'a str; "
+        );
+    }
+}
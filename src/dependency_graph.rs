@@ -0,0 +1,88 @@
+use daipendency_extractor::DependencyResolutionError;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+/// The resolved dependencies of every library in the dependency tree of a dependant.
+#[derive(Debug, Default)]
+pub struct DependencyGraph {
+    /// The path of each dependency, keyed by the path of the library that declares it and the
+    /// dependency's name.
+    paths: HashMap<(PathBuf, String), PathBuf>,
+}
+
+impl DependencyGraph {
+    /// Record that the library at `declarer_path` depends on `name`, found at `dependency_path`.
+    ///
+    /// The first path recorded for a dependency wins.
+    pub fn insert(&mut self, declarer_path: &Path, name: &str, dependency_path: PathBuf) {
+        self.paths
+            .entry((normalise_path(declarer_path), name.to_string()))
+            .or_insert(dependency_path);
+    }
+
+    /// Resolve the dependency called `name` of the library at `declarer_path`.
+    pub fn resolve(
+        &self,
+        name: &str,
+        declarer_path: &Path,
+    ) -> Result<PathBuf, DependencyResolutionError> {
+        self.paths
+            .get(&(normalise_path(declarer_path), name.to_string()))
+            .cloned()
+            .ok_or_else(|| DependencyResolutionError::MissingDependency(name.to_string()))
+    }
+}
+
+fn normalise_path(path: &Path) -> PathBuf {
+    path.canonicalize().unwrap_or_else(|_| path.to_path_buf())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use assertables::assert_matches;
+    use daipendency_testing::tempdir::TempDir;
+
+    const STUB_DEPENDENCY: &str = "syn";
+
+    #[test]
+    fn declared_dependency() {
+        let temp_dir = TempDir::new();
+        let mut graph = DependencyGraph::default();
+        graph.insert(&temp_dir.path, STUB_DEPENDENCY, PathBuf::from("/syn-2"));
+
+        let result = graph.resolve(STUB_DEPENDENCY, &temp_dir.path);
+
+        assert_eq!(result.unwrap(), PathBuf::from("/syn-2"));
+    }
+
+    #[test]
+    fn dependency_of_other_library() {
+        let temp_dir = TempDir::new();
+        let mut graph = DependencyGraph::default();
+        graph.insert(&temp_dir.path, STUB_DEPENDENCY, PathBuf::from("/syn-2"));
+
+        let result = graph.resolve(STUB_DEPENDENCY, &temp_dir.path.join("other"));
+
+        assert_matches!(
+            result,
+            Err(DependencyResolutionError::MissingDependency(name)) if name == STUB_DEPENDENCY
+        );
+    }
+
+    #[test]
+    fn equivalent_declarer_paths() {
+        let temp_dir = TempDir::new();
+        temp_dir.create_file("app/Cargo.toml", "").unwrap();
+        let mut graph = DependencyGraph::default();
+        graph.insert(
+            &temp_dir.path.join("app"),
+            STUB_DEPENDENCY,
+            PathBuf::from("/syn-2"),
+        );
+
+        let result = graph.resolve(STUB_DEPENDENCY, &temp_dir.path.join("app/../app"));
+
+        assert_eq!(result.unwrap(), PathBuf::from("/syn-2"));
+    }
+}
@@ -0,0 +1,596 @@
+use crate::languages::{Language, LanguageConfig};
+use crate::library::Library;
+use crate::semver::{get_version_bump, SemverImpact, SemverVerdict};
+use crate::symbol_kind::SymbolKind;
+use std::collections::{BTreeMap, BTreeSet};
+
+/// The differences between the public APIs of two versions of a library.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LibraryDiff {
+    pub name: String,
+    pub language: Language,
+    pub old_version: Option<String>,
+    pub new_version: Option<String>,
+    pub added_namespaces: Vec<String>,
+    pub removed_namespaces: Vec<String>,
+    /// The symbols that were added, removed or changed, sorted by namespace and name.
+    pub changes: Vec<SymbolChange>,
+}
+
+impl LibraryDiff {
+    pub fn is_empty(&self) -> bool {
+        self.added_namespaces.is_empty()
+            && self.removed_namespaces.is_empty()
+            && self.changes.is_empty()
+    }
+
+    /// Compare the version bump that the changes require with the actual one.
+    ///
+    /// Removing a namespace is breaking and adding one is a minor change, regardless of the
+    /// symbols in it.
+    pub fn get_verdict(&self) -> SemverVerdict {
+        let namespace_impact = if !self.removed_namespaces.is_empty() {
+            SemverImpact::Major
+        } else if !self.added_namespaces.is_empty() {
+            SemverImpact::Minor
+        } else {
+            SemverImpact::Patch
+        };
+        let required_bump = self
+            .changes
+            .iter()
+            .map(|change| change.impact)
+            .fold(namespace_impact, SemverImpact::max);
+        let actual_bump = match (&self.old_version, &self.new_version) {
+            (Some(old_version), Some(new_version)) => get_version_bump(old_version, new_version),
+            _ => None,
+        };
+        SemverVerdict {
+            required_bump,
+            actual_bump,
+        }
+    }
+}
+
+/// A symbol that differs between two versions of a library.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SymbolChange {
+    pub namespace: String,
+    pub name: String,
+    pub kind: ChangeKind,
+    /// The kind of item that the symbol declares in the new version, or in the old one if it
+    /// was removed.
+    pub symbol_kind: Option<SymbolKind>,
+    /// The version bump that the change requires.
+    pub impact: SemverImpact,
+    /// The source code in the old version, unless the symbol was added.
+    pub old_source_code: Option<String>,
+    /// The source code in the new version, unless the symbol was removed.
+    pub new_source_code: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChangeKind {
+    Added,
+    Removed,
+    Changed,
+}
+
+impl ChangeKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Added => "added",
+            Self::Removed => "removed",
+            Self::Changed => "changed",
+        }
+    }
+}
+
+/// The source code of each symbol, keyed by namespace and then by symbol name and kind.
+type SymbolIndex<'a> = BTreeMap<&'a str, BTreeMap<(&'a str, Option<SymbolKind>), String>>;
+
+impl Library {
+    /// Compare the public API of this library with that of a `new` version of it.
+    ///
+    /// Symbols are matched by namespace, name and kind, and they are only reported as changed if
+    /// their source code differs once comments, docs and whitespace are ignored.
+    pub fn diff(&self, new: &Library) -> LibraryDiff {
+        let old_symbols = index_symbols(self);
+        let new_symbols = index_symbols(new);
+        let namespaces = old_symbols
+            .keys()
+            .chain(new_symbols.keys())
+            .copied()
+            .collect::<BTreeSet<_>>();
+        let empty_namespace = BTreeMap::new();
+        let config = LanguageConfig::get_from_language(new.language);
+
+        let mut diff = LibraryDiff {
+            name: new.name.clone(),
+            language: new.language,
+            old_version: self.version.clone(),
+            new_version: new.version.clone(),
+            added_namespaces: Vec::new(),
+            removed_namespaces: Vec::new(),
+            changes: Vec::new(),
+        };
+        for namespace in namespaces {
+            let old_namespace = old_symbols.get(namespace);
+            let new_namespace = new_symbols.get(namespace);
+            match (old_namespace, new_namespace) {
+                (None, Some(_)) => diff.added_namespaces.push(namespace.to_string()),
+                (Some(_), None) => diff.removed_namespaces.push(namespace.to_string()),
+                _ => {}
+            }
+            let old_namespace = old_namespace.unwrap_or(&empty_namespace);
+            let new_namespace = new_namespace.unwrap_or(&empty_namespace);
+            let keys = old_namespace
+                .keys()
+                .chain(new_namespace.keys())
+                .copied()
+                .collect::<BTreeSet<_>>();
+            for key in keys {
+                let (name, symbol_kind) = key;
+                let old_source_code = old_namespace.get(&key);
+                let new_source_code = new_namespace.get(&key);
+                let old_signature =
+                    old_source_code.map(|source_code| get_signature(source_code, config));
+                let new_signature =
+                    new_source_code.map(|source_code| get_signature(source_code, config));
+                let kind = match (&old_signature, &new_signature) {
+                    (None, _) => ChangeKind::Added,
+                    (_, None) => ChangeKind::Removed,
+                    (Some(old), Some(new)) if old != new => ChangeKind::Changed,
+                    _ => continue,
+                };
+                diff.changes.push(SymbolChange {
+                    namespace: namespace.to_string(),
+                    name: name.to_string(),
+                    kind,
+                    symbol_kind,
+                    impact: (config.change_classifier)(
+                        kind,
+                        old_signature.as_deref(),
+                        new_signature.as_deref(),
+                    ),
+                    old_source_code: old_source_code.cloned(),
+                    new_source_code: new_source_code.cloned(),
+                });
+            }
+        }
+        diff
+    }
+}
+
+/// Index the symbols of `library`, merging the symbols that share a name and kind in the same
+/// namespace (e.g. the implementations of a type), but not those of different kinds (e.g. a
+/// function and a macro).
+fn index_symbols(library: &Library) -> SymbolIndex<'_> {
+    let mut index = SymbolIndex::new();
+    for namespace in &library.namespaces {
+        let symbols = index.entry(namespace.name.as_str()).or_default();
+        for symbol in &namespace.symbols {
+            symbols
+                .entry((symbol.name.as_str(), library.get_symbol_kind(symbol)))
+                .and_modify(|source_code| {
+                    source_code.push_str("\n\n");
+                    source_code.push_str(&symbol.source_code);
+                })
+                .or_insert_with(|| symbol.source_code.clone());
+        }
+    }
+    index
+}
+
+/// Strip the comments, docs and insignificant whitespace from `source_code`.
+fn get_signature(source_code: &str, config: &LanguageConfig) -> String {
+    (config.comment_stripper)(source_code)
+        .lines()
+        .map(|line| line.split_whitespace().collect::<Vec<_>>().join(" "))
+        .filter(|line| !line.is_empty())
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use daipendency_extractor::{Namespace, Symbol};
+
+    const STUB_LIBRARY_NAME: &str = "test-lib";
+
+    fn create_library(version: &str, namespaces: &[(&str, &[(&str, &str)])]) -> Library {
+        Library {
+            name: STUB_LIBRARY_NAME.to_string(),
+            version: Some(version.to_string()),
+            documentation: String::new(),
+            namespaces: namespaces
+                .iter()
+                .map(|(name, symbols)| Namespace {
+                    name: name.to_string(),
+                    symbols: symbols
+                        .iter()
+                        .map(|(name, source_code)| Symbol {
+                            name: name.to_string(),
+                            source_code: source_code.to_string(),
+                        })
+                        .collect(),
+                    doc_comment: None,
+                })
+                .collect(),
+            language: Language::Rust,
+            features: None,
+            target: None,
+        }
+    }
+
+    fn get_changes(diff: &LibraryDiff) -> Vec<(&str, &str, ChangeKind)> {
+        diff.changes
+            .iter()
+            .map(|change| (change.namespace.as_str(), change.name.as_str(), change.kind))
+            .collect()
+    }
+
+    mod symbols {
+        use super::*;
+
+        #[test]
+        fn identical_libraries() {
+            let library = create_library("1.0.0", &[("lib", &[("f", "pub fn f();")])]);
+
+            let diff = library.diff(&library);
+
+            assert!(diff.is_empty());
+        }
+
+        #[test]
+        fn added_symbol() {
+            let old = create_library("1.0.0", &[("lib", &[("f", "pub fn f();")])]);
+            let new = create_library(
+                "1.1.0",
+                &[("lib", &[("f", "pub fn f();"), ("g", "pub fn g();")])],
+            );
+
+            let diff = old.diff(&new);
+
+            assert_eq!(get_changes(&diff), vec![("lib", "g", ChangeKind::Added)]);
+            assert_eq!(diff.changes[0].symbol_kind, Some(SymbolKind::Function));
+            assert_eq!(diff.changes[0].old_source_code, None);
+            assert_eq!(
+                diff.changes[0].new_source_code.as_deref(),
+                Some("pub fn g();")
+            );
+        }
+
+        #[test]
+        fn removed_symbol() {
+            let old = create_library(
+                "1.0.0",
+                &[("lib", &[("f", "pub fn f();"), ("g", "pub fn g();")])],
+            );
+            let new = create_library("2.0.0", &[("lib", &[("f", "pub fn f();")])]);
+
+            let diff = old.diff(&new);
+
+            assert_eq!(get_changes(&diff), vec![("lib", "g", ChangeKind::Removed)]);
+        }
+
+        #[test]
+        fn changed_signature() {
+            let old = create_library("1.0.0", &[("lib", &[("f", "pub fn f();")])]);
+            let new = create_library("2.0.0", &[("lib", &[("f", "pub fn f(x: u8);")])]);
+
+            let diff = old.diff(&new);
+
+            assert_eq!(get_changes(&diff), vec![("lib", "f", ChangeKind::Changed)]);
+            assert_eq!(
+                diff.changes[0].old_source_code.as_deref(),
+                Some("pub fn f();")
+            );
+        }
+
+        #[test]
+        fn changed_documentation() {
+            let old = create_library("1.0.0", &[("lib", &[("f", "/// Old\npub fn f();")])]);
+            let new = create_library(
+                "1.0.1",
+                &[("lib", &[("f", "/**\n * New\n */\npub fn  f();")])],
+            );
+
+            let diff = old.diff(&new);
+
+            assert!(diff.is_empty());
+        }
+
+        #[test]
+        fn changed_docstring() {
+            let old = Library {
+                language: Language::Python,
+                ..create_library(
+                    "1.0.0",
+                    &[("lib", &[("f", "def f(x):\n    \"\"\"Old.\"\"\"\n    ...")])],
+                )
+            };
+            let new = Library {
+                language: Language::Python,
+                ..create_library(
+                    "1.0.1",
+                    &[(
+                        "lib",
+                        &[(
+                            "f",
+                            "def f(x):  # Returns x\n    \"\"\"\n    New.\n    \"\"\"\n    ...",
+                        )],
+                    )],
+                )
+            };
+
+            let diff = old.diff(&new);
+
+            assert!(diff.is_empty());
+        }
+
+        #[test]
+        fn changed_inline_documentation() {
+            let old = Library {
+                language: Language::Go,
+                ..create_library("1.0.0", &[("lib", &[("F", "// F does it.\nfunc F() int")])])
+            };
+            let new = Library {
+                language: Language::Go,
+                ..create_library(
+                    "1.0.1",
+                    &[(
+                        "lib",
+                        &[("F", "// F does it\n// quickly.\nfunc F() int /* cached */")],
+                    )],
+                )
+            };
+
+            let diff = old.diff(&new);
+
+            assert!(diff.is_empty());
+        }
+
+        #[test]
+        fn symbols_sharing_name_and_kind() {
+            let old = create_library(
+                "1.0.0",
+                &[("lib", &[("S", "impl S {}"), ("S", "impl Clone for S {}")])],
+            );
+            let new = create_library("2.0.0", &[("lib", &[("S", "impl S {}")])]);
+
+            let diff = old.diff(&new);
+
+            assert_eq!(get_changes(&diff), vec![("lib", "S", ChangeKind::Changed)]);
+        }
+
+        #[test]
+        fn function_and_macro_sharing_name() {
+            let old = create_library(
+                "1.0.0",
+                &[(
+                    "lib",
+                    &[
+                        ("thing", "pub fn thing();"),
+                        ("thing", "#[macro_export]\nmacro_rules! thing;"),
+                    ],
+                )],
+            );
+            let new = create_library(
+                "2.0.0",
+                &[(
+                    "lib",
+                    &[
+                        ("thing", "pub fn thing(x: u8);"),
+                        ("thing", "#[macro_export]\nmacro_rules! thing;"),
+                    ],
+                )],
+            );
+
+            let diff = old.diff(&new);
+
+            assert_eq!(
+                get_changes(&diff),
+                vec![("lib", "thing", ChangeKind::Changed)]
+            );
+            assert_eq!(diff.changes[0].symbol_kind, Some(SymbolKind::Function));
+            assert_eq!(diff.changes[0].impact, SemverImpact::Major);
+            assert_eq!(
+                diff.changes[0].new_source_code.as_deref(),
+                Some("pub fn thing(x: u8);")
+            );
+        }
+
+        #[test]
+        fn added_provided_trait_method() {
+            let old = create_library(
+                "1.0.0",
+                &[("lib", &[("T", "pub trait T {\n    fn a(&self);\n}")])],
+            );
+            let new = create_library(
+                "1.1.0",
+                &[(
+                    "lib",
+                    &[(
+                        "T",
+                        "pub trait T {\n    fn a(&self);\n    fn b(&self) { ... }\n}",
+                    )],
+                )],
+            );
+
+            let diff = old.diff(&new);
+
+            assert_eq!(get_changes(&diff), vec![("lib", "T", ChangeKind::Changed)]);
+            assert_eq!(diff.changes[0].impact, SemverImpact::Minor);
+        }
+
+        #[test]
+        fn added_method() {
+            let old = Library {
+                language: Language::Python,
+                ..create_library(
+                    "1.0.0",
+                    &[(
+                        "lib",
+                        &[(
+                            "C",
+                            "class C:\n    \"\"\"A class.\"\"\"\n    def f(self): ...",
+                        )],
+                    )],
+                )
+            };
+            let new = Library {
+                language: Language::Python,
+                ..create_library(
+                    "1.1.0",
+                    &[(
+                        "lib",
+                        &[("C", "class C:\n    \"\"\"The class.\"\"\"\n    def f(self): ...\n    def g(self): ...")],
+                    )],
+                )
+            };
+
+            let diff = old.diff(&new);
+
+            assert_eq!(get_changes(&diff), vec![("lib", "C", ChangeKind::Changed)]);
+            assert_eq!(diff.changes[0].impact, SemverImpact::Minor);
+        }
+
+        #[test]
+        fn block_commented_item() {
+            let old = create_library(
+                "1.0.0",
+                &[(
+                    "lib",
+                    &[(
+                        "S",
+                        "/**\n * A `{` brace\n */\npub struct S {\n    pub x: u8,\n}",
+                    )],
+                )],
+            );
+            let new = create_library(
+                "1.1.0",
+                &[(
+                    "lib",
+                    &[(
+                        "S",
+                        "/**\n * A `{` brace\n */\n#[derive(Debug)]\npub struct S {\n    pub x: u8,\n}",
+                    )],
+                )],
+            );
+
+            let diff = old.diff(&new);
+
+            assert_eq!(get_changes(&diff), vec![("lib", "S", ChangeKind::Changed)]);
+            assert_eq!(diff.changes[0].impact, SemverImpact::Minor);
+        }
+    }
+
+    mod namespaces {
+        use super::*;
+
+        #[test]
+        fn added_namespace() {
+            let old = create_library("1.0.0", &[("lib", &[])]);
+            let new = create_library(
+                "1.1.0",
+                &[
+                    ("lib", &[]),
+                    ("lib::net", &[("connect", "pub fn connect();")]),
+                ],
+            );
+
+            let diff = old.diff(&new);
+
+            assert_eq!(diff.added_namespaces, vec!["lib::net"]);
+            assert_eq!(
+                get_changes(&diff),
+                vec![("lib::net", "connect", ChangeKind::Added)]
+            );
+        }
+
+        #[test]
+        fn removed_namespace() {
+            let old = create_library("1.0.0", &[("lib", &[]), ("lib::net", &[])]);
+            let new = create_library("2.0.0", &[("lib", &[])]);
+
+            let diff = old.diff(&new);
+
+            assert_eq!(diff.removed_namespaces, vec!["lib::net"]);
+            assert!(diff.changes.is_empty());
+        }
+    }
+
+    mod verdict {
+        use super::*;
+
+        #[test]
+        fn breaking_change_in_minor_bump() {
+            let old = create_library("1.0.0", &[("lib", &[("f", "pub fn f();")])]);
+            let new = create_library("1.1.0", &[("lib", &[])]);
+
+            let verdict = old.diff(&new).get_verdict();
+
+            assert_eq!(verdict.required_bump, SemverImpact::Major);
+            assert_eq!(verdict.actual_bump, Some(SemverImpact::Minor));
+            assert_eq!(verdict.is_version_bump_sufficient(), Some(false));
+        }
+
+        #[test]
+        fn addition_in_minor_bump() {
+            let old = create_library("1.0.0", &[("lib", &[("f", "pub fn f();")])]);
+            let new = create_library(
+                "1.1.0",
+                &[("lib", &[("f", "pub fn f();"), ("g", "pub fn g();")])],
+            );
+
+            let diff = old.diff(&new);
+
+            assert_eq!(diff.changes[0].impact, SemverImpact::Minor);
+            assert_eq!(diff.get_verdict().is_version_bump_sufficient(), Some(true));
+        }
+
+        #[test]
+        fn no_changes() {
+            let library = create_library("1.0.0", &[("lib", &[("f", "pub fn f();")])]);
+
+            let verdict = library.diff(&library).get_verdict();
+
+            assert_eq!(verdict.required_bump, SemverImpact::Patch);
+        }
+
+        #[test]
+        fn removed_namespace() {
+            let old = create_library("1.0.0", &[("lib", &[]), ("lib::net", &[])]);
+            let new = create_library("1.0.1", &[("lib", &[])]);
+
+            let verdict = old.diff(&new).get_verdict();
+
+            assert_eq!(verdict.required_bump, SemverImpact::Major);
+        }
+
+        #[test]
+        fn unknown_version() {
+            let old = create_library("1.0.0", &[]);
+            let mut new = create_library("1.0.0", &[]);
+            new.version = None;
+
+            let verdict = old.diff(&new).get_verdict();
+
+            assert_eq!(verdict.actual_bump, None);
+        }
+    }
+
+    #[test]
+    fn versions() {
+        let old = create_library("1.0.0", &[]);
+        let new = create_library("2.0.0", &[]);
+
+        let diff = old.diff(&new);
+
+        assert_eq!(diff.name, STUB_LIBRARY_NAME);
+        assert_eq!(diff.old_version.as_deref(), Some("1.0.0"));
+        assert_eq!(diff.new_version.as_deref(), Some("2.0.0"));
+    }
+}
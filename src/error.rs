@@ -0,0 +1,145 @@
+use crate::languages::Language;
+use daipendency_extractor::{
+    DependencyResolutionError, ExtractionError, LibraryMetadataError, ParserError,
+};
+use thiserror::Error;
+
+/// Error whilst loading a library or its dependencies
+#[derive(Error, Debug)]
+#[non_exhaustive]
+pub enum LibraryError {
+    /// None of the supported languages recognised the library.
+    #[error("No supported language recognised the library")]
+    UnknownLanguage,
+    /// Several languages recognised the library, e.g. a Rust crate with Python bindings.
+    #[error(
+        "Several languages recognised the library: {}",
+        format_languages(candidates)
+    )]
+    AmbiguousLanguage { candidates: Vec<Language> },
+    /// The library has no manifest that the language can read.
+    #[error("Missing manifest: {0}")]
+    MissingManifest(#[source] std::io::Error),
+    /// The manifest of the library could not be parsed.
+    #[error("Malformed manifest for {language:?}: {error}")]
+    MalformedManifest { language: Language, error: String },
+    /// The library is the root of a workspace without a library of its own.
+    #[error(
+        "The library is a workspace, so one of its members has to be chosen: {}",
+        members.join(", ")
+    )]
+    WorkspaceRoot { members: Vec<String> },
+    /// The workspace does not have the member that was selected.
+    #[error(
+        "'{name}' is not a member of the workspace, {}",
+        format_members(members)
+    )]
+    UnknownWorkspaceMember { name: String, members: Vec<String> },
+    /// The crate does not have the feature that was selected.
+    #[error("'{0}' is not a feature of the library")]
+    UnknownFeature(String),
+    /// The target triple names an unknown platform.
+    #[error("'{0}' is not a recognised target triple")]
+    UnknownTarget(String),
+    /// The dependant does not declare the dependency.
+    #[error("'{0}' is not a dependency")]
+    UnknownDependency(String),
+    /// The dependency is declared but could not be found, e.g. because it is not installed.
+    #[error("Failed to retrieve dependency: {0}")]
+    DependencyRetrieval(String),
+    /// The parser for the language could not be set up.
+    #[error("Failed to initialise parser: {0}")]
+    ParserInitialisation(#[from] ParserError),
+    /// The source code could not be read or parsed.
+    #[error("Failed to extract public API: {0}")]
+    Extraction(#[from] ExtractionError),
+}
+
+impl LibraryError {
+    pub(crate) fn from_metadata_error(error: LibraryMetadataError, language: Language) -> Self {
+        match error {
+            LibraryMetadataError::MissingManifest(error) => Self::MissingManifest(error),
+            LibraryMetadataError::MalformedManifest(error) => {
+                Self::MalformedManifest { language, error }
+            }
+        }
+    }
+}
+
+fn format_languages(languages: &[Language]) -> String {
+    languages
+        .iter()
+        .map(Language::to_string)
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn format_members(members: &[String]) -> String {
+    match members {
+        [] => "which has no members".to_string(),
+        _ => format!("whose members are: {}", members.join(", ")),
+    }
+}
+
+impl From<DependencyResolutionError> for LibraryError {
+    fn from(error: DependencyResolutionError) -> Self {
+        match error {
+            DependencyResolutionError::MissingDependency(name) => Self::UnknownDependency(name),
+            DependencyResolutionError::RetrievalFailure(reason) => {
+                Self::DependencyRetrieval(reason)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use assertables::assert_matches;
+
+    #[test]
+    fn missing_dependency() {
+        let error = LibraryError::from(DependencyResolutionError::MissingDependency(
+            "serde".to_string(),
+        ));
+
+        assert_matches!(error, LibraryError::UnknownDependency(name) if name == "serde");
+    }
+
+    #[test]
+    fn retrieval_failure() {
+        let error = LibraryError::from(DependencyResolutionError::RetrievalFailure(
+            "Not installed".to_string(),
+        ));
+
+        assert_matches!(error, LibraryError::DependencyRetrieval(reason) if reason == "Not installed");
+    }
+
+    #[test]
+    fn malformed_manifest() {
+        let error = LibraryError::from_metadata_error(
+            LibraryMetadataError::MalformedManifest("Invalid TOML".to_string()),
+            Language::Rust,
+        );
+
+        assert_matches!(
+            error,
+            LibraryError::MalformedManifest {
+                language: Language::Rust,
+                error,
+            } if error == "Invalid TOML"
+        );
+    }
+
+    #[test]
+    fn ambiguous_language_message() {
+        let error = LibraryError::AmbiguousLanguage {
+            candidates: vec![Language::Rust, Language::Python],
+        };
+
+        assert_eq!(
+            error.to_string(),
+            "Several languages recognised the library: rust, python"
+        );
+    }
+}
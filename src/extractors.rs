@@ -1,9 +1,9 @@
 use std::path::Path;
 
-use crate::languages::{Language, LanguageConfig};
+use crate::error::LibraryError;
+use crate::languages::{Language, LanguageConfig, DISCOVERY_ORDER};
 use crate::library::BoxedExtractor;
-use daipendency_extractor::{LibraryMetadata, LibraryMetadataError};
-use thiserror::Error;
+use daipendency_extractor::{DependencyResolutionError, LibraryMetadata, LibraryMetadataError};
 
 pub fn get_extractor(language: Language) -> BoxedExtractor {
     let config = LanguageConfig::get_from_language(language);
@@ -11,6 +11,16 @@ pub fn get_extractor(language: Language) -> BoxedExtractor {
     (config.extractor_initialiser)()
 }
 
+/// List the direct dependencies declared in the manifest of the library at `path`.
+pub fn list_dependencies(
+    language: Language,
+    path: &Path,
+) -> Result<Vec<String>, DependencyResolutionError> {
+    let config = LanguageConfig::get_from_language(language);
+
+    (config.dependency_lister)(path)
+}
+
 pub struct ExtractorDiscovery {
     pub language: Language,
     pub extractor: BoxedExtractor,
@@ -27,35 +37,77 @@ impl std::fmt::Debug for ExtractorDiscovery {
     }
 }
 
-#[derive(Error, Debug)]
-pub enum ExtractorDiscoveryError {
-    #[error("Malformed manifest for {language:?}: {error}")]
-    MalformedManifest { language: Language, error: String },
-    #[error("No matching extractor found")]
-    NotFound,
-}
-
-pub fn discover_extractor(path: &Path) -> Result<ExtractorDiscovery, ExtractorDiscoveryError> {
-    for language in LanguageConfig::get_all().keys() {
-        let extractor = get_extractor(*language);
+/// Detect the language of the library at `path`.
+///
+/// Only the languages whose manifest files are present get their extractor initialised and their
+/// manifest parsed, in [`DISCOVERY_ORDER`]. A language sharing a manifest file with one that
+/// recognised the library already is a dialect of it and is skipped, but the library is ambiguous
+/// if languages with different manifests recognise it.
+pub fn discover_extractor(path: &Path) -> Result<ExtractorDiscovery, LibraryError> {
+    let mut discoveries: Vec<ExtractorDiscovery> = Vec::new();
+    let mut first_error = None;
+    for language in DISCOVERY_ORDER {
+        let config = LanguageConfig::get_from_language(language);
+        if !config.has_manifest(path) || shares_manifest(language, &discoveries) {
+            continue;
+        }
+        let extractor = (config.extractor_initialiser)();
         match extractor.get_library_metadata(path) {
-            Ok(library_metadata) => {
-                return Ok(ExtractorDiscovery {
-                    language: *language,
-                    extractor,
-                    library_metadata,
-                })
-            }
+            Ok(library_metadata) => discoveries.push(ExtractorDiscovery {
+                language,
+                extractor,
+                library_metadata,
+            }),
             Err(LibraryMetadataError::MalformedManifest(error)) => {
-                return Err(ExtractorDiscoveryError::MalformedManifest {
-                    language: *language,
-                    error,
-                })
+                first_error.get_or_insert(LibraryError::MalformedManifest { language, error });
             }
             Err(LibraryMetadataError::MissingManifest(_)) => continue,
         }
     }
-    Err(ExtractorDiscoveryError::NotFound)
+
+    match discoveries.len() {
+        0 => Err(first_error.unwrap_or(LibraryError::UnknownLanguage)),
+        1 => Ok(discoveries.remove(0)),
+        _ => Err(LibraryError::AmbiguousLanguage {
+            candidates: discoveries
+                .iter()
+                .map(|discovery| discovery.language)
+                .collect(),
+        }),
+    }
+}
+
+/// Detect the dialect of the library at `path`, among the languages sharing a manifest file with
+/// `language` (e.g. TypeScript for a JavaScript dependant whose dependency ships declarations).
+///
+/// Dependencies needn't be written in the dialect of their dependant, so it falls back to
+/// `language` only if no dialect recognises the library.
+pub fn detect_dialect(language: Language, path: &Path) -> Language {
+    let dialects: Vec<Language> = DISCOVERY_ORDER
+        .into_iter()
+        .filter(|dialect| have_common_manifest(language, *dialect))
+        .collect();
+    if dialects.len() < 2 {
+        return language;
+    }
+    dialects
+        .into_iter()
+        .find(|dialect| get_extractor(*dialect).get_library_metadata(path).is_ok())
+        .unwrap_or(language)
+}
+
+fn shares_manifest(language: Language, discoveries: &[ExtractorDiscovery]) -> bool {
+    discoveries
+        .iter()
+        .any(|discovery| have_common_manifest(language, discovery.language))
+}
+
+fn have_common_manifest(language: Language, other_language: Language) -> bool {
+    let manifest_file_names = LanguageConfig::get_from_language(language).manifest_file_names;
+    LanguageConfig::get_from_language(other_language)
+        .manifest_file_names
+        .iter()
+        .any(|file_name| manifest_file_names.contains(file_name))
 }
 
 #[cfg(test)]
@@ -78,6 +130,56 @@ mod tests {
         }
     }
 
+    mod dialect_detection {
+        use super::*;
+        use daipendency_testing::tempdir::TempDir;
+
+        fn create_package(temp_dir: &TempDir, module: &str) {
+            temp_dir
+                .create_file("package.json", r#"{"name": "left-pad"}"#)
+                .unwrap();
+            temp_dir.create_file(module, "").unwrap();
+        }
+
+        #[test]
+        fn declarations() {
+            let temp_dir = TempDir::new();
+            create_package(&temp_dir, "index.d.ts");
+
+            let dialect = detect_dialect(Language::JavaScript, &temp_dir.path);
+
+            assert_eq!(dialect, Language::TypeScript);
+        }
+
+        #[test]
+        fn no_declarations() {
+            let temp_dir = TempDir::new();
+            create_package(&temp_dir, "index.js");
+
+            let dialect = detect_dialect(Language::TypeScript, &temp_dir.path);
+
+            assert_eq!(dialect, Language::JavaScript);
+        }
+
+        #[test]
+        fn unrecognised_library() {
+            let temp_dir = TempDir::new();
+
+            let dialect = detect_dialect(Language::TypeScript, &temp_dir.path);
+
+            assert_eq!(dialect, Language::TypeScript);
+        }
+
+        #[test]
+        fn language_without_dialects() {
+            let temp_dir = TempDir::new();
+
+            let dialect = detect_dialect(Language::Rust, &temp_dir.path);
+
+            assert_eq!(dialect, Language::Rust);
+        }
+    }
+
     mod extractor_discovery {
         use super::*;
         use assertables::{assert_matches, assert_ok};
@@ -91,7 +193,7 @@ mod tests {
 
             let result = discover_extractor(&temp_dir.path);
 
-            assert_matches!(result, Err(ExtractorDiscoveryError::NotFound));
+            assert_matches!(result, Err(LibraryError::UnknownLanguage));
         }
 
         #[test]
@@ -103,7 +205,7 @@ mod tests {
 
             assert_matches!(
                 result,
-                Err(ExtractorDiscoveryError::MalformedManifest {
+                Err(LibraryError::MalformedManifest {
                     language: Language::Rust,
                     error: _
                 })
@@ -134,5 +236,107 @@ version = "1.0.0"
                 format!("{:?}", RustExtractor::new().get_parser_language())
             );
         }
+
+        #[test]
+        fn unrelated_files() {
+            let temp_dir = TempDir::new();
+            temp_dir
+                .create_file("README.md", "# Not a library")
+                .unwrap();
+            temp_dir.create_file("main.rs", "fn main() {}").unwrap();
+
+            let result = discover_extractor(&temp_dir.path);
+
+            assert_matches!(result, Err(LibraryError::UnknownLanguage));
+        }
+
+        #[test]
+        fn setup_cfg_project() {
+            let temp_dir = TempDir::new();
+            temp_dir
+                .create_file("setup.cfg", "[metadata]\nname = legacy\nversion = 1.0\n")
+                .unwrap();
+
+            let result = discover_extractor(&temp_dir.path);
+
+            assert_eq!(result.unwrap().language, Language::Python);
+        }
+
+        #[test]
+        fn typescript_before_javascript() {
+            let temp_dir = TempDir::new();
+            temp_dir
+                .create_file(
+                    "package.json",
+                    r#"{"name": "typed", "types": "index.d.ts"}"#,
+                )
+                .unwrap();
+            temp_dir.create_file("index.d.ts", "").unwrap();
+
+            let result = discover_extractor(&temp_dir.path);
+
+            assert_eq!(result.unwrap().language, Language::TypeScript);
+        }
+
+        #[test]
+        fn javascript_without_declarations() {
+            let temp_dir = TempDir::new();
+            temp_dir
+                .create_file("package.json", r#"{"name": "untyped"}"#)
+                .unwrap();
+
+            let result = discover_extractor(&temp_dir.path);
+
+            assert_eq!(result.unwrap().language, Language::JavaScript);
+        }
+
+        #[test]
+        fn ambiguous_languages() {
+            let temp_dir = TempDir::new();
+            temp_dir
+                .create_file("Cargo.toml", "[package]\nname = \"bindings\"\n")
+                .unwrap();
+            temp_dir
+                .create_file("pyproject.toml", "[project]\nname = \"bindings\"\n")
+                .unwrap();
+
+            let result = discover_extractor(&temp_dir.path);
+
+            assert_matches!(
+                result,
+                Err(LibraryError::AmbiguousLanguage { candidates })
+                    if candidates == vec![Language::Rust, Language::Python]
+            );
+        }
+
+        #[test]
+        fn malformed_manifest_of_other_language() {
+            let temp_dir = TempDir::new();
+            temp_dir
+                .create_file("Cargo.toml", "[package]\nname = \"crate\"\n")
+                .unwrap();
+            temp_dir
+                .create_file("pyproject.toml", "invalid toml")
+                .unwrap();
+
+            let result = discover_extractor(&temp_dir.path);
+
+            assert_eq!(result.unwrap().language, Language::Rust);
+        }
+
+        #[test]
+        fn gradle_project() {
+            let temp_dir = TempDir::new();
+            temp_dir
+                .create_file(
+                    "build.gradle.kts",
+                    "group = \"com.example\"\nversion = \"1.0\"\n",
+                )
+                .unwrap();
+
+            let result = discover_extractor(&temp_dir.path);
+
+            assert_eq!(result.unwrap().language, Language::Java);
+        }
     }
 }
@@ -0,0 +1,271 @@
+use crate::languages::LanguageConfig;
+use crate::library::Library;
+use crate::symbol_kind::SymbolKind;
+use daipendency_extractor::{Namespace, Symbol};
+use glob::{Pattern, PatternError};
+
+/// Glob patterns selecting the namespaces and symbols to keep in a library.
+///
+/// Patterns are matched against namespace names and qualified symbol names
+/// (e.g. `tokio::sync::Mutex` or `requests.Session`), and `*` matches across separators.
+#[derive(Debug, Clone, Default)]
+pub struct SymbolFilter {
+    include: Vec<Pattern>,
+    exclude: Vec<Pattern>,
+    kinds: Vec<SymbolKind>,
+}
+
+impl SymbolFilter {
+    /// Create a filter keeping what matches any `include` pattern (or everything if there are none)
+    /// and does not match any `exclude` pattern.
+    pub fn new<S: AsRef<str>>(include: &[S], exclude: &[S]) -> Result<Self, PatternError> {
+        Ok(Self {
+            include: compile_patterns(include)?,
+            exclude: compile_patterns(exclude)?,
+            kinds: Vec::new(),
+        })
+    }
+
+    /// Only keep the symbols of the given `kinds` (or of any kind if there are none).
+    ///
+    /// Symbols whose kind cannot be detected are dropped when kinds are given.
+    pub fn with_kinds(mut self, kinds: &[SymbolKind]) -> Self {
+        self.kinds = kinds.to_vec();
+        self
+    }
+
+    fn is_included(&self, name: &str) -> bool {
+        self.include.is_empty() || self.include.iter().any(|pattern| pattern.matches(name))
+    }
+
+    fn is_excluded(&self, name: &str) -> bool {
+        self.exclude.iter().any(|pattern| pattern.matches(name))
+    }
+
+    fn is_kind_selected(&self, symbol: &Symbol, library: &Library) -> bool {
+        self.kinds.is_empty()
+            || library
+                .get_symbol_kind(symbol)
+                .is_some_and(|kind| self.kinds.contains(&kind))
+    }
+
+    fn filter_namespace(
+        &self,
+        mut namespace: Namespace,
+        separator: &str,
+        library: &Library,
+    ) -> Option<Namespace> {
+        if self.is_excluded(&namespace.name) {
+            return None;
+        }
+        let is_namespace_included = self.is_included(&namespace.name);
+        namespace.symbols.retain(|symbol| {
+            let qualified_name = format!("{}{}{}", namespace.name, separator, symbol.name);
+            (is_namespace_included || self.is_included(&qualified_name))
+                && !self.is_excluded(&qualified_name)
+                && self.is_kind_selected(symbol, library)
+        });
+        (is_namespace_included || !namespace.symbols.is_empty()).then_some(namespace)
+    }
+}
+
+fn compile_patterns<S: AsRef<str>>(patterns: &[S]) -> Result<Vec<Pattern>, PatternError> {
+    patterns
+        .iter()
+        .map(|pattern| Pattern::new(pattern.as_ref()))
+        .collect()
+}
+
+impl Library {
+    /// Drop the namespaces and symbols that `filter` does not select.
+    pub fn filter(mut self, filter: &SymbolFilter) -> Self {
+        let separator = LanguageConfig::get_from_language(self.language).namespace_separator;
+        let namespaces = std::mem::take(&mut self.namespaces);
+        self.namespaces = namespaces
+            .into_iter()
+            .filter_map(|namespace| filter.filter_namespace(namespace, separator, &self))
+            .collect();
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::languages::Language;
+    use daipendency_extractor::Symbol;
+
+    fn create_library(language: Language, namespaces: &[(&str, &[&str])]) -> Library {
+        Library {
+            name: "test-lib".to_string(),
+            version: None,
+            documentation: String::new(),
+            namespaces: namespaces
+                .iter()
+                .map(|(name, symbol_names)| Namespace {
+                    name: name.to_string(),
+                    symbols: symbol_names
+                        .iter()
+                        .map(|symbol_name| Symbol {
+                            name: symbol_name.to_string(),
+                            source_code: format!("pub struct {symbol_name};"),
+                        })
+                        .collect(),
+                    doc_comment: None,
+                })
+                .collect(),
+            language,
+            features: None,
+            target: None,
+        }
+    }
+
+    fn create_rust_library() -> Library {
+        create_library(
+            Language::Rust,
+            &[
+                ("tokio", &["spawn", "Error"]),
+                ("tokio::sync", &["Mutex", "Error"]),
+                ("tokio::sync::mpsc", &["channel"]),
+                ("tokio::net", &["TcpStream"]),
+            ],
+        )
+    }
+
+    fn get_names(library: &Library) -> Vec<(&str, Vec<&str>)> {
+        library
+            .namespaces
+            .iter()
+            .map(|namespace| {
+                (
+                    namespace.name.as_str(),
+                    namespace.symbols.iter().map(|s| s.name.as_str()).collect(),
+                )
+            })
+            .collect()
+    }
+
+    fn filter(library: Library, include: &[&str], exclude: &[&str]) -> Library {
+        library.filter(&SymbolFilter::new(include, exclude).unwrap())
+    }
+
+    #[test]
+    fn no_patterns() {
+        let library = filter(create_rust_library(), &[], &[]);
+
+        assert_eq!(library.namespaces.len(), 4);
+    }
+
+    #[test]
+    fn include_module_contents() {
+        let library = filter(create_rust_library(), &["tokio::sync::*"], &[]);
+
+        assert_eq!(
+            get_names(&library),
+            vec![
+                ("tokio::sync", vec!["Mutex", "Error"]),
+                ("tokio::sync::mpsc", vec!["channel"]),
+            ]
+        );
+    }
+
+    #[test]
+    fn include_namespace_by_name() {
+        let library = filter(create_rust_library(), &["tokio::net"], &[]);
+
+        assert_eq!(get_names(&library), vec![("tokio::net", vec!["TcpStream"])]);
+    }
+
+    #[test]
+    fn include_symbol_in_any_namespace() {
+        let library = filter(create_rust_library(), &["*::Error"], &[]);
+
+        assert_eq!(
+            get_names(&library),
+            vec![("tokio", vec!["Error"]), ("tokio::sync", vec!["Error"])]
+        );
+    }
+
+    #[test]
+    fn exclude_symbol() {
+        let library = filter(create_rust_library(), &["tokio::sync::*"], &["*::Error"]);
+
+        assert_eq!(
+            get_names(&library),
+            vec![
+                ("tokio::sync", vec!["Mutex"]),
+                ("tokio::sync::mpsc", vec!["channel"]),
+            ]
+        );
+    }
+
+    #[test]
+    fn exclude_namespace() {
+        let library = filter(create_rust_library(), &[], &["tokio::sync*"]);
+
+        assert_eq!(
+            get_names(&library),
+            vec![
+                ("tokio", vec!["spawn", "Error"]),
+                ("tokio::net", vec!["TcpStream"]),
+            ]
+        );
+    }
+
+    #[test]
+    fn language_separator() {
+        let library = create_library(
+            Language::Python,
+            &[
+                ("requests", &["Session", "get"]),
+                ("requests.auth", &["AuthBase"]),
+            ],
+        );
+
+        let library = filter(library, &["requests.Session"], &[]);
+
+        assert_eq!(get_names(&library), vec![("requests", vec!["Session"])]);
+    }
+
+    #[test]
+    fn kinds() {
+        let mut library = create_rust_library();
+        library.namespaces[0].symbols[0].source_code = "pub fn spawn();".to_string();
+
+        let library = library.filter(
+            &SymbolFilter::new::<&str>(&[], &[])
+                .unwrap()
+                .with_kinds(&[SymbolKind::Function]),
+        );
+
+        assert_eq!(
+            get_names(&library),
+            vec![
+                ("tokio", vec!["spawn"]),
+                ("tokio::sync", vec![]),
+                ("tokio::sync::mpsc", vec![]),
+                ("tokio::net", vec![]),
+            ]
+        );
+    }
+
+    #[test]
+    fn kinds_without_detection() {
+        let library = create_library(Language::Python, &[("requests", &["Session"])]);
+
+        let library = library.filter(
+            &SymbolFilter::new::<&str>(&[], &[])
+                .unwrap()
+                .with_kinds(&[SymbolKind::Struct]),
+        );
+
+        assert_eq!(get_names(&library), vec![("requests", vec![])]);
+    }
+
+    #[test]
+    fn invalid_pattern() {
+        let result = SymbolFilter::new(&["tokio::[sync"], &[]);
+
+        assert!(result.is_err());
+    }
+}
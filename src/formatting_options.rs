@@ -0,0 +1,13 @@
+/// Options for the documentation generators.
+#[derive(Debug, Clone, Default)]
+pub struct FormattingOptions {
+    /// The approximate number of tokens the output should not exceed, omitting the least
+    /// important content if necessary.
+    pub max_tokens: Option<usize>,
+    /// Whether to split the symbols in each namespace of the Markdown output into a section per
+    /// kind (e.g. "Structs" and "Functions"), each with its own code block.
+    pub group_by_kind: bool,
+    /// Whether to start the API in the Markdown output with a table of contents linking to each
+    /// namespace, followed by an alphabetical index of the symbols.
+    pub table_of_contents: bool,
+}
@@ -0,0 +1,355 @@
+use crate::diff::{LibraryDiff, SymbolChange};
+use crate::formatting_options::FormattingOptions;
+use crate::languages::LanguageConfig;
+use crate::library::Library;
+use crate::token_budget::{estimate_tokens, fit_to_budget, Omissions};
+use daipendency_extractor::{Namespace, Symbol};
+use serde_json::{json, Value};
+
+/// Room left in the token budget for the `omissions` object.
+const OMISSIONS_TOKENS: usize = 50;
+
+/// Generate the documentation of `library` as a JSON document.
+///
+/// Unlike the Markdown output, namespaces without symbols are included.
+pub fn generate_json_documentation(library: &Library) -> String {
+    generate_json_documentation_with_options(library, &FormattingOptions::default())
+}
+
+/// Generate the documentation of `library` as a JSON document.
+///
+/// If content is omitted to honour `options.max_tokens`, the document has an `omissions` object.
+pub fn generate_json_documentation_with_options(
+    library: &Library,
+    options: &FormattingOptions,
+) -> String {
+    let Some(max_tokens) = options.max_tokens else {
+        return serialise(format_library(library));
+    };
+    let skeleton = format_library(&Library {
+        name: library.name.clone(),
+        version: library.version.clone(),
+        documentation: String::new(),
+        namespaces: vec![],
+        language: library.language,
+        features: library.features.clone(),
+        target: library.target.clone(),
+    });
+    let skeleton_tokens = estimate_tokens(&serialise(skeleton)) + OMISSIONS_TOKENS;
+    let (library, omissions) =
+        fit_to_budget(library, max_tokens.saturating_sub(skeleton_tokens), false);
+    let mut document = format_library(&library);
+    if !omissions.is_empty() {
+        document["omissions"] = format_omissions(&omissions);
+    }
+    serialise(document)
+}
+
+fn serialise(document: Value) -> String {
+    serde_json::to_string_pretty(&document).expect("JSON values are always serialisable")
+}
+
+fn format_library(library: &Library) -> Value {
+    let mut document = json!({
+        "name": library.name,
+        "version": library.version,
+        "language": LanguageConfig::get_from_language(library.language).name,
+        "documentation": library.documentation,
+        "namespaces": library
+            .namespaces
+            .iter()
+            .map(|namespace| format_namespace(namespace, library))
+            .collect::<Vec<_>>(),
+    });
+    if let Some(features) = &library.features {
+        document["features"] = json!(features);
+    }
+    if let Some(target) = &library.target {
+        document["target"] = json!(target);
+    }
+    document
+}
+
+/// Generate a JSON document listing the changes to the public API in `diff`.
+pub fn generate_json_diff(diff: &LibraryDiff) -> String {
+    let verdict = diff.get_verdict();
+    serialise(json!({
+        "name": diff.name,
+        "language": LanguageConfig::get_from_language(diff.language).name,
+        "old_version": diff.old_version,
+        "new_version": diff.new_version,
+        "added_namespaces": diff.added_namespaces,
+        "removed_namespaces": diff.removed_namespaces,
+        "changes": diff.changes.iter().map(format_symbol_change).collect::<Vec<_>>(),
+        "verdict": {
+            "required_bump": verdict.required_bump.as_str(),
+            "actual_bump": verdict.actual_bump.map(|bump| bump.as_str()),
+            "is_version_bump_sufficient": verdict.is_version_bump_sufficient(),
+        },
+    }))
+}
+
+fn format_symbol_change(change: &SymbolChange) -> Value {
+    json!({
+        "namespace": change.namespace,
+        "name": change.name,
+        "kind": change.kind.as_str(),
+        "symbol_kind": change.symbol_kind.map(|kind| kind.as_str()),
+        "impact": change.impact.as_str(),
+        "old_source_code": change.old_source_code,
+        "new_source_code": change.new_source_code,
+    })
+}
+
+fn format_omissions(omissions: &Omissions) -> Value {
+    json!({
+        "documentation_shortened": omissions.is_documentation_shortened,
+        "documentation_omitted": omissions.is_documentation_omitted,
+        "symbol_count": omissions.omitted_symbol_count,
+        "namespaces": omissions.omitted_namespaces,
+        "doc_comment_count": omissions.omitted_doc_comment_count,
+    })
+}
+
+fn format_namespace(namespace: &Namespace, library: &Library) -> Value {
+    json!({
+        "name": namespace.name,
+        "doc_comment": namespace.doc_comment,
+        "symbols": namespace
+            .symbols
+            .iter()
+            .map(|symbol| format_symbol(symbol, library))
+            .collect::<Vec<_>>(),
+    })
+}
+
+fn format_symbol(symbol: &Symbol, library: &Library) -> Value {
+    json!({
+        "name": symbol.name,
+        "kind": library.get_symbol_kind(symbol).map(|kind| kind.as_str()),
+        "source_code": symbol.source_code,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::languages::Language;
+
+    const STUB_LIBRARY_NAME: &str = "test-lib";
+    const STUB_LIBRARY_VERSION: &str = "1.0.0";
+    const STUB_DOCUMENTATION: &str = "Test documentation";
+    const STUB_SOURCE_CODE: &str = "pub fn test() {}";
+    const STUB_DOC_COMMENT: &str = "//! Namespace docs";
+
+    fn create_library(namespaces: Vec<Namespace>) -> Library {
+        Library {
+            name: STUB_LIBRARY_NAME.to_string(),
+            version: Some(STUB_LIBRARY_VERSION.to_string()),
+            documentation: STUB_DOCUMENTATION.to_string(),
+            namespaces,
+            language: Language::Rust,
+            features: None,
+            target: None,
+        }
+    }
+
+    fn generate(library: &Library) -> Value {
+        serde_json::from_str(&generate_json_documentation(library)).unwrap()
+    }
+
+    mod metadata {
+        use super::*;
+
+        #[test]
+        fn library_metadata() {
+            let document = generate(&create_library(vec![]));
+
+            assert_eq!(document["name"], STUB_LIBRARY_NAME);
+            assert_eq!(document["version"], STUB_LIBRARY_VERSION);
+            assert_eq!(document["language"], "rust");
+            assert_eq!(document["documentation"], STUB_DOCUMENTATION);
+        }
+
+        #[test]
+        fn without_library_version() {
+            let mut library = create_library(vec![]);
+            library.version = None;
+
+            let document = generate(&library);
+
+            assert_eq!(document["version"], Value::Null);
+        }
+
+        #[test]
+        fn without_configuration() {
+            let document = generate(&create_library(vec![]));
+
+            assert!(document.get("features").is_none());
+            assert!(document.get("target").is_none());
+        }
+
+        #[test]
+        fn with_configuration() {
+            let mut library = create_library(vec![]);
+            library.features = Some(vec!["net".to_string()]);
+            library.target = Some("x86_64-unknown-linux-gnu".to_string());
+
+            let document = generate(&library);
+
+            assert_eq!(document["features"], json!(["net"]));
+            assert_eq!(document["target"], "x86_64-unknown-linux-gnu");
+        }
+    }
+
+    mod token_budget {
+        use super::*;
+
+        fn generate_with_budget(library: &Library, max_tokens: usize) -> Value {
+            let options = FormattingOptions {
+                max_tokens: Some(max_tokens),
+                ..FormattingOptions::default()
+            };
+            serde_json::from_str(&generate_json_documentation_with_options(library, &options))
+                .unwrap()
+        }
+
+        #[test]
+        fn within_budget() {
+            let document = generate_with_budget(&create_library(vec![]), 1000);
+
+            assert_eq!(document.get("omissions"), None);
+        }
+
+        #[test]
+        fn over_budget() {
+            let library = create_library(vec![Namespace {
+                name: STUB_LIBRARY_NAME.to_string(),
+                symbols: (0..100)
+                    .map(|index| Symbol {
+                        name: format!("symbol{index}"),
+                        source_code: STUB_SOURCE_CODE.to_string(),
+                    })
+                    .collect(),
+                doc_comment: None,
+            }]);
+
+            let document = generate_with_budget(&library, 300);
+
+            let omitted_symbol_count = document["omissions"]["symbol_count"].as_u64().unwrap();
+            let kept_symbol_count = document["namespaces"][0]["symbols"]
+                .as_array()
+                .unwrap()
+                .len();
+            assert!(omitted_symbol_count > 0);
+            assert_eq!(omitted_symbol_count as usize + kept_symbol_count, 100);
+            assert_eq!(document["omissions"]["documentation_shortened"], false);
+        }
+    }
+
+    mod api {
+        use super::*;
+
+        #[test]
+        fn no_namespaces() {
+            let document = generate(&create_library(vec![]));
+
+            assert_eq!(document["namespaces"], json!([]));
+        }
+
+        #[test]
+        fn namespace_with_symbols() {
+            let library = create_library(vec![Namespace {
+                name: "test".to_string(),
+                symbols: vec![Symbol {
+                    name: "test".to_string(),
+                    source_code: STUB_SOURCE_CODE.to_string(),
+                }],
+                doc_comment: Some(STUB_DOC_COMMENT.to_string()),
+            }]);
+
+            let document = generate(&library);
+
+            assert_eq!(
+                document["namespaces"],
+                json!([{
+                    "name": "test",
+                    "doc_comment": STUB_DOC_COMMENT,
+                    "symbols": [{"name": "test", "kind": "function", "source_code": STUB_SOURCE_CODE}],
+                }])
+            );
+        }
+
+        #[test]
+        fn namespace_without_symbols() {
+            let library = create_library(vec![Namespace {
+                name: "empty".to_string(),
+                symbols: vec![],
+                doc_comment: None,
+            }]);
+
+            let document = generate(&library);
+
+            assert_eq!(
+                document["namespaces"],
+                json!([{"name": "empty", "doc_comment": null, "symbols": []}])
+            );
+        }
+    }
+
+    mod diff {
+        use super::*;
+        use crate::diff::{ChangeKind, LibraryDiff};
+        use crate::semver::SemverImpact;
+        use crate::symbol_kind::SymbolKind;
+
+        #[test]
+        fn changes() {
+            let diff = LibraryDiff {
+                name: STUB_LIBRARY_NAME.to_string(),
+                language: Language::Rust,
+                old_version: Some(STUB_LIBRARY_VERSION.to_string()),
+                new_version: Some("2.0.0".to_string()),
+                added_namespaces: vec![],
+                removed_namespaces: vec!["test::net".to_string()],
+                changes: vec![SymbolChange {
+                    namespace: "test".to_string(),
+                    name: "test".to_string(),
+                    kind: ChangeKind::Removed,
+                    symbol_kind: Some(SymbolKind::Function),
+                    impact: SemverImpact::Major,
+                    old_source_code: Some(STUB_SOURCE_CODE.to_string()),
+                    new_source_code: None,
+                }],
+            };
+
+            let document: Value = serde_json::from_str(&generate_json_diff(&diff)).unwrap();
+
+            assert_eq!(
+                document,
+                json!({
+                    "name": STUB_LIBRARY_NAME,
+                    "language": "rust",
+                    "old_version": STUB_LIBRARY_VERSION,
+                    "new_version": "2.0.0",
+                    "added_namespaces": [],
+                    "removed_namespaces": ["test::net"],
+                    "changes": [{
+                        "namespace": "test",
+                        "name": "test",
+                        "kind": "removed",
+                        "symbol_kind": "function",
+                        "impact": "major",
+                        "old_source_code": STUB_SOURCE_CODE,
+                        "new_source_code": null,
+                    }],
+                    "verdict": {
+                        "required_bump": "major",
+                        "actual_bump": "major",
+                        "is_version_bump_sufficient": true,
+                    },
+                })
+            );
+        }
+    }
+}
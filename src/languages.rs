@@ -1,21 +1,102 @@
-use daipendency_extractor::Extractor;
-use daipendency_extractor_rust::RustExtractor;
+use crate::comments::strip_c_style_comments;
+use crate::dependency_graph::DependencyGraph;
+use crate::diff::ChangeKind;
+use crate::error::LibraryError;
+use crate::loading_options::FeatureSelection;
+use crate::semver::{self, SemverImpact};
+use crate::symbol_kind::SymbolKind;
+use crate::workspaces::WorkspaceMember;
+use daipendency_extractor::{
+    DependencyResolutionError, Extractor, LibraryMetadata, LibraryMetadataError, Namespace,
+};
 use std::collections::HashMap;
+use std::path::Path;
 use std::sync::OnceLock;
 
+mod go;
+mod java;
+mod javascript;
+mod python;
+mod rust;
+
+use go::GoExtractor;
+use java::JavaExtractor;
+use javascript::{JavaScriptExtractor, TypeScriptExtractor};
+use python::PythonExtractor;
+use rust::RustExtractor;
+
 type ExtractorInitialiser = fn() -> Box<dyn Extractor + Send + Sync>;
+type DependencyLister = fn(&Path) -> Result<Vec<String>, DependencyResolutionError>;
+type DependencyGraphLoader = fn(&Path) -> Result<DependencyGraph, DependencyResolutionError>;
+type WorkspaceMemberLister = fn(&Path) -> Result<Vec<WorkspaceMember>, LibraryMetadataError>;
+type ConfigurationSelector = fn(
+    &Path,
+    &LibraryMetadata,
+    Option<&FeatureSelection>,
+    Option<&str>,
+    Vec<Namespace>,
+) -> Result<(Vec<Namespace>, Option<Vec<String>>), LibraryError>;
+type ChangeClassifier = fn(ChangeKind, Option<&str>, Option<&str>) -> SemverImpact;
+type SymbolKindDetector = fn(&str) -> Option<SymbolKind>;
+type CommentStripper = fn(&str) -> String;
+type CodeBlockLanguageDetector = fn(&[&str]) -> Option<&'static str>;
 
 /// The languages supported by daipendency
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "lowercase")]
 pub enum Language {
     Rust,
+    Python,
+    TypeScript,
+    JavaScript,
+    Go,
+    Java,
 }
 
 pub struct LanguageConfig {
     pub name: &'static str,
     pub extractor_initialiser: ExtractorInitialiser,
+    /// Lists the names of the direct dependencies declared in a library's manifest
+    pub dependency_lister: DependencyLister,
+    /// Resolves a dependant's whole dependency tree at once, if resolving each dependency through
+    /// the extractor is slow
+    pub dependency_graph_loader: Option<DependencyGraphLoader>,
+    /// Whether several versions of a package can be installed side by side, in which case
+    /// transitive dependencies are resolved from the library that declares them
+    pub has_nested_dependencies: bool,
+    /// Separates a namespace from the names qualified by it, as in `std::fs`
+    pub namespace_separator: &'static str,
+    /// Files that mark a library in this language, probed before the manifest is parsed
+    pub manifest_file_names: &'static [&'static str],
+    /// Lists the libraries in a workspace from its root, if the language has workspaces
+    pub workspace_member_lister: Option<WorkspaceMemberLister>,
+    /// Keeps the parts of the API compiled with the selected features and for the selected
+    /// target, if the language supports conditional compilation
+    pub configuration_selector: Option<ConfigurationSelector>,
+    /// Determines the version bump that a change to a symbol requires, from its source code
+    /// without comments
+    pub change_classifier: ChangeClassifier,
+    /// Detects the kind of item declared by a symbol's source code
+    pub symbol_kind_detector: SymbolKindDetector,
+    /// Removes the comments from a symbol's source code, including doc comments and docstrings
+    pub comment_stripper: CommentStripper,
+    /// Detects the language to highlight a code block of source code in, if the library may mix
+    /// in another language (e.g. Kotlin in JVM libraries)
+    pub code_block_language_detector: Option<CodeBlockLanguageDetector>,
 }
 
+/// The order in which languages are tried when detecting the language of a library.
+///
+/// Dialects come before the languages they extend, so that TypeScript wins over JavaScript.
+pub const DISCOVERY_ORDER: [Language; 6] = [
+    Language::Rust,
+    Language::Python,
+    Language::TypeScript,
+    Language::JavaScript,
+    Language::Go,
+    Language::Java,
+];
+
 static LANGUAGE_CONFIGS: OnceLock<HashMap<Language, LanguageConfig>> = OnceLock::new();
 
 fn initialise_config() -> HashMap<Language, LanguageConfig> {
@@ -25,12 +106,120 @@ fn initialise_config() -> HashMap<Language, LanguageConfig> {
         LanguageConfig {
             name: "rust",
             extractor_initialiser: || Box::new(RustExtractor::new()),
+            dependency_lister: rust::list_dependencies,
+            dependency_graph_loader: Some(rust::load_dependency_graph),
+            has_nested_dependencies: true,
+            namespace_separator: "::",
+            manifest_file_names: &["Cargo.toml"],
+            workspace_member_lister: Some(rust::list_workspace_members),
+            configuration_selector: Some(rust::select_configuration),
+            change_classifier: rust::classify_change,
+            symbol_kind_detector: rust::detect_symbol_kind,
+            comment_stripper: strip_c_style_comments,
+            code_block_language_detector: None,
+        },
+    );
+    configs.insert(
+        Language::Python,
+        LanguageConfig {
+            name: "python",
+            extractor_initialiser: || Box::new(PythonExtractor),
+            dependency_lister: python::list_dependencies,
+            dependency_graph_loader: None,
+            has_nested_dependencies: false,
+            namespace_separator: ".",
+            manifest_file_names: &["pyproject.toml", "setup.cfg", "METADATA"],
+            workspace_member_lister: None,
+            configuration_selector: None,
+            change_classifier: semver::classify_change,
+            symbol_kind_detector: python::detect_symbol_kind,
+            comment_stripper: python::strip_comments,
+            code_block_language_detector: None,
+        },
+    );
+    configs.insert(
+        Language::TypeScript,
+        LanguageConfig {
+            name: "typescript",
+            extractor_initialiser: || Box::new(TypeScriptExtractor),
+            dependency_lister: javascript::list_dependencies,
+            dependency_graph_loader: None,
+            has_nested_dependencies: true,
+            namespace_separator: ".",
+            manifest_file_names: &["package.json"],
+            workspace_member_lister: None,
+            configuration_selector: None,
+            change_classifier: semver::classify_change,
+            symbol_kind_detector: javascript::detect_symbol_kind,
+            comment_stripper: strip_c_style_comments,
+            code_block_language_detector: None,
+        },
+    );
+    configs.insert(
+        Language::JavaScript,
+        LanguageConfig {
+            name: "javascript",
+            extractor_initialiser: || Box::new(JavaScriptExtractor),
+            dependency_lister: javascript::list_dependencies,
+            dependency_graph_loader: None,
+            has_nested_dependencies: true,
+            namespace_separator: ".",
+            manifest_file_names: &["package.json"],
+            workspace_member_lister: None,
+            configuration_selector: None,
+            change_classifier: semver::classify_change,
+            symbol_kind_detector: javascript::detect_symbol_kind,
+            comment_stripper: strip_c_style_comments,
+            code_block_language_detector: None,
+        },
+    );
+    configs.insert(
+        Language::Go,
+        LanguageConfig {
+            name: "go",
+            extractor_initialiser: || Box::new(GoExtractor),
+            dependency_lister: go::list_dependencies,
+            dependency_graph_loader: None,
+            has_nested_dependencies: false,
+            namespace_separator: ".",
+            manifest_file_names: &["go.mod"],
+            workspace_member_lister: None,
+            configuration_selector: None,
+            change_classifier: semver::classify_change,
+            symbol_kind_detector: go::detect_symbol_kind,
+            comment_stripper: strip_c_style_comments,
+            code_block_language_detector: None,
+        },
+    );
+    configs.insert(
+        Language::Java,
+        LanguageConfig {
+            name: "java",
+            extractor_initialiser: || Box::new(JavaExtractor),
+            dependency_lister: java::list_dependencies,
+            dependency_graph_loader: None,
+            has_nested_dependencies: false,
+            namespace_separator: ".",
+            manifest_file_names: &["pom.xml", "build.gradle.kts", "build.gradle"],
+            workspace_member_lister: None,
+            configuration_selector: None,
+            change_classifier: semver::classify_change,
+            symbol_kind_detector: java::detect_symbol_kind,
+            comment_stripper: strip_c_style_comments,
+            code_block_language_detector: Some(java::detect_code_block_language),
         },
     );
     configs
 }
 
 impl LanguageConfig {
+    /// Get the language to highlight a code block with `source_codes` in, as in ` ```rust `.
+    pub fn get_code_block_language(&self, source_codes: &[&str]) -> &'static str {
+        self.code_block_language_detector
+            .and_then(|detector| detector(source_codes))
+            .unwrap_or(self.name)
+    }
+
     pub fn get_from_language(language: Language) -> &'static LanguageConfig {
         Self::get_all()
             .get(&language)
@@ -40,6 +229,19 @@ impl LanguageConfig {
     pub fn get_all() -> &'static HashMap<Language, LanguageConfig> {
         LANGUAGE_CONFIGS.get_or_init(initialise_config)
     }
+
+    /// Whether the directory at `path` contains any of the manifest files of this language.
+    pub fn has_manifest(&self, path: &Path) -> bool {
+        self.manifest_file_names
+            .iter()
+            .any(|file_name| path.join(file_name).is_file())
+    }
+}
+
+impl std::fmt::Display for Language {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{}", LanguageConfig::get_from_language(*self).name)
+    }
 }
 
 impl std::str::FromStr for Language {
@@ -60,6 +262,8 @@ mod tests {
 
     mod language_config {
         use super::*;
+        use daipendency_testing::tempdir::TempDir;
+        use std::fs;
 
         #[test]
         fn get_from_language() {
@@ -72,9 +276,46 @@ mod tests {
         fn get_all() {
             let configs = LanguageConfig::get_all();
 
-            assert_eq!(configs.len(), 1);
+            assert_eq!(configs.len(), 6);
             let rust_config = configs.get(&Language::Rust).unwrap();
             assert_eq!(rust_config.name, "rust");
+            let python_config = configs.get(&Language::Python).unwrap();
+            assert_eq!(python_config.name, "python");
+            let typescript_config = configs.get(&Language::TypeScript).unwrap();
+            assert_eq!(typescript_config.name, "typescript");
+            let javascript_config = configs.get(&Language::JavaScript).unwrap();
+            assert_eq!(javascript_config.name, "javascript");
+            let go_config = configs.get(&Language::Go).unwrap();
+            assert_eq!(go_config.name, "go");
+            let java_config = configs.get(&Language::Java).unwrap();
+            assert_eq!(java_config.name, "java");
+        }
+
+        #[test]
+        fn discovery_order_covers_all_languages() {
+            let configs = LanguageConfig::get_all();
+
+            assert_eq!(DISCOVERY_ORDER.len(), configs.len());
+            assert!(DISCOVERY_ORDER
+                .iter()
+                .all(|language| configs.contains_key(language)));
+        }
+
+        #[test]
+        fn has_manifest() {
+            let temp_dir = TempDir::new();
+            temp_dir.create_file("build.gradle", "").unwrap();
+
+            assert!(LanguageConfig::get_from_language(Language::Java).has_manifest(&temp_dir.path));
+            assert!(!LanguageConfig::get_from_language(Language::Rust).has_manifest(&temp_dir.path));
+        }
+
+        #[test]
+        fn manifest_directory() {
+            let temp_dir = TempDir::new();
+            fs::create_dir(temp_dir.path.join("go.mod")).unwrap();
+
+            assert!(!LanguageConfig::get_from_language(Language::Go).has_manifest(&temp_dir.path));
         }
     }
 
@@ -90,9 +331,14 @@ mod tests {
             assert_eq!(result, Language::Rust);
         }
 
+        #[test]
+        fn display() {
+            assert_eq!(Language::TypeScript.to_string(), "typescript");
+        }
+
         #[test]
         fn unsupported_language() {
-            let input = "python";
+            let input = "cobol";
 
             let result = input.parse::<Language>();
 
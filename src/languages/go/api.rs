@@ -0,0 +1,226 @@
+use super::go_mod::GO_MOD_PATH;
+use super::parsing::parse_source_file;
+use daipendency_extractor::{ExtractionError, Namespace};
+use std::fs;
+use std::path::{Path, PathBuf};
+use tree_sitter::Parser;
+
+const SOURCE_EXTENSION: &str = "go";
+const TEST_FILE_SUFFIX: &str = "_test.go";
+const TEST_PACKAGE_SUFFIX: &str = "_test";
+const MAIN_PACKAGE: &str = "main";
+/// Directories the Go tool ignores or that cannot be imported by other modules.
+const EXCLUDED_DIRECTORIES: [&str; 3] = ["testdata", "vendor", "internal"];
+
+/// Build the public API of the module rooted at `module_root`, with one namespace per package.
+pub fn build_public_api(
+    module_root: &Path,
+    module_path: &str,
+    parser: &mut Parser,
+) -> Result<Vec<Namespace>, ExtractionError> {
+    let mut namespaces = Vec::new();
+    collect_packages(module_root, module_path, parser, &mut namespaces)?;
+    Ok(namespaces)
+}
+
+fn collect_packages(
+    directory: &Path,
+    import_path: &str,
+    parser: &mut Parser,
+    namespaces: &mut Vec<Namespace>,
+) -> Result<(), ExtractionError> {
+    let mut entries = fs::read_dir(directory)?
+        .filter_map(Result::ok)
+        .map(|entry| entry.path())
+        .collect::<Vec<_>>();
+    entries.sort();
+
+    let source_files = entries
+        .iter()
+        .filter(|path| path.is_file() && is_package_source_file(path))
+        .collect::<Vec<_>>();
+    if let Some(namespace) = parse_package(&source_files, import_path, parser)? {
+        namespaces.push(namespace);
+    }
+
+    for subdirectory in entries.iter().filter(|path| path.is_dir()) {
+        let Some(name) = subdirectory.file_name().and_then(|name| name.to_str()) else {
+            continue;
+        };
+        let is_ignored = name.starts_with(['.', '_']) || EXCLUDED_DIRECTORIES.contains(&name);
+        // Directories with their own go.mod are separate modules
+        if is_ignored || subdirectory.join(GO_MOD_PATH).is_file() {
+            continue;
+        }
+        collect_packages(
+            subdirectory,
+            &format!("{import_path}/{name}"),
+            parser,
+            namespaces,
+        )?;
+    }
+    Ok(())
+}
+
+fn is_package_source_file(path: &Path) -> bool {
+    let Some(file_name) = path.file_name().and_then(|name| name.to_str()) else {
+        return false;
+    };
+    path.extension() == Some(SOURCE_EXTENSION.as_ref()) && !file_name.ends_with(TEST_FILE_SUFFIX)
+}
+
+fn parse_package(
+    source_files: &[&PathBuf],
+    import_path: &str,
+    parser: &mut Parser,
+) -> Result<Option<Namespace>, ExtractionError> {
+    let mut namespace: Option<Namespace> = None;
+    for path in source_files {
+        let source_code = fs::read_to_string(path)?;
+        let file = parse_source_file(&source_code, parser)?;
+        if file.package_name == MAIN_PACKAGE || file.package_name.ends_with(TEST_PACKAGE_SUFFIX) {
+            continue;
+        }
+        let namespace = namespace.get_or_insert_with(|| Namespace {
+            name: import_path.to_string(),
+            symbols: Vec::new(),
+            doc_comment: None,
+        });
+        namespace.symbols.extend(file.symbols);
+        if namespace.doc_comment.is_none() {
+            namespace.doc_comment = file.doc_comment;
+        }
+    }
+    Ok(namespace)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::languages::go::test_helpers::setup_parser;
+    use daipendency_testing::tempdir::TempDir;
+
+    const STUB_MODULE: &str = "github.com/example/project";
+
+    fn build(temp_dir: &TempDir) -> Vec<Namespace> {
+        build_public_api(&temp_dir.path, STUB_MODULE, &mut setup_parser()).unwrap()
+    }
+
+    fn get_namespace_names(namespaces: &[Namespace]) -> Vec<&str> {
+        namespaces
+            .iter()
+            .map(|namespace| namespace.name.as_str())
+            .collect()
+    }
+
+    fn get_symbol_names(namespace: &Namespace) -> Vec<&str> {
+        namespace
+            .symbols
+            .iter()
+            .map(|symbol| symbol.name.as_str())
+            .collect()
+    }
+
+    #[test]
+    fn root_package() {
+        let temp_dir = TempDir::new();
+        temp_dir
+            .create_file("client.go", "package project\n\nfunc New() {}\n")
+            .unwrap();
+
+        let namespaces = build(&temp_dir);
+
+        assert_eq!(get_namespace_names(&namespaces), vec![STUB_MODULE]);
+        assert_eq!(get_symbol_names(&namespaces[0]), vec!["New"]);
+    }
+
+    #[test]
+    fn multiple_files() {
+        let temp_dir = TempDir::new();
+        temp_dir
+            .create_file("a.go", "package project\n\nfunc A() {}\n")
+            .unwrap();
+        temp_dir
+            .create_file(
+                "b.go",
+                "// Package project does things.\npackage project\n\nfunc B() {}\n",
+            )
+            .unwrap();
+
+        let namespaces = build(&temp_dir);
+
+        assert_eq!(get_symbol_names(&namespaces[0]), vec!["A", "B"]);
+        assert_eq!(
+            namespaces[0].doc_comment,
+            Some("// Package project does things.".to_string())
+        );
+    }
+
+    #[test]
+    fn subpackages() {
+        let temp_dir = TempDir::new();
+        temp_dir
+            .create_file("sub/sub.go", "package sub\n\nfunc Sub() {}\n")
+            .unwrap();
+
+        let namespaces = build(&temp_dir);
+
+        assert_eq!(
+            get_namespace_names(&namespaces),
+            vec![format!("{STUB_MODULE}/sub")]
+        );
+    }
+
+    #[test]
+    fn test_files() {
+        let temp_dir = TempDir::new();
+        temp_dir
+            .create_file("client_test.go", "package project\n\nfunc TestNew() {}\n")
+            .unwrap();
+
+        let namespaces = build(&temp_dir);
+
+        assert!(namespaces.is_empty());
+    }
+
+    #[test]
+    fn main_package() {
+        let temp_dir = TempDir::new();
+        temp_dir
+            .create_file("cmd/tool/main.go", "package main\n\nfunc Run() {}\n")
+            .unwrap();
+
+        let namespaces = build(&temp_dir);
+
+        assert!(namespaces.is_empty());
+    }
+
+    #[test]
+    fn excluded_directories() {
+        let temp_dir = TempDir::new();
+        for directory in ["internal", "testdata", "vendor/x", ".git", "_examples"] {
+            temp_dir
+                .create_file(&format!("{directory}/x.go"), "package x\n\nfunc X() {}\n")
+                .unwrap();
+        }
+
+        let namespaces = build(&temp_dir);
+
+        assert!(namespaces.is_empty());
+    }
+
+    #[test]
+    fn nested_module() {
+        let temp_dir = TempDir::new();
+        temp_dir
+            .create_file("tools/go.mod", "module example.com/tools\n")
+            .unwrap();
+        temp_dir
+            .create_file("tools/tools.go", "package tools\n\nfunc T() {}\n")
+            .unwrap();
+
+        let namespaces = build(&temp_dir);
+
+        assert!(namespaces.is_empty());
+    }
+}
@@ -0,0 +1,352 @@
+use super::go_mod::{read_go_mod, ModuleVersion};
+use daipendency_extractor::DependencyResolutionError;
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const VENDOR_DIRECTORY: &str = "vendor";
+const GO_SUM_PATH: &str = "go.sum";
+
+/// Resolve a module required by the dependant, either from `vendor/` or from the module cache.
+///
+/// `dependency_name` may be the full module path or just its last element (e.g. `errors`).
+pub fn resolve_dependency_path(
+    dependency_name: &str,
+    dependant_path: &Path,
+) -> Result<PathBuf, DependencyResolutionError> {
+    resolve_dependency_path_in_cache(dependency_name, dependant_path, get_module_cache())
+}
+
+fn resolve_dependency_path_in_cache(
+    dependency_name: &str,
+    dependant_path: &Path,
+    module_cache: Option<PathBuf>,
+) -> Result<PathBuf, DependencyResolutionError> {
+    let go_mod = read_go_mod(dependant_path)
+        .map_err(|e| DependencyResolutionError::RetrievalFailure(e.to_string()))?;
+    let all_requirements = [
+        go_mod.requirements.as_slice(),
+        go_mod.indirect_requirements.as_slice(),
+    ]
+    .concat();
+    let requirement = find_requirement(dependency_name, &all_requirements)?;
+
+    let vendored_path = dependant_path
+        .join(VENDOR_DIRECTORY)
+        .join(&requirement.path);
+    if vendored_path.is_dir() {
+        return Ok(vendored_path);
+    }
+
+    let mut module = requirement.clone();
+    if let Some(replacement) = go_mod
+        .replacements
+        .iter()
+        .find(|replacement| replacement.old_path == requirement.path)
+    {
+        if is_local_path(&replacement.new_path) {
+            return Ok(dependant_path.join(&replacement.new_path));
+        }
+        module = ModuleVersion {
+            path: replacement.new_path.clone(),
+            version: replacement
+                .new_version
+                .clone()
+                .unwrap_or(requirement.version.clone()),
+        };
+    }
+
+    if !is_pinned(&module, dependant_path) {
+        return Err(DependencyResolutionError::RetrievalFailure(format!(
+            "{} {} is not pinned in {}",
+            module.path, module.version, GO_SUM_PATH
+        )));
+    }
+
+    let module_cache = module_cache.ok_or_else(|| {
+        DependencyResolutionError::RetrievalFailure("Could not locate module cache".to_string())
+    })?;
+    let module_path = module_cache.join(format!(
+        "{}@{}",
+        escape_path(&module.path),
+        escape_path(&module.version)
+    ));
+    if !module_path.is_dir() {
+        return Err(DependencyResolutionError::RetrievalFailure(format!(
+            "{} {} is not downloaded (run `go mod download`)",
+            module.path, module.version
+        )));
+    }
+    Ok(module_path)
+}
+
+/// List the modules the module at `path` requires directly.
+pub fn list_dependencies(path: &Path) -> Result<Vec<String>, DependencyResolutionError> {
+    let go_mod = read_go_mod(path)
+        .map_err(|e| DependencyResolutionError::RetrievalFailure(e.to_string()))?;
+    Ok(go_mod
+        .requirements
+        .into_iter()
+        .map(|requirement| requirement.path)
+        .collect())
+}
+
+fn find_requirement<'a>(
+    dependency_name: &str,
+    requirements: &'a [ModuleVersion],
+) -> Result<&'a ModuleVersion, DependencyResolutionError> {
+    if let Some(requirement) = requirements.iter().find(|r| r.path == dependency_name) {
+        return Ok(requirement);
+    }
+    let mut matches = requirements
+        .iter()
+        .filter(|r| r.path.rsplit('/').next() == Some(dependency_name));
+    match (matches.next(), matches.next()) {
+        (Some(requirement), None) => Ok(requirement),
+        _ => Err(DependencyResolutionError::MissingDependency(
+            dependency_name.to_string(),
+        )),
+    }
+}
+
+fn is_local_path(path: &str) -> bool {
+    path.starts_with("./") || path.starts_with("../") || Path::new(path).is_absolute()
+}
+
+fn is_pinned(module: &ModuleVersion, dependant_path: &Path) -> bool {
+    let Ok(go_sum) = fs::read_to_string(dependant_path.join(GO_SUM_PATH)) else {
+        return false;
+    };
+    go_sum.lines().any(|line| {
+        let mut fields = line.split_whitespace();
+        fields.next() == Some(module.path.as_str())
+            && fields.next() == Some(module.version.as_str())
+    })
+}
+
+fn get_module_cache() -> Option<PathBuf> {
+    if let Some(module_cache) = env::var_os("GOMODCACHE").filter(|value| !value.is_empty()) {
+        return Some(PathBuf::from(module_cache));
+    }
+    let gopath = env::var_os("GOPATH")
+        .and_then(|gopath| env::split_paths(&gopath).next())
+        .or_else(|| env::var_os("HOME").map(|home| PathBuf::from(home).join("go")))?;
+    Some(gopath.join("pkg").join("mod"))
+}
+
+/// Escape a module path or version as the module cache does, e.g. `BurntSushi` as `!burnt!sushi`.
+fn escape_path(path: &str) -> String {
+    path.chars()
+        .flat_map(|c| match c.is_ascii_uppercase() {
+            true => vec!['!', c.to_ascii_lowercase()],
+            false => vec![c],
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::languages::go::go_mod::GO_MOD_PATH;
+    use assertables::assert_matches;
+    use daipendency_testing::tempdir::TempDir;
+
+    const STUB_DEPENDENCY: &str = "github.com/pkg/errors";
+    const STUB_VERSION: &str = "v0.9.1";
+    const MODULE_CACHE_DIRECTORY: &str = "gomodcache";
+
+    fn create_dependant(temp_dir: &TempDir, directives: &str) {
+        temp_dir
+            .create_file(
+                &format!("project/{GO_MOD_PATH}"),
+                &format!(
+                    "module example.com/project\n\nrequire {STUB_DEPENDENCY} {STUB_VERSION}\n{directives}"
+                ),
+            )
+            .unwrap();
+    }
+
+    fn create_go_sum(temp_dir: &TempDir) {
+        temp_dir
+            .create_file(
+                &format!("project/{GO_SUM_PATH}"),
+                &format!(
+                    "{STUB_DEPENDENCY} {STUB_VERSION} h1:abc=\n{STUB_DEPENDENCY} {STUB_VERSION}/go.mod h1:def=\n"
+                ),
+            )
+            .unwrap();
+    }
+
+    fn create_cached_module(temp_dir: &TempDir, module_directory: &str) -> PathBuf {
+        temp_dir
+            .create_file(
+                &format!("{MODULE_CACHE_DIRECTORY}/{module_directory}/{GO_MOD_PATH}"),
+                "",
+            )
+            .unwrap()
+            .parent()
+            .unwrap()
+            .to_path_buf()
+    }
+
+    fn resolve(temp_dir: &TempDir, name: &str) -> Result<PathBuf, DependencyResolutionError> {
+        resolve_dependency_path_in_cache(
+            name,
+            &temp_dir.path.join("project"),
+            Some(temp_dir.path.join(MODULE_CACHE_DIRECTORY)),
+        )
+    }
+
+    #[test]
+    fn cached_module() {
+        let temp_dir = TempDir::new();
+        create_dependant(&temp_dir, "");
+        create_go_sum(&temp_dir);
+        let module_path =
+            create_cached_module(&temp_dir, &format!("{STUB_DEPENDENCY}@{STUB_VERSION}"));
+
+        let result = resolve(&temp_dir, STUB_DEPENDENCY).unwrap();
+
+        assert_eq!(result, module_path);
+    }
+
+    #[test]
+    fn short_name() {
+        let temp_dir = TempDir::new();
+        create_dependant(&temp_dir, "");
+        create_go_sum(&temp_dir);
+        let module_path =
+            create_cached_module(&temp_dir, &format!("{STUB_DEPENDENCY}@{STUB_VERSION}"));
+
+        let result = resolve(&temp_dir, "errors").unwrap();
+
+        assert_eq!(result, module_path);
+    }
+
+    #[test]
+    fn vendored_module() {
+        let temp_dir = TempDir::new();
+        create_dependant(&temp_dir, "");
+        let vendored_path = temp_dir
+            .create_file(
+                &format!("project/{VENDOR_DIRECTORY}/{STUB_DEPENDENCY}/errors.go"),
+                "",
+            )
+            .unwrap()
+            .parent()
+            .unwrap()
+            .to_path_buf();
+
+        let result = resolve(&temp_dir, STUB_DEPENDENCY).unwrap();
+
+        assert_eq!(result, vendored_path);
+    }
+
+    #[test]
+    fn local_replacement() {
+        let temp_dir = TempDir::new();
+        create_dependant(
+            &temp_dir,
+            &format!("replace {STUB_DEPENDENCY} => ../errors\n"),
+        );
+
+        let result = resolve(&temp_dir, STUB_DEPENDENCY).unwrap();
+
+        assert_eq!(result, temp_dir.path.join("project").join("../errors"));
+    }
+
+    #[test]
+    fn escaped_module_path() {
+        let temp_dir = TempDir::new();
+        create_dependant(
+            &temp_dir,
+            &format!("replace {STUB_DEPENDENCY} => github.com/BurntSushi/errors v1.0.0\n"),
+        );
+        temp_dir
+            .create_file(
+                &format!("project/{GO_SUM_PATH}"),
+                "github.com/BurntSushi/errors v1.0.0 h1:abc=\n",
+            )
+            .unwrap();
+        let module_path = create_cached_module(&temp_dir, "github.com/!burnt!sushi/errors@v1.0.0");
+
+        let result = resolve(&temp_dir, STUB_DEPENDENCY).unwrap();
+
+        assert_eq!(result, module_path);
+    }
+
+    #[test]
+    fn unpinned_module() {
+        let temp_dir = TempDir::new();
+        create_dependant(&temp_dir, "");
+        create_cached_module(&temp_dir, &format!("{STUB_DEPENDENCY}@{STUB_VERSION}"));
+
+        let result = resolve(&temp_dir, STUB_DEPENDENCY);
+
+        assert_matches!(result, Err(DependencyResolutionError::RetrievalFailure(_)));
+    }
+
+    #[test]
+    fn undownloaded_module() {
+        let temp_dir = TempDir::new();
+        create_dependant(&temp_dir, "");
+        create_go_sum(&temp_dir);
+
+        let result = resolve(&temp_dir, STUB_DEPENDENCY);
+
+        assert_matches!(result, Err(DependencyResolutionError::RetrievalFailure(_)));
+    }
+
+    #[test]
+    fn missing_requirement() {
+        let temp_dir = TempDir::new();
+        create_dependant(&temp_dir, "");
+
+        let result = resolve(&temp_dir, "github.com/other/module");
+
+        assert_matches!(
+            result,
+            Err(DependencyResolutionError::MissingDependency(name)) if name == "github.com/other/module"
+        );
+    }
+
+    #[test]
+    fn indirect_requirement() {
+        let temp_dir = TempDir::new();
+        temp_dir
+            .create_file(
+                &format!("project/{GO_MOD_PATH}"),
+                &format!("module example.com/project\n\nrequire {STUB_DEPENDENCY} {STUB_VERSION} // indirect\n"),
+            )
+            .unwrap();
+        create_go_sum(&temp_dir);
+        let module_path =
+            create_cached_module(&temp_dir, &format!("{STUB_DEPENDENCY}@{STUB_VERSION}"));
+
+        let result = resolve(&temp_dir, STUB_DEPENDENCY).unwrap();
+
+        assert_eq!(result, module_path);
+    }
+
+    #[test]
+    fn listed_dependencies() {
+        let temp_dir = TempDir::new();
+        create_dependant(
+            &temp_dir,
+            "require github.com/other/indirect v1.0.0 // indirect\n",
+        );
+
+        let names = list_dependencies(&temp_dir.path.join("project")).unwrap();
+
+        assert_eq!(names, vec![STUB_DEPENDENCY]);
+    }
+
+    #[test]
+    fn missing_dependant_manifest() {
+        let temp_dir = TempDir::new();
+
+        let result = resolve(&temp_dir, STUB_DEPENDENCY);
+
+        assert_matches!(result, Err(DependencyResolutionError::RetrievalFailure(_)));
+    }
+}
@@ -0,0 +1,219 @@
+use daipendency_extractor::LibraryMetadataError;
+use std::fs;
+use std::path::Path;
+
+pub const GO_MOD_PATH: &str = "go.mod";
+const INDIRECT_COMMENT: &str = "indirect";
+
+/// The directives of a `go.mod` file that matter for extraction.
+#[derive(Debug, Default, PartialEq)]
+pub struct GoMod {
+    pub module: String,
+    pub requirements: Vec<ModuleVersion>,
+    /// Requirements marked `// indirect`, which the module does not import itself.
+    pub indirect_requirements: Vec<ModuleVersion>,
+    pub replacements: Vec<Replacement>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ModuleVersion {
+    pub path: String,
+    pub version: String,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Replacement {
+    pub old_path: String,
+    /// A module path or, for local replacements, a filesystem path.
+    pub new_path: String,
+    pub new_version: Option<String>,
+}
+
+pub fn read_go_mod(directory: &Path) -> Result<GoMod, LibraryMetadataError> {
+    let content = fs::read_to_string(directory.join(GO_MOD_PATH))
+        .map_err(LibraryMetadataError::MissingManifest)?;
+    parse_go_mod(&content)
+}
+
+fn parse_go_mod(content: &str) -> Result<GoMod, LibraryMetadataError> {
+    let mut module = None;
+    let mut requirements = Vec::new();
+    let mut indirect_requirements = Vec::new();
+    let mut replacements = Vec::new();
+    let mut block_directive: Option<String> = None;
+
+    for line in content.lines() {
+        let (line, comment) = line.split_once("//").unwrap_or((line, ""));
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+        let tokens: Vec<&str> = line.split_whitespace().map(unquote).collect();
+
+        let (directive, arguments) = match &block_directive {
+            Some(_) if tokens == [")"] => {
+                block_directive = None;
+                continue;
+            }
+            Some(directive) => (directive.as_str(), tokens.as_slice()),
+            None if tokens.len() == 2 && tokens[1] == "(" => {
+                block_directive = Some(tokens[0].to_string());
+                continue;
+            }
+            None => (tokens[0], &tokens[1..]),
+        };
+
+        match (directive, arguments) {
+            ("module", [path]) => module = Some(path.to_string()),
+            ("require", [path, version, ..]) => {
+                let requirement = ModuleVersion {
+                    path: path.to_string(),
+                    version: version.to_string(),
+                };
+                if comment.trim() == INDIRECT_COMMENT {
+                    indirect_requirements.push(requirement);
+                } else {
+                    requirements.push(requirement);
+                }
+            }
+            ("replace", arguments) => {
+                if let Some(replacement) = parse_replacement(arguments) {
+                    replacements.push(replacement);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    let module = module.ok_or_else(|| {
+        LibraryMetadataError::MalformedManifest("Missing module directive".to_string())
+    })?;
+    Ok(GoMod {
+        module,
+        requirements,
+        indirect_requirements,
+        replacements,
+    })
+}
+
+fn unquote(token: &str) -> &str {
+    token.trim_matches(|c| c == '"' || c == '`')
+}
+
+fn parse_replacement(arguments: &[&str]) -> Option<Replacement> {
+    let arrow = arguments.iter().position(|token| *token == "=>")?;
+    let old_path = arguments.first()?.to_string();
+    let new = &arguments[arrow + 1..];
+    Some(Replacement {
+        old_path,
+        new_path: new.first()?.to_string(),
+        new_version: new.get(1).map(|version| version.to_string()),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use assertables::assert_matches;
+
+    const STUB_MODULE: &str = "github.com/example/project";
+
+    #[test]
+    fn module_directive() {
+        let go_mod = parse_go_mod(&format!("module {STUB_MODULE}\n\ngo 1.22\n")).unwrap();
+
+        assert_eq!(go_mod.module, STUB_MODULE);
+    }
+
+    #[test]
+    fn quoted_module_directive() {
+        let go_mod = parse_go_mod(&format!("module \"{STUB_MODULE}\"\n")).unwrap();
+
+        assert_eq!(go_mod.module, STUB_MODULE);
+    }
+
+    #[test]
+    fn missing_module_directive() {
+        let result = parse_go_mod("go 1.22\n");
+
+        assert_matches!(result, Err(LibraryMetadataError::MalformedManifest(_)));
+    }
+
+    #[test]
+    fn single_requirement() {
+        let go_mod = parse_go_mod(&format!(
+            "module {STUB_MODULE}\nrequire github.com/pkg/errors v0.9.1 // pinned\n"
+        ))
+        .unwrap();
+
+        assert_eq!(
+            go_mod.requirements,
+            vec![ModuleVersion {
+                path: "github.com/pkg/errors".to_string(),
+                version: "v0.9.1".to_string(),
+            }]
+        );
+    }
+
+    #[test]
+    fn requirement_block() {
+        let go_mod = parse_go_mod(&format!(
+            "module {STUB_MODULE}\n\nrequire (\n\tgithub.com/a/a v1.0.0\n\tgithub.com/b/b v2.0.0+incompatible\n)\n"
+        ))
+        .unwrap();
+
+        assert_eq!(go_mod.requirements.len(), 2);
+        assert_eq!(go_mod.requirements[1].version, "v2.0.0+incompatible");
+    }
+
+    #[test]
+    fn indirect_requirement() {
+        let go_mod = parse_go_mod(&format!(
+            "module {STUB_MODULE}\n\nrequire (\n\tgithub.com/a/a v1.0.0\n\tgithub.com/b/b v1.0.0 // indirect\n)\n"
+        ))
+        .unwrap();
+
+        assert_eq!(go_mod.requirements.len(), 1);
+        assert_eq!(
+            go_mod.indirect_requirements,
+            vec![ModuleVersion {
+                path: "github.com/b/b".to_string(),
+                version: "v1.0.0".to_string(),
+            }]
+        );
+    }
+
+    #[test]
+    fn local_replacement() {
+        let go_mod = parse_go_mod(&format!(
+            "module {STUB_MODULE}\nreplace github.com/a/a => ../a\n"
+        ))
+        .unwrap();
+
+        assert_eq!(
+            go_mod.replacements,
+            vec![Replacement {
+                old_path: "github.com/a/a".to_string(),
+                new_path: "../a".to_string(),
+                new_version: None,
+            }]
+        );
+    }
+
+    #[test]
+    fn module_replacement_block() {
+        let go_mod = parse_go_mod(&format!(
+            "module {STUB_MODULE}\nreplace (\n\tgithub.com/a/a v1.0.0 => github.com/fork/a v1.0.1\n)\n"
+        ))
+        .unwrap();
+
+        assert_eq!(
+            go_mod.replacements,
+            vec![Replacement {
+                old_path: "github.com/a/a".to_string(),
+                new_path: "github.com/fork/a".to_string(),
+                new_version: Some("v1.0.1".to_string()),
+            }]
+        );
+    }
+}
@@ -0,0 +1,145 @@
+use super::go_mod::read_go_mod;
+use daipendency_extractor::{LibraryMetadata, LibraryMetadataError};
+use std::fs;
+use std::path::Path;
+
+const VENDOR_DIRECTORY: &str = "vendor";
+const VENDOR_MANIFEST_PATH: &str = "modules.txt";
+const README_PATHS: [&str; 3] = ["README.md", "README", "README.txt"];
+
+/// Extract the metadata of the module at `path`.
+///
+/// Vendored modules have no `go.mod`, so their path and version come from `vendor/modules.txt`.
+pub fn extract_metadata(path: &Path) -> Result<LibraryMetadata, LibraryMetadataError> {
+    let (name, version) = match read_go_mod(path) {
+        Ok(go_mod) => (go_mod.module, get_module_cache_version(path)),
+        Err(LibraryMetadataError::MissingManifest(error)) => {
+            get_vendored_module(path).ok_or(LibraryMetadataError::MissingManifest(error))?
+        }
+        Err(error) => return Err(error),
+    };
+
+    let documentation = README_PATHS
+        .iter()
+        .find_map(|readme| fs::read_to_string(path.join(readme)).ok())
+        .unwrap_or_default();
+
+    Ok(LibraryMetadata {
+        name,
+        version,
+        documentation,
+        entry_point: path.to_path_buf(),
+    })
+}
+
+/// Get the version from a module cache directory name such as `errors@v0.9.1`.
+fn get_module_cache_version(path: &Path) -> Option<String> {
+    path.file_name()?
+        .to_str()?
+        .split_once('@')
+        .map(|(_, version)| version.to_string())
+}
+
+fn get_vendored_module(path: &Path) -> Option<(String, Option<String>)> {
+    let vendor_directory = path
+        .ancestors()
+        .find(|ancestor| ancestor.file_name() == Some(VENDOR_DIRECTORY.as_ref()))?;
+    let module_path = path
+        .strip_prefix(vendor_directory)
+        .ok()?
+        .components()
+        .map(|component| component.as_os_str().to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/");
+
+    let vendor_manifest = fs::read_to_string(vendor_directory.join(VENDOR_MANIFEST_PATH)).ok()?;
+    let version = vendor_manifest.lines().find_map(|line| {
+        match line.split_whitespace().collect::<Vec<_>>().as_slice() {
+            ["#", path, version, ..] if *path == module_path => Some(version.to_string()),
+            _ => None,
+        }
+    });
+    Some((module_path, version))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::languages::go::go_mod::GO_MOD_PATH;
+    use assertables::assert_matches;
+    use daipendency_testing::tempdir::TempDir;
+
+    const STUB_MODULE: &str = "github.com/example/project";
+    const STUB_DOCUMENTATION: &str = "Test documentation";
+
+    #[test]
+    fn module() {
+        let temp_dir = TempDir::new();
+        temp_dir
+            .create_file(GO_MOD_PATH, &format!("module {STUB_MODULE}\n"))
+            .unwrap();
+        temp_dir
+            .create_file("README.md", STUB_DOCUMENTATION)
+            .unwrap();
+
+        let metadata = extract_metadata(&temp_dir.path).unwrap();
+
+        assert_eq!(metadata.name, STUB_MODULE);
+        assert_eq!(metadata.version, None);
+        assert_eq!(metadata.documentation, STUB_DOCUMENTATION);
+        assert_eq!(metadata.entry_point, temp_dir.path);
+    }
+
+    #[test]
+    fn module_cache_version() {
+        let temp_dir = TempDir::new();
+        let go_mod = temp_dir
+            .create_file(
+                &format!("pkg/mod/{STUB_MODULE}@v1.2.3/{GO_MOD_PATH}"),
+                &format!("module {STUB_MODULE}\n"),
+            )
+            .unwrap();
+
+        let metadata = extract_metadata(go_mod.parent().unwrap()).unwrap();
+
+        assert_eq!(metadata.version, Some("v1.2.3".to_string()));
+    }
+
+    #[test]
+    fn vendored_module() {
+        let temp_dir = TempDir::new();
+        temp_dir
+            .create_file(
+                &format!("{VENDOR_DIRECTORY}/{VENDOR_MANIFEST_PATH}"),
+                &format!("# {STUB_MODULE} v1.2.3\n## explicit\n{STUB_MODULE}\n"),
+            )
+            .unwrap();
+        let source = temp_dir
+            .create_file(&format!("{VENDOR_DIRECTORY}/{STUB_MODULE}/lib.go"), "")
+            .unwrap();
+
+        let metadata = extract_metadata(source.parent().unwrap()).unwrap();
+
+        assert_eq!(metadata.name, STUB_MODULE);
+        assert_eq!(metadata.version, Some("v1.2.3".to_string()));
+    }
+
+    #[test]
+    fn missing_manifest() {
+        let temp_dir = TempDir::new();
+
+        let result = extract_metadata(&temp_dir.path);
+
+        assert_matches!(result, Err(LibraryMetadataError::MissingManifest(_)));
+    }
+
+    #[test]
+    fn malformed_manifest() {
+        let temp_dir = TempDir::new();
+        temp_dir.create_file(GO_MOD_PATH, "go 1.22\n").unwrap();
+
+        let result = extract_metadata(&temp_dir.path);
+
+        assert_matches!(result, Err(LibraryMetadataError::MalformedManifest(_)));
+    }
+}
@@ -0,0 +1,46 @@
+mod api;
+mod dependencies;
+mod go_mod;
+mod metadata;
+mod parsing;
+mod symbol_kind;
+#[cfg(test)]
+mod test_helpers;
+
+pub use dependencies::list_dependencies;
+pub use symbol_kind::detect_symbol_kind;
+
+use daipendency_extractor::{
+    DependencyResolutionError, ExtractionError, Extractor, LibraryMetadata, LibraryMetadataError,
+    Namespace,
+};
+use std::path::{Path, PathBuf};
+use tree_sitter::{Language, Parser};
+
+pub struct GoExtractor;
+
+impl Extractor for GoExtractor {
+    fn get_parser_language(&self) -> Language {
+        tree_sitter_go::LANGUAGE.into()
+    }
+
+    fn get_library_metadata(&self, path: &Path) -> Result<LibraryMetadata, LibraryMetadataError> {
+        metadata::extract_metadata(path)
+    }
+
+    fn extract_public_api(
+        &self,
+        metadata: &LibraryMetadata,
+        parser: &mut Parser,
+    ) -> Result<Vec<Namespace>, ExtractionError> {
+        api::build_public_api(&metadata.entry_point, &metadata.name, parser)
+    }
+
+    fn resolve_dependency_path(
+        &self,
+        dependency_name: &str,
+        dependant_path: &Path,
+    ) -> Result<PathBuf, DependencyResolutionError> {
+        dependencies::resolve_dependency_path(dependency_name, dependant_path)
+    }
+}
@@ -0,0 +1,440 @@
+use daipendency_extractor::{ExtractionError, Symbol};
+use tree_sitter::{Node, Parser};
+
+/// The exported API of a Go source file.
+#[derive(Debug)]
+pub struct SourceFile {
+    pub package_name: String,
+    pub doc_comment: Option<String>,
+    pub symbols: Vec<Symbol>,
+}
+
+pub fn parse_source_file(
+    source_code: &str,
+    parser: &mut Parser,
+) -> Result<SourceFile, ExtractionError> {
+    let tree = parser
+        .parse(source_code, None)
+        .ok_or_else(|| ExtractionError::Malformed("Failed to parse Go file".to_string()))?;
+    let root = tree.root_node();
+    let source = source_code.as_bytes();
+
+    let mut package_name = String::new();
+    let mut doc_comment = None;
+    let mut symbols = Vec::new();
+    let mut cursor = root.walk();
+    for node in root.named_children(&mut cursor) {
+        match node.kind() {
+            "package_clause" => {
+                package_name = node
+                    .named_child(0)
+                    .map(|name| get_text(&name, source).to_string())
+                    .unwrap_or_default();
+                doc_comment = get_doc_comment(&node, source);
+            }
+            "function_declaration" => symbols.extend(render_function(&node, None, source)),
+            "method_declaration" => {
+                let receiver_type = node
+                    .child_by_field_name("receiver")
+                    .and_then(|receiver| get_receiver_type(&receiver, source));
+                if let Some(receiver_type) = receiver_type.filter(|name| is_exported(name)) {
+                    symbols.extend(render_function(&node, Some(&receiver_type), source));
+                }
+            }
+            "type_declaration" => symbols.extend(render_type_declaration(&node, source)),
+            "const_declaration" | "var_declaration" => {
+                symbols.extend(render_value_declaration(&node, source))
+            }
+            _ => {}
+        }
+    }
+
+    Ok(SourceFile {
+        package_name,
+        doc_comment,
+        symbols,
+    })
+}
+
+/// Whether an identifier is exported, i.e. it starts with an upper-case letter.
+pub fn is_exported(name: &str) -> bool {
+    name.chars().next().is_some_and(char::is_uppercase)
+}
+
+fn get_text<'a>(node: &Node, source: &'a [u8]) -> &'a str {
+    node.utf8_text(source).unwrap_or_default()
+}
+
+fn get_slice<'a>(start: &Node, end: &Node, source: &'a [u8]) -> &'a str {
+    std::str::from_utf8(&source[start.start_byte()..end.start_byte()])
+        .unwrap_or_default()
+        .trim_end()
+}
+
+/// Get the whitespace preceding `node` on its line, which in Go is typically tabs.
+fn get_indent<'a>(node: &Node, source: &'a [u8]) -> &'a str {
+    let start = node.start_byte() - node.start_position().column;
+    std::str::from_utf8(&source[start..node.start_byte()])
+        .map(|prefix| &prefix[..prefix.len() - prefix.trim_start().len()])
+        .unwrap_or_default()
+}
+
+/// Get the contiguous `//` comments immediately above `node`.
+fn get_doc_comment(node: &Node, source: &[u8]) -> Option<String> {
+    let mut lines = Vec::new();
+    let mut next_row = node.start_position().row;
+    let mut sibling = node.prev_named_sibling();
+    while let Some(comment) = sibling.filter(|s| s.kind() == "comment") {
+        if comment.end_position().row + 1 != next_row {
+            break;
+        }
+        lines.push(get_text(&comment, source));
+        next_row = comment.start_position().row;
+        sibling = comment.prev_named_sibling();
+    }
+    if lines.is_empty() {
+        return None;
+    }
+    lines.reverse();
+    Some(lines.join("\n"))
+}
+
+fn with_doc_comment(node: &Node, rendered: String, source: &[u8]) -> String {
+    match get_doc_comment(node, source) {
+        Some(doc_comment) => format!("{doc_comment}\n{rendered}"),
+        None => rendered,
+    }
+}
+
+fn get_receiver_type(receiver: &Node, source: &[u8]) -> Option<String> {
+    let parameter = receiver.named_child(0)?;
+    let mut receiver_type = parameter.child_by_field_name("type")?;
+    loop {
+        match receiver_type.kind() {
+            "pointer_type" | "parenthesized_type" => {
+                receiver_type = receiver_type.named_child(0)?
+            }
+            "generic_type" => receiver_type = receiver_type.child_by_field_name("type")?,
+            _ => return Some(get_text(&receiver_type, source).to_string()),
+        }
+    }
+}
+
+fn render_function(node: &Node, receiver_type: Option<&str>, source: &[u8]) -> Option<Symbol> {
+    let name = get_text(&node.child_by_field_name("name")?, source);
+    if !is_exported(name) {
+        return None;
+    }
+    let signature = match node.child_by_field_name("body") {
+        Some(body) => get_slice(node, &body, source),
+        None => get_text(node, source),
+    };
+    Some(Symbol {
+        name: match receiver_type {
+            Some(receiver_type) => format!("{receiver_type}.{name}"),
+            None => name.to_string(),
+        },
+        source_code: with_doc_comment(node, signature.to_string(), source),
+    })
+}
+
+/// Render each exported spec in a `type`, `const` or `var` declaration, which may be grouped.
+fn render_specs(
+    declaration: &Node,
+    source: &[u8],
+    render_spec: impl Fn(&Node) -> Option<(String, String)>,
+) -> Vec<Symbol> {
+    let keyword = declaration
+        .child(0)
+        .map(|keyword| get_text(&keyword, source))
+        .unwrap_or_default();
+    let mut cursor = declaration.walk();
+    let specs = declaration.named_children(&mut cursor).collect::<Vec<_>>();
+    let is_grouped = specs.iter().any(|spec| spec.kind() == "comment")
+        || declaration.child(1).is_some_and(|c| c.kind() == "(");
+
+    specs
+        .iter()
+        .filter(|spec| spec.kind() != "comment")
+        .filter_map(|spec| {
+            let (name, rendered) = render_spec(spec)?;
+            let rendered = format!("{keyword} {rendered}");
+            let documented_node = if is_grouped { spec } else { declaration };
+            Some(Symbol {
+                name,
+                source_code: with_doc_comment(documented_node, rendered, source),
+            })
+        })
+        .collect()
+}
+
+fn render_type_declaration(declaration: &Node, source: &[u8]) -> Vec<Symbol> {
+    render_specs(declaration, source, |spec| {
+        let name = get_text(&spec.child_by_field_name("name")?, source);
+        if !is_exported(name) {
+            return None;
+        }
+        let rendered = match spec.child_by_field_name("type") {
+            Some(struct_type) if struct_type.kind() == "struct_type" => {
+                render_struct(spec, &struct_type, source)
+            }
+            _ => get_text(spec, source).to_string(),
+        };
+        Some((name.to_string(), rendered))
+    })
+}
+
+fn render_struct(spec: &Node, struct_type: &Node, source: &[u8]) -> String {
+    let mut cursor = struct_type.walk();
+    let Some(fields) = struct_type
+        .named_children(&mut cursor)
+        .find(|child| child.kind() == "field_declaration_list")
+    else {
+        return get_text(spec, source).to_string();
+    };
+
+    let mut field_cursor = fields.walk();
+    let exported_fields = fields
+        .named_children(&mut field_cursor)
+        .filter(|field| field.kind() == "field_declaration" && is_exported_field(field, source))
+        .map(|field| {
+            let indent = get_indent(&field, source);
+            let rendered = format!("{indent}{}", get_text(&field, source));
+            match get_doc_comment(&field, source) {
+                Some(doc_comment) => format!("{indent}{doc_comment}\n{rendered}"),
+                None => rendered,
+            }
+        })
+        .collect::<Vec<_>>();
+
+    let header = get_slice(spec, &fields, source);
+    if exported_fields.is_empty() {
+        format!("{header} {{}}")
+    } else {
+        format!("{header} {{\n{}\n}}", exported_fields.join("\n"))
+    }
+}
+
+fn is_exported_field(field: &Node, source: &[u8]) -> bool {
+    let mut cursor = field.walk();
+    let mut names = field.children_by_field_name("name", &mut cursor).peekable();
+    if names.peek().is_some() {
+        return names.any(|name| is_exported(get_text(&name, source)));
+    }
+    // Embedded fields are exported if their type is
+    let embedded_type = field
+        .child_by_field_name("type")
+        .map(|field_type| get_text(&field_type, source))
+        .unwrap_or_default();
+    let type_name = embedded_type
+        .trim_start_matches('*')
+        .rsplit('.')
+        .next()
+        .unwrap_or_default();
+    is_exported(type_name)
+}
+
+fn render_value_declaration(declaration: &Node, source: &[u8]) -> Vec<Symbol> {
+    render_specs(declaration, source, |spec| {
+        let mut cursor = spec.walk();
+        let name = spec
+            .children_by_field_name("name", &mut cursor)
+            .map(|name| get_text(&name, source))
+            .find(|name| is_exported(name))?;
+        Some((name.to_string(), get_text(spec, source).to_string()))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::languages::go::test_helpers::setup_parser;
+
+    fn parse(source_code: &str) -> SourceFile {
+        parse_source_file(
+            &format!("package demo\n\n{source_code}"),
+            &mut setup_parser(),
+        )
+        .unwrap()
+    }
+
+    fn get_symbol<'a>(file: &'a SourceFile, name: &str) -> &'a Symbol {
+        file.symbols
+            .iter()
+            .find(|symbol| symbol.name == name)
+            .unwrap_or_else(|| panic!("Symbol {name} not found"))
+    }
+
+    fn get_symbol_names(file: &SourceFile) -> Vec<&str> {
+        file.symbols
+            .iter()
+            .map(|symbol| symbol.name.as_str())
+            .collect()
+    }
+
+    mod package {
+        use super::*;
+
+        #[test]
+        fn package_name() {
+            let file = parse("");
+
+            assert_eq!(file.package_name, "demo");
+        }
+
+        #[test]
+        fn package_doc_comment() {
+            let file = parse_source_file(
+                "// Package demo does things.\n// Really.\npackage demo\n",
+                &mut setup_parser(),
+            )
+            .unwrap();
+
+            assert_eq!(
+                file.doc_comment,
+                Some("// Package demo does things.\n// Really.".to_string())
+            );
+        }
+
+        #[test]
+        fn detached_comment() {
+            let file = parse_source_file(
+                "// Copyright notice.\n\npackage demo\n",
+                &mut setup_parser(),
+            )
+            .unwrap();
+
+            assert_eq!(file.doc_comment, None);
+        }
+    }
+
+    mod functions {
+        use super::*;
+
+        #[test]
+        fn exported_function() {
+            let file =
+                parse("// New creates.\nfunc New[T any](url string) *Client { return nil }\n");
+
+            assert_eq!(
+                get_symbol(&file, "New").source_code,
+                "// New creates.\nfunc New[T any](url string) *Client"
+            );
+        }
+
+        #[test]
+        fn unexported_function() {
+            let file = parse("func helper() {}\n");
+
+            assert!(file.symbols.is_empty());
+        }
+
+        #[test]
+        fn exported_method() {
+            let file =
+                parse("func (c *Client) Get(path string) (string, error) { return \"\", nil }\n");
+
+            assert_eq!(
+                get_symbol(&file, "Client.Get").source_code,
+                "func (c *Client) Get(path string) (string, error)"
+            );
+        }
+
+        #[test]
+        fn generic_receiver() {
+            let file = parse("func (l List[T]) Len() int { return 0 }\n");
+
+            assert_eq!(get_symbol_names(&file), vec!["List.Len"]);
+        }
+
+        #[test]
+        fn method_on_unexported_type() {
+            let file = parse("func (c client) Get() {}\n");
+
+            assert!(file.symbols.is_empty());
+        }
+
+        #[test]
+        fn unexported_method() {
+            let file = parse("func (c Client) get() {}\n");
+
+            assert!(file.symbols.is_empty());
+        }
+    }
+
+    mod types {
+        use super::*;
+
+        #[test]
+        fn struct_fields() {
+            let file = parse(
+                "// Client talks.\ntype Client struct {\n\t// URL doc\n\tURL string\n\tsecret int\n\tEmbedded\n\t*hidden\n}\n",
+            );
+
+            assert_eq!(
+                get_symbol(&file, "Client").source_code,
+                "// Client talks.\ntype Client struct {\n\t// URL doc\n\tURL string\n\tEmbedded\n}"
+            );
+        }
+
+        #[test]
+        fn struct_without_exported_fields() {
+            let file = parse("type Client struct {\n\tsecret int\n}\n");
+
+            assert_eq!(
+                get_symbol(&file, "Client").source_code,
+                "type Client struct {}"
+            );
+        }
+
+        #[test]
+        fn interface() {
+            let file = parse("type Reader interface {\n\tRead(p []byte) (int, error)\n}\n");
+
+            assert_eq!(
+                get_symbol(&file, "Reader").source_code,
+                "type Reader interface {\n\tRead(p []byte) (int, error)\n}"
+            );
+        }
+
+        #[test]
+        fn grouped_types() {
+            let file = parse("type (\n\t// ID doc\n\tID int\n\talias = int\n)\n");
+
+            assert_eq!(get_symbol_names(&file), vec!["ID"]);
+            assert_eq!(
+                get_symbol(&file, "ID").source_code,
+                "// ID doc\ntype ID int"
+            );
+        }
+    }
+
+    mod values {
+        use super::*;
+
+        #[test]
+        fn constant() {
+            let file = parse("// Version is the version.\nconst Version = \"1.0\"\n");
+
+            assert_eq!(
+                get_symbol(&file, "Version").source_code,
+                "// Version is the version.\nconst Version = \"1.0\""
+            );
+        }
+
+        #[test]
+        fn grouped_constants() {
+            let file = parse("const (\n\tA Kind = iota\n\tb\n\tC\n)\n");
+
+            assert_eq!(get_symbol_names(&file), vec!["A", "C"]);
+            assert_eq!(get_symbol(&file, "A").source_code, "const A Kind = iota");
+        }
+
+        #[test]
+        fn variables() {
+            let file = parse("var x, Y int = 1, 2\n");
+
+            assert_eq!(get_symbol(&file, "Y").source_code, "var x, Y int = 1, 2");
+        }
+    }
+}
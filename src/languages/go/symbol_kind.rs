@@ -0,0 +1,106 @@
+use crate::symbol_kind::{skip_comments_and_annotations, SymbolKind};
+
+const LINE_COMMENT: &str = "//";
+
+/// Detect the kind of the declaration in `source_code`, skipping its doc comment.
+pub fn detect_symbol_kind(source_code: &str) -> Option<SymbolKind> {
+    let declaration = skip_comments_and_annotations(source_code, LINE_COMMENT);
+    let (keyword, rest) = declaration.split_once(char::is_whitespace)?;
+    match keyword {
+        "func" => Some(SymbolKind::Function),
+        "const" | "var" => Some(SymbolKind::Constant),
+        "type" => Some(detect_type_kind(rest)),
+        _ => None,
+    }
+}
+
+/// Detect the kind of the type spec (e.g. `Client struct {}`) that follows the `type` keyword.
+fn detect_type_kind(spec: &str) -> SymbolKind {
+    let name_end = spec
+        .find(|character: char| character.is_whitespace() || character == '[')
+        .unwrap_or(spec.len());
+    let mut definition = spec[name_end..].trim_start();
+    // Skip the type parameters of generic types, as in `Set[T comparable] struct`.
+    if definition.starts_with('[') {
+        let mut depth = 0usize;
+        for (index, character) in definition.char_indices() {
+            match character {
+                '[' => depth += 1,
+                ']' => {
+                    depth -= 1;
+                    if depth == 0 {
+                        definition = definition[index + 1..].trim_start();
+                        break;
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+    if definition.starts_with("struct") {
+        SymbolKind::Struct
+    } else if definition.starts_with("interface") {
+        SymbolKind::Interface
+    } else {
+        SymbolKind::TypeAlias
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn functions() {
+        assert_eq!(
+            detect_symbol_kind("// New creates.\nfunc New[T any](url string) *Client"),
+            Some(SymbolKind::Function)
+        );
+        assert_eq!(
+            detect_symbol_kind("func (c *Client) Get(path string) (string, error)"),
+            Some(SymbolKind::Function)
+        );
+    }
+
+    #[test]
+    fn types() {
+        assert_eq!(
+            detect_symbol_kind("// Client talks.\ntype Client struct {\n\tURL string\n}"),
+            Some(SymbolKind::Struct)
+        );
+        assert_eq!(
+            detect_symbol_kind("type Set[T comparable] struct {}"),
+            Some(SymbolKind::Struct)
+        );
+        assert_eq!(
+            detect_symbol_kind("type Reader interface {\n\tRead(p []byte) (int, error)\n}"),
+            Some(SymbolKind::Interface)
+        );
+        assert_eq!(
+            detect_symbol_kind("type ID int"),
+            Some(SymbolKind::TypeAlias)
+        );
+        assert_eq!(
+            detect_symbol_kind("type Alias = Client"),
+            Some(SymbolKind::TypeAlias)
+        );
+    }
+
+    #[test]
+    fn constants_and_variables() {
+        assert_eq!(
+            detect_symbol_kind("// Version is the version.\nconst Version = \"1.0\""),
+            Some(SymbolKind::Constant)
+        );
+        assert_eq!(
+            detect_symbol_kind("var Default Client"),
+            Some(SymbolKind::Constant)
+        );
+    }
+
+    #[test]
+    fn unrecognised_declarations() {
+        assert_eq!(detect_symbol_kind("package client"), None);
+        assert_eq!(detect_symbol_kind(""), None);
+    }
+}
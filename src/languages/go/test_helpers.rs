@@ -0,0 +1,8 @@
+use daipendency_extractor::{get_parser, Extractor};
+use tree_sitter::Parser;
+
+use super::GoExtractor;
+
+pub fn setup_parser() -> Parser {
+    get_parser(&GoExtractor.get_parser_language()).unwrap()
+}
@@ -0,0 +1,208 @@
+use super::kotlin::parse_kotlin_file;
+use super::parsing::parse_compilation_unit;
+use daipendency_extractor::{ExtractionError, Namespace};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use tree_sitter::Parser;
+
+const JAVA_EXTENSION: &str = "java";
+const KOTLIN_EXTENSION: &str = "kt";
+const PACKAGE_INFO_FILE: &str = "package-info.java";
+const MODULE_INFO_FILE: &str = "module-info.java";
+
+/// Build the public API of the Java and Kotlin sources under `source_root`, with one namespace per
+/// package.
+///
+/// Classes in the default package are skipped as they cannot be imported from other packages.
+pub fn build_public_api(
+    source_root: &Path,
+    parser: &mut Parser,
+) -> Result<Vec<Namespace>, ExtractionError> {
+    let mut packages: BTreeMap<String, Namespace> = BTreeMap::new();
+    for path in collect_source_files(source_root)? {
+        let source_code = fs::read_to_string(&path)?;
+        let unit = if path.extension() == Some(KOTLIN_EXTENSION.as_ref()) {
+            parse_kotlin_file(&source_code)
+        } else {
+            parse_compilation_unit(&source_code, parser)?
+        };
+        let Some(package_name) = unit.package_name else {
+            continue;
+        };
+        let namespace = packages
+            .entry(package_name.clone())
+            .or_insert_with(|| Namespace {
+                name: package_name,
+                symbols: Vec::new(),
+                doc_comment: None,
+            });
+        if path.file_name() == Some(PACKAGE_INFO_FILE.as_ref()) {
+            namespace.doc_comment = unit.doc_comment;
+        } else {
+            namespace.symbols.extend(unit.symbols);
+        }
+    }
+
+    Ok(packages
+        .into_values()
+        .filter(|namespace| !namespace.symbols.is_empty())
+        .collect())
+}
+
+fn collect_source_files(directory: &Path) -> Result<Vec<PathBuf>, ExtractionError> {
+    let mut entries = fs::read_dir(directory)?
+        .filter_map(Result::ok)
+        .map(|entry| entry.path())
+        .collect::<Vec<_>>();
+    entries.sort();
+
+    let mut source_files = Vec::new();
+    for path in entries {
+        if path.is_dir() {
+            source_files.extend(collect_source_files(&path)?);
+        } else if [JAVA_EXTENSION, KOTLIN_EXTENSION]
+            .iter()
+            .any(|extension| path.extension() == Some(extension.as_ref()))
+            && path.file_name() != Some(MODULE_INFO_FILE.as_ref())
+        {
+            source_files.push(path);
+        }
+    }
+    Ok(source_files)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::languages::java::test_helpers::setup_parser;
+    use daipendency_testing::tempdir::TempDir;
+
+    fn build(temp_dir: &TempDir) -> Vec<Namespace> {
+        build_public_api(&temp_dir.path, &mut setup_parser()).unwrap()
+    }
+
+    fn get_symbol_names(namespace: &Namespace) -> Vec<&str> {
+        namespace
+            .symbols
+            .iter()
+            .map(|symbol| symbol.name.as_str())
+            .collect()
+    }
+
+    #[test]
+    fn classes_grouped_by_package() {
+        let temp_dir = TempDir::new();
+        temp_dir
+            .create_file(
+                "com/example/A.java",
+                "package com.example;\npublic class A {}\n",
+            )
+            .unwrap();
+        temp_dir
+            .create_file(
+                "com/example/B.java",
+                "package com.example;\npublic class B {}\n",
+            )
+            .unwrap();
+        temp_dir
+            .create_file(
+                "com/example/util/C.java",
+                "package com.example.util;\npublic class C {}\n",
+            )
+            .unwrap();
+
+        let namespaces = build(&temp_dir);
+
+        assert_eq!(namespaces.len(), 2);
+        assert_eq!(namespaces[0].name, "com.example");
+        assert_eq!(get_symbol_names(&namespaces[0]), vec!["A", "B"]);
+        assert_eq!(namespaces[1].name, "com.example.util");
+    }
+
+    #[test]
+    fn kotlin_sources() {
+        let temp_dir = TempDir::new();
+        temp_dir
+            .create_file(
+                "com/example/A.java",
+                "package com.example;\npublic class A {}\n",
+            )
+            .unwrap();
+        temp_dir
+            .create_file(
+                "com/example/B.kt",
+                "package com.example\n\nclass B\n\nfun c() {}\n",
+            )
+            .unwrap();
+
+        let namespaces = build(&temp_dir);
+
+        assert_eq!(namespaces.len(), 1);
+        assert_eq!(get_symbol_names(&namespaces[0]), vec!["A", "B", "c"]);
+    }
+
+    #[test]
+    fn package_info() {
+        let temp_dir = TempDir::new();
+        temp_dir
+            .create_file(
+                "com/example/package-info.java",
+                "/** Example package. */\npackage com.example;\n",
+            )
+            .unwrap();
+        temp_dir
+            .create_file(
+                "com/example/A.java",
+                "package com.example;\npublic class A {}\n",
+            )
+            .unwrap();
+
+        let namespaces = build(&temp_dir);
+
+        assert_eq!(
+            namespaces[0].doc_comment,
+            Some("/** Example package. */".to_string())
+        );
+        assert_eq!(get_symbol_names(&namespaces[0]), vec!["A"]);
+    }
+
+    #[test]
+    fn package_without_public_classes() {
+        let temp_dir = TempDir::new();
+        temp_dir
+            .create_file("com/example/A.java", "package com.example;\nclass A {}\n")
+            .unwrap();
+
+        let namespaces = build(&temp_dir);
+
+        assert!(namespaces.is_empty());
+    }
+
+    #[test]
+    fn default_package() {
+        let temp_dir = TempDir::new();
+        temp_dir
+            .create_file("Main.java", "public class Main {}\n")
+            .unwrap();
+
+        let namespaces = build(&temp_dir);
+
+        assert!(namespaces.is_empty());
+    }
+
+    #[test]
+    fn non_java_files() {
+        let temp_dir = TempDir::new();
+        temp_dir
+            .create_file("META-INF/MANIFEST.MF", "Manifest-Version: 1.0\n")
+            .unwrap();
+        temp_dir
+            .create_file("module-info.java", "module com.example {}\n")
+            .unwrap();
+
+        let namespaces = build(&temp_dir);
+
+        assert!(namespaces.is_empty());
+    }
+}
@@ -0,0 +1,477 @@
+use super::maven::POM_PATH;
+use super::project::{read_project, Coordinates};
+use crate::cache::Cache;
+use daipendency_extractor::DependencyResolutionError;
+use std::env;
+use std::fs::{self, File};
+use std::path::{Path, PathBuf};
+use std::process;
+use std::time::{SystemTime, UNIX_EPOCH};
+use zip::ZipArchive;
+
+const SOURCES_JAR_SUFFIX: &str = "-sources.jar";
+const POM_SUFFIX: &str = ".pom";
+const MAVEN_REPOSITORY_PATH: &str = ".m2/repository";
+const GRADLE_CACHE_PATH: &str = "caches/modules-2/files-2.1";
+const UNPACK_DIRECTORY: &str = "daipendency-sources";
+
+/// A local repository of downloaded artifacts.
+#[derive(Debug)]
+enum Repository {
+    /// Artifacts under `group/id/as/path/artifact/version/`.
+    Maven(PathBuf),
+    /// Artifacts under `group.id/artifact/version/<hash>/`.
+    Gradle(PathBuf),
+}
+
+impl Repository {
+    fn find_artifact_file(
+        &self,
+        group_id: &str,
+        artifact_id: &str,
+        version: &str,
+        suffix: &str,
+    ) -> Option<PathBuf> {
+        let file_name = format!("{artifact_id}-{version}{suffix}");
+        match self {
+            Repository::Maven(root) => Some(
+                root.join(group_id.replace('.', "/"))
+                    .join(artifact_id)
+                    .join(version)
+                    .join(file_name),
+            )
+            .filter(|path| path.is_file()),
+            Repository::Gradle(root) => {
+                let mut hash_directories =
+                    fs::read_dir(root.join(group_id).join(artifact_id).join(version))
+                        .ok()?
+                        .filter_map(Result::ok)
+                        .map(|entry| entry.path())
+                        .collect::<Vec<_>>();
+                hash_directories.sort();
+                hash_directories
+                    .into_iter()
+                    .map(|directory| directory.join(&file_name))
+                    .find(|path| path.is_file())
+            }
+        }
+    }
+}
+
+/// Resolve a dependency declared in the dependant's `pom.xml` or Gradle build script.
+///
+/// Its sources jar is located in the local Maven repository or Gradle cache and unpacked to the
+/// user's cache directory, alongside its POM so that the unpacked sources form a Maven project.
+pub fn resolve_dependency_path(
+    dependency_name: &str,
+    dependant_path: &Path,
+) -> Result<PathBuf, DependencyResolutionError> {
+    let cache = Cache::get_default().ok_or_else(|| {
+        DependencyResolutionError::RetrievalFailure(
+            "Could not determine the cache directory to unpack sources jars to".to_string(),
+        )
+    })?;
+    resolve_dependency_path_in_repositories(
+        dependency_name,
+        dependant_path,
+        &get_repositories(),
+        &cache.get_directory().join(UNPACK_DIRECTORY),
+    )
+}
+
+fn resolve_dependency_path_in_repositories(
+    dependency_name: &str,
+    dependant_path: &Path,
+    repositories: &[Repository],
+    unpack_root: &Path,
+) -> Result<PathBuf, DependencyResolutionError> {
+    let project = read_project(dependant_path)
+        .map_err(|e| DependencyResolutionError::RetrievalFailure(e.to_string()))?;
+    let all_dependencies = [
+        project.dependencies.as_slice(),
+        project.test_dependencies.as_slice(),
+    ]
+    .concat();
+    let dependency = find_dependency(dependency_name, &all_dependencies)?;
+    let (Some(group_id), Some(version)) = (&dependency.group_id, &dependency.version) else {
+        return Err(DependencyResolutionError::RetrievalFailure(format!(
+            "Could not determine the group and version of '{}'",
+            dependency.get_name()
+        )));
+    };
+    let artifact_id = &dependency.artifact_id;
+
+    let sources_jar = repositories
+        .iter()
+        .find_map(|repository| {
+            repository.find_artifact_file(group_id, artifact_id, version, SOURCES_JAR_SUFFIX)
+        })
+        .ok_or_else(|| {
+            DependencyResolutionError::RetrievalFailure(format!(
+                "No sources jar for {}:{} found in the local Maven repository or Gradle cache",
+                dependency.get_name(),
+                version
+            ))
+        })?;
+
+    let destination = unpack_root
+        .join(group_id)
+        .join(format!("{artifact_id}-{version}"));
+    // Directories are only moved into place once complete, so an existing one can be reused.
+    if !destination.is_dir() {
+        let pom = repositories
+            .iter()
+            .find_map(|repository| {
+                repository.find_artifact_file(group_id, artifact_id, version, POM_SUFFIX)
+            })
+            .and_then(|pom_path| fs::read_to_string(pom_path).ok())
+            .unwrap_or_else(|| make_minimal_pom(group_id, artifact_id, version));
+        unpack_project(&sources_jar, &pom, &destination)?;
+    }
+    Ok(destination)
+}
+
+/// Unpack the sources jar and the POM to a fresh directory and then move it to `destination`,
+/// so that concurrent runs never see a partially unpacked project.
+fn unpack_project(
+    sources_jar: &Path,
+    pom: &str,
+    destination: &Path,
+) -> Result<(), DependencyResolutionError> {
+    let to_error = |error: std::io::Error| {
+        DependencyResolutionError::RetrievalFailure(format!(
+            "Failed to unpack {}: {}",
+            sources_jar.display(),
+            error
+        ))
+    };
+    let parent = destination
+        .parent()
+        .expect("Projects are unpacked by group");
+    fs::create_dir_all(parent).map_err(to_error)?;
+    let nanoseconds = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |duration| duration.subsec_nanos());
+    let staging_directory = parent.join(format!(
+        ".{}.{}-{}.tmp",
+        destination
+            .file_name()
+            .unwrap_or_default()
+            .to_string_lossy(),
+        process::id(),
+        nanoseconds
+    ));
+    // Creating the directory fails if it already exists, so nobody else can have seeded it.
+    fs::create_dir(&staging_directory).map_err(to_error)?;
+
+    let result = unpack_sources_jar(sources_jar, &staging_directory).and_then(|_| {
+        fs::write(staging_directory.join(POM_PATH), pom).map_err(to_error)?;
+        match fs::rename(&staging_directory, destination) {
+            // Another run moved its own copy into place first.
+            Err(_) if destination.is_dir() => Ok(()),
+            result => result.map_err(to_error),
+        }
+    });
+    if staging_directory.exists() {
+        let _ = fs::remove_dir_all(&staging_directory);
+    }
+    result
+}
+
+/// List the non-test dependencies of the project at `path` by their `groupId:artifactId` names.
+pub fn list_dependencies(path: &Path) -> Result<Vec<String>, DependencyResolutionError> {
+    let project = read_project(path)
+        .map_err(|e| DependencyResolutionError::RetrievalFailure(e.to_string()))?;
+    Ok(project
+        .dependencies
+        .iter()
+        .map(Coordinates::get_name)
+        .collect())
+}
+
+/// Find a dependency by its `groupId:artifactId` name or, if unambiguous, its artifact id alone.
+fn find_dependency<'a>(
+    dependency_name: &str,
+    dependencies: &'a [Coordinates],
+) -> Result<&'a Coordinates, DependencyResolutionError> {
+    if let Some(dependency) = dependencies
+        .iter()
+        .find(|d| d.get_name() == dependency_name)
+    {
+        return Ok(dependency);
+    }
+    let mut matches = dependencies
+        .iter()
+        .filter(|d| d.artifact_id == dependency_name);
+    match (matches.next(), matches.next()) {
+        (Some(dependency), None) => Ok(dependency),
+        _ => Err(DependencyResolutionError::MissingDependency(
+            dependency_name.to_string(),
+        )),
+    }
+}
+
+fn get_repositories() -> Vec<Repository> {
+    let home = env::var_os("HOME").map(PathBuf::from);
+    let gradle_home = env::var_os("GRADLE_USER_HOME")
+        .map(PathBuf::from)
+        .or_else(|| home.as_ref().map(|home| home.join(".gradle")));
+
+    let mut repositories = Vec::new();
+    if let Some(home) = home {
+        repositories.push(Repository::Maven(home.join(MAVEN_REPOSITORY_PATH)));
+    }
+    if let Some(gradle_home) = gradle_home {
+        repositories.push(Repository::Gradle(gradle_home.join(GRADLE_CACHE_PATH)));
+    }
+    repositories
+}
+
+fn unpack_sources_jar(
+    jar_path: &Path,
+    destination: &Path,
+) -> Result<(), DependencyResolutionError> {
+    let to_error = |error: &dyn std::fmt::Display| {
+        DependencyResolutionError::RetrievalFailure(format!(
+            "Failed to unpack {}: {}",
+            jar_path.display(),
+            error
+        ))
+    };
+    let jar = File::open(jar_path).map_err(|e| to_error(&e))?;
+    let mut archive = ZipArchive::new(jar).map_err(|e| to_error(&e))?;
+    archive.extract(destination).map_err(|e| to_error(&e))
+}
+
+fn make_minimal_pom(group_id: &str, artifact_id: &str, version: &str) -> String {
+    format!(
+        "<project>\n  <groupId>{group_id}</groupId>\n  <artifactId>{artifact_id}</artifactId>\n  <version>{version}</version>\n</project>\n"
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::languages::java::project::read_project;
+    use assertables::assert_matches;
+    use daipendency_testing::tempdir::TempDir;
+    use std::io::Write;
+    use zip::write::SimpleFileOptions;
+    use zip::ZipWriter;
+
+    const STUB_GROUP_ID: &str = "com.google.guava";
+    const STUB_ARTIFACT_ID: &str = "guava";
+    const STUB_VERSION: &str = "33.0.0-jre";
+    const STUB_SOURCE_PATH: &str = "com/google/common/base/Strings.java";
+
+    fn create_dependant(temp_dir: &TempDir) -> PathBuf {
+        temp_dir
+            .create_file(
+                &format!("project/{POM_PATH}"),
+                &format!(
+                    "<project><artifactId>app</artifactId><dependencies><dependency><groupId>{STUB_GROUP_ID}</groupId><artifactId>{STUB_ARTIFACT_ID}</artifactId><version>{STUB_VERSION}</version></dependency></dependencies></project>"
+                ),
+            )
+            .unwrap()
+            .parent()
+            .unwrap()
+            .to_path_buf()
+    }
+
+    fn create_sources_jar(path: &Path) {
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        let mut writer = ZipWriter::new(File::create(path).unwrap());
+        writer
+            .start_file(STUB_SOURCE_PATH, SimpleFileOptions::default())
+            .unwrap();
+        writer
+            .write_all(b"package com.google.common.base;\npublic final class Strings {}\n")
+            .unwrap();
+        writer.finish().unwrap();
+    }
+
+    fn get_maven_artifact_directory(root: &Path) -> PathBuf {
+        root.join("com/google/guava")
+            .join(STUB_ARTIFACT_ID)
+            .join(STUB_VERSION)
+    }
+
+    fn resolve(
+        temp_dir: &TempDir,
+        name: &str,
+        repositories: &[Repository],
+    ) -> Result<PathBuf, DependencyResolutionError> {
+        resolve_dependency_path_in_repositories(
+            name,
+            &temp_dir.path.join("project"),
+            repositories,
+            &temp_dir.path.join("unpacked"),
+        )
+    }
+
+    #[test]
+    fn maven_repository() {
+        let temp_dir = TempDir::new();
+        create_dependant(&temp_dir);
+        let repository_root = temp_dir.path.join("m2");
+        let artifact_directory = get_maven_artifact_directory(&repository_root);
+        create_sources_jar(&artifact_directory.join(format!("guava-{STUB_VERSION}-sources.jar")));
+        fs::write(
+            artifact_directory.join(format!("guava-{STUB_VERSION}.pom")),
+            format!(
+                "<project><groupId>{STUB_GROUP_ID}</groupId><artifactId>{STUB_ARTIFACT_ID}</artifactId><version>{STUB_VERSION}</version><description>Google core libraries</description></project>"
+            ),
+        )
+        .unwrap();
+
+        let path = resolve(
+            &temp_dir,
+            "com.google.guava:guava",
+            &[Repository::Maven(repository_root)],
+        )
+        .unwrap();
+
+        assert!(path.join(STUB_SOURCE_PATH).is_file());
+        let pom = fs::read_to_string(path.join(POM_PATH)).unwrap();
+        assert!(pom.contains("Google core libraries"));
+    }
+
+    #[test]
+    fn gradle_cache() {
+        let temp_dir = TempDir::new();
+        create_dependant(&temp_dir);
+        let cache_root = temp_dir.path.join("gradle");
+        create_sources_jar(
+            &cache_root
+                .join(STUB_GROUP_ID)
+                .join(STUB_ARTIFACT_ID)
+                .join(STUB_VERSION)
+                .join("0123abcd")
+                .join(format!("guava-{STUB_VERSION}-sources.jar")),
+        );
+
+        let path = resolve(
+            &temp_dir,
+            STUB_ARTIFACT_ID,
+            &[Repository::Gradle(cache_root)],
+        )
+        .unwrap();
+
+        assert!(path.join(STUB_SOURCE_PATH).is_file());
+        let project = read_project(&path).unwrap();
+        assert_eq!(project.coordinates.get_name(), "com.google.guava:guava");
+        assert_eq!(project.coordinates.version, Some(STUB_VERSION.to_string()));
+    }
+
+    #[test]
+    fn unpacked_project_reused() {
+        let temp_dir = TempDir::new();
+        create_dependant(&temp_dir);
+        let repository_root = temp_dir.path.join("m2");
+        create_sources_jar(
+            &get_maven_artifact_directory(&repository_root)
+                .join(format!("guava-{STUB_VERSION}-sources.jar")),
+        );
+        let repositories = [Repository::Maven(repository_root)];
+        let first_path = resolve(&temp_dir, STUB_ARTIFACT_ID, &repositories).unwrap();
+        fs::remove_file(first_path.join(STUB_SOURCE_PATH)).unwrap();
+
+        let second_path = resolve(&temp_dir, STUB_ARTIFACT_ID, &repositories).unwrap();
+
+        assert_eq!(second_path, first_path);
+        assert!(!second_path.join(STUB_SOURCE_PATH).exists());
+    }
+
+    #[test]
+    fn no_staging_directories_left() {
+        let temp_dir = TempDir::new();
+        create_dependant(&temp_dir);
+        let repository_root = temp_dir.path.join("m2");
+        create_sources_jar(
+            &get_maven_artifact_directory(&repository_root)
+                .join(format!("guava-{STUB_VERSION}-sources.jar")),
+        );
+
+        let path = resolve(
+            &temp_dir,
+            STUB_ARTIFACT_ID,
+            &[Repository::Maven(repository_root)],
+        )
+        .unwrap();
+
+        let entries = fs::read_dir(path.parent().unwrap())
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name().into_string().unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(entries, vec![format!("{STUB_ARTIFACT_ID}-{STUB_VERSION}")]);
+    }
+
+    #[test]
+    fn missing_sources_jar() {
+        let temp_dir = TempDir::new();
+        create_dependant(&temp_dir);
+
+        let result = resolve(
+            &temp_dir,
+            STUB_ARTIFACT_ID,
+            &[Repository::Maven(temp_dir.path.join("m2"))],
+        );
+
+        assert_matches!(result, Err(DependencyResolutionError::RetrievalFailure(_)));
+    }
+
+    #[test]
+    fn undeclared_dependency() {
+        let temp_dir = TempDir::new();
+        create_dependant(&temp_dir);
+
+        let result = resolve(&temp_dir, "commons-lang3", &[]);
+
+        assert_matches!(
+            result,
+            Err(DependencyResolutionError::MissingDependency(name)) if name == "commons-lang3"
+        );
+    }
+
+    #[test]
+    fn listed_dependencies() {
+        let temp_dir = TempDir::new();
+        let dependant_path = create_dependant(&temp_dir);
+
+        let names = list_dependencies(&dependant_path).unwrap();
+
+        assert_eq!(names, vec!["com.google.guava:guava"]);
+    }
+
+    #[test]
+    fn missing_dependant_manifest() {
+        let temp_dir = TempDir::new();
+
+        let result = resolve(&temp_dir, STUB_ARTIFACT_ID, &[]);
+
+        assert_matches!(result, Err(DependencyResolutionError::RetrievalFailure(_)));
+    }
+
+    #[test]
+    fn invalid_sources_jar() {
+        let temp_dir = TempDir::new();
+        create_dependant(&temp_dir);
+        let repository_root = temp_dir.path.join("m2");
+        temp_dir
+            .create_file(
+                &format!(
+                    "m2/com/google/guava/guava/{STUB_VERSION}/guava-{STUB_VERSION}-sources.jar"
+                ),
+                "not a zip",
+            )
+            .unwrap();
+
+        let result = resolve(
+            &temp_dir,
+            STUB_ARTIFACT_ID,
+            &[Repository::Maven(repository_root)],
+        );
+
+        assert_matches!(result, Err(DependencyResolutionError::RetrievalFailure(_)));
+    }
+}
@@ -0,0 +1,208 @@
+use super::project::{Coordinates, Project};
+use daipendency_extractor::LibraryMetadataError;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const BUILD_SCRIPT_PATHS: [&str; 2] = ["build.gradle.kts", "build.gradle"];
+const SETTINGS_SCRIPT_PATHS: [&str; 2] = ["settings.gradle.kts", "settings.gradle"];
+const TEST_CONFIGURATION_PREFIX: &str = "test";
+const COMMENT_PREFIXES: [&str; 3] = ["//", "/*", "*"];
+
+pub fn find_build_script(directory: &Path) -> Option<PathBuf> {
+    BUILD_SCRIPT_PATHS
+        .iter()
+        .map(|path| directory.join(path))
+        .find(|path| path.is_file())
+}
+
+/// Read a Gradle project from its Groovy or Kotlin build script.
+///
+/// Build scripts are programs, so only literal values are recognised.
+pub fn read_build(directory: &Path) -> Result<Project, LibraryMetadataError> {
+    let build_script_path = find_build_script(directory).ok_or_else(|| {
+        LibraryMetadataError::MissingManifest(std::io::Error::new(
+            std::io::ErrorKind::NotFound,
+            format!("No Gradle build script in {}", directory.display()),
+        ))
+    })?;
+    let build_script =
+        fs::read_to_string(build_script_path).map_err(LibraryMetadataError::MissingManifest)?;
+    let settings_script = SETTINGS_SCRIPT_PATHS
+        .iter()
+        .find_map(|path| fs::read_to_string(directory.join(path)).ok())
+        .unwrap_or_default();
+
+    let artifact_id = get_property(&settings_script, "rootProject.name")
+        .or_else(|| {
+            directory
+                .file_name()
+                .map(|name| name.to_string_lossy().to_string())
+        })
+        .ok_or_else(|| {
+            LibraryMetadataError::MalformedManifest("Could not determine project name".to_string())
+        })?;
+
+    Ok(Project {
+        coordinates: Coordinates {
+            group_id: get_property(&build_script, "group"),
+            artifact_id,
+            version: get_property(&build_script, "version"),
+        },
+        dependencies: get_dependencies(&build_script, false),
+        test_dependencies: get_dependencies(&build_script, true),
+    })
+}
+
+/// Get the value of a property assigned a string literal, as in `version = "1.0"` or `version '1.0'`.
+fn get_property(script: &str, name: &str) -> Option<String> {
+    script.lines().find_map(|line| {
+        let value = line.trim().strip_prefix(name)?.trim_start();
+        let value = value.strip_prefix('=').unwrap_or(value).trim_start();
+        get_string_literals(value)
+            .first()
+            .filter(|_| value.starts_with(['"', '\'']))
+            .map(|literal| literal.to_string())
+    })
+}
+
+fn get_string_literals(script: &str) -> Vec<&str> {
+    let mut literals = Vec::new();
+    let mut remainder = script;
+    while let Some(start) = remainder.find(['"', '\'']) {
+        let quote = &remainder[start..start + 1];
+        let content = &remainder[start + 1..];
+        let Some(end) = content.find(quote) else {
+            break;
+        };
+        literals.push(&content[..end]);
+        remainder = &content[end + 1..];
+    }
+    literals
+}
+
+/// Get the dependencies declared in the `group:artifact:version` notation.
+///
+/// Test dependencies are those in configurations such as `testImplementation`.
+fn get_dependencies(build_script: &str, is_test: bool) -> Vec<Coordinates> {
+    build_script
+        .lines()
+        .map(str::trim)
+        .filter(|line| {
+            !COMMENT_PREFIXES
+                .iter()
+                .any(|prefix| line.starts_with(prefix))
+        })
+        .filter(|line| line.starts_with(TEST_CONFIGURATION_PREFIX) == is_test)
+        .flat_map(get_string_literals)
+        .filter_map(
+            |literal| match literal.split(':').collect::<Vec<_>>().as_slice() {
+                [group_id, artifact_id, version] | [group_id, artifact_id, version, _]
+                    if !literal.contains(char::is_whitespace) =>
+                {
+                    Some(Coordinates {
+                        group_id: Some(group_id.to_string()),
+                        artifact_id: artifact_id.to_string(),
+                        version: Some(version.to_string()),
+                    })
+                }
+                _ => None,
+            },
+        )
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use daipendency_testing::tempdir::TempDir;
+
+    const STUB_NAME: &str = "gradle-lib";
+
+    fn read(build_script_path: &str, build_script: &str) -> Project {
+        let temp_dir = TempDir::new();
+        temp_dir
+            .create_file(&format!("{STUB_NAME}/{build_script_path}"), build_script)
+            .unwrap();
+        read_build(&temp_dir.path.join(STUB_NAME)).unwrap()
+    }
+
+    #[test]
+    fn groovy_coordinates() {
+        let project = read("build.gradle", "group 'com.example'\nversion = '1.0'\n");
+
+        assert_eq!(
+            project.coordinates,
+            Coordinates {
+                group_id: Some("com.example".to_string()),
+                artifact_id: STUB_NAME.to_string(),
+                version: Some("1.0".to_string()),
+            }
+        );
+    }
+
+    #[test]
+    fn kotlin_coordinates() {
+        let project = read(
+            "build.gradle.kts",
+            "group = \"com.example\"\nversion = \"1.0\"\n",
+        );
+
+        assert_eq!(
+            project.coordinates.group_id,
+            Some("com.example".to_string())
+        );
+        assert_eq!(project.coordinates.version, Some("1.0".to_string()));
+    }
+
+    #[test]
+    fn non_literal_version() {
+        let project = read("build.gradle.kts", "version = computeVersion()\n");
+
+        assert_eq!(project.coordinates.version, None);
+    }
+
+    #[test]
+    fn root_project_name() {
+        let temp_dir = TempDir::new();
+        temp_dir.create_file("build.gradle", "").unwrap();
+        temp_dir
+            .create_file("settings.gradle", "rootProject.name = 'custom-name'\n")
+            .unwrap();
+
+        let project = read_build(&temp_dir.path).unwrap();
+
+        assert_eq!(project.coordinates.artifact_id, "custom-name");
+    }
+
+    #[test]
+    fn dependencies() {
+        let project = read(
+            "build.gradle.kts",
+            r#"plugins { id("java-library") }
+dependencies {
+    // Don't forget: "com.example:commented:1.0"
+    implementation("com.google.guava:guava:33.0.0-jre")
+    testImplementation 'org.junit.jupiter:junit-jupiter:5.10.0'
+    runtimeOnly("org.lwjgl:lwjgl:3.3.3:natives-linux")
+}
+"#,
+        );
+
+        assert_eq!(
+            project
+                .dependencies
+                .iter()
+                .map(Coordinates::get_name)
+                .collect::<Vec<_>>(),
+            vec!["com.google.guava:guava", "org.lwjgl:lwjgl"]
+        );
+        assert_eq!(
+            project.test_dependencies[0].get_name(),
+            "org.junit.jupiter:junit-jupiter"
+        );
+        assert_eq!(
+            project.dependencies[0].version,
+            Some("33.0.0-jre".to_string())
+        );
+    }
+}
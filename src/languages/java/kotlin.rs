@@ -0,0 +1,979 @@
+//! Extraction of the public API of Kotlin source files.
+//!
+//! There's no tree-sitter grammar for Kotlin among our dependencies, so declarations are found by
+//! matching brackets in the source code after masking its comments and string literals.
+
+use super::parsing::{reindent_comment, CompilationUnit};
+use daipendency_extractor::Symbol;
+use std::ops::Range;
+
+const MEMBER_INDENT: &str = "    ";
+const MODIFIERS: [&str; 26] = [
+    "public",
+    "protected",
+    "private",
+    "internal",
+    "open",
+    "final",
+    "abstract",
+    "sealed",
+    "data",
+    "enum",
+    "annotation",
+    "value",
+    "inner",
+    "companion",
+    "override",
+    "suspend",
+    "inline",
+    "operator",
+    "infix",
+    "tailrec",
+    "external",
+    "const",
+    "lateinit",
+    "expect",
+    "actual",
+    "fun",
+];
+/// Modifiers that Java declarations don't have.
+const KOTLIN_ONLY_MODIFIERS: [&str; 19] = [
+    "internal",
+    "open",
+    "data",
+    "value",
+    "inner",
+    "companion",
+    "override",
+    "suspend",
+    "inline",
+    "operator",
+    "infix",
+    "tailrec",
+    "external",
+    "const",
+    "lateinit",
+    "expect",
+    "actual",
+    "annotation",
+    "fun",
+];
+/// Keywords of declarations that Java doesn't have.
+const KOTLIN_ONLY_KEYWORDS: [&str; 5] = ["fun", "val", "var", "object", "typealias"];
+const TYPE_KEYWORDS: [&str; 2] = ["class", "interface"];
+const KOTLIN_CODE_BLOCK_LANGUAGE: &str = "kotlin";
+const HIDDEN_VISIBILITIES: [&str; 2] = ["private", "internal"];
+/// Line endings after which a declaration continues on the next line.
+const CONTINUATION_SUFFIXES: [&str; 8] = ["=", ",", "(", ".", ":", "->", "&&", "||"];
+/// Line beginnings that continue the declaration on the previous line.
+const CONTINUATION_PREFIXES: [&str; 17] = [
+    ".",
+    "?.",
+    "?:",
+    ":",
+    "=",
+    "{",
+    "->",
+    "where ",
+    ",",
+    ")",
+    "&&",
+    "||",
+    "by ",
+    "get(",
+    "set(",
+    "private set",
+    "protected set",
+];
+const COMPANION_OBJECT_NAME: &str = "Companion";
+
+/// Extract the package name and the public declarations in a Kotlin source file.
+pub fn parse_kotlin_file(source_code: &str) -> CompilationUnit {
+    let masked = mask(source_code);
+    let mut package_name = None;
+    let mut symbols = Vec::new();
+    for range in split_declarations(&masked, 0..masked.len()) {
+        let Some(header) = parse_header(&masked[range.clone()]) else {
+            continue;
+        };
+        if header.keyword == "package" {
+            let name = &source_code[range.start + header.keyword_end..range.end];
+            package_name = Some(name.trim().trim_end_matches(';').trim().to_string());
+        } else if let Some((name, source_code)) =
+            render_declaration(source_code, &masked, range, "", false)
+        {
+            symbols.push(Symbol { name, source_code });
+        }
+    }
+    CompilationUnit {
+        package_name,
+        doc_comment: None,
+        symbols,
+    }
+}
+
+/// Highlight code blocks as Kotlin if most of their declarations are written in Kotlin.
+pub fn detect_code_block_language(source_codes: &[&str]) -> Option<&'static str> {
+    let kotlin_count = source_codes
+        .iter()
+        .filter(|source_code| is_kotlin_declaration(source_code))
+        .count();
+    (kotlin_count * 2 > source_codes.len()).then_some(KOTLIN_CODE_BLOCK_LANGUAGE)
+}
+
+/// Whether a rendered declaration is written in Kotlin rather than Java.
+///
+/// Extracted Java types are always declared `public`, which is optional in Kotlin, so only Kotlin
+/// declarations in explicit API mode can be mistaken for Java ones.
+fn is_kotlin_declaration(source_code: &str) -> bool {
+    let masked = mask(source_code);
+    let Some(header) = parse_header(&masked) else {
+        return false;
+    };
+    let type_header = masked[header.keyword_end..]
+        .split('{')
+        .next()
+        .unwrap_or_default();
+    !header.modifiers.contains(&"public")
+        || KOTLIN_ONLY_KEYWORDS.contains(&header.keyword)
+        || header
+            .modifiers
+            .iter()
+            .any(|modifier| KOTLIN_ONLY_MODIFIERS.contains(modifier))
+        // Primary constructors and supertypes follow the name of Kotlin types.
+        || (TYPE_KEYWORDS.contains(&header.keyword) && type_header.contains(['(', ':']))
+}
+
+/// Replace comments and the contents of string and character literals with spaces, keeping byte
+/// offsets, so that brackets can be matched.
+///
+/// Line breaks in comments are kept, whilst those in multi-line strings are blanked so that
+/// declarations aren't split inside them.
+fn mask(source_code: &str) -> String {
+    let bytes = source_code.as_bytes();
+    let mut masked = bytes.to_vec();
+    let mut blank = |range: Range<usize>, keeps_line_breaks: bool| {
+        for byte in &mut masked[range] {
+            if *byte != b'\n' || !keeps_line_breaks {
+                *byte = b' ';
+            }
+        }
+    };
+    let find = |pattern: &[u8], from: usize| {
+        bytes[from.min(bytes.len())..]
+            .windows(pattern.len())
+            .position(|window| window == pattern)
+            .map(|position| from + position)
+    };
+
+    let mut index = 0;
+    while index < bytes.len() {
+        let rest = &bytes[index..];
+        if rest.starts_with(b"//") {
+            let end = find(b"\n", index).unwrap_or(bytes.len());
+            blank(index..end, true);
+            index = end;
+        } else if rest.starts_with(b"/*") {
+            let mut depth = 0;
+            let mut end = index;
+            while end < bytes.len() {
+                if bytes[end..].starts_with(b"/*") {
+                    depth += 1;
+                    end += 2;
+                } else if bytes[end..].starts_with(b"*/") {
+                    depth -= 1;
+                    end += 2;
+                    if depth == 0 {
+                        break;
+                    }
+                } else {
+                    end += 1;
+                }
+            }
+            let end = end.min(bytes.len());
+            blank(index..end, true);
+            index = end;
+        } else if rest.starts_with(b"\"\"\"") {
+            let end = find(b"\"\"\"", index + 3).unwrap_or(bytes.len());
+            blank(index + 3..end, false);
+            index = (end + 3).min(bytes.len());
+        } else if rest[0] == b'"' || rest[0] == b'\'' {
+            let mut end = index + 1;
+            while end < bytes.len() && bytes[end] != rest[0] && bytes[end] != b'\n' {
+                end += if bytes[end] == b'\\' { 2 } else { 1 };
+            }
+            let end = end.min(bytes.len());
+            blank(index + 1..end, false);
+            index = end + 1;
+        } else {
+            index += 1;
+        }
+    }
+    String::from_utf8(masked).expect("Masking whole literals and comments keeps UTF-8 valid")
+}
+
+/// Split the declarations in `range` of the masked source code, which end at a semicolon or at a
+/// line break outside brackets, unless the next line continues them.
+fn split_declarations(masked: &str, range: Range<usize>) -> Vec<Range<usize>> {
+    let bytes = masked.as_bytes();
+    let mut declarations = Vec::new();
+    let mut depth = 0usize;
+    let mut start = None;
+    for index in range.clone() {
+        let byte = bytes[index];
+        match byte {
+            b'(' | b'[' | b'{' => depth += 1,
+            b')' | b']' | b'}' => depth = depth.saturating_sub(1),
+            _ => {}
+        }
+        if start.is_none() && !byte.is_ascii_whitespace() && byte != b';' {
+            start = Some(index);
+        }
+        let Some(declaration_start) = start else {
+            continue;
+        };
+        let is_end = depth == 0
+            && (byte == b';'
+                || (byte == b'\n' && !is_continued(masked, declaration_start, index, range.end)));
+        if is_end {
+            declarations.push(declaration_start..index);
+            start = None;
+        }
+    }
+    if let Some(declaration_start) = start {
+        declarations.push(declaration_start..range.end);
+    }
+    declarations
+        .into_iter()
+        .map(|declaration| {
+            let length = masked[declaration.clone()].trim_end().len();
+            declaration.start..declaration.start + length
+        })
+        .filter(|declaration| !declaration.is_empty())
+        .collect()
+}
+
+fn is_continued(masked: &str, start: usize, line_break: usize, end: usize) -> bool {
+    let current = masked[start..line_break].trim_end();
+    let next = masked[line_break + 1..end].trim_start();
+    !next.is_empty()
+        && (CONTINUATION_SUFFIXES
+            .iter()
+            .any(|suffix| current.ends_with(suffix))
+            || CONTINUATION_PREFIXES
+                .iter()
+                .any(|prefix| next.starts_with(prefix))
+            // Annotations and modifiers on their own line precede the declaration.
+            || is_preamble(current))
+}
+
+/// The modifiers and keyword at the start of a declaration.
+#[derive(Debug, PartialEq)]
+struct Header<'a> {
+    modifiers: Vec<&'a str>,
+    keyword: &'a str,
+    /// The offset of the end of the keyword in the declaration.
+    keyword_end: usize,
+}
+
+/// Parse the modifiers and keyword of a masked declaration, skipping its annotations.
+fn parse_header(declaration: &str) -> Option<Header<'_>> {
+    let (modifiers, keyword_start) = skip_preamble(declaration)?;
+    let keyword_end = keyword_start
+        + declaration[keyword_start..]
+            .bytes()
+            .take_while(|byte| is_identifier_byte(*byte))
+            .count();
+    let keyword = &declaration[keyword_start..keyword_end];
+    (!keyword.is_empty()).then_some(Header {
+        modifiers,
+        keyword,
+        keyword_end,
+    })
+}
+
+/// Whether a masked fragment only has annotations and modifiers, which precede a declaration on
+/// a later line.
+fn is_preamble(fragment: &str) -> bool {
+    skip_preamble(fragment).is_some_and(|(_, keyword_start)| keyword_start == fragment.len())
+}
+
+/// Skip the annotations and modifiers at the start of a masked declaration, returning the
+/// modifiers and the offset of what follows them.
+fn skip_preamble(declaration: &str) -> Option<(Vec<&str>, usize)> {
+    let bytes = declaration.as_bytes();
+    let mut modifiers = Vec::new();
+    let mut index = 0;
+    loop {
+        while index < bytes.len() && bytes[index].is_ascii_whitespace() {
+            index += 1;
+        }
+        if bytes.get(index) == Some(&b'@') {
+            index += 1;
+            while index < bytes.len()
+                && (is_identifier_byte(bytes[index]) || matches!(bytes[index], b'.' | b':'))
+            {
+                index += 1;
+            }
+            if matches!(bytes.get(index), Some(b'(' | b'[')) {
+                index = find_closing_bracket(declaration, index)? + 1;
+            }
+            continue;
+        }
+        let start = index;
+        while index < bytes.len() && is_identifier_byte(bytes[index]) {
+            index += 1;
+        }
+        let word = &declaration[start..index];
+        let is_fun_interface =
+            word == "fun" && declaration[index..].trim_start().starts_with("interface");
+        if !MODIFIERS.contains(&word) || (word == "fun" && !is_fun_interface) {
+            return Some((modifiers, start));
+        }
+        modifiers.push(word);
+    }
+}
+
+fn is_identifier_byte(byte: u8) -> bool {
+    byte.is_ascii_alphanumeric() || byte == b'_' || !byte.is_ascii()
+}
+
+/// Find the bracket that closes the one at `open`.
+fn find_closing_bracket(masked: &str, open: usize) -> Option<usize> {
+    let mut depth = 0usize;
+    for (index, byte) in masked.bytes().enumerate().skip(open) {
+        match byte {
+            b'(' | b'[' | b'{' => depth += 1,
+            b')' | b']' | b'}' => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(index);
+                }
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Find the first of `targets` outside brackets in `masked`, starting at `from`.
+fn find_outside_brackets(masked: &str, from: usize, targets: &[u8]) -> Option<usize> {
+    let mut depth = 0usize;
+    for (index, byte) in masked.bytes().enumerate().skip(from) {
+        if depth == 0 && targets.contains(&byte) {
+            return Some(index);
+        }
+        match byte {
+            b'(' | b'[' | b'{' => depth += 1,
+            b')' | b']' | b'}' => depth = depth.saturating_sub(1),
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Get the name after the keyword of a declaration, skipping type parameters and receivers
+/// (e.g. `foo` in `fun <T> List<T>.foo()`).
+fn get_name(after_keyword: &str) -> Option<String> {
+    let mut rest = skip_type_arguments(after_keyword.trim_start())?;
+    loop {
+        let length = rest
+            .find(|character: char| {
+                !(character.is_alphanumeric() || character == '_' || character == '`')
+            })
+            .unwrap_or(rest.len());
+        if length == 0 {
+            return None;
+        }
+        let name = rest[..length].trim_matches('`');
+        rest = skip_type_arguments(rest[length..].trim_start_matches('?'))?;
+        match rest.trim_start_matches('?').strip_prefix('.') {
+            Some(remainder) => rest = remainder,
+            None => return Some(name.to_string()),
+        }
+    }
+}
+
+/// Skip the type arguments or parameters at the start of `text`, if any.
+fn skip_type_arguments(text: &str) -> Option<&str> {
+    if !text.starts_with('<') {
+        return Some(text);
+    }
+    let mut depth = 0;
+    let end = text.find(|character| {
+        match character {
+            '<' => depth += 1,
+            '>' => depth -= 1,
+            _ => {}
+        }
+        depth == 0
+    })?;
+    Some(text[end + 1..].trim_start())
+}
+
+/// Render the public parts of the declaration at `range`, returning its name and source code.
+fn render_declaration(
+    source_code: &str,
+    masked: &str,
+    range: Range<usize>,
+    indent: &str,
+    is_member: bool,
+) -> Option<(String, String)> {
+    let declaration = &masked[range.clone()];
+    let header = parse_header(declaration)?;
+    if header
+        .modifiers
+        .iter()
+        .any(|modifier| HIDDEN_VISIBILITIES.contains(modifier))
+        || (!is_member && header.modifiers.contains(&"protected"))
+    {
+        return None;
+    }
+    let source_indent = get_line_indent(source_code, range.start);
+    let after_keyword = &declaration[header.keyword_end..];
+    let (name, end) = match header.keyword {
+        "fun" | "constructor" => {
+            let name = match header.keyword {
+                "fun" => get_name(after_keyword)?,
+                _ => header.keyword.to_string(),
+            };
+            let end = find_outside_brackets(declaration, header.keyword_end, b"{=");
+            (name, end.unwrap_or(declaration.len()))
+        }
+        "val" | "var" => {
+            let targets: &[u8] = if header.modifiers.contains(&"const") {
+                b"\n"
+            } else {
+                b"=\n"
+            };
+            let end = find_outside_brackets(declaration, header.keyword_end, targets)
+                .unwrap_or(declaration.len());
+            let end = declaration[..end]
+                .find(" by ")
+                .filter(|&by| by > header.keyword_end)
+                .unwrap_or(end);
+            (get_name(after_keyword)?, end)
+        }
+        "typealias" => (get_name(after_keyword)?, declaration.len()),
+        "class" | "interface" | "object" => {
+            let name = get_name(after_keyword)
+                .filter(|_| !after_keyword.trim_start().starts_with(['{', ':']))
+                .or_else(|| {
+                    (header.keyword == "object" && header.modifiers.contains(&"companion"))
+                        .then(|| COMPANION_OBJECT_NAME.to_string())
+                })?;
+            let rendered = render_type(source_code, masked, range.clone(), &header, indent);
+            return Some((
+                name,
+                with_doc_comment(source_code, range.start, indent, rendered),
+            ));
+        }
+        _ => return None,
+    };
+    let rendered = reindent(
+        source_code[range.start..range.start + end].trim_end(),
+        source_indent,
+        indent,
+    );
+    Some((
+        name,
+        with_doc_comment(source_code, range.start, indent, rendered),
+    ))
+}
+
+/// Render a class, interface or object with its public members, omitting function bodies.
+fn render_type(
+    source_code: &str,
+    masked: &str,
+    range: Range<usize>,
+    header: &Header,
+    indent: &str,
+) -> String {
+    let declaration = &masked[range.clone()];
+    let source_indent = get_line_indent(source_code, range.start);
+    let Some(body_start) = find_outside_brackets(declaration, header.keyword_end, b"{") else {
+        return reindent(&source_code[range], source_indent, indent);
+    };
+    let body_end = find_closing_bracket(declaration, body_start).unwrap_or(declaration.len());
+    let head = reindent(
+        source_code[range.start..range.start + body_start].trim_end(),
+        source_indent,
+        indent,
+    );
+    let body = range.start + body_start + 1..range.start + body_end;
+    let member_indent = format!("{indent}{MEMBER_INDENT}");
+
+    let mut members = Vec::new();
+    let mut members_start = body.start;
+    if header.modifiers.contains(&"enum") {
+        let entries_end = find_outside_brackets(masked, body.start, b";")
+            .filter(|&end| end < body.end)
+            .unwrap_or(body.end);
+        let entries =
+            render_enum_entries(source_code, masked, body.start..entries_end, &member_indent);
+        if !entries.is_empty() {
+            members.push(format!("{};", entries.join(",\n")));
+        }
+        members_start = (entries_end + 1).min(body.end);
+    }
+    members.extend(
+        split_declarations(masked, members_start..body.end)
+            .into_iter()
+            .filter_map(|member| {
+                render_declaration(source_code, masked, member, &member_indent, true)
+            })
+            .map(|(_, rendered)| rendered),
+    );
+
+    if members.is_empty() {
+        format!("{head} {{}}")
+    } else {
+        format!("{head} {{\n{}\n{indent}}}", members.join("\n"))
+    }
+}
+
+fn render_enum_entries(
+    source_code: &str,
+    masked: &str,
+    range: Range<usize>,
+    indent: &str,
+) -> Vec<String> {
+    let mut entries = Vec::new();
+    let mut start = range.start;
+    while start < range.end {
+        let end = find_outside_brackets(masked, start, b",")
+            .filter(|&end| end < range.end)
+            .unwrap_or(range.end);
+        let entry = &masked[start..end];
+        let offset = entry.len() - entry.trim_start().len();
+        let entry_end = find_outside_brackets(masked, start + offset, b"{")
+            .filter(|&entry_end| entry_end < end)
+            .unwrap_or(end);
+        let text = source_code[start + offset..entry_end].trim();
+        if !text.is_empty() {
+            entries.push(with_doc_comment(
+                source_code,
+                start + offset,
+                indent,
+                format!("{indent}{text}"),
+            ));
+        }
+        start = end + 1;
+    }
+    entries
+}
+
+fn get_line_indent(source_code: &str, offset: usize) -> &str {
+    let line_start = source_code[..offset]
+        .rfind('\n')
+        .map_or(0, |index| index + 1);
+    &source_code[line_start..offset]
+}
+
+/// Indent `text` with `indent`, replacing the `source_indent` of its continuation lines.
+fn reindent(text: &str, source_indent: &str, indent: &str) -> String {
+    text.lines()
+        .enumerate()
+        .map(|(index, line)| match index {
+            0 => format!("{indent}{line}"),
+            _ => format!(
+                "{indent}{}",
+                line.strip_prefix(source_indent).unwrap_or(line)
+            ),
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Prepend the KDoc comment immediately preceding the declaration at `start`, if any.
+fn with_doc_comment(source_code: &str, start: usize, indent: &str, rendered: String) -> String {
+    let preceding = source_code[..start].trim_end();
+    let doc_comment = preceding
+        .strip_suffix("*/")
+        .and_then(|_| preceding.rfind("/**"))
+        .map(|comment_start| &preceding[comment_start..])
+        .filter(|comment| !comment[3..comment.len() - 2].contains("*/"));
+    match doc_comment {
+        Some(doc_comment) => {
+            let doc_comment = reindent_comment(doc_comment).replace('\n', &format!("\n{indent}"));
+            format!("{indent}{doc_comment}\n{rendered}")
+        }
+        None => rendered,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn get_symbol<'a>(unit: &'a CompilationUnit, name: &str) -> &'a Symbol {
+        unit.symbols
+            .iter()
+            .find(|symbol| symbol.name == name)
+            .unwrap_or_else(|| panic!("Symbol {name} not found"))
+    }
+
+    fn get_symbol_names(unit: &CompilationUnit) -> Vec<&str> {
+        unit.symbols
+            .iter()
+            .map(|symbol| symbol.name.as_str())
+            .collect()
+    }
+
+    mod code_block_language {
+        use super::*;
+
+        #[test]
+        fn kotlin_declarations() {
+            for source_code in [
+                "/** Greets. */\nfun greet(): String",
+                "class Repo {\n    fun find()\n}",
+                "enum class Colour { RED }",
+                "public data class Point(val x: Int)",
+                "public class Client(url: String)",
+                "public interface Shape : Comparable<Shape>",
+                "public const val MAX = 10",
+            ] {
+                assert_eq!(
+                    detect_code_block_language(&[source_code]),
+                    Some(KOTLIN_CODE_BLOCK_LANGUAGE),
+                    "{source_code}"
+                );
+            }
+        }
+
+        #[test]
+        fn java_declarations() {
+            for source_code in [
+                "/** A client. */\n@Deprecated\npublic final class Client {\n    public void run();\n}",
+                "public enum Level {\n    LOW;\n}",
+                "public @interface Marker {}",
+                "public record Point(int x, int y) {}",
+                "public interface Shape<T extends Comparable<T>> {}",
+            ] {
+                assert_eq!(
+                    detect_code_block_language(&[source_code]),
+                    None,
+                    "{source_code}"
+                );
+            }
+        }
+
+        #[test]
+        fn mixed_declarations() {
+            let java = "public class A {}";
+            let kotlin = "class B";
+
+            assert_eq!(detect_code_block_language(&[java, kotlin]), None);
+            assert_eq!(
+                detect_code_block_language(&[java, kotlin, kotlin]),
+                Some(KOTLIN_CODE_BLOCK_LANGUAGE)
+            );
+        }
+    }
+
+    mod package {
+        use super::*;
+
+        #[test]
+        fn package_name() {
+            let unit = parse_kotlin_file("@file:JvmName(\"Util\")\npackage com.example.util\n");
+
+            assert_eq!(unit.package_name, Some("com.example.util".to_string()));
+        }
+
+        #[test]
+        fn default_package() {
+            let unit = parse_kotlin_file("fun main() {}\n");
+
+            assert_eq!(unit.package_name, None);
+        }
+    }
+
+    mod visibility {
+        use super::*;
+
+        #[test]
+        fn hidden_declarations() {
+            let unit = parse_kotlin_file(
+                "private fun a() {}\ninternal class B\nfun c() {}\npublic val d = 1\n",
+            );
+
+            assert_eq!(get_symbol_names(&unit), vec!["c", "d"]);
+        }
+
+        #[test]
+        fn imports() {
+            let unit = parse_kotlin_file("package a\n\nimport b.C\nimport d.*\n\nfun e() {}\n");
+
+            assert_eq!(get_symbol_names(&unit), vec!["e"]);
+        }
+    }
+
+    mod functions {
+        use super::*;
+
+        #[test]
+        fn block_body() {
+            let unit = parse_kotlin_file(
+                "/**\n   * Greet.\n   */\nfun greet(name: String = \"}\"): String {\n    return \"Hi $name\"\n}\n",
+            );
+
+            assert_eq!(
+                get_symbol(&unit, "greet").source_code,
+                "/**\n * Greet.\n */\nfun greet(name: String = \"}\"): String"
+            );
+        }
+
+        #[test]
+        fn expression_body() {
+            let unit = parse_kotlin_file("fun twice(x: Int): Int =\n    x * 2\n");
+
+            assert_eq!(
+                get_symbol(&unit, "twice").source_code,
+                "fun twice(x: Int): Int"
+            );
+        }
+
+        #[test]
+        fn extension_function() {
+            let unit = parse_kotlin_file("inline fun <T> List<T>.second(): T = this[1]\n");
+
+            assert_eq!(
+                get_symbol(&unit, "second").source_code,
+                "inline fun <T> List<T>.second(): T"
+            );
+        }
+
+        #[test]
+        fn annotations() {
+            let unit = parse_kotlin_file(
+                "@Deprecated(\"Use b\")\n@JvmStatic\nfun a() {}\n// Comment\nfun b() {}\n",
+            );
+
+            assert_eq!(get_symbol_names(&unit), vec!["a", "b"]);
+            assert_eq!(
+                get_symbol(&unit, "a").source_code,
+                "@Deprecated(\"Use b\")\n@JvmStatic\nfun a()"
+            );
+        }
+
+        #[test]
+        fn multi_line_signature() {
+            let unit = parse_kotlin_file(
+                "fun connect(\n    host: String,\n    port: Int,\n): Socket {\n}\n",
+            );
+
+            assert_eq!(
+                get_symbol(&unit, "connect").source_code,
+                "fun connect(\n    host: String,\n    port: Int,\n): Socket"
+            );
+        }
+    }
+
+    mod properties {
+        use super::*;
+
+        #[test]
+        fn multi_line_string() {
+            let unit = parse_kotlin_file(
+                "const val QUERY = \"\"\"\n    SELECT *\n    FROM t\n\"\"\"\n\nclass Repo {\n    fun find() {}\n}\n\nfun main() {}\n",
+            );
+
+            assert_eq!(get_symbol_names(&unit), vec!["QUERY", "Repo", "main"]);
+            assert_eq!(
+                get_symbol(&unit, "QUERY").source_code,
+                "const val QUERY = \"\"\"\n    SELECT *\n    FROM t\n\"\"\""
+            );
+        }
+
+        #[test]
+        fn initialiser() {
+            let unit = parse_kotlin_file("val timeout: Duration = Duration.ofSeconds(\n    5\n)\n");
+
+            assert_eq!(
+                get_symbol(&unit, "timeout").source_code,
+                "val timeout: Duration"
+            );
+        }
+
+        #[test]
+        fn constant() {
+            let unit = parse_kotlin_file("const val MAX = 10\n");
+
+            assert_eq!(get_symbol(&unit, "MAX").source_code, "const val MAX = 10");
+        }
+
+        #[test]
+        fn delegate_and_accessor() {
+            let unit = parse_kotlin_file(
+                "val lazyValue: String by lazy { \"x\" }\nvar counter: Int\n    get() = 1\n    private set\nfun after() {}\n",
+            );
+
+            assert_eq!(
+                get_symbol_names(&unit),
+                vec!["lazyValue", "counter", "after"]
+            );
+            assert_eq!(
+                get_symbol(&unit, "lazyValue").source_code,
+                "val lazyValue: String"
+            );
+            assert_eq!(get_symbol(&unit, "counter").source_code, "var counter: Int");
+        }
+    }
+
+    mod types {
+        use super::*;
+
+        #[test]
+        fn class_members() {
+            let unit = parse_kotlin_file(
+                r#"/** A client. */
+open class Client(val url: String) : Base(), AutoCloseable {
+    private val secret = "}"
+    internal fun helper() {}
+
+    /** Connect. */
+    fun connect(): Boolean {
+        return true
+    }
+
+    protected open fun hook() = Unit
+
+    override fun close() {}
+
+    init {
+        println("created")
+    }
+}
+"#,
+            );
+
+            assert_eq!(
+                get_symbol(&unit, "Client").source_code,
+                r#"/** A client. */
+open class Client(val url: String) : Base(), AutoCloseable {
+    /** Connect. */
+    fun connect(): Boolean
+    protected open fun hook()
+    override fun close()
+}"#
+            );
+        }
+
+        #[test]
+        fn class_without_body() {
+            let unit = parse_kotlin_file("data class Point(val x: Int, val y: Int)\n");
+
+            assert_eq!(
+                get_symbol(&unit, "Point").source_code,
+                "data class Point(val x: Int, val y: Int)"
+            );
+        }
+
+        #[test]
+        fn class_without_public_members() {
+            let unit = parse_kotlin_file("class Util {\n    private fun helper() {}\n}\n");
+
+            assert_eq!(get_symbol(&unit, "Util").source_code, "class Util {}");
+        }
+
+        #[test]
+        fn interface() {
+            let unit = parse_kotlin_file(
+                "interface Shape {\n    val area: Double\n    fun describe(): String = \"shape\"\n}\n",
+            );
+
+            assert_eq!(
+                get_symbol(&unit, "Shape").source_code,
+                "interface Shape {\n    val area: Double\n    fun describe(): String\n}"
+            );
+        }
+
+        #[test]
+        fn fun_interface() {
+            let unit = parse_kotlin_file("fun interface Action {\n    fun run()\n}\n");
+
+            assert_eq!(
+                get_symbol(&unit, "Action").source_code,
+                "fun interface Action {\n    fun run()\n}"
+            );
+        }
+
+        #[test]
+        fn enum_class() {
+            let unit = parse_kotlin_file(
+                "enum class Level(val weight: Int) {\n    /** Low. */\n    LOW(1),\n    HIGH(2) {\n        override fun x() {}\n    };\n\n    fun isHigh() = this == HIGH\n}\n",
+            );
+
+            assert_eq!(
+                get_symbol(&unit, "Level").source_code,
+                "enum class Level(val weight: Int) {\n    /** Low. */\n    LOW(1),\n    HIGH(2);\n    fun isHigh()\n}"
+            );
+        }
+
+        #[test]
+        fn enum_class_without_members() {
+            let unit = parse_kotlin_file("enum class Colour { RED, GREEN, }\n");
+
+            assert_eq!(
+                get_symbol(&unit, "Colour").source_code,
+                "enum class Colour {\n    RED,\n    GREEN;\n}"
+            );
+        }
+
+        #[test]
+        fn objects() {
+            let unit = parse_kotlin_file(
+                "object Registry {\n    fun register() {}\n}\nclass Factory {\n    companion object {\n        fun create(): Factory = Factory()\n    }\n}\n",
+            );
+
+            assert_eq!(
+                get_symbol(&unit, "Registry").source_code,
+                "object Registry {\n    fun register()\n}"
+            );
+            assert_eq!(
+                get_symbol(&unit, "Factory").source_code,
+                "class Factory {\n    companion object {\n        fun create(): Factory\n    }\n}"
+            );
+        }
+
+        #[test]
+        fn nested_types_and_constructors() {
+            let unit = parse_kotlin_file(
+                "class Outer {\n    constructor(x: Int) : this() {}\n    class Inner {\n        fun run() {}\n    }\n    private class Hidden\n}\n",
+            );
+
+            assert_eq!(
+                get_symbol(&unit, "Outer").source_code,
+                "class Outer {\n    constructor(x: Int) : this()\n    class Inner {\n        fun run()\n    }\n}"
+            );
+        }
+
+        #[test]
+        fn type_alias() {
+            let unit = parse_kotlin_file("typealias Handler = (String) -> Unit\n");
+
+            assert_eq!(
+                get_symbol(&unit, "Handler").source_code,
+                "typealias Handler = (String) -> Unit"
+            );
+        }
+    }
+
+    mod masking {
+        use super::*;
+
+        #[test]
+        fn comments_and_strings() {
+            let source_code = "a /* { /* } */ */ \"{\" '}' \"\"\"\n{\"\"\" // }\nb";
+
+            let masked = mask(source_code);
+
+            assert_eq!(masked.len(), source_code.len());
+            assert!(!masked.contains(['{', '}']));
+            assert!(masked.starts_with("a "));
+            assert!(masked.ends_with("\nb"));
+        }
+    }
+}
@@ -0,0 +1,243 @@
+use super::project::{Coordinates, Project};
+use daipendency_extractor::LibraryMetadataError;
+use roxmltree::{Document, Node};
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+pub const POM_PATH: &str = "pom.xml";
+const TEST_SCOPE: &str = "test";
+
+pub fn read_pom(directory: &Path) -> Result<Project, LibraryMetadataError> {
+    let content = fs::read_to_string(directory.join(POM_PATH))
+        .map_err(LibraryMetadataError::MissingManifest)?;
+    parse_pom(&content)
+}
+
+fn parse_pom(content: &str) -> Result<Project, LibraryMetadataError> {
+    let document = Document::parse(content)
+        .map_err(|e| LibraryMetadataError::MalformedManifest(e.to_string()))?;
+    let project = document.root_element();
+    let parent = get_child(project, "parent");
+
+    let artifact_id = get_child_text(project, "artifactId")
+        .ok_or_else(|| LibraryMetadataError::MalformedManifest("Missing artifactId".to_string()))?;
+    let group_id = get_child_text(project, "groupId")
+        .or_else(|| parent.and_then(|parent| get_child_text(parent, "groupId")));
+    let version = get_child_text(project, "version")
+        .or_else(|| parent.and_then(|parent| get_child_text(parent, "version")));
+
+    let mut properties = get_child(project, "properties")
+        .map(|properties| {
+            properties
+                .children()
+                .filter(Node::is_element)
+                .filter_map(|property| {
+                    Some((
+                        property.tag_name().name().to_string(),
+                        property.text()?.trim().to_string(),
+                    ))
+                })
+                .collect::<HashMap<_, _>>()
+        })
+        .unwrap_or_default();
+    if let Some(group_id) = &group_id {
+        properties.insert("project.groupId".to_string(), group_id.clone());
+    }
+    if let Some(version) = &version {
+        properties.insert("project.version".to_string(), version.clone());
+    }
+
+    let managed_dependencies = get_child(project, "dependencyManagement")
+        .map(|management| get_dependencies(management, &properties))
+        .unwrap_or_default();
+    let mut dependencies = Vec::new();
+    let mut test_dependencies = Vec::new();
+    for (mut dependency, scope) in get_dependencies(project, &properties) {
+        if dependency.version.is_none() {
+            dependency.version = managed_dependencies
+                .iter()
+                .find(|(managed, _)| {
+                    managed.group_id == dependency.group_id
+                        && managed.artifact_id == dependency.artifact_id
+                })
+                .and_then(|(managed, _)| managed.version.clone());
+        }
+        if scope.as_deref() == Some(TEST_SCOPE) {
+            test_dependencies.push(dependency);
+        } else {
+            dependencies.push(dependency);
+        }
+    }
+
+    Ok(Project {
+        coordinates: Coordinates {
+            group_id,
+            artifact_id,
+            version,
+        },
+        dependencies,
+        test_dependencies,
+    })
+}
+
+fn get_child<'a, 'input>(node: Node<'a, 'input>, name: &str) -> Option<Node<'a, 'input>> {
+    node.children()
+        .find(|child| child.is_element() && child.tag_name().name() == name)
+}
+
+fn get_child_text(node: Node, name: &str) -> Option<String> {
+    get_child(node, name)
+        .and_then(|child| child.text())
+        .map(|text| text.trim().to_string())
+}
+
+/// Get the dependencies under `node`, along with their scopes.
+fn get_dependencies(
+    node: Node,
+    properties: &HashMap<String, String>,
+) -> Vec<(Coordinates, Option<String>)> {
+    let Some(dependencies) = get_child(node, "dependencies") else {
+        return Vec::new();
+    };
+    dependencies
+        .children()
+        .filter(|child| child.is_element() && child.tag_name().name() == "dependency")
+        .filter_map(|dependency| {
+            let get_value = |name| {
+                get_child_text(dependency, name).map(|value| interpolate(&value, properties))
+            };
+            let coordinates = Coordinates {
+                group_id: get_value("groupId"),
+                artifact_id: get_value("artifactId")?,
+                version: get_value("version"),
+            };
+            Some((coordinates, get_value("scope")))
+        })
+        .collect()
+}
+
+/// Replace `${property}` references with their values, leaving unknown properties as they are.
+fn interpolate(value: &str, properties: &HashMap<String, String>) -> String {
+    let mut result = value.to_string();
+    for (name, property_value) in properties {
+        result = result.replace(&format!("${{{name}}}"), property_value);
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use assertables::assert_matches;
+
+    fn make_pom(content: &str) -> String {
+        format!(
+            r#"<?xml version="1.0" encoding="UTF-8"?>
+<project xmlns="http://maven.apache.org/POM/4.0.0">
+  <modelVersion>4.0.0</modelVersion>
+  {content}
+</project>"#
+        )
+    }
+
+    #[test]
+    fn coordinates() {
+        let project = parse_pom(&make_pom(
+            "<groupId>com.example</groupId><artifactId>lib</artifactId><version>1.0.0</version>",
+        ))
+        .unwrap();
+
+        assert_eq!(
+            project.coordinates,
+            Coordinates {
+                group_id: Some("com.example".to_string()),
+                artifact_id: "lib".to_string(),
+                version: Some("1.0.0".to_string()),
+            }
+        );
+    }
+
+    #[test]
+    fn inherited_coordinates() {
+        let project = parse_pom(&make_pom(
+            "<parent><groupId>com.example</groupId><artifactId>parent</artifactId><version>2.0</version></parent><artifactId>lib</artifactId>",
+        ))
+        .unwrap();
+
+        assert_eq!(
+            project.coordinates.group_id,
+            Some("com.example".to_string())
+        );
+        assert_eq!(project.coordinates.version, Some("2.0".to_string()));
+    }
+
+    #[test]
+    fn missing_artifact_id() {
+        let result = parse_pom(&make_pom("<groupId>com.example</groupId>"));
+
+        assert_matches!(result, Err(LibraryMetadataError::MalformedManifest(_)));
+    }
+
+    #[test]
+    fn invalid_xml() {
+        let result = parse_pom("<project>");
+
+        assert_matches!(result, Err(LibraryMetadataError::MalformedManifest(_)));
+    }
+
+    mod dependencies {
+        use super::*;
+
+        #[test]
+        fn explicit_version() {
+            let project = parse_pom(&make_pom(
+                "<artifactId>lib</artifactId><dependencies><dependency><groupId>com.google.guava</groupId><artifactId>guava</artifactId><version>33.0.0-jre</version></dependency></dependencies>",
+            ))
+            .unwrap();
+
+            assert_eq!(
+                project.dependencies,
+                vec![Coordinates {
+                    group_id: Some("com.google.guava".to_string()),
+                    artifact_id: "guava".to_string(),
+                    version: Some("33.0.0-jre".to_string()),
+                }]
+            );
+        }
+
+        #[test]
+        fn property_version() {
+            let project = parse_pom(&make_pom(
+                "<artifactId>lib</artifactId><properties><guava.version>33.0.0-jre</guava.version></properties><dependencies><dependency><groupId>g</groupId><artifactId>guava</artifactId><version>${guava.version}</version></dependency></dependencies>",
+            ))
+            .unwrap();
+
+            assert_eq!(
+                project.dependencies[0].version,
+                Some("33.0.0-jre".to_string())
+            );
+        }
+
+        #[test]
+        fn test_scope() {
+            let project = parse_pom(&make_pom(
+                "<artifactId>lib</artifactId><dependencies><dependency><groupId>org.junit.jupiter</groupId><artifactId>junit-jupiter</artifactId><version>5.10.0</version><scope>test</scope></dependency></dependencies>",
+            ))
+            .unwrap();
+
+            assert!(project.dependencies.is_empty());
+            assert_eq!(project.test_dependencies[0].artifact_id, "junit-jupiter");
+        }
+
+        #[test]
+        fn managed_version() {
+            let project = parse_pom(&make_pom(
+                "<artifactId>lib</artifactId><dependencyManagement><dependencies><dependency><groupId>g</groupId><artifactId>a</artifactId><version>1.2</version></dependency></dependencies></dependencyManagement><dependencies><dependency><groupId>g</groupId><artifactId>a</artifactId></dependency></dependencies>",
+            ))
+            .unwrap();
+
+            assert_eq!(project.dependencies[0].version, Some("1.2".to_string()));
+        }
+    }
+}
@@ -0,0 +1,122 @@
+use super::project::read_project;
+use daipendency_extractor::{LibraryMetadata, LibraryMetadataError};
+use std::fs;
+use std::path::Path;
+
+const SOURCE_ROOT_PATHS: [&str; 2] = ["src/main/java", "src/main/kotlin"];
+const SOURCES_PATH: &str = "src/main";
+const README_PATHS: [&str; 3] = ["README.md", "README", "README.txt"];
+
+/// Extract the metadata of the Maven or Gradle project at `path`.
+///
+/// The entry point is the conventional Java or Kotlin source root if present (or their parent if
+/// both are); otherwise the project directory, as in unpacked sources jars.
+pub fn extract_metadata(path: &Path) -> Result<LibraryMetadata, LibraryMetadataError> {
+    let project = read_project(path)?;
+
+    let documentation = README_PATHS
+        .iter()
+        .find_map(|readme| fs::read_to_string(path.join(readme)).ok())
+        .unwrap_or_default();
+
+    let source_roots: Vec<_> = SOURCE_ROOT_PATHS
+        .iter()
+        .map(|source_root| path.join(source_root))
+        .filter(|source_root| source_root.is_dir())
+        .collect();
+    let entry_point = match source_roots.as_slice() {
+        [] => path.to_path_buf(),
+        [source_root] => source_root.clone(),
+        _ => path.join(SOURCES_PATH),
+    };
+
+    Ok(LibraryMetadata {
+        name: project.coordinates.get_name(),
+        version: project.coordinates.version,
+        documentation,
+        entry_point,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::languages::java::maven::POM_PATH;
+    use assertables::assert_matches;
+    use daipendency_testing::tempdir::TempDir;
+
+    const STUB_POM: &str = "<project><groupId>com.example</groupId><artifactId>lib</artifactId><version>1.0</version></project>";
+    const STUB_DOCUMENTATION: &str = "Test documentation";
+
+    #[test]
+    fn maven_project() {
+        let temp_dir = TempDir::new();
+        temp_dir.create_file(POM_PATH, STUB_POM).unwrap();
+        temp_dir
+            .create_file("README.md", STUB_DOCUMENTATION)
+            .unwrap();
+
+        let metadata = extract_metadata(&temp_dir.path).unwrap();
+
+        assert_eq!(metadata.name, "com.example:lib");
+        assert_eq!(metadata.version, Some("1.0".to_string()));
+        assert_eq!(metadata.documentation, STUB_DOCUMENTATION);
+        assert_eq!(metadata.entry_point, temp_dir.path);
+    }
+
+    #[test]
+    fn conventional_source_root() {
+        let temp_dir = TempDir::new();
+        temp_dir.create_file(POM_PATH, STUB_POM).unwrap();
+        temp_dir
+            .create_file(&format!("{}/Lib.java", SOURCE_ROOT_PATHS[0]), "")
+            .unwrap();
+
+        let metadata = extract_metadata(&temp_dir.path).unwrap();
+
+        assert_eq!(
+            metadata.entry_point,
+            temp_dir.path.join(SOURCE_ROOT_PATHS[0])
+        );
+    }
+
+    #[test]
+    fn kotlin_source_root() {
+        let temp_dir = TempDir::new();
+        temp_dir.create_file(POM_PATH, STUB_POM).unwrap();
+        temp_dir
+            .create_file(&format!("{}/Lib.kt", SOURCE_ROOT_PATHS[1]), "")
+            .unwrap();
+
+        let metadata = extract_metadata(&temp_dir.path).unwrap();
+
+        assert_eq!(
+            metadata.entry_point,
+            temp_dir.path.join(SOURCE_ROOT_PATHS[1])
+        );
+    }
+
+    #[test]
+    fn mixed_source_roots() {
+        let temp_dir = TempDir::new();
+        temp_dir.create_file(POM_PATH, STUB_POM).unwrap();
+        for source_root in SOURCE_ROOT_PATHS {
+            temp_dir
+                .create_file(&format!("{source_root}/Lib.txt"), "")
+                .unwrap();
+        }
+
+        let metadata = extract_metadata(&temp_dir.path).unwrap();
+
+        assert_eq!(metadata.entry_point, temp_dir.path.join(SOURCES_PATH));
+    }
+
+    #[test]
+    fn missing_manifest() {
+        let temp_dir = TempDir::new();
+
+        let result = extract_metadata(&temp_dir.path);
+
+        assert_matches!(result, Err(LibraryMetadataError::MissingManifest(_)));
+    }
+}
@@ -0,0 +1,50 @@
+mod api;
+mod dependencies;
+mod gradle;
+mod kotlin;
+mod maven;
+mod metadata;
+mod parsing;
+mod project;
+mod symbol_kind;
+#[cfg(test)]
+mod test_helpers;
+
+pub use dependencies::list_dependencies;
+pub use kotlin::detect_code_block_language;
+pub use symbol_kind::detect_symbol_kind;
+
+use daipendency_extractor::{
+    DependencyResolutionError, ExtractionError, Extractor, LibraryMetadata, LibraryMetadataError,
+    Namespace,
+};
+use std::path::{Path, PathBuf};
+use tree_sitter::{Language, Parser};
+
+pub struct JavaExtractor;
+
+impl Extractor for JavaExtractor {
+    fn get_parser_language(&self) -> Language {
+        tree_sitter_java::LANGUAGE.into()
+    }
+
+    fn get_library_metadata(&self, path: &Path) -> Result<LibraryMetadata, LibraryMetadataError> {
+        metadata::extract_metadata(path)
+    }
+
+    fn extract_public_api(
+        &self,
+        metadata: &LibraryMetadata,
+        parser: &mut Parser,
+    ) -> Result<Vec<Namespace>, ExtractionError> {
+        api::build_public_api(&metadata.entry_point, parser)
+    }
+
+    fn resolve_dependency_path(
+        &self,
+        dependency_name: &str,
+        dependant_path: &Path,
+    ) -> Result<PathBuf, DependencyResolutionError> {
+        dependencies::resolve_dependency_path(dependency_name, dependant_path)
+    }
+}
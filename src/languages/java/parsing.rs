@@ -0,0 +1,385 @@
+use daipendency_extractor::{ExtractionError, Symbol};
+use tree_sitter::{Node, Parser};
+
+const DEFAULT_MEMBER_INDENT: &str = "    ";
+const TYPE_DECLARATION_KINDS: [&str; 5] = [
+    "class_declaration",
+    "interface_declaration",
+    "enum_declaration",
+    "record_declaration",
+    "annotation_type_declaration",
+];
+
+/// The public API of a Java source file.
+#[derive(Debug)]
+pub struct CompilationUnit {
+    pub package_name: Option<String>,
+    pub doc_comment: Option<String>,
+    pub symbols: Vec<Symbol>,
+}
+
+pub fn parse_compilation_unit(
+    source_code: &str,
+    parser: &mut Parser,
+) -> Result<CompilationUnit, ExtractionError> {
+    let tree = parser
+        .parse(source_code, None)
+        .ok_or_else(|| ExtractionError::Malformed("Failed to parse Java file".to_string()))?;
+    let root = tree.root_node();
+    let source = source_code.as_bytes();
+
+    let mut package_name = None;
+    let mut doc_comment = None;
+    let mut symbols = Vec::new();
+    let mut cursor = root.walk();
+    for node in root.named_children(&mut cursor) {
+        if node.kind() == "package_declaration" {
+            let mut package_cursor = node.walk();
+            package_name = node
+                .named_children(&mut package_cursor)
+                .find(|child| matches!(child.kind(), "identifier" | "scoped_identifier"))
+                .map(|name| get_text(&name, source).to_string());
+            doc_comment = get_doc_comment(&node, source);
+        } else if TYPE_DECLARATION_KINDS.contains(&node.kind()) && has_modifier(&node, "public") {
+            let Some(name) = node.child_by_field_name("name") else {
+                continue;
+            };
+            symbols.push(Symbol {
+                name: get_text(&name, source).to_string(),
+                source_code: with_doc_comment(&node, "", render_type(&node, "", source), source),
+            });
+        }
+    }
+
+    Ok(CompilationUnit {
+        package_name,
+        doc_comment,
+        symbols,
+    })
+}
+
+fn get_text<'a>(node: &Node, source: &'a [u8]) -> &'a str {
+    node.utf8_text(source).unwrap_or_default()
+}
+
+fn get_slice<'a>(start: &Node, end: &Node, source: &'a [u8]) -> &'a str {
+    std::str::from_utf8(&source[start.start_byte()..end.start_byte()])
+        .unwrap_or_default()
+        .trim_end()
+}
+
+fn get_indent<'a>(node: &Node, source: &'a [u8]) -> &'a str {
+    let start = node.start_byte() - node.start_position().column;
+    std::str::from_utf8(&source[start..node.start_byte()])
+        .map(|prefix| &prefix[..prefix.len() - prefix.trim_start().len()])
+        .unwrap_or_default()
+}
+
+fn has_modifier(node: &Node, modifier: &str) -> bool {
+    let mut cursor = node.walk();
+    let modifiers = node
+        .named_children(&mut cursor)
+        .find(|child| child.kind() == "modifiers");
+    modifiers.is_some_and(|modifiers| {
+        let mut modifier_cursor = modifiers.walk();
+        let has_modifier = modifiers
+            .children(&mut modifier_cursor)
+            .any(|child| child.kind() == modifier);
+        has_modifier
+    })
+}
+
+/// Get the Javadoc comment immediately preceding `node`, if any.
+fn get_doc_comment(node: &Node, source: &[u8]) -> Option<String> {
+    node.prev_named_sibling()
+        .filter(|sibling| sibling.kind() == "block_comment")
+        .map(|comment| get_text(&comment, source))
+        .filter(|comment| comment.starts_with("/**"))
+        .map(reindent_comment)
+}
+
+/// Strip the source indentation from the continuation lines of a block comment.
+pub(super) fn reindent_comment(comment: &str) -> String {
+    comment
+        .lines()
+        .enumerate()
+        .map(|(index, line)| match index {
+            0 => line.to_string(),
+            _ => format!(" {}", line.trim_start()),
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn with_doc_comment(node: &Node, indent: &str, rendered: String, source: &[u8]) -> String {
+    match get_doc_comment(node, source) {
+        Some(doc_comment) => {
+            let doc_comment = doc_comment.replace('\n', &format!("\n{indent}"));
+            format!("{indent}{doc_comment}\n{indent}{rendered}")
+        }
+        None => format!("{indent}{rendered}"),
+    }
+}
+
+/// Render a type declaration with its accessible members, omitting method bodies.
+fn render_type(node: &Node, indent: &str, source: &[u8]) -> String {
+    let Some(body) = node.child_by_field_name("body") else {
+        return get_text(node, source).to_string();
+    };
+    let is_interface = matches!(
+        node.kind(),
+        "interface_declaration" | "annotation_type_declaration"
+    );
+    let member_indent = get_member_indent(&body, indent, source);
+
+    let mut members = Vec::new();
+    let mut enum_constants = Vec::new();
+    let mut cursor = body.walk();
+    for member in body.named_children(&mut cursor) {
+        match member.kind() {
+            "enum_constant" => enum_constants.push(with_doc_comment(
+                &member,
+                &member_indent,
+                render_enum_constant(&member, source),
+                source,
+            )),
+            "enum_body_declarations" => {
+                let mut declarations_cursor = member.walk();
+                members.extend(
+                    member
+                        .named_children(&mut declarations_cursor)
+                        .filter_map(|m| render_member(&m, false, &member_indent, source)),
+                );
+            }
+            _ => members.extend(render_member(&member, is_interface, &member_indent, source)),
+        }
+    }
+    if !enum_constants.is_empty() {
+        members.insert(0, format!("{};", enum_constants.join(",\n")));
+    }
+
+    let header = get_slice(node, &body, source);
+    if members.is_empty() {
+        format!("{header} {{}}")
+    } else {
+        format!("{header} {{\n{}\n{indent}}}", members.join("\n"))
+    }
+}
+
+fn get_member_indent(body: &Node, indent: &str, source: &[u8]) -> String {
+    let mut cursor = body.walk();
+    let first_member = body
+        .named_children(&mut cursor)
+        .find(|member| member.start_position().row != body.start_position().row);
+    match first_member {
+        Some(member) => get_indent(&member, source).to_string(),
+        None => format!("{indent}{DEFAULT_MEMBER_INDENT}"),
+    }
+}
+
+fn render_enum_constant(constant: &Node, source: &[u8]) -> String {
+    match constant.child_by_field_name("body") {
+        Some(body) => get_slice(constant, &body, source).to_string(),
+        None => get_text(constant, source).to_string(),
+    }
+}
+
+fn render_member(member: &Node, is_interface: bool, indent: &str, source: &[u8]) -> Option<String> {
+    let is_accessible = if is_interface {
+        !has_modifier(member, "private")
+    } else {
+        has_modifier(member, "public") || has_modifier(member, "protected")
+    };
+    if !is_accessible {
+        return None;
+    }
+
+    let rendered = match member.kind() {
+        kind if TYPE_DECLARATION_KINDS.contains(&kind) => render_type(member, indent, source),
+        "method_declaration" | "constructor_declaration" | "compact_constructor_declaration" => {
+            match member.child_by_field_name("body") {
+                Some(body) => format!("{};", get_slice(member, &body, source)),
+                None => get_text(member, source).to_string(),
+            }
+        }
+        "field_declaration" | "constant_declaration" | "annotation_type_element_declaration" => {
+            get_text(member, source).to_string()
+        }
+        _ => return None,
+    };
+    Some(with_doc_comment(member, indent, rendered, source))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::languages::java::test_helpers::setup_parser;
+
+    fn parse(source_code: &str) -> CompilationUnit {
+        parse_compilation_unit(source_code, &mut setup_parser()).unwrap()
+    }
+
+    fn get_symbol<'a>(unit: &'a CompilationUnit, name: &str) -> &'a Symbol {
+        unit.symbols
+            .iter()
+            .find(|symbol| symbol.name == name)
+            .unwrap_or_else(|| panic!("Symbol {name} not found"))
+    }
+
+    mod package {
+        use super::*;
+
+        #[test]
+        fn package_name() {
+            let unit = parse("package com.example.util;\n");
+
+            assert_eq!(unit.package_name, Some("com.example.util".to_string()));
+        }
+
+        #[test]
+        fn default_package() {
+            let unit = parse("public class Foo {}\n");
+
+            assert_eq!(unit.package_name, None);
+        }
+
+        #[test]
+        fn package_doc_comment() {
+            let unit = parse("/**\n * Utilities.\n */\npackage com.example;\n");
+
+            assert_eq!(
+                unit.doc_comment,
+                Some("/**\n * Utilities.\n */".to_string())
+            );
+        }
+    }
+
+    mod types {
+        use super::*;
+
+        #[test]
+        fn non_public_type() {
+            let unit = parse("class Hidden {}\n");
+
+            assert!(unit.symbols.is_empty());
+        }
+
+        #[test]
+        fn class_members() {
+            let unit = parse(
+                r#"/** A client. */
+public class Client extends Base implements AutoCloseable {
+    public static final int TIMEOUT = 5;
+    private String secret;
+    int packagePrivate;
+
+    /** Create a client. */
+    public Client(String url) {
+        this.url = url;
+    }
+
+    protected void hook() {}
+
+    private void helper() {}
+
+    @Override
+    public void close() throws Exception {
+        helper();
+    }
+}
+"#,
+            );
+
+            assert_eq!(
+                get_symbol(&unit, "Client").source_code,
+                r#"/** A client. */
+public class Client extends Base implements AutoCloseable {
+    public static final int TIMEOUT = 5;
+    /** Create a client. */
+    public Client(String url);
+    protected void hook();
+    @Override
+    public void close() throws Exception;
+}"#
+            );
+        }
+
+        #[test]
+        fn class_without_accessible_members() {
+            let unit = parse("public final class Util {\n    private Util() {}\n}\n");
+
+            assert_eq!(
+                get_symbol(&unit, "Util").source_code,
+                "public final class Util {}"
+            );
+        }
+
+        #[test]
+        fn interface_members() {
+            let unit = parse(
+                "public interface Shape {\n    double PI = 3.14;\n    double area();\n    default String describe() { return \"shape\"; }\n    private void helper() {}\n}\n",
+            );
+
+            assert_eq!(
+                get_symbol(&unit, "Shape").source_code,
+                "public interface Shape {\n    double PI = 3.14;\n    double area();\n    default String describe();\n}"
+            );
+        }
+
+        #[test]
+        fn enum_constants() {
+            let unit = parse(
+                "public enum Level {\n    /** Low. */\n    LOW(1),\n    HIGH(2) { void x() {} };\n\n    public int weight() { return 0; }\n}\n",
+            );
+
+            assert_eq!(
+                get_symbol(&unit, "Level").source_code,
+                "public enum Level {\n    /** Low. */\n    LOW(1),\n    HIGH(2);\n    public int weight();\n}"
+            );
+        }
+
+        #[test]
+        fn record() {
+            let unit = parse(
+                "public record Point(int x, int y) {\n    public int sum() { return x + y; }\n}\n",
+            );
+
+            assert_eq!(
+                get_symbol(&unit, "Point").source_code,
+                "public record Point(int x, int y) {\n    public int sum();\n}"
+            );
+        }
+
+        #[test]
+        fn annotation_type() {
+            let unit = parse("public @interface Marker {\n    String value() default \"\";\n}\n");
+
+            assert_eq!(
+                get_symbol(&unit, "Marker").source_code,
+                "public @interface Marker {\n    String value() default \"\";\n}"
+            );
+        }
+
+        #[test]
+        fn nested_types() {
+            let unit = parse(
+                "public class Outer {\n    public static class Inner {\n        public void run() {}\n    }\n    private static class Hidden {}\n}\n",
+            );
+
+            assert_eq!(
+                get_symbol(&unit, "Outer").source_code,
+                "public class Outer {\n    public static class Inner {\n        public void run();\n    }\n}"
+            );
+        }
+
+        #[test]
+        fn single_line_class() {
+            let unit = parse("public class Tiny { public void run() {} }\n");
+
+            assert_eq!(
+                get_symbol(&unit, "Tiny").source_code,
+                "public class Tiny {\n    public void run();\n}"
+            );
+        }
+    }
+}
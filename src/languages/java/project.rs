@@ -0,0 +1,114 @@
+use super::{gradle, maven};
+use daipendency_extractor::LibraryMetadataError;
+use std::io::{Error, ErrorKind};
+use std::path::Path;
+
+/// A Maven or Gradle project.
+#[derive(Debug, Default, PartialEq)]
+pub struct Project {
+    pub coordinates: Coordinates,
+    pub dependencies: Vec<Coordinates>,
+    /// Dependencies only needed to compile and run the tests.
+    pub test_dependencies: Vec<Coordinates>,
+}
+
+/// The Maven coordinates of an artifact, as used by both Maven and Gradle.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Coordinates {
+    pub group_id: Option<String>,
+    pub artifact_id: String,
+    pub version: Option<String>,
+}
+
+impl Coordinates {
+    /// Get the `groupId:artifactId` name of the artifact, or just the artifact id if the group is unknown.
+    pub fn get_name(&self) -> String {
+        match &self.group_id {
+            Some(group_id) => format!("{}:{}", group_id, self.artifact_id),
+            None => self.artifact_id.clone(),
+        }
+    }
+}
+
+pub fn read_project(directory: &Path) -> Result<Project, LibraryMetadataError> {
+    if directory.join(maven::POM_PATH).is_file() {
+        maven::read_pom(directory)
+    } else if gradle::find_build_script(directory).is_some() {
+        gradle::read_build(directory)
+    } else {
+        Err(LibraryMetadataError::MissingManifest(Error::new(
+            ErrorKind::NotFound,
+            format!(
+                "No pom.xml or Gradle build script in {}",
+                directory.display()
+            ),
+        )))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use assertables::assert_matches;
+    use daipendency_testing::tempdir::TempDir;
+
+    #[test]
+    fn maven_project() {
+        let temp_dir = TempDir::new();
+        temp_dir
+            .create_file(
+                maven::POM_PATH,
+                "<project><artifactId>maven-lib</artifactId></project>",
+            )
+            .unwrap();
+
+        let project = read_project(&temp_dir.path).unwrap();
+
+        assert_eq!(project.coordinates.artifact_id, "maven-lib");
+    }
+
+    #[test]
+    fn gradle_project() {
+        let temp_dir = TempDir::new();
+        temp_dir.create_file("gradle-lib/build.gradle", "").unwrap();
+
+        let project = read_project(&temp_dir.path.join("gradle-lib")).unwrap();
+
+        assert_eq!(project.coordinates.artifact_id, "gradle-lib");
+    }
+
+    #[test]
+    fn missing_manifest() {
+        let temp_dir = TempDir::new();
+
+        let result = read_project(&temp_dir.path);
+
+        assert_matches!(result, Err(LibraryMetadataError::MissingManifest(_)));
+    }
+
+    mod coordinates {
+        use super::*;
+
+        #[test]
+        fn name_with_group() {
+            let coordinates = Coordinates {
+                group_id: Some("com.example".to_string()),
+                artifact_id: "lib".to_string(),
+                version: None,
+            };
+
+            assert_eq!(coordinates.get_name(), "com.example:lib");
+        }
+
+        #[test]
+        fn name_without_group() {
+            let coordinates = Coordinates {
+                group_id: None,
+                artifact_id: "lib".to_string(),
+                version: None,
+            };
+
+            assert_eq!(coordinates.get_name(), "lib");
+        }
+    }
+}
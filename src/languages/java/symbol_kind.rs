@@ -0,0 +1,131 @@
+use crate::symbol_kind::{skip_comments_and_annotations, SymbolKind};
+
+const LINE_COMMENT: &str = "//";
+/// Words that may precede the keyword of a Java or Kotlin declaration.
+const MODIFIERS: [&str; 26] = [
+    "public",
+    "protected",
+    "private",
+    "internal",
+    "static",
+    "final",
+    "abstract",
+    "sealed",
+    "non-sealed",
+    "strictfp",
+    "open",
+    "data",
+    "value",
+    "inner",
+    "companion",
+    "override",
+    "suspend",
+    "inline",
+    "operator",
+    "infix",
+    "tailrec",
+    "external",
+    "const",
+    "lateinit",
+    "expect",
+    "actual",
+];
+
+/// Detect the kind of the Java or Kotlin declaration in `source_code`, skipping its doc comment
+/// and annotations.
+pub fn detect_symbol_kind(source_code: &str) -> Option<SymbolKind> {
+    let declaration = skip_comments_and_annotations(source_code, LINE_COMMENT);
+    let mut words = declaration
+        .split(|character: char| character.is_whitespace() || matches!(character, '(' | '<' | '{'))
+        .filter(|word| !word.is_empty())
+        .skip_while(|word| MODIFIERS.contains(word));
+    match words.next()? {
+        "class" | "record" | "object" => Some(SymbolKind::Class),
+        "interface" | "@interface" | "annotation" => Some(SymbolKind::Interface),
+        "enum" => Some(SymbolKind::Enum),
+        "fun" if words.next() == Some("interface") => Some(SymbolKind::Interface),
+        "fun" => Some(SymbolKind::Function),
+        "val" | "var" => Some(SymbolKind::Constant),
+        "typealias" => Some(SymbolKind::TypeAlias),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn java_types() {
+        assert_eq!(
+            detect_symbol_kind("/** A client. */\n@Deprecated\npublic final class Client {}"),
+            Some(SymbolKind::Class)
+        );
+        assert_eq!(
+            detect_symbol_kind("public record Point(int x, int y) {}"),
+            Some(SymbolKind::Class)
+        );
+        assert_eq!(
+            detect_symbol_kind("public sealed interface Shape permits Circle {}"),
+            Some(SymbolKind::Interface)
+        );
+        assert_eq!(
+            detect_symbol_kind("public @interface Marker {}"),
+            Some(SymbolKind::Interface)
+        );
+        assert_eq!(
+            detect_symbol_kind("public enum Level {\n    LOW;\n}"),
+            Some(SymbolKind::Enum)
+        );
+    }
+
+    #[test]
+    fn kotlin_types() {
+        assert_eq!(
+            detect_symbol_kind("data class Point(val x: Int, val y: Int)"),
+            Some(SymbolKind::Class)
+        );
+        assert_eq!(
+            detect_symbol_kind("companion object {}"),
+            Some(SymbolKind::Class)
+        );
+        assert_eq!(
+            detect_symbol_kind("enum class Colour { RED }"),
+            Some(SymbolKind::Enum)
+        );
+        assert_eq!(
+            detect_symbol_kind("fun interface Action {\n    fun run()\n}"),
+            Some(SymbolKind::Interface)
+        );
+        assert_eq!(
+            detect_symbol_kind("annotation class Marker"),
+            Some(SymbolKind::Interface)
+        );
+        assert_eq!(
+            detect_symbol_kind("typealias Handler = (String) -> Unit"),
+            Some(SymbolKind::TypeAlias)
+        );
+    }
+
+    #[test]
+    fn kotlin_functions_and_properties() {
+        assert_eq!(
+            detect_symbol_kind("/** Doubles. */\nsuspend fun <T> twice(x: T): T"),
+            Some(SymbolKind::Function)
+        );
+        assert_eq!(
+            detect_symbol_kind("const val TIMEOUT: Int"),
+            Some(SymbolKind::Constant)
+        );
+        assert_eq!(
+            detect_symbol_kind("var counter: Int"),
+            Some(SymbolKind::Constant)
+        );
+    }
+
+    #[test]
+    fn unrecognised_declarations() {
+        assert_eq!(detect_symbol_kind("package com.example"), None);
+        assert_eq!(detect_symbol_kind(""), None);
+    }
+}
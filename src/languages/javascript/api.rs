@@ -0,0 +1,501 @@
+use super::manifest::{read_manifest, ROOT_SUBPATH};
+use super::parsing::{parse_module, Export, ImportedName, Module};
+use super::Dialect;
+use daipendency_extractor::{ExtractionError, Namespace, Symbol};
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
+use tree_sitter::Parser;
+
+const DEFAULT_EXPORT: &str = "default";
+
+/// The exports of a module after following re-exports.
+#[derive(Debug, Default)]
+struct ResolvedModule {
+    doc_comment: Option<String>,
+    symbols: Vec<Symbol>,
+    /// Modules re-exported as a namespace (`export * as name from "source"`).
+    namespaces: Vec<(String, PathBuf)>,
+}
+
+/// Build the public API of the package at `package_path`, with a namespace per exposed module.
+///
+/// Packages without any module in `dialect` are reported as malformed rather than as empty.
+pub fn build_public_api(
+    package_path: &Path,
+    package_name: &str,
+    dialect: Dialect,
+    parser: &mut Parser,
+) -> Result<Vec<Namespace>, ExtractionError> {
+    let manifest =
+        read_manifest(package_path).map_err(|e| ExtractionError::Malformed(e.to_string()))?;
+    let mut resolver = ModuleResolver {
+        dialect,
+        parser,
+        modules: HashMap::new(),
+        modules_in_progress: HashSet::new(),
+    };
+
+    let entry_points = manifest.get_entry_points(package_path, dialect);
+    if entry_points.is_empty() {
+        return Err(ExtractionError::Malformed(format!(
+            "No entry point found in {}",
+            package_path.display()
+        )));
+    }
+
+    let mut namespaces = Vec::new();
+    for entry_point in entry_points {
+        let namespace_name = match entry_point.subpath.as_str() {
+            ROOT_SUBPATH => package_name.to_string(),
+            subpath => format!("{}/{}", package_name, subpath.trim_start_matches("./")),
+        };
+        resolver.collect_namespaces(
+            &namespace_name,
+            &entry_point.path,
+            &mut HashSet::new(),
+            &mut namespaces,
+        )?;
+    }
+    Ok(namespaces)
+}
+
+struct ModuleResolver<'a> {
+    dialect: Dialect,
+    parser: &'a mut Parser,
+    modules: HashMap<PathBuf, Rc<ResolvedModule>>,
+    modules_in_progress: HashSet<PathBuf>,
+}
+
+impl ModuleResolver<'_> {
+    fn collect_namespaces(
+        &mut self,
+        name: &str,
+        path: &Path,
+        ancestors: &mut HashSet<PathBuf>,
+        namespaces: &mut Vec<Namespace>,
+    ) -> Result<(), ExtractionError> {
+        if !ancestors.insert(path.to_path_buf()) {
+            return Ok(());
+        }
+        let module = self.resolve_module(path)?;
+        namespaces.push(Namespace {
+            name: name.to_string(),
+            symbols: module.symbols.clone(),
+            doc_comment: module.doc_comment.clone(),
+        });
+        for (namespace_name, namespace_path) in &module.namespaces {
+            self.collect_namespaces(
+                &format!("{name}.{namespace_name}"),
+                namespace_path,
+                ancestors,
+                namespaces,
+            )?;
+        }
+        ancestors.remove(path);
+        Ok(())
+    }
+
+    fn resolve_module(&mut self, path: &Path) -> Result<Rc<ResolvedModule>, ExtractionError> {
+        if let Some(module) = self.modules.get(path) {
+            return Ok(module.clone());
+        }
+        if !self.modules_in_progress.insert(path.to_path_buf()) {
+            // Circular re-exports contribute nothing beyond what's already been collected
+            return Ok(Rc::new(ResolvedModule::default()));
+        }
+
+        let source_code = fs::read_to_string(path)?;
+        let mut parsed_module = parse_module(&source_code, self.parser)?;
+        let directory = path.parent().unwrap_or(path);
+
+        let mut module = ResolvedModule {
+            doc_comment: parsed_module.doc_comment.take(),
+            ..Default::default()
+        };
+        for export in std::mem::take(&mut parsed_module.exports) {
+            match export {
+                Export::Declaration(symbol) => module.symbols.push(symbol),
+                Export::Local { name, local_name } => {
+                    let symbol = self.resolve_local(directory, &parsed_module, &local_name)?;
+                    module
+                        .symbols
+                        .extend(symbol.map(|symbol| rename(symbol, name)));
+                }
+                Export::Assignment { local_name } => {
+                    match parsed_module.namespaces.get(&local_name) {
+                        // The members of the namespace are exported alongside the value it merges with.
+                        Some(namespace) => {
+                            module
+                                .symbols
+                                .extend(parsed_module.declarations.get(&local_name).cloned());
+                            module.symbols.extend(namespace.members.iter().cloned());
+                        }
+                        None => module.symbols.extend(self.resolve_local(
+                            directory,
+                            &parsed_module,
+                            &local_name,
+                        )?),
+                    }
+                }
+                Export::Named {
+                    name,
+                    imported_name,
+                } => {
+                    let symbol = self.resolve_import(directory, &imported_name)?;
+                    module
+                        .symbols
+                        .extend(symbol.map(|symbol| rename(symbol, name)));
+                }
+                Export::All { source } => {
+                    if let Some(source_path) = self.resolve_module_path(directory, &source) {
+                        let source_module = self.resolve_module(&source_path)?;
+                        module.symbols.extend(
+                            source_module
+                                .symbols
+                                .iter()
+                                .filter(|symbol| symbol.name != DEFAULT_EXPORT)
+                                .cloned(),
+                        );
+                        module
+                            .namespaces
+                            .extend(source_module.namespaces.iter().cloned());
+                    }
+                }
+                Export::Namespace { name, source } => {
+                    if let Some(source_path) = self.resolve_module_path(directory, &source) {
+                        module.namespaces.push((name, source_path));
+                    }
+                }
+            }
+        }
+
+        let module = Rc::new(module);
+        self.modules_in_progress.remove(path);
+        self.modules.insert(path.to_path_buf(), module.clone());
+        Ok(module)
+    }
+
+    /// Resolve a relative module specifier; bare specifiers refer to other packages and are ignored.
+    fn resolve_module_path(&self, directory: &Path, specifier: &str) -> Option<PathBuf> {
+        if !specifier.starts_with('.') {
+            return None;
+        }
+        self.dialect.resolve_module_file(&directory.join(specifier))
+    }
+
+    /// Resolve the top-level declaration called `local_name` in `module`, following imports.
+    fn resolve_local(
+        &mut self,
+        directory: &Path,
+        module: &Module,
+        local_name: &str,
+    ) -> Result<Option<Symbol>, ExtractionError> {
+        match module.get_declaration(local_name) {
+            Some(symbol) => Ok(Some(symbol.clone())),
+            None => match module.imports.get(local_name) {
+                Some(imported_name) => self.resolve_import(directory, imported_name),
+                None => Ok(None),
+            },
+        }
+    }
+
+    fn resolve_import(
+        &mut self,
+        directory: &Path,
+        imported_name: &ImportedName,
+    ) -> Result<Option<Symbol>, ExtractionError> {
+        let Some(source_path) = self.resolve_module_path(directory, &imported_name.source) else {
+            return Ok(None);
+        };
+        let source_module = self.resolve_module(&source_path)?;
+        Ok(source_module
+            .symbols
+            .iter()
+            .find(|symbol| symbol.name == imported_name.name)
+            .cloned())
+    }
+}
+
+fn rename(symbol: Symbol, name: String) -> Symbol {
+    Symbol {
+        name,
+        source_code: symbol.source_code,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::languages::javascript::manifest::MANIFEST_PATH;
+    use crate::languages::javascript::test_helpers::{
+        setup_javascript_parser, setup_typescript_parser,
+    };
+    use assertables::assert_matches;
+    use daipendency_testing::tempdir::TempDir;
+
+    const STUB_NAME: &str = "test-package";
+
+    fn create_package(manifest_fields: &str, files: &[(&str, &str)]) -> TempDir {
+        let temp_dir = TempDir::new();
+        temp_dir
+            .create_file(
+                MANIFEST_PATH,
+                &format!("{{\"name\": \"{STUB_NAME}\"{manifest_fields}}}"),
+            )
+            .unwrap();
+        for (path, content) in files {
+            temp_dir.create_file(path, content).unwrap();
+        }
+        temp_dir
+    }
+
+    fn build_typescript(temp_dir: &TempDir) -> Vec<Namespace> {
+        build_public_api(
+            &temp_dir.path,
+            STUB_NAME,
+            Dialect::TypeScript,
+            &mut setup_typescript_parser(),
+        )
+        .unwrap()
+    }
+
+    fn get_symbol_names(namespace: &Namespace) -> Vec<&str> {
+        namespace
+            .symbols
+            .iter()
+            .map(|symbol| symbol.name.as_str())
+            .collect()
+    }
+
+    #[test]
+    fn root_module() {
+        let temp_dir = create_package(
+            "",
+            &[(
+                "index.d.ts",
+                "/** @packageDocumentation */\nexport declare function f(): void;\n",
+            )],
+        );
+
+        let namespaces = build_typescript(&temp_dir);
+
+        assert_eq!(namespaces.len(), 1);
+        assert_eq!(namespaces[0].name, STUB_NAME);
+        assert_eq!(
+            namespaces[0].doc_comment,
+            Some("/** @packageDocumentation */".to_string())
+        );
+        assert_eq!(get_symbol_names(&namespaces[0]), vec!["f"]);
+    }
+
+    #[test]
+    fn missing_entry_point() {
+        let temp_dir = create_package(r#", "main": "lib/main.js""#, &[]);
+
+        let result = build_public_api(
+            &temp_dir.path,
+            STUB_NAME,
+            Dialect::JavaScript,
+            &mut setup_javascript_parser(),
+        );
+
+        assert_matches!(result, Err(ExtractionError::Malformed(_)));
+    }
+
+    #[test]
+    fn export_assignment_merged_with_namespace() {
+        let temp_dir = create_package(
+            r#", "types": "index.d.ts""#,
+            &[(
+                "index.d.ts",
+                "export = Legacy;\n\
+                 declare function Legacy(options: Legacy.Options): Legacy.Result;\n\
+                 declare namespace Legacy {\n\
+                 \x20 interface Options { verbose: boolean }\n\
+                 \x20 type Result = string;\n\
+                 }\n",
+            )],
+        );
+
+        let namespaces = build_typescript(&temp_dir);
+
+        assert_eq!(namespaces.len(), 1);
+        assert_eq!(
+            get_symbol_names(&namespaces[0]),
+            vec!["Legacy", "Options", "Result"]
+        );
+        assert_eq!(
+            namespaces[0].symbols[0].source_code,
+            "declare function Legacy(options: Legacy.Options): Legacy.Result;"
+        );
+    }
+
+    #[test]
+    fn export_assignment_of_namespace() {
+        let temp_dir = create_package(
+            "",
+            &[(
+                "index.d.ts",
+                "export = Legacy;\ndeclare namespace Legacy {\n  function run(): void;\n}\n",
+            )],
+        );
+
+        let namespaces = build_typescript(&temp_dir);
+
+        assert_eq!(get_symbol_names(&namespaces[0]), vec!["run"]);
+    }
+
+    #[test]
+    fn subpath_modules() {
+        let temp_dir = create_package(
+            r#", "exports": {".": "./index.js", "./server": "./server.js"}"#,
+            &[
+                ("index.js", "export function render() {}\n"),
+                ("server.js", "export function renderToString() {}\n"),
+            ],
+        );
+
+        let namespaces = build_public_api(
+            &temp_dir.path,
+            STUB_NAME,
+            Dialect::JavaScript,
+            &mut setup_javascript_parser(),
+        )
+        .unwrap();
+
+        assert_eq!(
+            namespaces
+                .iter()
+                .map(|n| n.name.as_str())
+                .collect::<Vec<_>>(),
+            vec![STUB_NAME, "test-package/server"]
+        );
+        assert_eq!(get_symbol_names(&namespaces[1]), vec!["renderToString"]);
+    }
+
+    mod reexports {
+        use super::*;
+
+        #[test]
+        fn local_declaration() {
+            let temp_dir = create_package(
+                "",
+                &[(
+                    "index.ts",
+                    "function helper(): void {}\nexport { helper as assist };\n",
+                )],
+            );
+
+            let namespaces = build_typescript(&temp_dir);
+
+            assert_eq!(get_symbol_names(&namespaces[0]), vec!["assist"]);
+            assert_eq!(
+                namespaces[0].symbols[0].source_code,
+                "function helper(): void;"
+            );
+        }
+
+        #[test]
+        fn imported_name() {
+            let temp_dir = create_package(
+                "",
+                &[
+                    (
+                        "index.ts",
+                        "import { Client } from './client';\nexport { Client };\n",
+                    ),
+                    ("client.ts", "export interface Client {}\n"),
+                ],
+            );
+
+            let namespaces = build_typescript(&temp_dir);
+
+            assert_eq!(get_symbol_names(&namespaces[0]), vec!["Client"]);
+        }
+
+        #[test]
+        fn named_from_module() {
+            let temp_dir = create_package(
+                "",
+                &[
+                    (
+                        "index.d.ts",
+                        "export { Client as HttpClient } from './client.js';\n",
+                    ),
+                    ("client.d.ts", "export interface Client {}\n"),
+                ],
+            );
+
+            let namespaces = build_typescript(&temp_dir);
+
+            assert_eq!(get_symbol_names(&namespaces[0]), vec!["HttpClient"]);
+        }
+
+        #[test]
+        fn all_from_module() {
+            let temp_dir = create_package(
+                "",
+                &[
+                    ("index.ts", "export * from './lib';\n"),
+                    (
+                        "lib/index.ts",
+                        "export type A = string;\nexport default interface B {}\n",
+                    ),
+                ],
+            );
+
+            let namespaces = build_typescript(&temp_dir);
+
+            assert_eq!(get_symbol_names(&namespaces[0]), vec!["A"]);
+        }
+
+        #[test]
+        fn namespace_from_module() {
+            let temp_dir = create_package(
+                "",
+                &[
+                    ("index.ts", "export * as utils from './utils';\n"),
+                    ("utils.ts", "export type A = string;\n"),
+                ],
+            );
+
+            let namespaces = build_typescript(&temp_dir);
+
+            assert_eq!(
+                namespaces
+                    .iter()
+                    .map(|n| n.name.as_str())
+                    .collect::<Vec<_>>(),
+                vec![STUB_NAME, "test-package.utils"]
+            );
+            assert_eq!(get_symbol_names(&namespaces[1]), vec!["A"]);
+        }
+
+        #[test]
+        fn external_module_ignored() {
+            let temp_dir = create_package("", &[("index.ts", "export * from 'react';\n")]);
+
+            let namespaces = build_typescript(&temp_dir);
+
+            assert!(namespaces[0].symbols.is_empty());
+        }
+
+        #[test]
+        fn circular_reexports() {
+            let temp_dir = create_package(
+                "",
+                &[
+                    ("index.ts", "export * from './a';\n"),
+                    ("a.ts", "export * from './b';\nexport type A = string;\n"),
+                    ("b.ts", "export * from './a';\nexport type B = string;\n"),
+                ],
+            );
+
+            let namespaces = build_typescript(&temp_dir);
+
+            assert_eq!(get_symbol_names(&namespaces[0]), vec!["B", "A"]);
+        }
+    }
+}
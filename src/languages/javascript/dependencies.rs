@@ -0,0 +1,249 @@
+use super::manifest::{read_manifest, MANIFEST_PATH, ROOT_SUBPATH};
+use super::Dialect;
+use daipendency_extractor::DependencyResolutionError;
+use std::path::{Path, PathBuf};
+
+const NODE_MODULES_DIRECTORY: &str = "node_modules";
+const TYPES_SCOPE: &str = "@types";
+
+/// Resolve a dependency installed under `node_modules` in the dependant or any of its ancestors.
+///
+/// Peer and optional dependencies that aren't installed are reported as missing, like undeclared
+/// ones, since package managers may legitimately skip them.
+///
+/// Dependencies without their own TypeScript declarations fall back to their `@types` package,
+/// regardless of the dialect of the dependant, since the declarations describe their API best.
+pub fn resolve_dependency_path(
+    dependency_name: &str,
+    dependant_path: &Path,
+) -> Result<PathBuf, DependencyResolutionError> {
+    let manifest = read_manifest(dependant_path)
+        .map_err(|e| DependencyResolutionError::RetrievalFailure(e.to_string()))?;
+    if !manifest.declares_dependency(dependency_name) {
+        return Err(DependencyResolutionError::MissingDependency(
+            dependency_name.to_string(),
+        ));
+    }
+
+    let package_path =
+        find_installed_package(dependency_name, dependant_path).ok_or_else(|| {
+            match manifest.declares_optional_dependency(dependency_name) {
+                true => DependencyResolutionError::MissingDependency(dependency_name.to_string()),
+                false => DependencyResolutionError::RetrievalFailure(format!(
+                    "'{}' is not installed in {}",
+                    dependency_name, NODE_MODULES_DIRECTORY
+                )),
+            }
+        })?;
+
+    if !has_root_module(&package_path, Dialect::TypeScript) {
+        if let Some(types_path) =
+            find_installed_package(&get_types_package_name(dependency_name), dependant_path)
+        {
+            return Ok(types_path);
+        }
+    }
+    Ok(package_path)
+}
+
+/// List the runtime dependencies of the package at `path`.
+pub fn list_dependencies(path: &Path) -> Result<Vec<String>, DependencyResolutionError> {
+    read_manifest(path)
+        .map(|manifest| manifest.get_runtime_dependency_names())
+        .map_err(|e| DependencyResolutionError::RetrievalFailure(e.to_string()))
+}
+
+fn find_installed_package(name: &str, dependant_path: &Path) -> Option<PathBuf> {
+    dependant_path
+        .ancestors()
+        .map(|directory| directory.join(NODE_MODULES_DIRECTORY).join(name))
+        .find(|package_path| package_path.join(MANIFEST_PATH).is_file())
+}
+
+fn has_root_module(package_path: &Path, dialect: Dialect) -> bool {
+    read_manifest(package_path).is_ok_and(|manifest| {
+        manifest
+            .get_entry_points(package_path, dialect)
+            .iter()
+            .any(|entry_point| entry_point.subpath == ROOT_SUBPATH)
+    })
+}
+
+/// Get the name of the DefinitelyTyped package for `name` (e.g. `@types/babel__core` for `@babel/core`).
+fn get_types_package_name(name: &str) -> String {
+    let unscoped_name = name
+        .strip_prefix('@')
+        .map_or(name.to_string(), |scoped_name| {
+            scoped_name.replacen('/', "__", 1)
+        });
+    format!("{TYPES_SCOPE}/{unscoped_name}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use assertables::assert_matches;
+    use daipendency_testing::tempdir::TempDir;
+
+    const STUB_DEPENDENCY: &str = "left-pad";
+
+    fn create_dependant(temp_dir: &TempDir, dependency_name: &str) {
+        temp_dir
+            .create_file(
+                MANIFEST_PATH,
+                &format!(
+                    "{{\"name\": \"dependant\", \"dependencies\": {{\"{dependency_name}\": \"^1\"}}}}"
+                ),
+            )
+            .unwrap();
+    }
+
+    fn create_installed_package(temp_dir: &TempDir, name: &str, files: &[&str]) -> PathBuf {
+        let manifest_path = temp_dir
+            .create_file(
+                &format!("{NODE_MODULES_DIRECTORY}/{name}/{MANIFEST_PATH}"),
+                &format!("{{\"name\": \"{name}\"}}"),
+            )
+            .unwrap();
+        for file in files {
+            temp_dir
+                .create_file(&format!("{NODE_MODULES_DIRECTORY}/{name}/{file}"), "")
+                .unwrap();
+        }
+        manifest_path.parent().unwrap().to_path_buf()
+    }
+
+    #[test]
+    fn installed_dependency() {
+        let temp_dir = TempDir::new();
+        create_dependant(&temp_dir, STUB_DEPENDENCY);
+        let package_path = create_installed_package(&temp_dir, STUB_DEPENDENCY, &["index.js"]);
+
+        let result = resolve_dependency_path(STUB_DEPENDENCY, &temp_dir.path).unwrap();
+
+        assert_eq!(result, package_path);
+    }
+
+    #[test]
+    fn hoisted_dependency() {
+        let temp_dir = TempDir::new();
+        let package_path = create_installed_package(&temp_dir, STUB_DEPENDENCY, &["index.js"]);
+        temp_dir
+            .create_file(
+                &format!("packages/web/{MANIFEST_PATH}"),
+                &format!(
+                    "{{\"name\": \"web\", \"dependencies\": {{\"{STUB_DEPENDENCY}\": \"^1\"}}}}"
+                ),
+            )
+            .unwrap();
+
+        let result =
+            resolve_dependency_path(STUB_DEPENDENCY, &temp_dir.path.join("packages/web")).unwrap();
+
+        assert_eq!(result, package_path);
+    }
+
+    #[test]
+    fn undeclared_dependency() {
+        let temp_dir = TempDir::new();
+        create_dependant(&temp_dir, "other");
+        create_installed_package(&temp_dir, STUB_DEPENDENCY, &["index.js"]);
+
+        let result = resolve_dependency_path(STUB_DEPENDENCY, &temp_dir.path);
+
+        assert_matches!(
+            result,
+            Err(DependencyResolutionError::MissingDependency(name)) if name == STUB_DEPENDENCY
+        );
+    }
+
+    #[test]
+    fn uninstalled_dependency() {
+        let temp_dir = TempDir::new();
+        create_dependant(&temp_dir, STUB_DEPENDENCY);
+
+        let result = resolve_dependency_path(STUB_DEPENDENCY, &temp_dir.path);
+
+        assert_matches!(result, Err(DependencyResolutionError::RetrievalFailure(_)));
+    }
+
+    #[test]
+    fn uninstalled_optional_dependency() {
+        let temp_dir = TempDir::new();
+        temp_dir
+            .create_file(
+                MANIFEST_PATH,
+                &format!(
+                    "{{\"name\": \"dependant\", \"optionalDependencies\": {{\"{STUB_DEPENDENCY}\": \"^1\"}}}}"
+                ),
+            )
+            .unwrap();
+
+        let result = resolve_dependency_path(STUB_DEPENDENCY, &temp_dir.path);
+
+        assert_matches!(
+            result,
+            Err(DependencyResolutionError::MissingDependency(name)) if name == STUB_DEPENDENCY
+        );
+    }
+
+    #[test]
+    fn missing_dependant_manifest() {
+        let temp_dir = TempDir::new();
+
+        let result = resolve_dependency_path(STUB_DEPENDENCY, &temp_dir.path);
+
+        assert_matches!(result, Err(DependencyResolutionError::RetrievalFailure(_)));
+    }
+
+    mod types_package {
+        use super::*;
+
+        #[test]
+        fn bundled_declarations() {
+            let temp_dir = TempDir::new();
+            create_dependant(&temp_dir, STUB_DEPENDENCY);
+            let package_path =
+                create_installed_package(&temp_dir, STUB_DEPENDENCY, &["index.d.ts"]);
+            create_installed_package(&temp_dir, "@types/left-pad", &["index.d.ts"]);
+
+            let result = resolve_dependency_path(STUB_DEPENDENCY, &temp_dir.path).unwrap();
+
+            assert_eq!(result, package_path);
+        }
+
+        #[test]
+        fn definitely_typed_declarations() {
+            let temp_dir = TempDir::new();
+            create_dependant(&temp_dir, STUB_DEPENDENCY);
+            create_installed_package(&temp_dir, STUB_DEPENDENCY, &["index.js"]);
+            let types_path =
+                create_installed_package(&temp_dir, "@types/left-pad", &["index.d.ts"]);
+
+            let result = resolve_dependency_path(STUB_DEPENDENCY, &temp_dir.path).unwrap();
+
+            assert_eq!(result, types_path);
+        }
+
+        #[test]
+        fn untyped_package() {
+            let temp_dir = TempDir::new();
+            create_dependant(&temp_dir, STUB_DEPENDENCY);
+            let package_path = create_installed_package(&temp_dir, STUB_DEPENDENCY, &["index.js"]);
+
+            let result = resolve_dependency_path(STUB_DEPENDENCY, &temp_dir.path).unwrap();
+
+            assert_eq!(result, package_path);
+        }
+
+        #[test]
+        fn scoped_package_name() {
+            assert_eq!(get_types_package_name("@babel/core"), "@types/babel__core");
+        }
+
+        #[test]
+        fn unscoped_package_name() {
+            assert_eq!(get_types_package_name("react"), "@types/react");
+        }
+    }
+}
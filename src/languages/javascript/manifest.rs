@@ -0,0 +1,410 @@
+use super::Dialect;
+use daipendency_extractor::LibraryMetadataError;
+use serde_json::{Map, Value};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+pub const MANIFEST_PATH: &str = "package.json";
+pub const ROOT_SUBPATH: &str = ".";
+const DEPENDENCY_FIELDS: [&str; 4] = [
+    "dependencies",
+    "devDependencies",
+    "peerDependencies",
+    "optionalDependencies",
+];
+const RUNTIME_DEPENDENCY_FIELDS: [&str; 3] =
+    ["dependencies", "peerDependencies", "optionalDependencies"];
+/// Fields whose dependencies may legitimately be absent from `node_modules`.
+const OPTIONAL_DEPENDENCY_FIELDS: [&str; 2] = ["peerDependencies", "optionalDependencies"];
+
+/// The relevant fields of a `package.json` file.
+#[derive(Debug)]
+pub struct PackageManifest {
+    pub name: String,
+    pub version: Option<String>,
+    fields: Map<String, Value>,
+}
+
+/// A module that can be imported from a package, such as `pkg` or `pkg/server`.
+#[derive(Debug, PartialEq)]
+pub struct EntryPoint {
+    pub subpath: String,
+    pub path: PathBuf,
+}
+
+pub fn read_manifest(directory: &Path) -> Result<PackageManifest, LibraryMetadataError> {
+    let content = fs::read_to_string(directory.join(MANIFEST_PATH))
+        .map_err(LibraryMetadataError::MissingManifest)?;
+    let fields = match serde_json::from_str(&content) {
+        Ok(Value::Object(fields)) => fields,
+        Ok(_) => {
+            return Err(LibraryMetadataError::MalformedManifest(
+                "Manifest is not an object".to_string(),
+            ))
+        }
+        Err(error) => return Err(LibraryMetadataError::MalformedManifest(error.to_string())),
+    };
+
+    let name = fields
+        .get("name")
+        .and_then(Value::as_str)
+        .ok_or_else(|| LibraryMetadataError::MalformedManifest("Missing package name".to_string()))?
+        .to_string();
+    let version = fields
+        .get("version")
+        .and_then(Value::as_str)
+        .map(String::from);
+
+    Ok(PackageManifest {
+        name,
+        version,
+        fields,
+    })
+}
+
+impl PackageManifest {
+    /// Whether `name` is declared in any of the dependency fields.
+    pub fn declares_dependency(&self, name: &str) -> bool {
+        DEPENDENCY_FIELDS.iter().any(|field| {
+            self.fields
+                .get(*field)
+                .and_then(Value::as_object)
+                .is_some_and(|dependencies| dependencies.contains_key(name))
+        })
+    }
+
+    /// Whether `name` is only declared as a peer or optional dependency, which package managers
+    /// may leave uninstalled.
+    pub fn declares_optional_dependency(&self, name: &str) -> bool {
+        let declares = |field: &str| {
+            self.fields
+                .get(field)
+                .and_then(Value::as_object)
+                .is_some_and(|dependencies| dependencies.contains_key(name))
+        };
+        OPTIONAL_DEPENDENCY_FIELDS.into_iter().any(declares) && !declares("dependencies")
+    }
+
+    /// The names of the dependencies needed at runtime, which excludes `devDependencies`.
+    pub fn get_runtime_dependency_names(&self) -> Vec<String> {
+        let mut names = Vec::new();
+        for field in RUNTIME_DEPENDENCY_FIELDS {
+            let dependencies = self.fields.get(field).and_then(Value::as_object);
+            for name in dependencies.into_iter().flat_map(|d| d.keys()) {
+                if !names.contains(name) {
+                    names.push(name.clone());
+                }
+            }
+        }
+        names
+    }
+
+    /// The modules exposed by the package, starting with the root module if it exists.
+    pub fn get_entry_points(&self, directory: &Path, dialect: Dialect) -> Vec<EntryPoint> {
+        let mut targets = self.get_export_targets(dialect);
+        if !targets.iter().any(|(subpath, _)| subpath == ROOT_SUBPATH) {
+            let fallback = self
+                .get_legacy_entry_point_fields(dialect)
+                .iter()
+                .find_map(|field| self.fields.get(*field).and_then(Value::as_str))
+                .unwrap_or("index");
+            targets.insert(0, (ROOT_SUBPATH.to_string(), fallback.to_string()));
+        }
+
+        targets
+            .into_iter()
+            .filter_map(|(subpath, target)| {
+                let path = dialect.resolve_module_file(&directory.join(target))?;
+                Some(EntryPoint { subpath, path })
+            })
+            .collect()
+    }
+
+    fn get_legacy_entry_point_fields(&self, dialect: Dialect) -> &'static [&'static str] {
+        match dialect {
+            Dialect::JavaScript => &["module", "main"],
+            Dialect::TypeScript => &["types", "typings", "module", "main"],
+        }
+    }
+
+    fn get_export_targets(&self, dialect: Dialect) -> Vec<(String, String)> {
+        let Some(exports) = self.fields.get("exports") else {
+            return vec![];
+        };
+        let conditions = dialect.get_export_conditions();
+
+        let subpaths = exports
+            .as_object()
+            .filter(|map| map.keys().any(|key| key.starts_with('.')));
+        let mut targets = match subpaths {
+            Some(subpaths) => subpaths
+                .iter()
+                .filter(|(subpath, _)| !subpath.contains('*') && !subpath.ends_with(".json"))
+                .filter_map(|(subpath, value)| {
+                    Some((subpath.clone(), resolve_conditions(value, conditions)?))
+                })
+                .collect::<Vec<_>>(),
+            None => resolve_conditions(exports, conditions)
+                .map(|target| vec![(ROOT_SUBPATH.to_string(), target)])
+                .unwrap_or_default(),
+        };
+        targets.sort_by_key(|(subpath, _)| (subpath != ROOT_SUBPATH, subpath.clone()));
+        targets
+    }
+}
+
+fn resolve_conditions(value: &Value, conditions: &[&str]) -> Option<String> {
+    match value {
+        Value::String(target) => Some(target.clone()),
+        Value::Array(alternatives) => alternatives
+            .iter()
+            .find_map(|alternative| resolve_conditions(alternative, conditions)),
+        Value::Object(map) => conditions
+            .iter()
+            .find_map(|condition| resolve_conditions(map.get(*condition)?, conditions)),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use assertables::assert_matches;
+    use daipendency_testing::tempdir::TempDir;
+
+    const STUB_NAME: &str = "test-package";
+    const STUB_VERSION: &str = "1.0.0";
+
+    fn create_manifest(temp_dir: &TempDir, fields: &str) -> PackageManifest {
+        temp_dir
+            .create_file(
+                MANIFEST_PATH,
+                &format!("{{\"name\": \"{STUB_NAME}\", \"version\": \"{STUB_VERSION}\"{fields}}}"),
+            )
+            .unwrap();
+        read_manifest(&temp_dir.path).unwrap()
+    }
+
+    mod reading {
+        use super::*;
+
+        #[test]
+        fn name_and_version() {
+            let temp_dir = TempDir::new();
+
+            let manifest = create_manifest(&temp_dir, "");
+
+            assert_eq!(manifest.name, STUB_NAME);
+            assert_eq!(manifest.version, Some(STUB_VERSION.to_string()));
+        }
+
+        #[test]
+        fn missing_manifest() {
+            let temp_dir = TempDir::new();
+
+            let result = read_manifest(&temp_dir.path);
+
+            assert_matches!(result, Err(LibraryMetadataError::MissingManifest(_)));
+        }
+
+        #[test]
+        fn invalid_json() {
+            let temp_dir = TempDir::new();
+            temp_dir.create_file(MANIFEST_PATH, "{").unwrap();
+
+            let result = read_manifest(&temp_dir.path);
+
+            assert_matches!(result, Err(LibraryMetadataError::MalformedManifest(_)));
+        }
+
+        #[test]
+        fn missing_name() {
+            let temp_dir = TempDir::new();
+            temp_dir.create_file(MANIFEST_PATH, "{}").unwrap();
+
+            let result = read_manifest(&temp_dir.path);
+
+            assert_matches!(result, Err(LibraryMetadataError::MalformedManifest(_)));
+        }
+
+        #[test]
+        fn declared_dependencies() {
+            let temp_dir = TempDir::new();
+
+            let manifest = create_manifest(
+                &temp_dir,
+                r#", "dependencies": {"react": "^18"}, "devDependencies": {"vitest": "^1"}"#,
+            );
+
+            assert!(manifest.declares_dependency("react"));
+            assert!(manifest.declares_dependency("vitest"));
+            assert!(!manifest.declares_dependency("vue"));
+        }
+
+        #[test]
+        fn optional_dependencies() {
+            let temp_dir = TempDir::new();
+
+            let manifest = create_manifest(
+                &temp_dir,
+                r#", "dependencies": {"react": "^18"}, "peerDependencies": {"react": "^18", "react-dom": "^18"}, "optionalDependencies": {"fsevents": "^2"}"#,
+            );
+
+            assert!(!manifest.declares_optional_dependency("react"));
+            assert!(manifest.declares_optional_dependency("react-dom"));
+            assert!(manifest.declares_optional_dependency("fsevents"));
+            assert!(!manifest.declares_optional_dependency("vue"));
+        }
+
+        #[test]
+        fn runtime_dependency_names() {
+            let temp_dir = TempDir::new();
+
+            let manifest = create_manifest(
+                &temp_dir,
+                r#", "dependencies": {"react": "^18"}, "devDependencies": {"vitest": "^1"}, "peerDependencies": {"react": "^18", "react-dom": "^18"}"#,
+            );
+
+            assert_eq!(
+                manifest.get_runtime_dependency_names(),
+                vec!["react", "react-dom"]
+            );
+        }
+    }
+
+    mod entry_points {
+        use super::*;
+
+        fn get_entry_points(
+            temp_dir: &TempDir,
+            fields: &str,
+            dialect: Dialect,
+        ) -> Vec<(String, PathBuf)> {
+            create_manifest(temp_dir, fields)
+                .get_entry_points(&temp_dir.path, dialect)
+                .into_iter()
+                .map(|entry_point| (entry_point.subpath, entry_point.path))
+                .collect()
+        }
+
+        #[test]
+        fn types_field() {
+            let temp_dir = TempDir::new();
+            let types = temp_dir.create_file("dist/main.d.ts", "").unwrap();
+
+            let entry_points = get_entry_points(
+                &temp_dir,
+                r#", "types": "dist/main.d.ts""#,
+                Dialect::TypeScript,
+            );
+
+            assert_eq!(entry_points, vec![(ROOT_SUBPATH.to_string(), types)]);
+        }
+
+        #[test]
+        fn main_field() {
+            let temp_dir = TempDir::new();
+            let main = temp_dir.create_file("lib/main.js", "").unwrap();
+
+            let entry_points =
+                get_entry_points(&temp_dir, r#", "main": "lib/main.js""#, Dialect::JavaScript);
+
+            assert_eq!(entry_points, vec![(ROOT_SUBPATH.to_string(), main)]);
+        }
+
+        #[test]
+        fn declarations_next_to_main() {
+            let temp_dir = TempDir::new();
+            temp_dir.create_file("lib/main.js", "").unwrap();
+            let declarations = temp_dir.create_file("lib/main.d.ts", "").unwrap();
+
+            let entry_points =
+                get_entry_points(&temp_dir, r#", "main": "lib/main.js""#, Dialect::TypeScript);
+
+            assert_eq!(entry_points, vec![(ROOT_SUBPATH.to_string(), declarations)]);
+        }
+
+        #[test]
+        fn index_fallback() {
+            let temp_dir = TempDir::new();
+            let index = temp_dir.create_file("index.js", "").unwrap();
+
+            let entry_points = get_entry_points(&temp_dir, "", Dialect::JavaScript);
+
+            assert_eq!(entry_points, vec![(ROOT_SUBPATH.to_string(), index)]);
+        }
+
+        #[test]
+        fn missing_entry_point() {
+            let temp_dir = TempDir::new();
+
+            let entry_points = get_entry_points(&temp_dir, "", Dialect::TypeScript);
+
+            assert!(entry_points.is_empty());
+        }
+
+        #[test]
+        fn exports_string() {
+            let temp_dir = TempDir::new();
+            let index = temp_dir.create_file("dist/index.mjs", "").unwrap();
+
+            let entry_points = get_entry_points(
+                &temp_dir,
+                r#", "exports": "./dist/index.mjs""#,
+                Dialect::JavaScript,
+            );
+
+            assert_eq!(entry_points, vec![(ROOT_SUBPATH.to_string(), index)]);
+        }
+
+        #[test]
+        fn exports_conditions() {
+            let temp_dir = TempDir::new();
+            temp_dir.create_file("dist/index.js", "").unwrap();
+            let types = temp_dir.create_file("dist/types.d.ts", "").unwrap();
+
+            let entry_points = get_entry_points(
+                &temp_dir,
+                r#", "exports": {"import": "./dist/index.js", "types": "./dist/types.d.ts"}"#,
+                Dialect::TypeScript,
+            );
+
+            assert_eq!(entry_points, vec![(ROOT_SUBPATH.to_string(), types)]);
+        }
+
+        #[test]
+        fn exports_nested_conditions() {
+            let temp_dir = TempDir::new();
+            let import = temp_dir.create_file("esm/index.js", "").unwrap();
+
+            let entry_points = get_entry_points(
+                &temp_dir,
+                r#", "exports": {"node": {"import": "./esm/index.js", "require": "./cjs/index.js"}}"#,
+                Dialect::JavaScript,
+            );
+
+            assert_eq!(entry_points, vec![(ROOT_SUBPATH.to_string(), import)]);
+        }
+
+        #[test]
+        fn exports_subpaths() {
+            let temp_dir = TempDir::new();
+            let index = temp_dir.create_file("index.js", "").unwrap();
+            let server = temp_dir.create_file("server.js", "").unwrap();
+
+            let entry_points = get_entry_points(
+                &temp_dir,
+                r#", "exports": {"./server": "./server.js", ".": "./index.js", "./package.json": "./package.json", "./*": "./*.js"}"#,
+                Dialect::JavaScript,
+            );
+
+            assert_eq!(
+                entry_points,
+                vec![
+                    (ROOT_SUBPATH.to_string(), index),
+                    ("./server".to_string(), server)
+                ]
+            );
+        }
+    }
+}
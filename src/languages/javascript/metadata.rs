@@ -0,0 +1,139 @@
+use super::manifest::{read_manifest, ROOT_SUBPATH};
+use super::Dialect;
+use daipendency_extractor::{LibraryMetadata, LibraryMetadataError};
+use std::fs;
+use std::io::{Error, ErrorKind};
+use std::path::Path;
+
+const TSCONFIG_PATH: &str = "tsconfig.json";
+const README_PATHS: [&str; 3] = ["README.md", "readme.md", "README"];
+
+/// Extract the metadata of the package at `path`.
+///
+/// The entry point is the package directory itself, since packages can expose several modules.
+/// Packages only count as TypeScript if they ship declarations or are configured for TypeScript.
+pub fn extract_metadata(
+    path: &Path,
+    dialect: Dialect,
+) -> Result<LibraryMetadata, LibraryMetadataError> {
+    let manifest = read_manifest(path)?;
+
+    if dialect == Dialect::TypeScript {
+        let has_root_module = manifest
+            .get_entry_points(path, dialect)
+            .iter()
+            .any(|entry_point| entry_point.subpath == ROOT_SUBPATH);
+        if !has_root_module && !path.join(TSCONFIG_PATH).is_file() {
+            return Err(LibraryMetadataError::MissingManifest(Error::new(
+                ErrorKind::NotFound,
+                format!("No TypeScript declarations found in {}", path.display()),
+            )));
+        }
+    }
+
+    let documentation = README_PATHS
+        .iter()
+        .find_map(|readme| fs::read_to_string(path.join(readme)).ok())
+        .unwrap_or_default();
+
+    Ok(LibraryMetadata {
+        name: manifest.name,
+        version: manifest.version,
+        documentation,
+        entry_point: path.to_path_buf(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::languages::javascript::manifest::MANIFEST_PATH;
+    use assertables::assert_matches;
+    use daipendency_testing::tempdir::TempDir;
+
+    const STUB_NAME: &str = "test-package";
+    const STUB_DOCUMENTATION: &str = "Test documentation";
+
+    fn create_package(temp_dir: &TempDir) {
+        temp_dir
+            .create_file(MANIFEST_PATH, &format!("{{\"name\": \"{STUB_NAME}\"}}"))
+            .unwrap();
+        temp_dir.create_file("index.js", "").unwrap();
+    }
+
+    #[test]
+    fn valid_package() {
+        let temp_dir = TempDir::new();
+        create_package(&temp_dir);
+        temp_dir
+            .create_file("README.md", STUB_DOCUMENTATION)
+            .unwrap();
+
+        let metadata = extract_metadata(&temp_dir.path, Dialect::JavaScript).unwrap();
+
+        assert_eq!(metadata.name, STUB_NAME);
+        assert_eq!(metadata.version, None);
+        assert_eq!(metadata.documentation, STUB_DOCUMENTATION);
+        assert_eq!(metadata.entry_point, temp_dir.path);
+    }
+
+    #[test]
+    fn missing_readme() {
+        let temp_dir = TempDir::new();
+        create_package(&temp_dir);
+
+        let metadata = extract_metadata(&temp_dir.path, Dialect::JavaScript).unwrap();
+
+        assert_eq!(metadata.documentation, "");
+    }
+
+    mod typescript {
+        use super::*;
+
+        #[test]
+        fn missing_declarations() {
+            let temp_dir = TempDir::new();
+            create_package(&temp_dir);
+
+            let result = extract_metadata(&temp_dir.path, Dialect::TypeScript);
+
+            assert_matches!(result, Err(LibraryMetadataError::MissingManifest(_)));
+        }
+
+        #[test]
+        fn bundled_declarations() {
+            let temp_dir = TempDir::new();
+            create_package(&temp_dir);
+            temp_dir.create_file("index.d.ts", "").unwrap();
+
+            let result = extract_metadata(&temp_dir.path, Dialect::TypeScript);
+
+            assert!(result.is_ok());
+        }
+
+        #[test]
+        fn typescript_project() {
+            let temp_dir = TempDir::new();
+            temp_dir
+                .create_file(MANIFEST_PATH, &format!("{{\"name\": \"{STUB_NAME}\"}}"))
+                .unwrap();
+            temp_dir.create_file(TSCONFIG_PATH, "{}").unwrap();
+
+            let result = extract_metadata(&temp_dir.path, Dialect::TypeScript);
+
+            assert!(result.is_ok());
+        }
+    }
+
+    #[test]
+    fn javascript_without_entry_point() {
+        let temp_dir = TempDir::new();
+        temp_dir
+            .create_file(MANIFEST_PATH, &format!("{{\"name\": \"{STUB_NAME}\"}}"))
+            .unwrap();
+
+        let result = extract_metadata(&temp_dir.path, Dialect::JavaScript);
+
+        assert!(result.is_ok());
+    }
+}
@@ -0,0 +1,136 @@
+mod api;
+mod dependencies;
+mod manifest;
+mod metadata;
+mod parsing;
+mod symbol_kind;
+#[cfg(test)]
+mod test_helpers;
+
+pub use dependencies::list_dependencies;
+pub use symbol_kind::detect_symbol_kind;
+
+use daipendency_extractor::{
+    DependencyResolutionError, ExtractionError, Extractor, LibraryMetadata, LibraryMetadataError,
+    Namespace,
+};
+use std::path::{Path, PathBuf};
+use tree_sitter::{Language, Parser};
+
+const INDEX_MODULE_STEM: &str = "index";
+const ALL_MODULE_EXTENSIONS: [&str; 11] = [
+    "d.ts", "d.mts", "d.cts", "ts", "tsx", "mts", "cts", "js", "mjs", "cjs", "jsx",
+];
+
+/// The flavour of ECMAScript whose API is extracted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Dialect {
+    JavaScript,
+    TypeScript,
+}
+
+impl Dialect {
+    /// The file extensions of modules in this dialect, in order of preference.
+    fn get_module_extensions(self) -> &'static [&'static str] {
+        match self {
+            Dialect::JavaScript => &["js", "mjs", "cjs", "jsx"],
+            Dialect::TypeScript => &["d.ts", "ts", "tsx", "d.mts", "mts", "d.cts", "cts"],
+        }
+    }
+
+    /// Resolve a module path (e.g. `./utils` or `./utils.js`) to a file.
+    ///
+    /// Modules in this dialect take precedence, so `./utils.js` resolves to `./utils.d.ts` in TypeScript.
+    fn resolve_module_file(self, path: &Path) -> Option<PathBuf> {
+        let path_string = path.to_string_lossy();
+        let stem = ALL_MODULE_EXTENSIONS
+            .iter()
+            .find_map(|extension| path_string.strip_suffix(&format!(".{extension}")))
+            .unwrap_or(&path_string);
+        let index = Path::new(stem).join(INDEX_MODULE_STEM);
+        let index_string = index.to_string_lossy();
+
+        self.get_module_extensions()
+            .iter()
+            .map(|extension| PathBuf::from(format!("{stem}.{extension}")))
+            .chain(std::iter::once(path.to_path_buf()))
+            .chain(
+                self.get_module_extensions()
+                    .iter()
+                    .map(|extension| PathBuf::from(format!("{index_string}.{extension}"))),
+            )
+            .find(|candidate| candidate.is_file())
+    }
+
+    /// The conditions to use when resolving the `exports` field, in order of preference.
+    fn get_export_conditions(self) -> &'static [&'static str] {
+        match self {
+            Dialect::JavaScript => &["import", "module", "default", "require", "node"],
+            Dialect::TypeScript => &["types", "typings", "import", "default", "require", "node"],
+        }
+    }
+}
+
+pub struct JavaScriptExtractor;
+
+pub struct TypeScriptExtractor;
+
+fn extract_public_api(
+    dialect: Dialect,
+    metadata: &LibraryMetadata,
+    parser: &mut Parser,
+) -> Result<Vec<Namespace>, ExtractionError> {
+    api::build_public_api(&metadata.entry_point, &metadata.name, dialect, parser)
+}
+
+impl Extractor for JavaScriptExtractor {
+    fn get_parser_language(&self) -> Language {
+        tree_sitter_javascript::LANGUAGE.into()
+    }
+
+    fn get_library_metadata(&self, path: &Path) -> Result<LibraryMetadata, LibraryMetadataError> {
+        metadata::extract_metadata(path, Dialect::JavaScript)
+    }
+
+    fn extract_public_api(
+        &self,
+        metadata: &LibraryMetadata,
+        parser: &mut Parser,
+    ) -> Result<Vec<Namespace>, ExtractionError> {
+        extract_public_api(Dialect::JavaScript, metadata, parser)
+    }
+
+    fn resolve_dependency_path(
+        &self,
+        dependency_name: &str,
+        dependant_path: &Path,
+    ) -> Result<PathBuf, DependencyResolutionError> {
+        dependencies::resolve_dependency_path(dependency_name, dependant_path)
+    }
+}
+
+impl Extractor for TypeScriptExtractor {
+    fn get_parser_language(&self) -> Language {
+        tree_sitter_typescript::LANGUAGE_TYPESCRIPT.into()
+    }
+
+    fn get_library_metadata(&self, path: &Path) -> Result<LibraryMetadata, LibraryMetadataError> {
+        metadata::extract_metadata(path, Dialect::TypeScript)
+    }
+
+    fn extract_public_api(
+        &self,
+        metadata: &LibraryMetadata,
+        parser: &mut Parser,
+    ) -> Result<Vec<Namespace>, ExtractionError> {
+        extract_public_api(Dialect::TypeScript, metadata, parser)
+    }
+
+    fn resolve_dependency_path(
+        &self,
+        dependency_name: &str,
+        dependant_path: &Path,
+    ) -> Result<PathBuf, DependencyResolutionError> {
+        dependencies::resolve_dependency_path(dependency_name, dependant_path)
+    }
+}
@@ -0,0 +1,909 @@
+use daipendency_extractor::{ExtractionError, Symbol};
+use std::collections::hash_map::Entry;
+use std::collections::HashMap;
+use tree_sitter::{Node, Parser};
+
+const DEFAULT_EXPORT: &str = "default";
+const DEFAULT_MEMBER_INDENT: &str = "  ";
+const MODULE_DOC_TAGS: [&str; 3] = ["@packageDocumentation", "@module", "@fileoverview"];
+
+/// The declarations and exports of an ECMAScript module.
+#[derive(Debug, Default)]
+pub struct Module {
+    pub doc_comment: Option<String>,
+    /// Top-level declarations other than namespaces, whether exported or not.
+    pub declarations: HashMap<String, Symbol>,
+    /// Top-level namespaces, which merge with any declaration of the same name.
+    pub namespaces: HashMap<String, NamespaceDeclaration>,
+    pub exports: Vec<Export>,
+    /// Names imported from other modules, keyed by local name.
+    pub imports: HashMap<String, ImportedName>,
+}
+
+impl Module {
+    /// Get the top-level declaration called `name`, falling back to a namespace.
+    pub fn get_declaration(&self, name: &str) -> Option<&Symbol> {
+        self.declarations
+            .get(name)
+            .or_else(|| self.namespaces.get(name).map(|namespace| &namespace.symbol))
+    }
+}
+
+#[derive(Debug)]
+pub struct NamespaceDeclaration {
+    pub symbol: Symbol,
+    /// The exported members, which `export =` exposes as the exports of the module.
+    pub members: Vec<Symbol>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ImportedName {
+    pub source: String,
+    pub name: String,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum Export {
+    /// `export function name() {}`
+    Declaration(Symbol),
+    /// `export { local as name }`
+    Local { name: String, local_name: String },
+    /// `export { source_name as name } from "source"`
+    Named {
+        name: String,
+        imported_name: ImportedName,
+    },
+    /// `export * from "source"`
+    All { source: String },
+    /// `export * as name from "source"`
+    Namespace { name: String, source: String },
+    /// `export = local`
+    Assignment { local_name: String },
+}
+
+pub fn parse_module(source_code: &str, parser: &mut Parser) -> Result<Module, ExtractionError> {
+    let tree = parser.parse(source_code, None).ok_or_else(|| {
+        ExtractionError::Malformed("Failed to parse ECMAScript module".to_string())
+    })?;
+    let root = tree.root_node();
+    let source = source_code.as_bytes();
+
+    let mut module = Module {
+        doc_comment: get_module_doc_comment(&root, source),
+        ..Default::default()
+    };
+    let mut cursor = root.walk();
+    for statement in root.named_children(&mut cursor) {
+        match statement.kind() {
+            "export_statement" => module.exports.extend(parse_export(&statement, source)),
+            "import_statement" => module.imports.extend(parse_import(&statement, source)),
+            _ => match get_namespace(&statement) {
+                Some((namespace, is_ambient)) => add_namespace(
+                    &mut module.namespaces,
+                    &statement,
+                    &namespace,
+                    is_ambient,
+                    source,
+                ),
+                None => {
+                    for symbol in render_declarations(&statement, &statement, source) {
+                        add_declaration(&mut module.declarations, symbol);
+                    }
+                }
+            },
+        }
+    }
+    Ok(module)
+}
+
+/// Add `symbol` to `declarations`, merging it with any previous declaration of the same name
+/// (e.g. function overloads).
+fn add_declaration(declarations: &mut HashMap<String, Symbol>, symbol: Symbol) {
+    match declarations.entry(symbol.name.clone()) {
+        Entry::Occupied(mut entry) => {
+            let declaration = entry.get_mut();
+            declaration.source_code =
+                format!("{}\n{}", declaration.source_code, symbol.source_code);
+        }
+        Entry::Vacant(entry) => {
+            entry.insert(symbol);
+        }
+    }
+}
+
+/// Add the `namespace` declared by `statement`, merging it with any previous namespace of the same
+/// name.
+fn add_namespace(
+    namespaces: &mut HashMap<String, NamespaceDeclaration>,
+    statement: &Node,
+    namespace: &Node,
+    is_ambient: bool,
+    source: &[u8],
+) {
+    let Some(name) = get_declaration_name(namespace, source) else {
+        return;
+    };
+    let symbol = Symbol {
+        name: name.clone(),
+        source_code: render_statement(statement, namespace, source),
+    };
+    let members = render_namespace_members(namespace, is_ambient, source);
+    match namespaces.entry(name) {
+        Entry::Occupied(mut entry) => {
+            let declaration = entry.get_mut();
+            declaration.symbol.source_code =
+                format!("{}\n{}", declaration.symbol.source_code, symbol.source_code);
+            declaration.members.extend(members);
+        }
+        Entry::Vacant(entry) => {
+            entry.insert(NamespaceDeclaration { symbol, members });
+        }
+    }
+}
+
+/// Get the namespace declared by `statement`, if any, and whether it's ambient.
+fn get_namespace<'a>(statement: &Node<'a>) -> Option<(Node<'a>, bool)> {
+    let is_ambient = match statement.kind() {
+        "ambient_declaration" => true,
+        "expression_statement" => false,
+        _ => return None,
+    };
+    let namespace = statement.named_child(0)?;
+    (namespace.kind() == "internal_module").then_some((namespace, is_ambient))
+}
+
+/// Render the exported members of `namespace`, where every member is exported if it's ambient.
+fn render_namespace_members(namespace: &Node, is_ambient: bool, source: &[u8]) -> Vec<Symbol> {
+    let Some(body) = namespace.child_by_field_name("body") else {
+        return vec![];
+    };
+    let mut cursor = body.walk();
+    let members = body.named_children(&mut cursor).collect::<Vec<_>>();
+    members
+        .iter()
+        .flat_map(|member| match member.kind() {
+            "export_statement" => member
+                .child_by_field_name("declaration")
+                .map(|declaration| render_declarations(member, &declaration, source))
+                .unwrap_or_default(),
+            "expression_statement" if is_ambient => member
+                .named_child(0)
+                .map(|declaration| render_declarations(member, &declaration, source))
+                .unwrap_or_default(),
+            _ if is_ambient => render_declarations(member, member, source),
+            _ => vec![],
+        })
+        .collect()
+}
+
+fn get_text<'a>(node: &Node, source: &'a [u8]) -> &'a str {
+    node.utf8_text(source).unwrap_or_default()
+}
+
+fn get_slice<'a>(start: &Node, end: &Node, source: &'a [u8]) -> &'a str {
+    std::str::from_utf8(&source[start.start_byte()..end.start_byte()])
+        .unwrap_or_default()
+        .trim_end()
+}
+
+fn get_string_content(node: &Node, source: &[u8]) -> String {
+    get_text(node, source)
+        .trim_matches(|c| c == '"' || c == '\'' || c == '`')
+        .to_string()
+}
+
+fn get_module_doc_comment(root: &Node, source: &[u8]) -> Option<String> {
+    let first = root.named_child(0)?;
+    let comment = get_text(&first, source);
+    (first.kind() == "comment" && MODULE_DOC_TAGS.iter().any(|tag| comment.contains(tag)))
+        .then(|| comment.to_string())
+}
+
+fn get_doc_comment<'a>(node: &Node, source: &'a [u8]) -> Option<&'a str> {
+    let comment = node.prev_named_sibling()?;
+    let text = get_text(&comment, source);
+    let is_adjacent = comment.end_position().row + 1 >= node.start_position().row;
+    (comment.kind() == "comment" && text.starts_with("/**") && is_adjacent).then_some(text)
+}
+
+fn parse_export(statement: &Node, source: &[u8]) -> Vec<Export> {
+    let is_default = has_child_of_kind(statement, "default");
+    let export_source = statement
+        .child_by_field_name("source")
+        .map(|source_node| get_string_content(&source_node, source));
+
+    if let Some(declaration) = statement.child_by_field_name("declaration") {
+        let mut symbols = render_declarations(statement, &declaration, source);
+        if is_default {
+            symbols
+                .iter_mut()
+                .for_each(|symbol| symbol.name = DEFAULT_EXPORT.to_string());
+        }
+        return symbols.into_iter().map(Export::Declaration).collect();
+    }
+
+    if let Some(value) = statement.child_by_field_name("value") {
+        return vec![match value.kind() {
+            "identifier" => Export::Local {
+                name: DEFAULT_EXPORT.to_string(),
+                local_name: get_text(&value, source).to_string(),
+            },
+            _ => Export::Declaration(Symbol {
+                name: DEFAULT_EXPORT.to_string(),
+                source_code: render_statement(statement, &value, source),
+            }),
+        }];
+    }
+
+    let mut cursor = statement.walk();
+    let children = statement.named_children(&mut cursor).collect::<Vec<_>>();
+    if let Some(clause) = children.iter().find(|c| c.kind() == "export_clause") {
+        let mut clause_cursor = clause.walk();
+        return clause
+            .named_children(&mut clause_cursor)
+            .filter(|specifier| specifier.kind() == "export_specifier")
+            .filter_map(|specifier| {
+                let local_name = get_text(&specifier.child_by_field_name("name")?, source);
+                let name = specifier
+                    .child_by_field_name("alias")
+                    .map_or(local_name, |alias| get_text(&alias, source))
+                    .to_string();
+                Some(match &export_source {
+                    Some(module) => Export::Named {
+                        name,
+                        imported_name: ImportedName {
+                            source: module.clone(),
+                            name: local_name.to_string(),
+                        },
+                    },
+                    None => Export::Local {
+                        name,
+                        local_name: local_name.to_string(),
+                    },
+                })
+            })
+            .collect();
+    }
+
+    if let Some(namespace_export) = children.iter().find(|c| c.kind() == "namespace_export") {
+        let name = namespace_export
+            .named_child(0)
+            .map(|name| get_string_content(&name, source));
+        return match (name, export_source) {
+            (Some(name), Some(source)) => vec![Export::Namespace { name, source }],
+            _ => vec![],
+        };
+    }
+
+    if let Some(source) = export_source {
+        return vec![Export::All { source }];
+    }
+
+    // `export = name;`
+    children
+        .iter()
+        .find(|c| c.kind() == "identifier")
+        .map(|identifier| {
+            vec![Export::Assignment {
+                local_name: get_text(identifier, source).to_string(),
+            }]
+        })
+        .unwrap_or_default()
+}
+
+fn parse_import(statement: &Node, source: &[u8]) -> Vec<(String, ImportedName)> {
+    let Some(import_source) = statement
+        .child_by_field_name("source")
+        .map(|source_node| get_string_content(&source_node, source))
+    else {
+        return vec![];
+    };
+    let mut cursor = statement.walk();
+    let Some(clause) = statement
+        .named_children(&mut cursor)
+        .find(|child| child.kind() == "import_clause")
+    else {
+        return vec![];
+    };
+
+    let mut imports = Vec::new();
+    let mut clause_cursor = clause.walk();
+    for child in clause.named_children(&mut clause_cursor) {
+        match child.kind() {
+            "identifier" => imports.push((
+                get_text(&child, source).to_string(),
+                ImportedName {
+                    source: import_source.clone(),
+                    name: DEFAULT_EXPORT.to_string(),
+                },
+            )),
+            "named_imports" => {
+                let mut specifier_cursor = child.walk();
+                for specifier in child.named_children(&mut specifier_cursor) {
+                    let Some(name) = specifier.child_by_field_name("name") else {
+                        continue;
+                    };
+                    let name = get_text(&name, source).to_string();
+                    let local_name = specifier
+                        .child_by_field_name("alias")
+                        .map_or(name.clone(), |alias| get_text(&alias, source).to_string());
+                    imports.push((
+                        local_name,
+                        ImportedName {
+                            source: import_source.clone(),
+                            name,
+                        },
+                    ));
+                }
+            }
+            _ => {}
+        }
+    }
+    imports
+}
+
+fn has_child_of_kind(node: &Node, kind: &str) -> bool {
+    let mut cursor = node.walk();
+    let has_child = node.children(&mut cursor).any(|child| child.kind() == kind);
+    has_child
+}
+
+/// Render the symbols introduced by `declaration`, which is part of `statement`.
+fn render_declarations(statement: &Node, declaration: &Node, source: &[u8]) -> Vec<Symbol> {
+    match declaration.kind() {
+        "lexical_declaration" | "variable_declaration" => {
+            render_variable_declarators(statement, declaration, source)
+        }
+        "ambient_declaration" => declaration
+            .named_child(0)
+            .and_then(|inner| {
+                Some(Symbol {
+                    name: get_declaration_name(&inner, source)?,
+                    source_code: render_statement(statement, &inner, source),
+                })
+            })
+            .into_iter()
+            .collect(),
+        "function_declaration"
+        | "generator_function_declaration"
+        | "function_signature"
+        | "class_declaration"
+        | "abstract_class_declaration"
+        | "interface_declaration"
+        | "type_alias_declaration"
+        | "enum_declaration"
+        | "internal_module" => get_declaration_name(declaration, source)
+            .map(|name| Symbol {
+                name,
+                source_code: render_statement(statement, declaration, source),
+            })
+            .into_iter()
+            .collect(),
+        _ => vec![],
+    }
+}
+
+fn get_declaration_name(declaration: &Node, source: &[u8]) -> Option<String> {
+    match declaration.kind() {
+        "lexical_declaration" | "variable_declaration" => declaration
+            .named_child(0)
+            .and_then(|declarator| declarator.child_by_field_name("name")),
+        _ => declaration.child_by_field_name("name"),
+    }
+    .map(|name| get_text(&name, source).to_string())
+}
+
+fn with_doc_comment(statement: &Node, rendered: String, source: &[u8]) -> String {
+    match get_doc_comment(statement, source) {
+        Some(doc_comment) => format!("{doc_comment}\n{rendered}"),
+        None => rendered,
+    }
+}
+
+/// Render `statement` without implementation details of `declaration`.
+fn render_statement(statement: &Node, declaration: &Node, source: &[u8]) -> String {
+    let rendered = match (declaration.kind(), declaration.child_by_field_name("body")) {
+        ("function_declaration" | "generator_function_declaration", Some(body)) => {
+            format!("{};", get_slice(statement, &body, source))
+        }
+        ("class_declaration" | "abstract_class_declaration" | "class", Some(body)) => {
+            render_class(statement, &body, source)
+        }
+        _ => get_text(statement, source).to_string(),
+    };
+    with_doc_comment(statement, rendered, source)
+}
+
+fn render_variable_declarators(statement: &Node, declaration: &Node, source: &[u8]) -> Vec<Symbol> {
+    let mut cursor = declaration.walk();
+    let declarators = declaration
+        .named_children(&mut cursor)
+        .filter(|child| child.kind() == "variable_declarator")
+        .collect::<Vec<_>>();
+    let Some(first_declarator) = declarators.first() else {
+        return vec![];
+    };
+    let prefix = get_slice(statement, first_declarator, source);
+
+    declarators
+        .iter()
+        .filter_map(|declarator| {
+            let name = declarator.child_by_field_name("name")?;
+            if name.kind() != "identifier" {
+                return None;
+            }
+            let rendered = match declarator.child_by_field_name("value") {
+                Some(value) => {
+                    let binding = get_slice(declarator, &value, source)
+                        .trim_end_matches('=')
+                        .trim_end();
+                    match (value.kind(), value.child_by_field_name("body")) {
+                        ("arrow_function" | "function_expression", Some(body)) => format!(
+                            "{prefix} {binding} = {} ...;",
+                            get_slice(&value, &body, source)
+                        ),
+                        _ => format!("{prefix} {binding};"),
+                    }
+                }
+                None => format!("{prefix} {};", get_text(declarator, source)),
+            };
+            Some(Symbol {
+                name: get_text(&name, source).to_string(),
+                source_code: with_doc_comment(statement, rendered, source),
+            })
+        })
+        .collect()
+}
+
+fn render_class(statement: &Node, body: &Node, source: &[u8]) -> String {
+    let header = get_slice(statement, body, source);
+    let mut cursor = body.walk();
+    let members = body
+        .named_children(&mut cursor)
+        .filter_map(|member| render_class_member(&member, body, source))
+        .collect::<Vec<_>>();
+
+    if members.is_empty() {
+        format!("{header} {{}}")
+    } else {
+        format!("{header} {{\n{}\n}}", members.join("\n"))
+    }
+}
+
+fn render_class_member(member: &Node, body: &Node, source: &[u8]) -> Option<String> {
+    if !is_public_member(member, source) {
+        return None;
+    }
+    let rendered = match (member.kind(), member.child_by_field_name("body")) {
+        ("method_definition", Some(body)) => format!("{};", get_slice(member, &body, source)),
+        (
+            "field_definition"
+            | "public_field_definition"
+            | "method_signature"
+            | "abstract_method_signature"
+            | "index_signature",
+            _,
+        ) => format!("{};", get_text(member, source).trim_end_matches(';')),
+        _ => return None,
+    };
+
+    let indent = match member.start_position().row == body.start_position().row {
+        true => DEFAULT_MEMBER_INDENT.to_string(),
+        false => " ".repeat(member.start_position().column),
+    };
+    Some(match get_doc_comment(member, source) {
+        Some(doc_comment) => format!("{indent}{doc_comment}\n{indent}{rendered}"),
+        None => format!("{indent}{rendered}"),
+    })
+}
+
+fn is_public_member(member: &Node, source: &[u8]) -> bool {
+    let mut cursor = member.walk();
+    let is_private = member
+        .named_children(&mut cursor)
+        .any(|child| match child.kind() {
+            "accessibility_modifier" => get_text(&child, source) == "private",
+            "private_property_identifier" => true,
+            _ => false,
+        });
+    !is_private
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::languages::javascript::test_helpers::{
+        setup_javascript_parser, setup_typescript_parser,
+    };
+
+    fn parse_typescript(source_code: &str) -> Module {
+        parse_module(source_code, &mut setup_typescript_parser()).unwrap()
+    }
+
+    fn get_exported_symbol<'a>(module: &'a Module, name: &str) -> &'a Symbol {
+        module
+            .exports
+            .iter()
+            .find_map(|export| match export {
+                Export::Declaration(symbol) if symbol.name == name => Some(symbol),
+                _ => None,
+            })
+            .unwrap_or_else(|| panic!("Export {name} not found"))
+    }
+
+    mod doc_comment {
+        use super::*;
+
+        #[test]
+        fn package_documentation() {
+            let module =
+                parse_typescript("/** Package docs. @packageDocumentation */\nexport {};\n");
+
+            assert_eq!(
+                module.doc_comment,
+                Some("/** Package docs. @packageDocumentation */".to_string())
+            );
+        }
+
+        #[test]
+        fn symbol_doc_comment_not_module_doc_comment() {
+            let module = parse_typescript("/** Function docs. */\nexport function f(): void {}\n");
+
+            assert_eq!(module.doc_comment, None);
+        }
+
+        #[test]
+        fn symbol_doc_comment() {
+            let module = parse_typescript("/** Function docs. */\nexport function f(): void {}\n");
+
+            assert_eq!(
+                get_exported_symbol(&module, "f").source_code,
+                "/** Function docs. */\nexport function f(): void;"
+            );
+        }
+
+        #[test]
+        fn detached_comment_ignored() {
+            let module = parse_typescript("/** Unrelated. */\n\nexport function f(): void {}\n");
+
+            assert_eq!(
+                get_exported_symbol(&module, "f").source_code,
+                "export function f(): void;"
+            );
+        }
+    }
+
+    mod declarations {
+        use super::*;
+
+        #[test]
+        fn function() {
+            let module = parse_typescript("export function f<T>(x: T): T { return x; }\n");
+
+            assert_eq!(
+                get_exported_symbol(&module, "f").source_code,
+                "export function f<T>(x: T): T;"
+            );
+        }
+
+        #[test]
+        fn ambient_function() {
+            let module = parse_typescript("export declare function f(a: number): string;\n");
+
+            assert_eq!(
+                get_exported_symbol(&module, "f").source_code,
+                "export declare function f(a: number): string;"
+            );
+        }
+
+        #[test]
+        fn interface() {
+            let module = parse_typescript("export interface I { a: number }\n");
+
+            assert_eq!(
+                get_exported_symbol(&module, "I").source_code,
+                "export interface I { a: number }"
+            );
+        }
+
+        #[test]
+        fn type_alias() {
+            let module = parse_typescript("export type T = string | number;\n");
+
+            assert_eq!(
+                get_exported_symbol(&module, "T").source_code,
+                "export type T = string | number;"
+            );
+        }
+
+        #[test]
+        fn enumeration() {
+            let module = parse_typescript("export enum E { A, B }\n");
+
+            assert_eq!(
+                get_exported_symbol(&module, "E").source_code,
+                "export enum E { A, B }"
+            );
+        }
+
+        #[test]
+        fn constants() {
+            let module = parse_typescript("export const x: number = 1, y = 2;\n");
+
+            assert_eq!(
+                get_exported_symbol(&module, "x").source_code,
+                "export const x: number;"
+            );
+            assert_eq!(
+                get_exported_symbol(&module, "y").source_code,
+                "export const y;"
+            );
+        }
+
+        #[test]
+        fn arrow_function() {
+            let module = parse_typescript("export const h = (a: number): string => String(a);\n");
+
+            assert_eq!(
+                get_exported_symbol(&module, "h").source_code,
+                "export const h = (a: number): string => ...;"
+            );
+        }
+
+        #[test]
+        fn function_overloads() {
+            let module = parse_typescript(
+                "declare function f(a: string): void;\ndeclare function f(a: number): void;\n",
+            );
+
+            assert_eq!(
+                module.declarations["f"].source_code,
+                "declare function f(a: string): void;\ndeclare function f(a: number): void;"
+            );
+        }
+
+        #[test]
+        fn local_declaration() {
+            let module = parse_typescript("function helper(): void {}\n");
+
+            assert!(module.exports.is_empty());
+            assert_eq!(
+                module.declarations["helper"].source_code,
+                "function helper(): void;"
+            );
+        }
+    }
+
+    mod namespaces {
+        use super::*;
+
+        #[test]
+        fn ambient_namespace() {
+            let module = parse_typescript(
+                "declare namespace N {\n  interface Options { a: string }\n  const version: string;\n}\n",
+            );
+
+            let members = &module.namespaces["N"].members;
+            assert_eq!(members.len(), 2);
+            assert_eq!(members[0].name, "Options");
+            assert_eq!(members[0].source_code, "interface Options { a: string }");
+            assert_eq!(members[1].name, "version");
+            assert!(module.namespaces["N"]
+                .symbol
+                .source_code
+                .starts_with("declare namespace N"));
+        }
+
+        #[test]
+        fn non_ambient_namespace() {
+            let module = parse_typescript(
+                "namespace N {\n  export type T = string;\n  type Hidden = number;\n}\n",
+            );
+
+            let members = &module.namespaces["N"].members;
+            assert_eq!(members.len(), 1);
+            assert_eq!(members[0].name, "T");
+        }
+
+        #[test]
+        fn namespace_merged_with_function() {
+            let module = parse_typescript(
+                "declare namespace f {\n  const x: number;\n}\ndeclare function f(): void;\n",
+            );
+
+            assert_eq!(
+                module.declarations["f"].source_code,
+                "declare function f(): void;"
+            );
+            assert_eq!(module.namespaces["f"].members[0].name, "x");
+        }
+    }
+
+    mod classes {
+        use super::*;
+
+        #[test]
+        fn members() {
+            let module = parse_typescript(
+                r#"export class C<T> extends B {
+  /** Method docs. */
+  public m(a: string): void {}
+  private p: number;
+  #q = 1;
+  protected r?: string;
+  constructor(x: number) {}
+}
+"#,
+            );
+
+            assert_eq!(
+                get_exported_symbol(&module, "C").source_code,
+                r#"export class C<T> extends B {
+  /** Method docs. */
+  public m(a: string): void;
+  protected r?: string;
+  constructor(x: number);
+}"#
+            );
+        }
+
+        #[test]
+        fn ambient_class() {
+            let module =
+                parse_typescript("export declare class D {\n  m(): void;\n  private secret;\n}\n");
+
+            assert_eq!(
+                get_exported_symbol(&module, "D").source_code,
+                "export declare class D {\n  m(): void;\n}"
+            );
+        }
+
+        #[test]
+        fn empty_class() {
+            let module = parse_typescript("export class C { private x = 1; }\n");
+
+            assert_eq!(
+                get_exported_symbol(&module, "C").source_code,
+                "export class C {}"
+            );
+        }
+
+        #[test]
+        fn javascript_fields() {
+            let module = parse_module(
+                "export class C { x = 1; static #y; m(a) { return a } }\n",
+                &mut setup_javascript_parser(),
+            )
+            .unwrap();
+
+            assert_eq!(
+                get_exported_symbol(&module, "C").source_code,
+                "export class C {\n  x = 1;\n  m(a);\n}"
+            );
+        }
+    }
+
+    mod exports {
+        use super::*;
+
+        #[test]
+        fn default_declaration() {
+            let module = parse_typescript("export default function named(): void {}\n");
+
+            assert_eq!(
+                get_exported_symbol(&module, DEFAULT_EXPORT).source_code,
+                "export default function named(): void;"
+            );
+        }
+
+        #[test]
+        fn default_identifier() {
+            let module = parse_typescript("export default foo;\n");
+
+            assert_eq!(
+                module.exports,
+                vec![Export::Local {
+                    name: DEFAULT_EXPORT.to_string(),
+                    local_name: "foo".to_string(),
+                }]
+            );
+        }
+
+        #[test]
+        fn local_specifiers() {
+            let module = parse_typescript("export { a, b as c };\n");
+
+            assert_eq!(
+                module.exports,
+                vec![
+                    Export::Local {
+                        name: "a".to_string(),
+                        local_name: "a".to_string(),
+                    },
+                    Export::Local {
+                        name: "c".to_string(),
+                        local_name: "b".to_string(),
+                    },
+                ]
+            );
+        }
+
+        #[test]
+        fn named_reexport() {
+            let module = parse_typescript("export { b as c } from './mod';\n");
+
+            assert_eq!(
+                module.exports,
+                vec![Export::Named {
+                    name: "c".to_string(),
+                    imported_name: ImportedName {
+                        source: "./mod".to_string(),
+                        name: "b".to_string(),
+                    },
+                }]
+            );
+        }
+
+        #[test]
+        fn wildcard_reexport() {
+            let module = parse_typescript("export * from './other';\n");
+
+            assert_eq!(
+                module.exports,
+                vec![Export::All {
+                    source: "./other".to_string()
+                }]
+            );
+        }
+
+        #[test]
+        fn namespace_reexport() {
+            let module = parse_typescript("export * as ns from './ns';\n");
+
+            assert_eq!(
+                module.exports,
+                vec![Export::Namespace {
+                    name: "ns".to_string(),
+                    source: "./ns".to_string(),
+                }]
+            );
+        }
+
+        #[test]
+        fn export_assignment() {
+            let module = parse_typescript("export = Foo;\n");
+
+            assert_eq!(
+                module.exports,
+                vec![Export::Assignment {
+                    local_name: "Foo".to_string(),
+                }]
+            );
+        }
+    }
+
+    mod imports {
+        use super::*;
+
+        #[test]
+        fn named_imports() {
+            let module = parse_typescript("import { a, b as c } from './mod';\n");
+
+            assert_eq!(
+                module.imports["c"],
+                ImportedName {
+                    source: "./mod".to_string(),
+                    name: "b".to_string(),
+                }
+            );
+            assert!(module.imports.contains_key("a"));
+        }
+
+        #[test]
+        fn default_import() {
+            let module = parse_typescript("import thing from './thing';\n");
+
+            assert_eq!(module.imports["thing"].name, DEFAULT_EXPORT);
+        }
+    }
+}
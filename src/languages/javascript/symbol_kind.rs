@@ -0,0 +1,111 @@
+use crate::symbol_kind::{skip_comments_and_annotations, SymbolKind};
+
+const LINE_COMMENT: &str = "//";
+/// Words that may precede the keyword of a declaration (e.g. `export default abstract class`).
+const MODIFIERS: [&str; 5] = ["export", "default", "declare", "abstract", "async"];
+/// The placeholder that replaces the bodies of functions assigned to variables.
+const FUNCTION_BODY_PLACEHOLDER: &str = "...;";
+
+/// Detect the kind of the declaration in `source_code`, skipping its doc comment and decorators.
+pub fn detect_symbol_kind(source_code: &str) -> Option<SymbolKind> {
+    let declaration = skip_comments_and_annotations(source_code, LINE_COMMENT);
+    let mut words = declaration
+        .split(|character: char| character.is_whitespace() || matches!(character, '*' | '('))
+        .filter(|word| !word.is_empty())
+        .skip_while(|word| MODIFIERS.contains(word));
+    match words.next()? {
+        "function" => Some(SymbolKind::Function),
+        "class" => Some(SymbolKind::Class),
+        "interface" => Some(SymbolKind::Interface),
+        "type" => Some(SymbolKind::TypeAlias),
+        "enum" => Some(SymbolKind::Enum),
+        "const" if words.next() == Some("enum") => Some(SymbolKind::Enum),
+        "namespace" | "module" => Some(SymbolKind::Module),
+        // Arrow functions and function expressions are rendered with a placeholder body.
+        "const" | "let" | "var"
+            if declaration.contains('=')
+                && declaration.trim_end().ends_with(FUNCTION_BODY_PLACEHOLDER) =>
+        {
+            Some(SymbolKind::Function)
+        }
+        "const" | "let" | "var" => Some(SymbolKind::Constant),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn functions() {
+        for source_code in [
+            "/** Function docs. */\nexport function f(): void;",
+            "export declare function f(a: number): string;",
+            "export default function named(): void;",
+            "export async function* f();",
+            "function helper(): void;",
+            "export const h = (a: number): string => ...;",
+        ] {
+            assert_eq!(
+                detect_symbol_kind(source_code),
+                Some(SymbolKind::Function),
+                "{source_code}"
+            );
+        }
+    }
+
+    #[test]
+    fn types() {
+        assert_eq!(
+            detect_symbol_kind("@Component({ selector: \"app\" })\nexport class C {}"),
+            Some(SymbolKind::Class)
+        );
+        assert_eq!(
+            detect_symbol_kind("export declare abstract class D {\n  m(): void;\n}"),
+            Some(SymbolKind::Class)
+        );
+        assert_eq!(
+            detect_symbol_kind("export interface I { a: number }"),
+            Some(SymbolKind::Interface)
+        );
+        assert_eq!(
+            detect_symbol_kind("export type T = string | number;"),
+            Some(SymbolKind::TypeAlias)
+        );
+        assert_eq!(
+            detect_symbol_kind("export enum E { A, B }"),
+            Some(SymbolKind::Enum)
+        );
+        assert_eq!(
+            detect_symbol_kind("export declare const enum E { A }"),
+            Some(SymbolKind::Enum)
+        );
+    }
+
+    #[test]
+    fn variables() {
+        assert_eq!(
+            detect_symbol_kind("export const x: number;"),
+            Some(SymbolKind::Constant)
+        );
+        assert_eq!(
+            detect_symbol_kind("export let y;"),
+            Some(SymbolKind::Constant)
+        );
+    }
+
+    #[test]
+    fn namespaces() {
+        assert_eq!(
+            detect_symbol_kind("export declare namespace N {}"),
+            Some(SymbolKind::Module)
+        );
+    }
+
+    #[test]
+    fn unrecognised_statements() {
+        assert_eq!(detect_symbol_kind("export default 42;"), None);
+        assert_eq!(detect_symbol_kind(""), None);
+    }
+}
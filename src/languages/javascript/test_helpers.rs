@@ -0,0 +1,12 @@
+use daipendency_extractor::{get_parser, Extractor};
+use tree_sitter::Parser;
+
+use super::{JavaScriptExtractor, TypeScriptExtractor};
+
+pub fn setup_javascript_parser() -> Parser {
+    get_parser(&JavaScriptExtractor.get_parser_language()).unwrap()
+}
+
+pub fn setup_typescript_parser() -> Parser {
+    get_parser(&TypeScriptExtractor.get_parser_language()).unwrap()
+}
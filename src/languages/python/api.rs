@@ -0,0 +1,421 @@
+use super::parsing::{is_public_name, parse_module, Module};
+use daipendency_extractor::{ExtractionError, Namespace, Symbol};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+use tree_sitter::Parser;
+
+const PACKAGE_INIT_FILE: &str = "__init__.py";
+const MODULE_EXTENSION: &str = "py";
+const MAX_REEXPORT_DEPTH: usize = 8;
+
+/// Build the public API of the package or module at `entry_point`.
+pub fn build_public_api(
+    entry_point: &Path,
+    parser: &mut Parser,
+) -> Result<Vec<Namespace>, ExtractionError> {
+    let modules = collect_modules(entry_point, parser)?;
+
+    Ok(modules
+        .iter()
+        .filter(|(name, _)| name.split('.').all(is_public_name))
+        .map(|(name, module)| Namespace {
+            name: name.clone(),
+            symbols: get_exported_symbols(module, &modules),
+            doc_comment: module.doc_comment.clone(),
+        })
+        .collect())
+}
+
+fn collect_modules(
+    entry_point: &Path,
+    parser: &mut Parser,
+) -> Result<BTreeMap<String, Module>, ExtractionError> {
+    let mut modules = BTreeMap::new();
+    let is_package = entry_point.file_name() == Some(PACKAGE_INIT_FILE.as_ref());
+    let root_path = if is_package {
+        entry_point.parent().unwrap_or(entry_point)
+    } else {
+        entry_point
+    };
+    let root_name = root_path
+        .file_stem()
+        .and_then(|stem| stem.to_str())
+        .ok_or_else(|| {
+            ExtractionError::Malformed(format!("Invalid module path {}", root_path.display()))
+        })?;
+
+    if is_package {
+        collect_package_modules(root_path, root_name, parser, &mut modules)?;
+    } else {
+        let source_code = fs::read_to_string(entry_point)?;
+        modules.insert(
+            root_name.to_string(),
+            parse_module(&source_code, root_name, parser)?,
+        );
+    }
+    Ok(modules)
+}
+
+fn collect_package_modules(
+    directory: &Path,
+    package_name: &str,
+    parser: &mut Parser,
+    modules: &mut BTreeMap<String, Module>,
+) -> Result<(), ExtractionError> {
+    let init_source = fs::read_to_string(directory.join(PACKAGE_INIT_FILE))?;
+    modules.insert(
+        package_name.to_string(),
+        parse_module(&init_source, package_name, parser)?,
+    );
+
+    let mut entries = fs::read_dir(directory)?
+        .filter_map(Result::ok)
+        .map(|entry| entry.path())
+        .collect::<Vec<_>>();
+    entries.sort();
+    for path in entries {
+        let Some(stem) = path.file_stem().and_then(|stem| stem.to_str()) else {
+            continue;
+        };
+        let module_name = format!("{package_name}.{stem}");
+        if path.is_dir() {
+            if path.join(PACKAGE_INIT_FILE).is_file() {
+                collect_package_modules(&path, &module_name, parser, modules)?;
+            }
+        } else if path.extension() == Some(MODULE_EXTENSION.as_ref())
+            && path.file_name() != Some(PACKAGE_INIT_FILE.as_ref())
+        {
+            let source_code = fs::read_to_string(&path)?;
+            modules.insert(
+                module_name,
+                parse_module(&source_code, package_name, parser)?,
+            );
+        }
+    }
+    Ok(())
+}
+
+fn is_exported(module: &Module, name: &str) -> bool {
+    match &module.exports {
+        Some(exports) => exports.iter().any(|export| export == name),
+        None => is_public_name(name),
+    }
+}
+
+fn get_exported_symbols(module: &Module, modules: &BTreeMap<String, Module>) -> Vec<Symbol> {
+    let definitions = module
+        .definitions
+        .iter()
+        .filter(|symbol| is_exported(module, &symbol.name))
+        .cloned();
+    let variables = module
+        .variables
+        .iter()
+        .filter(|symbol| is_exported(module, &symbol.name))
+        .cloned();
+    let reexports = module
+        .imports
+        .iter()
+        .filter(|import| match &module.exports {
+            Some(exports) => exports.contains(&import.name),
+            None => import.is_explicit_reexport,
+        })
+        .filter_map(|import| {
+            let symbol = resolve_symbol(
+                &import.source_module,
+                &import.source_name,
+                modules,
+                MAX_REEXPORT_DEPTH,
+            )?;
+            Some(Symbol {
+                name: import.name.clone(),
+                source_code: symbol.source_code,
+            })
+        });
+
+    definitions.chain(variables).chain(reexports).collect()
+}
+
+/// Find the definition of `name` in `module_name`, following imports up to `depth` levels deep.
+fn resolve_symbol(
+    module_name: &str,
+    name: &str,
+    modules: &BTreeMap<String, Module>,
+    depth: usize,
+) -> Option<Symbol> {
+    let module = modules.get(module_name)?;
+    if let Some(symbol) = module
+        .definitions
+        .iter()
+        .chain(&module.variables)
+        .find(|symbol| symbol.name == name)
+    {
+        return Some(symbol.clone());
+    }
+    if depth == 0 {
+        return None;
+    }
+    let import = module.imports.iter().find(|import| import.name == name)?;
+    resolve_symbol(
+        &import.source_module,
+        &import.source_name,
+        modules,
+        depth - 1,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::languages::python::test_helpers::setup_parser;
+    use daipendency_testing::tempdir::TempDir;
+
+    const STUB_PACKAGE: &str = "package";
+
+    fn build(temp_dir: &TempDir, entry_point: &str) -> Vec<Namespace> {
+        build_public_api(&temp_dir.path.join(entry_point), &mut setup_parser()).unwrap()
+    }
+
+    fn get_namespace<'a>(namespaces: &'a [Namespace], name: &str) -> &'a Namespace {
+        namespaces
+            .iter()
+            .find(|namespace| namespace.name == name)
+            .unwrap_or_else(|| panic!("Namespace {name} not found"))
+    }
+
+    fn get_symbol_names(namespace: &Namespace) -> Vec<&str> {
+        namespace
+            .symbols
+            .iter()
+            .map(|symbol| symbol.name.as_str())
+            .collect()
+    }
+
+    #[test]
+    fn single_module() {
+        let temp_dir = TempDir::new();
+        temp_dir
+            .create_file("module.py", "def public(): pass\n")
+            .unwrap();
+
+        let namespaces = build(&temp_dir, "module.py");
+
+        assert_eq!(namespaces.len(), 1);
+        assert_eq!(get_symbol_names(&namespaces[0]), vec!["public"]);
+        assert_eq!(namespaces[0].name, "module");
+    }
+
+    #[test]
+    fn package_submodules() {
+        let temp_dir = TempDir::new();
+        temp_dir
+            .create_file("package/__init__.py", "def root(): pass\n")
+            .unwrap();
+        temp_dir
+            .create_file("package/sub.py", "def leaf(): pass\n")
+            .unwrap();
+        temp_dir
+            .create_file("package/nested/__init__.py", "class Nested: pass\n")
+            .unwrap();
+
+        let namespaces = build(&temp_dir, "package/__init__.py");
+
+        assert_eq!(
+            namespaces
+                .iter()
+                .map(|n| n.name.as_str())
+                .collect::<Vec<_>>(),
+            vec!["package", "package.nested", "package.sub"]
+        );
+        assert_eq!(
+            get_symbol_names(get_namespace(&namespaces, "package.sub")),
+            vec!["leaf"]
+        );
+    }
+
+    #[test]
+    fn directory_without_init_ignored() {
+        let temp_dir = TempDir::new();
+        temp_dir.create_file("package/__init__.py", "").unwrap();
+        temp_dir
+            .create_file("package/scripts/tool.py", "def main(): pass\n")
+            .unwrap();
+
+        let namespaces = build(&temp_dir, "package/__init__.py");
+
+        assert_eq!(namespaces.len(), 1);
+    }
+
+    #[test]
+    fn private_modules_omitted() {
+        let temp_dir = TempDir::new();
+        temp_dir.create_file("package/__init__.py", "").unwrap();
+        temp_dir
+            .create_file("package/_internal.py", "def helper(): pass\n")
+            .unwrap();
+
+        let namespaces = build(&temp_dir, "package/__init__.py");
+
+        assert_eq!(namespaces.len(), 1);
+        assert_eq!(namespaces[0].name, STUB_PACKAGE);
+    }
+
+    #[test]
+    fn module_doc_comment() {
+        let temp_dir = TempDir::new();
+        temp_dir
+            .create_file("package/__init__.py", "\"\"\"Package docs.\"\"\"\n")
+            .unwrap();
+
+        let namespaces = build(&temp_dir, "package/__init__.py");
+
+        assert_eq!(
+            namespaces[0].doc_comment,
+            Some("\"\"\"Package docs.\"\"\"".to_string())
+        );
+    }
+
+    mod privacy {
+        use super::*;
+
+        #[test]
+        fn underscore_names_omitted() {
+            let temp_dir = TempDir::new();
+            temp_dir
+                .create_file(
+                    "package/__init__.py",
+                    "def public(): pass\ndef _private(): pass\nclass _Hidden: pass\n",
+                )
+                .unwrap();
+
+            let namespaces = build(&temp_dir, "package/__init__.py");
+
+            assert_eq!(get_symbol_names(&namespaces[0]), vec!["public"]);
+        }
+
+        #[test]
+        fn public_variables_without_all() {
+            let temp_dir = TempDir::new();
+            temp_dir
+                .create_file(
+                    "package/__init__.py",
+                    "DEFAULT_TIMEOUT = 30\n_cache = {}\ndef public(): pass\n",
+                )
+                .unwrap();
+
+            let namespaces = build(&temp_dir, "package/__init__.py");
+
+            assert_eq!(
+                get_symbol_names(&namespaces[0]),
+                vec!["public", "DEFAULT_TIMEOUT"]
+            );
+        }
+
+        #[test]
+        fn all_restricts_definitions() {
+            let temp_dir = TempDir::new();
+            temp_dir
+                .create_file(
+                    "package/__init__.py",
+                    "__all__ = [\"listed\", \"VERSION\"]\nVERSION = \"1.0\"\ndef listed(): pass\ndef unlisted(): pass\n",
+                )
+                .unwrap();
+
+            let namespaces = build(&temp_dir, "package/__init__.py");
+
+            assert_eq!(get_symbol_names(&namespaces[0]), vec!["listed", "VERSION"]);
+        }
+    }
+
+    mod reexports {
+        use super::*;
+
+        fn create_private_implementation(temp_dir: &TempDir) {
+            temp_dir
+                .create_file("package/_impl.py", "class Client:\n    pass\n")
+                .unwrap();
+        }
+
+        #[test]
+        fn listed_in_all() {
+            let temp_dir = TempDir::new();
+            create_private_implementation(&temp_dir);
+            temp_dir
+                .create_file(
+                    "package/__init__.py",
+                    "from ._impl import Client\n__all__ = [\"Client\"]\n",
+                )
+                .unwrap();
+
+            let namespaces = build(&temp_dir, "package/__init__.py");
+
+            let root = get_namespace(&namespaces, STUB_PACKAGE);
+            assert_eq!(get_symbol_names(root), vec!["Client"]);
+            assert_eq!(root.symbols[0].source_code, "class Client: ...");
+        }
+
+        #[test]
+        fn explicit_alias() {
+            let temp_dir = TempDir::new();
+            create_private_implementation(&temp_dir);
+            temp_dir
+                .create_file(
+                    "package/__init__.py",
+                    "from ._impl import Client as Client\n",
+                )
+                .unwrap();
+
+            let namespaces = build(&temp_dir, "package/__init__.py");
+
+            assert_eq!(get_symbol_names(&namespaces[0]), vec!["Client"]);
+        }
+
+        #[test]
+        fn plain_import_not_reexported() {
+            let temp_dir = TempDir::new();
+            create_private_implementation(&temp_dir);
+            temp_dir
+                .create_file("package/__init__.py", "from ._impl import Client\n")
+                .unwrap();
+
+            let namespaces = build(&temp_dir, "package/__init__.py");
+
+            assert!(namespaces[0].symbols.is_empty());
+        }
+
+        #[test]
+        fn chained_reexport() {
+            let temp_dir = TempDir::new();
+            create_private_implementation(&temp_dir);
+            temp_dir
+                .create_file("package/_api.py", "from ._impl import Client\n")
+                .unwrap();
+            temp_dir
+                .create_file(
+                    "package/__init__.py",
+                    "from ._api import Client\n__all__ = [\"Client\"]\n",
+                )
+                .unwrap();
+
+            let namespaces = build(&temp_dir, "package/__init__.py");
+
+            assert_eq!(get_symbol_names(&namespaces[0]), vec!["Client"]);
+        }
+
+        #[test]
+        fn external_import_omitted() {
+            let temp_dir = TempDir::new();
+            temp_dir
+                .create_file(
+                    "package/__init__.py",
+                    "from typing import Any\n__all__ = [\"Any\"]\n",
+                )
+                .unwrap();
+
+            let namespaces = build(&temp_dir, "package/__init__.py");
+
+            assert!(namespaces[0].symbols.is_empty());
+        }
+    }
+}
@@ -0,0 +1,91 @@
+const STRING_PREFIX_CHARACTERS: [char; 8] = ['r', 'R', 'b', 'B', 'u', 'U', 'f', 'F'];
+
+/// Remove the `#` comments and the docstrings from `source_code`.
+///
+/// Docstrings are told apart from other strings by starting a line outside brackets, since strings
+/// used as values follow an `=`, a bracket or a keyword.
+pub fn strip_comments(source_code: &str) -> String {
+    let mut output = String::with_capacity(source_code.len());
+    let mut depth = 0_usize;
+    let mut characters = source_code.chars().peekable();
+    while let Some(character) = characters.next() {
+        match character {
+            '#' => while characters.next_if(|next| *next != '\n').is_some() {},
+            '(' | '[' | '{' => {
+                depth += 1;
+                output.push(character);
+            }
+            ')' | ']' | '}' => {
+                depth = depth.saturating_sub(1);
+                output.push(character);
+            }
+            '"' | '\'' => {
+                let is_triple_quoted = characters.clone().take(2).all(|next| next == character);
+                let mut literal = character.to_string();
+                if is_triple_quoted {
+                    literal.extend(characters.by_ref().take(2));
+                }
+                let mut closing_quote_count = 0;
+                let closing_quote_length = literal.len();
+                while closing_quote_count < closing_quote_length {
+                    let Some(next) = characters.next() else {
+                        break;
+                    };
+                    literal.push(next);
+                    match next {
+                        '\\' => literal.extend(characters.next()),
+                        next if next == character => closing_quote_count += 1,
+                        '\n' if !is_triple_quoted => break,
+                        _ => closing_quote_count = 0,
+                    }
+                }
+
+                let line_start = output.rfind('\n').map_or(0, |index| index + 1);
+                let line = output[line_start..].trim_start();
+                let is_docstring =
+                    depth == 0 && line.chars().all(|c| STRING_PREFIX_CHARACTERS.contains(&c));
+                if is_docstring {
+                    output.truncate(output.len() - line.len());
+                } else {
+                    output.push_str(&literal);
+                }
+            }
+            _ => output.push(character),
+        }
+    }
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn line_comments() {
+        assert_eq!(
+            strip_comments("# Leading\ndef f(x):  # Trailing\n    ..."),
+            "\ndef f(x):  \n    ..."
+        );
+    }
+
+    #[test]
+    fn docstrings() {
+        assert_eq!(
+            strip_comments(
+                "def f():\n    \"\"\"Docs with \"quotes\".\n\n    More docs.\n    \"\"\"\n    ..."
+            ),
+            "def f():\n    \n    ..."
+        );
+        assert_eq!(
+            strip_comments("class C:\n    r'''Raw docs.'''\n"),
+            "class C:\n    \n"
+        );
+    }
+
+    #[test]
+    fn string_values() {
+        let source_code = "GREETING = \"# Hello\"\nQUERY = \"\"\"\nSELECT 1\n\"\"\"\n__all__ = [\n    \"f\",\n]\ndef f(x='\\''): ...";
+
+        assert_eq!(strip_comments(source_code), source_code);
+    }
+}
@@ -0,0 +1,136 @@
+use super::metadata::extract_dependency_names;
+use daipendency_extractor::DependencyResolutionError;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const VIRTUAL_ENV_DIRECTORIES: [&str; 3] = [".venv", "venv", "env"];
+const DIST_INFO_SUFFIX: &str = ".dist-info";
+
+/// Resolve the `.dist-info` directory of a dependency installed in the dependant's virtual environment.
+pub fn resolve_dependency_path(
+    dependency_name: &str,
+    dependant_path: &Path,
+) -> Result<PathBuf, DependencyResolutionError> {
+    let site_packages_directories = find_site_packages(dependant_path);
+    if site_packages_directories.is_empty() {
+        return Err(DependencyResolutionError::RetrievalFailure(format!(
+            "No virtual environment found in {}",
+            dependant_path.display()
+        )));
+    }
+
+    let normalised_name = normalise_name(dependency_name);
+    site_packages_directories
+        .iter()
+        .filter_map(|site_packages| fs::read_dir(site_packages).ok())
+        .flatten()
+        .filter_map(Result::ok)
+        .map(|entry| entry.path())
+        .find(|path| {
+            path.file_name()
+                .and_then(|file_name| file_name.to_str())
+                .and_then(|file_name| file_name.strip_suffix(DIST_INFO_SUFFIX))
+                .and_then(|stem| stem.split('-').next())
+                .is_some_and(|name| normalise_name(name) == normalised_name)
+        })
+        .ok_or_else(|| DependencyResolutionError::MissingDependency(dependency_name.to_string()))
+}
+
+/// List the distributions required by the package at `path`.
+pub fn list_dependencies(path: &Path) -> Result<Vec<String>, DependencyResolutionError> {
+    extract_dependency_names(path)
+        .map_err(|e| DependencyResolutionError::RetrievalFailure(e.to_string()))
+}
+
+fn find_site_packages(dependant_path: &Path) -> Vec<PathBuf> {
+    VIRTUAL_ENV_DIRECTORIES
+        .iter()
+        .map(|directory| dependant_path.join(directory))
+        .filter(|virtual_env| virtual_env.is_dir())
+        .flat_map(|virtual_env| {
+            let windows_site_packages = virtual_env.join("Lib").join("site-packages");
+            let posix_site_packages = fs::read_dir(virtual_env.join("lib"))
+                .into_iter()
+                .flatten()
+                .filter_map(Result::ok)
+                .map(|entry| entry.path().join("site-packages"));
+            posix_site_packages.chain(std::iter::once(windows_site_packages))
+        })
+        .filter(|site_packages| site_packages.is_dir())
+        .collect()
+}
+
+/// Normalise a distribution name as per PEP 503.
+fn normalise_name(name: &str) -> String {
+    name.to_lowercase()
+        .split(['-', '_', '.'])
+        .filter(|part| !part.is_empty())
+        .collect::<Vec<_>>()
+        .join("-")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use assertables::assert_matches;
+    use daipendency_testing::tempdir::TempDir;
+
+    const SITE_PACKAGES_PATH: &str = ".venv/lib/python3.12/site-packages";
+
+    #[test]
+    fn installed_dependency() {
+        let temp_dir = TempDir::new();
+        let metadata_path = temp_dir
+            .create_file(
+                &format!("{SITE_PACKAGES_PATH}/typing_extensions-4.12.2.dist-info/METADATA"),
+                "",
+            )
+            .unwrap();
+
+        let result = resolve_dependency_path("Typing-Extensions", &temp_dir.path).unwrap();
+
+        assert_eq!(result, metadata_path.parent().unwrap());
+    }
+
+    #[test]
+    fn windows_virtual_env() {
+        let temp_dir = TempDir::new();
+        let metadata_path = temp_dir
+            .create_file(
+                "venv/Lib/site-packages/requests-2.0.0.dist-info/METADATA",
+                "",
+            )
+            .unwrap();
+
+        let result = resolve_dependency_path("requests", &temp_dir.path).unwrap();
+
+        assert_eq!(result, metadata_path.parent().unwrap());
+    }
+
+    #[test]
+    fn missing_dependency() {
+        let temp_dir = TempDir::new();
+        temp_dir
+            .create_file(
+                &format!("{SITE_PACKAGES_PATH}/requests-2.0.0.dist-info/METADATA"),
+                "",
+            )
+            .unwrap();
+
+        let result = resolve_dependency_path("httpx", &temp_dir.path);
+
+        assert_matches!(
+            result,
+            Err(DependencyResolutionError::MissingDependency(name)) if name == "httpx"
+        );
+    }
+
+    #[test]
+    fn missing_virtual_env() {
+        let temp_dir = TempDir::new();
+
+        let result = resolve_dependency_path("requests", &temp_dir.path);
+
+        assert_matches!(result, Err(DependencyResolutionError::RetrievalFailure(_)));
+    }
+}
@@ -0,0 +1,628 @@
+use daipendency_extractor::{LibraryMetadata, LibraryMetadataError};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const PYPROJECT_PATH: &str = "pyproject.toml";
+const SETUP_CFG_PATH: &str = "setup.cfg";
+const DIST_INFO_METADATA_PATH: &str = "METADATA";
+const DIST_INFO_TOP_LEVEL_PATH: &str = "top_level.txt";
+const README_PATHS: [&str; 4] = ["README.md", "README.rst", "README.txt", "README"];
+
+const POETRY_PYTHON_DEPENDENCY: &str = "python";
+
+struct PackageConfig {
+    name: String,
+    version: Option<String>,
+    readme: Option<String>,
+    dependencies: Vec<String>,
+}
+
+pub fn extract_metadata(path: &Path) -> Result<LibraryMetadata, LibraryMetadataError> {
+    if path.join(DIST_INFO_METADATA_PATH).is_file() {
+        return extract_dist_info_metadata(path);
+    }
+
+    let config = read_package_config(path)?;
+
+    let documentation = config
+        .readme
+        .map(|readme| path.join(readme))
+        .into_iter()
+        .chain(README_PATHS.iter().map(|readme| path.join(readme)))
+        .find_map(|readme_path| fs::read_to_string(readme_path).ok())
+        .unwrap_or_default();
+    let entry_point = find_entry_point(path, &get_import_name(&config.name));
+
+    Ok(LibraryMetadata {
+        name: config.name,
+        version: config.version,
+        documentation,
+        entry_point,
+    })
+}
+
+/// Extract the names of the distributions the package requires, excluding optional ones.
+pub fn extract_dependency_names(path: &Path) -> Result<Vec<String>, LibraryMetadataError> {
+    if path.join(DIST_INFO_METADATA_PATH).is_file() {
+        let content = fs::read_to_string(path.join(DIST_INFO_METADATA_PATH))
+            .map_err(LibraryMetadataError::MissingManifest)?;
+        return Ok(parse_requires_dist(&content));
+    }
+    Ok(read_package_config(path)?.dependencies)
+}
+
+/// Read the package configuration from `pyproject.toml`, falling back to `setup.cfg` if the former
+/// is missing or only configures the build system (as in most setuptools projects).
+fn read_package_config(path: &Path) -> Result<PackageConfig, LibraryMetadataError> {
+    let pyproject_error = match fs::read_to_string(path.join(PYPROJECT_PATH)) {
+        Ok(content) => match parse_pyproject(&content)? {
+            Some(config) => return Ok(config),
+            None => None,
+        },
+        Err(error) => Some(error),
+    };
+    match fs::read_to_string(path.join(SETUP_CFG_PATH)) {
+        Ok(content) => parse_setup_cfg(&content),
+        Err(_) => Err(match pyproject_error {
+            Some(error) => LibraryMetadataError::MissingManifest(error),
+            None => LibraryMetadataError::MalformedManifest(
+                "Missing [project] table in pyproject.toml and no setup.cfg".to_string(),
+            ),
+        }),
+    }
+}
+
+/// Parse the `[project]` or `[tool.poetry]` table, or return `None` if there is neither.
+fn parse_pyproject(content: &str) -> Result<Option<PackageConfig>, LibraryMetadataError> {
+    let pyproject: toml::Table = toml::from_str(content)
+        .map_err(|e| LibraryMetadataError::MalformedManifest(format!("{}", e)))?;
+
+    let poetry = pyproject
+        .get("tool")
+        .and_then(|tool| tool.get("poetry"))
+        .and_then(|poetry| poetry.as_table());
+    let Some(project) = pyproject
+        .get("project")
+        .and_then(|project| project.as_table())
+        .or(poetry)
+    else {
+        return Ok(None);
+    };
+
+    let name = project
+        .get("name")
+        .and_then(|name| name.as_str())
+        .ok_or_else(|| LibraryMetadataError::MalformedManifest("Missing project name".to_string()))?
+        .to_string();
+    let version = project
+        .get("version")
+        .and_then(|version| version.as_str())
+        .map(String::from);
+    let readme = project.get("readme").and_then(|readme| match readme {
+        toml::Value::String(path) => Some(path.clone()),
+        toml::Value::Table(table) => table.get("file")?.as_str().map(String::from),
+        _ => None,
+    });
+    let dependencies = match project.get("dependencies") {
+        Some(toml::Value::Array(requirements)) => requirements
+            .iter()
+            .filter_map(toml::Value::as_str)
+            .map(get_requirement_name)
+            .collect(),
+        Some(toml::Value::Table(poetry_dependencies)) => poetry_dependencies
+            .keys()
+            .filter(|name| *name != POETRY_PYTHON_DEPENDENCY)
+            .cloned()
+            .collect(),
+        _ => Vec::new(),
+    };
+
+    Ok(Some(PackageConfig {
+        name,
+        version,
+        readme,
+        dependencies,
+    }))
+}
+
+fn parse_setup_cfg(content: &str) -> Result<PackageConfig, LibraryMetadataError> {
+    let metadata = parse_ini_section(content, "metadata");
+
+    let name = metadata.get("name").cloned().ok_or_else(|| {
+        LibraryMetadataError::MalformedManifest("Missing name in [metadata] section".to_string())
+    })?;
+    let readme = metadata
+        .get("long_description")
+        .and_then(|description| description.strip_prefix("file:"))
+        .map(|path| path.trim().to_string());
+
+    let dependencies = parse_ini_section(content, "options")
+        .get("install_requires")
+        .map(|requirements| {
+            requirements
+                .lines()
+                .filter(|requirement| !requirement.is_empty())
+                .map(get_requirement_name)
+                .collect()
+        })
+        .unwrap_or_default();
+
+    Ok(PackageConfig {
+        name,
+        version: metadata.get("version").cloned(),
+        readme,
+        dependencies,
+    })
+}
+
+/// Parse the key-value pairs in an INI section, where indented lines continue the previous value.
+fn parse_ini_section(content: &str, section_name: &str) -> HashMap<String, String> {
+    let mut values: HashMap<String, String> = HashMap::new();
+    let mut in_section = false;
+    let mut current_key = None;
+    for raw_line in content.lines() {
+        let line = raw_line.trim();
+        if line.is_empty() || line.starts_with('#') || line.starts_with(';') {
+            continue;
+        }
+        if let Some(header) = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+            in_section = header.trim() == section_name;
+            current_key = None;
+        } else if in_section {
+            if let Some(value) = current_key
+                .as_ref()
+                .filter(|_| raw_line.starts_with(char::is_whitespace))
+                .and_then(|key| values.get_mut(key))
+            {
+                if !value.is_empty() {
+                    value.push('\n');
+                }
+                value.push_str(line);
+            } else if let Some((key, value)) = line.split_once('=') {
+                let key = key.trim().to_string();
+                values.insert(key.clone(), value.trim().to_string());
+                current_key = Some(key);
+            }
+        }
+    }
+    values
+}
+
+/// Get the names in the `Requires-Dist` headers of a `METADATA` file, skipping extras.
+fn parse_requires_dist(content: &str) -> Vec<String> {
+    let headers = content.split("\n\n").next().unwrap_or_default();
+    headers
+        .lines()
+        .filter_map(|line| line.strip_prefix("Requires-Dist:"))
+        .filter(|requirement| !requirement.contains("extra =="))
+        .map(get_requirement_name)
+        .collect()
+}
+
+/// Get the distribution name in a PEP 508 requirement such as `requests[socks]>=2.0`.
+fn get_requirement_name(requirement: &str) -> String {
+    requirement
+        .trim()
+        .split(|c: char| !(c.is_alphanumeric() || matches!(c, '-' | '_' | '.')))
+        .next()
+        .unwrap_or_default()
+        .to_string()
+}
+
+fn extract_dist_info_metadata(path: &Path) -> Result<LibraryMetadata, LibraryMetadataError> {
+    let content = fs::read_to_string(path.join(DIST_INFO_METADATA_PATH))
+        .map_err(LibraryMetadataError::MissingManifest)?;
+    let (headers, body) = content.split_once("\n\n").unwrap_or((&content, ""));
+
+    let mut name = None;
+    let mut version = None;
+    for line in headers.lines() {
+        if let Some((key, value)) = line.split_once(':') {
+            match key {
+                "Name" => name = Some(value.trim().to_string()),
+                "Version" => version = Some(value.trim().to_string()),
+                _ => {}
+            }
+        }
+    }
+    let name = name.ok_or_else(|| {
+        LibraryMetadataError::MalformedManifest("Missing Name in METADATA".to_string())
+    })?;
+
+    let site_packages = path.parent().unwrap_or(path);
+    let import_name = fs::read_to_string(path.join(DIST_INFO_TOP_LEVEL_PATH))
+        .ok()
+        .and_then(|top_level| top_level.lines().next().map(|l| l.trim().to_string()))
+        .filter(|top_level| !top_level.is_empty())
+        .unwrap_or_else(|| get_import_name(&name));
+
+    Ok(LibraryMetadata {
+        entry_point: find_entry_point(site_packages, &import_name),
+        name,
+        version,
+        documentation: body.trim().to_string(),
+    })
+}
+
+/// Convert a distribution name (e.g. `python-dateutil`) to its conventional import name.
+fn get_import_name(distribution_name: &str) -> String {
+    distribution_name.to_lowercase().replace(['-', '.'], "_")
+}
+
+fn find_entry_point(path: &Path, import_name: &str) -> PathBuf {
+    let package_init = Path::new(import_name).join("__init__.py");
+    let module = format!("{}.py", import_name);
+    [
+        path.join("src").join(&package_init),
+        path.join(&package_init),
+        path.join("src").join(&module),
+        path.join(&module),
+    ]
+    .into_iter()
+    .find(|candidate| candidate.is_file())
+    .unwrap_or_else(|| path.join(package_init))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use assertables::assert_matches;
+    use daipendency_testing::tempdir::TempDir;
+
+    const STUB_NAME: &str = "test-package";
+    const STUB_IMPORT_NAME: &str = "test_package";
+    const STUB_VERSION: &str = "1.0.0";
+    const STUB_DOCUMENTATION: &str = "Test documentation";
+
+    mod pyproject {
+        use super::*;
+
+        #[test]
+        fn project_table() {
+            let temp_dir = TempDir::new();
+            temp_dir
+                .create_file(
+                    PYPROJECT_PATH,
+                    &format!("[project]\nname = \"{STUB_NAME}\"\nversion = \"{STUB_VERSION}\"\n"),
+                )
+                .unwrap();
+
+            let metadata = extract_metadata(&temp_dir.path).unwrap();
+
+            assert_eq!(metadata.name, STUB_NAME);
+            assert_eq!(metadata.version, Some(STUB_VERSION.to_string()));
+        }
+
+        #[test]
+        fn poetry_table() {
+            let temp_dir = TempDir::new();
+            temp_dir
+                .create_file(
+                    PYPROJECT_PATH,
+                    &format!(
+                        "[tool.poetry]\nname = \"{STUB_NAME}\"\nversion = \"{STUB_VERSION}\"\n"
+                    ),
+                )
+                .unwrap();
+
+            let metadata = extract_metadata(&temp_dir.path).unwrap();
+
+            assert_eq!(metadata.name, STUB_NAME);
+            assert_eq!(metadata.version, Some(STUB_VERSION.to_string()));
+        }
+
+        #[test]
+        fn dynamic_version() {
+            let temp_dir = TempDir::new();
+            temp_dir
+                .create_file(
+                    PYPROJECT_PATH,
+                    &format!("[project]\nname = \"{STUB_NAME}\"\ndynamic = [\"version\"]\n"),
+                )
+                .unwrap();
+
+            let metadata = extract_metadata(&temp_dir.path).unwrap();
+
+            assert_eq!(metadata.version, None);
+        }
+
+        #[test]
+        fn build_system_only() {
+            let temp_dir = TempDir::new();
+            temp_dir
+                .create_file(PYPROJECT_PATH, "[build-system]\nrequires = []\n")
+                .unwrap();
+            temp_dir
+                .create_file(
+                    SETUP_CFG_PATH,
+                    &format!("[metadata]\nname = {STUB_NAME}\nversion = {STUB_VERSION}\n"),
+                )
+                .unwrap();
+
+            let metadata = extract_metadata(&temp_dir.path).unwrap();
+
+            assert_eq!(metadata.name, STUB_NAME);
+            assert_eq!(metadata.version, Some(STUB_VERSION.to_string()));
+        }
+
+        #[test]
+        fn build_system_only_without_setup_cfg() {
+            let temp_dir = TempDir::new();
+            temp_dir
+                .create_file(PYPROJECT_PATH, "[build-system]\nrequires = []\n")
+                .unwrap();
+
+            let result = extract_metadata(&temp_dir.path);
+
+            assert_matches!(result, Err(LibraryMetadataError::MalformedManifest(_)));
+        }
+
+        #[test]
+        fn invalid_toml() {
+            let temp_dir = TempDir::new();
+            temp_dir
+                .create_file(PYPROJECT_PATH, "invalid toml")
+                .unwrap();
+
+            let result = extract_metadata(&temp_dir.path);
+
+            assert_matches!(result, Err(LibraryMetadataError::MalformedManifest(_)));
+        }
+
+        #[test]
+        fn custom_readme() {
+            let temp_dir = TempDir::new();
+            temp_dir
+                .create_file(
+                    PYPROJECT_PATH,
+                    &format!("[project]\nname = \"{STUB_NAME}\"\nreadme = \"docs/intro.md\"\n"),
+                )
+                .unwrap();
+            temp_dir
+                .create_file("docs/intro.md", STUB_DOCUMENTATION)
+                .unwrap();
+
+            let metadata = extract_metadata(&temp_dir.path).unwrap();
+
+            assert_eq!(metadata.documentation, STUB_DOCUMENTATION);
+        }
+
+        #[test]
+        fn default_readme() {
+            let temp_dir = TempDir::new();
+            temp_dir
+                .create_file(
+                    PYPROJECT_PATH,
+                    &format!("[project]\nname = \"{STUB_NAME}\"\n"),
+                )
+                .unwrap();
+            temp_dir
+                .create_file("README.rst", STUB_DOCUMENTATION)
+                .unwrap();
+
+            let metadata = extract_metadata(&temp_dir.path).unwrap();
+
+            assert_eq!(metadata.documentation, STUB_DOCUMENTATION);
+        }
+    }
+
+    mod setup_cfg {
+        use super::*;
+
+        #[test]
+        fn metadata_section() {
+            let temp_dir = TempDir::new();
+            temp_dir
+                .create_file(
+                    SETUP_CFG_PATH,
+                    &format!(
+                        "[metadata]\nname = {STUB_NAME}\nversion = {STUB_VERSION}\n\n[options]\nname = other\n"
+                    ),
+                )
+                .unwrap();
+
+            let metadata = extract_metadata(&temp_dir.path).unwrap();
+
+            assert_eq!(metadata.name, STUB_NAME);
+            assert_eq!(metadata.version, Some(STUB_VERSION.to_string()));
+        }
+
+        #[test]
+        fn long_description_file() {
+            let temp_dir = TempDir::new();
+            temp_dir
+                .create_file(
+                    SETUP_CFG_PATH,
+                    &format!(
+                        "[metadata]\nname = {STUB_NAME}\nlong_description = file: DESCRIPTION.md\n"
+                    ),
+                )
+                .unwrap();
+            temp_dir
+                .create_file("DESCRIPTION.md", STUB_DOCUMENTATION)
+                .unwrap();
+
+            let metadata = extract_metadata(&temp_dir.path).unwrap();
+
+            assert_eq!(metadata.documentation, STUB_DOCUMENTATION);
+        }
+
+        #[test]
+        fn missing_name() {
+            let temp_dir = TempDir::new();
+            temp_dir
+                .create_file(SETUP_CFG_PATH, "[metadata]\nversion = 1.0.0\n")
+                .unwrap();
+
+            let result = extract_metadata(&temp_dir.path);
+
+            assert_matches!(result, Err(LibraryMetadataError::MalformedManifest(_)));
+        }
+    }
+
+    mod dist_info {
+        use super::*;
+
+        #[test]
+        fn metadata_file() {
+            let temp_dir = TempDir::new();
+            let dist_info = temp_dir
+                .create_file(
+                    "test_package-1.0.0.dist-info/METADATA",
+                    &format!(
+                        "Metadata-Version: 2.1\nName: {STUB_NAME}\nVersion: {STUB_VERSION}\n\n{STUB_DOCUMENTATION}\n"
+                    ),
+                )
+                .unwrap();
+
+            let metadata = extract_metadata(dist_info.parent().unwrap()).unwrap();
+
+            assert_eq!(metadata.name, STUB_NAME);
+            assert_eq!(metadata.version, Some(STUB_VERSION.to_string()));
+            assert_eq!(metadata.documentation, STUB_DOCUMENTATION);
+            assert_eq!(
+                metadata.entry_point,
+                temp_dir.path.join(STUB_IMPORT_NAME).join("__init__.py")
+            );
+        }
+
+        #[test]
+        fn top_level_file() {
+            let temp_dir = TempDir::new();
+            let dist_info = temp_dir
+                .create_file(
+                    "test_package-1.0.0.dist-info/METADATA",
+                    &format!("Name: {STUB_NAME}\n"),
+                )
+                .unwrap();
+            temp_dir
+                .create_file("test_package-1.0.0.dist-info/top_level.txt", "other\n")
+                .unwrap();
+            temp_dir.create_file("other.py", "").unwrap();
+
+            let metadata = extract_metadata(dist_info.parent().unwrap()).unwrap();
+
+            assert_eq!(metadata.entry_point, temp_dir.path.join("other.py"));
+        }
+    }
+
+    #[test]
+    fn missing_manifest() {
+        let temp_dir = TempDir::new();
+
+        let result = extract_metadata(&temp_dir.path);
+
+        assert_matches!(result, Err(LibraryMetadataError::MissingManifest(_)));
+    }
+
+    mod entry_point {
+        use super::*;
+
+        fn create_manifest(temp_dir: &TempDir) {
+            temp_dir
+                .create_file(
+                    PYPROJECT_PATH,
+                    &format!("[project]\nname = \"{STUB_NAME}\"\n"),
+                )
+                .unwrap();
+        }
+
+        #[test]
+        fn flat_package() {
+            let temp_dir = TempDir::new();
+            create_manifest(&temp_dir);
+            let init = temp_dir
+                .create_file("test_package/__init__.py", "")
+                .unwrap();
+
+            let metadata = extract_metadata(&temp_dir.path).unwrap();
+
+            assert_eq!(metadata.entry_point, init);
+        }
+
+        #[test]
+        fn src_layout_package() {
+            let temp_dir = TempDir::new();
+            create_manifest(&temp_dir);
+            let init = temp_dir
+                .create_file("src/test_package/__init__.py", "")
+                .unwrap();
+
+            let metadata = extract_metadata(&temp_dir.path).unwrap();
+
+            assert_eq!(metadata.entry_point, init);
+        }
+
+        #[test]
+        fn single_module() {
+            let temp_dir = TempDir::new();
+            create_manifest(&temp_dir);
+            let module = temp_dir.create_file("test_package.py", "").unwrap();
+
+            let metadata = extract_metadata(&temp_dir.path).unwrap();
+
+            assert_eq!(metadata.entry_point, module);
+        }
+    }
+
+    mod dependency_names {
+        use super::*;
+
+        fn extract_from(file_path: &str, content: &str) -> Vec<String> {
+            let temp_dir = TempDir::new();
+            let path = temp_dir.create_file(file_path, content).unwrap();
+            extract_dependency_names(path.parent().unwrap()).unwrap()
+        }
+
+        #[test]
+        fn project_dependencies() {
+            let names = extract_from(
+                PYPROJECT_PATH,
+                &format!("[project]\nname = \"{STUB_NAME}\"\ndependencies = [\"requests[socks]>=2.0\", \"attrs\"]\n"),
+            );
+
+            assert_eq!(names, vec!["requests", "attrs"]);
+        }
+
+        #[test]
+        fn poetry_dependencies() {
+            let names = extract_from(
+                PYPROJECT_PATH,
+                &format!("[tool.poetry]\nname = \"{STUB_NAME}\"\n\n[tool.poetry.dependencies]\npython = \"^3.9\"\nhttpx = \"^0.27\"\n"),
+            );
+
+            assert_eq!(names, vec!["httpx"]);
+        }
+
+        #[test]
+        fn setup_cfg_dependencies() {
+            let names = extract_from(
+                SETUP_CFG_PATH,
+                &format!("[metadata]\nname = {STUB_NAME}\n\n[options]\ninstall_requires =\n    click>=8\n    rich\n"),
+            );
+
+            assert_eq!(names, vec!["click", "rich"]);
+        }
+
+        #[test]
+        fn requires_dist() {
+            let names = extract_from(
+                "test_package-1.0.0.dist-info/METADATA",
+                &format!("Name: {STUB_NAME}\nRequires-Dist: idna (>=2.5)\nRequires-Dist: PySocks!=1.5.7 ; extra == 'socks'\n\nRequires-Dist: not-a-header\n"),
+            );
+
+            assert_eq!(names, vec!["idna"]);
+        }
+
+        #[test]
+        fn no_dependencies() {
+            let names = extract_from(
+                PYPROJECT_PATH,
+                &format!("[project]\nname = \"{STUB_NAME}\"\n"),
+            );
+
+            assert!(names.is_empty());
+        }
+    }
+}
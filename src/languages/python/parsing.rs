@@ -0,0 +1,553 @@
+use daipendency_extractor::{ExtractionError, Symbol};
+use tree_sitter::{Node, Parser};
+
+const ALL_VARIABLE: &str = "__all__";
+
+/// The public-facing contents of a Python module.
+#[derive(Debug)]
+pub struct Module {
+    pub doc_comment: Option<String>,
+    pub definitions: Vec<Symbol>,
+    pub variables: Vec<Symbol>,
+    pub exports: Option<Vec<String>>,
+    pub imports: Vec<Import>,
+}
+
+/// A name imported with `from module import name`.
+#[derive(Debug, PartialEq)]
+pub struct Import {
+    pub name: String,
+    pub source_module: String,
+    pub source_name: String,
+    /// Whether the import uses the `import name as name` re-export idiom.
+    pub is_explicit_reexport: bool,
+}
+
+/// Parse a module, resolving relative imports against `package`.
+pub fn parse_module(
+    source_code: &str,
+    package: &str,
+    parser: &mut Parser,
+) -> Result<Module, ExtractionError> {
+    let tree = parser
+        .parse(source_code, None)
+        .ok_or_else(|| ExtractionError::Malformed("Failed to parse Python module".to_string()))?;
+    let root = tree.root_node();
+    let source = source_code.as_bytes();
+
+    let mut definitions = Vec::new();
+    let mut variables = Vec::new();
+    let mut exports: Option<Vec<String>> = None;
+    let mut imports = Vec::new();
+    let mut cursor = root.walk();
+    for node in root.named_children(&mut cursor) {
+        match node.kind() {
+            "function_definition" | "class_definition" | "decorated_definition" => {
+                if let Some(name) = get_definition_name(&node, source) {
+                    definitions.push(Symbol {
+                        name,
+                        source_code: render_definition(&node, source),
+                    });
+                }
+            }
+            "expression_statement" => {
+                let Some(statement) = node.named_child(0) else {
+                    continue;
+                };
+                let Some(name) = get_assignment_target(&statement, source) else {
+                    continue;
+                };
+                if name == ALL_VARIABLE {
+                    let names = statement
+                        .child_by_field_name("right")
+                        .map(|value| get_string_items(&value, source))
+                        .unwrap_or_default();
+                    match statement.kind() {
+                        "augmented_assignment" => {
+                            exports.get_or_insert_with(Vec::new).extend(names)
+                        }
+                        _ => exports = Some(names),
+                    }
+                } else if statement.kind() == "assignment" {
+                    // Only the last assignment to a variable is visible to importers.
+                    variables.retain(|variable: &Symbol| variable.name != name);
+                    variables.push(Symbol {
+                        name,
+                        source_code: get_text(&node, source).to_string(),
+                    });
+                }
+            }
+            "import_from_statement" => imports.extend(parse_import(&node, package, source)),
+            _ => {}
+        }
+    }
+
+    Ok(Module {
+        doc_comment: get_docstring(&root, source),
+        definitions,
+        variables,
+        exports,
+        imports,
+    })
+}
+
+/// Whether a name is public by Python convention.
+pub fn is_public_name(name: &str) -> bool {
+    !name.starts_with('_') || (name.starts_with("__") && name.ends_with("__"))
+}
+
+fn get_text<'a>(node: &Node, source: &'a [u8]) -> &'a str {
+    node.utf8_text(source).unwrap_or_default()
+}
+
+fn get_definition_name(node: &Node, source: &[u8]) -> Option<String> {
+    let definition = match node.kind() {
+        "decorated_definition" => node.child_by_field_name("definition")?,
+        _ => *node,
+    };
+    definition
+        .child_by_field_name("name")
+        .map(|name| get_text(&name, source).to_string())
+}
+
+fn get_assignment_target(statement: &Node, source: &[u8]) -> Option<String> {
+    if !matches!(statement.kind(), "assignment" | "augmented_assignment") {
+        return None;
+    }
+    let left = statement.child_by_field_name("left")?;
+    (left.kind() == "identifier").then(|| get_text(&left, source).to_string())
+}
+
+fn get_string_items(node: &Node, source: &[u8]) -> Vec<String> {
+    let mut cursor = node.walk();
+    node.named_children(&mut cursor)
+        .filter(|item| item.kind() == "string")
+        .map(|item| {
+            get_text(&item, source)
+                .trim_matches(|c| c == '"' || c == '\'')
+                .to_string()
+        })
+        .collect()
+}
+
+fn get_docstring(node: &Node, source: &[u8]) -> Option<String> {
+    let first_statement = node.named_child(0)?;
+    if first_statement.kind() != "expression_statement" {
+        return None;
+    }
+    let expression = first_statement.named_child(0)?;
+    matches!(expression.kind(), "string" | "concatenated_string")
+        .then(|| get_text(&expression, source).to_string())
+}
+
+fn parse_import(node: &Node, package: &str, source: &[u8]) -> Vec<Import> {
+    let Some(source_module) = node
+        .child_by_field_name("module_name")
+        .and_then(|module_name| resolve_module_name(&module_name, package, source))
+    else {
+        return vec![];
+    };
+
+    let mut cursor = node.walk();
+    node.children_by_field_name("name", &mut cursor)
+        .filter_map(|name_node| {
+            let (source_name, alias) = match name_node.kind() {
+                "aliased_import" => (
+                    name_node.child_by_field_name("name")?,
+                    name_node.child_by_field_name("alias"),
+                ),
+                _ => (name_node, None),
+            };
+            let source_name = get_text(&source_name, source).to_string();
+            let alias = alias.map(|alias| get_text(&alias, source).to_string());
+            Some(Import {
+                is_explicit_reexport: alias.as_ref() == Some(&source_name),
+                name: alias.unwrap_or_else(|| source_name.clone()),
+                source_module: source_module.clone(),
+                source_name,
+            })
+        })
+        .collect()
+}
+
+fn resolve_module_name(module_name: &Node, package: &str, source: &[u8]) -> Option<String> {
+    if module_name.kind() != "relative_import" {
+        return Some(get_text(module_name, source).to_string());
+    }
+
+    let mut cursor = module_name.walk();
+    let mut level = 0;
+    let mut relative_name = None;
+    for child in module_name.children(&mut cursor) {
+        match child.kind() {
+            "import_prefix" => level = get_text(&child, source).len(),
+            "dotted_name" => relative_name = Some(get_text(&child, source)),
+            _ => {}
+        }
+    }
+
+    let mut components: Vec<&str> = package.split('.').collect();
+    if level > components.len() {
+        return None;
+    }
+    components.truncate(components.len() + 1 - level);
+    components.extend(relative_name);
+    Some(components.join("."))
+}
+
+fn render_definition(node: &Node, source: &[u8]) -> String {
+    let indent = " ".repeat(node.start_position().column);
+    match node.kind() {
+        "decorated_definition" => {
+            let mut cursor = node.walk();
+            let decorators = node
+                .named_children(&mut cursor)
+                .filter(|child| child.kind() == "decorator")
+                .map(|decorator| format!("{indent}{}\n", get_text(&decorator, source)))
+                .collect::<String>();
+            let definition = node
+                .child_by_field_name("definition")
+                .map(|definition| render_definition(&definition, source))
+                .unwrap_or_default();
+            format!("{decorators}{definition}")
+        }
+        "class_definition" => render_class(node, source, &indent),
+        _ => render_function(node, source, &indent),
+    }
+}
+
+fn get_header<'a>(node: &Node, body: &Node, source: &'a [u8]) -> &'a str {
+    std::str::from_utf8(&source[node.start_byte()..body.start_byte()])
+        .unwrap_or_default()
+        .trim_end()
+}
+
+fn render_function(node: &Node, source: &[u8], indent: &str) -> String {
+    let Some(body) = node.child_by_field_name("body") else {
+        return format!("{indent}{}", get_text(node, source));
+    };
+    let header = get_header(node, &body, source);
+    match get_docstring(&body, source) {
+        Some(docstring) => format!(
+            "{indent}{header}\n{}{docstring}",
+            " ".repeat(body.start_position().column)
+        ),
+        None => format!("{indent}{header} ..."),
+    }
+}
+
+fn render_class(node: &Node, source: &[u8], indent: &str) -> String {
+    let Some(body) = node.child_by_field_name("body") else {
+        return format!("{indent}{}", get_text(node, source));
+    };
+    let header = get_header(node, &body, source);
+    let body_indent = " ".repeat(body.start_position().column);
+
+    let mut members = Vec::new();
+    if let Some(docstring) = get_docstring(&body, source) {
+        members.push(format!("{body_indent}{docstring}"));
+    }
+    let mut cursor = body.walk();
+    members.extend(
+        body.named_children(&mut cursor)
+            .filter_map(|member| render_class_member(&member, source, &body_indent)),
+    );
+
+    if members.is_empty() {
+        format!("{indent}{header} ...")
+    } else {
+        format!("{indent}{header}\n{}", members.join("\n"))
+    }
+}
+
+fn render_class_member(member: &Node, source: &[u8], body_indent: &str) -> Option<String> {
+    match member.kind() {
+        "function_definition" | "class_definition" | "decorated_definition" => {
+            let name = get_definition_name(member, source)?;
+            is_public_name(&name).then(|| render_definition(member, source))
+        }
+        "expression_statement" => {
+            let statement = member
+                .named_child(0)
+                .filter(|statement| statement.kind() == "assignment")?;
+            let name = get_assignment_target(&statement, source)?;
+            is_public_name(&name).then(|| format!("{body_indent}{}", get_text(member, source)))
+        }
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::languages::python::test_helpers::setup_parser;
+
+    const STUB_PACKAGE: &str = "package";
+
+    fn parse(source_code: &str) -> Module {
+        parse_module(source_code, STUB_PACKAGE, &mut setup_parser()).unwrap()
+    }
+
+    fn get_definition<'a>(module: &'a Module, name: &str) -> &'a Symbol {
+        module
+            .definitions
+            .iter()
+            .chain(&module.variables)
+            .find(|symbol| symbol.name == name)
+            .unwrap_or_else(|| panic!("Definition {name} not found"))
+    }
+
+    mod doc_comment {
+        use super::*;
+
+        #[test]
+        fn module_docstring() {
+            let module = parse("\"\"\"Module docs.\"\"\"\n\nimport os\n");
+
+            assert_eq!(
+                module.doc_comment,
+                Some("\"\"\"Module docs.\"\"\"".to_string())
+            );
+        }
+
+        #[test]
+        fn no_docstring() {
+            let module = parse("import os\n");
+
+            assert_eq!(module.doc_comment, None);
+        }
+    }
+
+    mod functions {
+        use super::*;
+
+        #[test]
+        fn without_docstring() {
+            let module = parse("def greet(name: str) -> str:\n    return name\n");
+
+            assert_eq!(
+                get_definition(&module, "greet").source_code,
+                "def greet(name: str) -> str: ..."
+            );
+        }
+
+        #[test]
+        fn with_docstring() {
+            let module =
+                parse("def greet(name):\n    \"\"\"Greet someone.\"\"\"\n    return name\n");
+
+            assert_eq!(
+                get_definition(&module, "greet").source_code,
+                "def greet(name):\n    \"\"\"Greet someone.\"\"\""
+            );
+        }
+
+        #[test]
+        fn async_function() {
+            let module = parse("async def fetch():\n    pass\n");
+
+            assert_eq!(
+                get_definition(&module, "fetch").source_code,
+                "async def fetch(): ..."
+            );
+        }
+
+        #[test]
+        fn decorated_function() {
+            let module = parse("@cache\ndef compute():\n    pass\n");
+
+            assert_eq!(
+                get_definition(&module, "compute").source_code,
+                "@cache\ndef compute(): ..."
+            );
+        }
+    }
+
+    mod classes {
+        use super::*;
+
+        #[test]
+        fn empty_class() {
+            let module = parse("class Empty(Base):\n    pass\n");
+
+            assert_eq!(
+                get_definition(&module, "Empty").source_code,
+                "class Empty(Base): ..."
+            );
+        }
+
+        #[test]
+        fn docstring_and_methods() {
+            let module = parse(
+                r#"class Greeter:
+    """A greeter."""
+
+    def __init__(self, name):
+        self.name = name
+
+    def greet(self):
+        """Greet."""
+        return self.name
+
+    def _helper(self):
+        pass
+"#,
+            );
+
+            assert_eq!(
+                get_definition(&module, "Greeter").source_code,
+                r#"class Greeter:
+    """A greeter."""
+    def __init__(self, name): ...
+    def greet(self):
+        """Greet.""""#
+            );
+        }
+
+        #[test]
+        fn decorated_method() {
+            let module = parse("class Shape:\n    @property\n    def area(self):\n        pass\n");
+
+            assert_eq!(
+                get_definition(&module, "Shape").source_code,
+                "class Shape:\n    @property\n    def area(self): ..."
+            );
+        }
+
+        #[test]
+        fn attributes() {
+            let module = parse("class Point:\n    x: int = 0\n    _cache = None\n");
+
+            assert_eq!(
+                get_definition(&module, "Point").source_code,
+                "class Point:\n    x: int = 0"
+            );
+        }
+    }
+
+    mod assignments {
+        use super::*;
+
+        #[test]
+        fn module_variable() {
+            let module = parse("VERSION: str = \"1.0\"\n");
+
+            assert_eq!(
+                get_definition(&module, "VERSION").source_code,
+                "VERSION: str = \"1.0\""
+            );
+        }
+
+        #[test]
+        fn reassigned_variable() {
+            let module = parse("TIMEOUT = 10\nTIMEOUT = 30\n");
+
+            assert_eq!(module.variables.len(), 1);
+            assert_eq!(module.variables[0].source_code, "TIMEOUT = 30");
+        }
+
+        #[test]
+        fn all_list() {
+            let module = parse("__all__ = [\"foo\", 'bar']\n");
+
+            assert_eq!(
+                module.exports,
+                Some(vec!["foo".to_string(), "bar".to_string()])
+            );
+            assert!(module.variables.is_empty());
+        }
+
+        #[test]
+        fn all_extended() {
+            let module = parse("__all__ = (\"foo\",)\n__all__ += [\"bar\"]\n");
+
+            assert_eq!(
+                module.exports,
+                Some(vec!["foo".to_string(), "bar".to_string()])
+            );
+        }
+
+        #[test]
+        fn no_all() {
+            let module = parse("x = 1\n");
+
+            assert_eq!(module.exports, None);
+        }
+    }
+
+    mod imports {
+        use super::*;
+
+        #[test]
+        fn absolute_import() {
+            let module = parse("from other.module import Thing\n");
+
+            assert_eq!(
+                module.imports,
+                vec![Import {
+                    name: "Thing".to_string(),
+                    source_module: "other.module".to_string(),
+                    source_name: "Thing".to_string(),
+                    is_explicit_reexport: false,
+                }]
+            );
+        }
+
+        #[test]
+        fn relative_import() {
+            let module = parse("from ._impl import Thing\n");
+
+            assert_eq!(module.imports[0].source_module, "package._impl");
+        }
+
+        #[test]
+        fn parent_relative_import() {
+            let module =
+                parse_module("from .. import Thing\n", "package.sub", &mut setup_parser()).unwrap();
+
+            assert_eq!(module.imports[0].source_module, "package");
+        }
+
+        #[test]
+        fn aliased_import() {
+            let module = parse("from ._impl import Thing as Alias\n");
+
+            assert_eq!(module.imports[0].name, "Alias");
+            assert_eq!(module.imports[0].source_name, "Thing");
+            assert!(!module.imports[0].is_explicit_reexport);
+        }
+
+        #[test]
+        fn explicit_reexport() {
+            let module = parse("from ._impl import Thing as Thing\n");
+
+            assert!(module.imports[0].is_explicit_reexport);
+        }
+
+        #[test]
+        fn plain_import_ignored() {
+            let module = parse("import os\n");
+
+            assert!(module.imports.is_empty());
+        }
+    }
+
+    mod public_names {
+        use super::*;
+
+        #[test]
+        fn regular_name() {
+            assert!(is_public_name("name"));
+        }
+
+        #[test]
+        fn private_name() {
+            assert!(!is_public_name("_name"));
+        }
+
+        #[test]
+        fn dunder_name() {
+            assert!(is_public_name("__init__"));
+        }
+    }
+}
@@ -0,0 +1,128 @@
+use crate::symbol_kind::{skip_comments_and_annotations, SymbolKind};
+
+const LINE_COMMENT: &str = "#";
+/// Base classes that make a class an enumeration.
+const ENUM_BASES: [&str; 5] = ["Enum", "IntEnum", "StrEnum", "Flag", "IntFlag"];
+/// Base classes that make a class a structural interface.
+const INTERFACE_BASES: [&str; 1] = ["Protocol"];
+const TYPE_ALIAS_ANNOTATION: &str = "TypeAlias";
+
+/// Detect the kind of the definition or assignment in `source_code`, skipping its decorators.
+pub fn detect_symbol_kind(source_code: &str) -> Option<SymbolKind> {
+    let declaration = skip_comments_and_annotations(source_code, LINE_COMMENT);
+    let mut words = declaration
+        .split(|character: char| character.is_whitespace() || character == '(')
+        .filter(|word| !word.is_empty());
+    match words.next()? {
+        "def" => Some(SymbolKind::Function),
+        "async" if words.next() == Some("def") => Some(SymbolKind::Function),
+        "class" => Some(detect_class_kind(declaration)),
+        _ => {
+            let (target, _) = declaration.split_once('=')?;
+            match target.split_once(':') {
+                Some((_, annotation)) if annotation.trim().ends_with(TYPE_ALIAS_ANNOTATION) => {
+                    Some(SymbolKind::TypeAlias)
+                }
+                _ => Some(SymbolKind::Constant),
+            }
+        }
+    }
+}
+
+fn detect_class_kind(declaration: &str) -> SymbolKind {
+    let header = declaration
+        .split_once(':')
+        .map_or(declaration, |(header, _)| header);
+    let bases = header
+        .split_once('(')
+        .map_or("", |(_, bases)| bases.trim_end().trim_end_matches(')'));
+    // Generic bases such as `Protocol[T]` and qualified ones such as `enum.Enum` count too.
+    let base_names = bases.split(',').map(|base| {
+        let base = base.split('[').next().unwrap_or_default().trim();
+        base.rsplit('.').next().unwrap_or(base)
+    });
+    let mut kind = SymbolKind::Class;
+    for name in base_names {
+        if ENUM_BASES.contains(&name) {
+            return SymbolKind::Enum;
+        }
+        if INTERFACE_BASES.contains(&name) {
+            kind = SymbolKind::Interface;
+        }
+    }
+    kind
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn functions() {
+        for source_code in [
+            "def greet(name: str) -> str: ...",
+            "async def fetch(): ...",
+            "@cache\ndef compute(): ...",
+        ] {
+            assert_eq!(
+                detect_symbol_kind(source_code),
+                Some(SymbolKind::Function),
+                "{source_code}"
+            );
+        }
+    }
+
+    #[test]
+    fn classes() {
+        assert_eq!(
+            detect_symbol_kind("@dataclass\nclass Point:\n    x: int = 0"),
+            Some(SymbolKind::Class)
+        );
+        assert_eq!(
+            detect_symbol_kind("class Empty(Base): ..."),
+            Some(SymbolKind::Class)
+        );
+    }
+
+    #[test]
+    fn enums() {
+        assert_eq!(
+            detect_symbol_kind("class Colour(Enum): ..."),
+            Some(SymbolKind::Enum)
+        );
+        assert_eq!(
+            detect_symbol_kind("class Level(str, enum.Enum): ..."),
+            Some(SymbolKind::Enum)
+        );
+    }
+
+    #[test]
+    fn protocols() {
+        assert_eq!(
+            detect_symbol_kind("class Greeter(Protocol[T]):\n    def greet(self): ..."),
+            Some(SymbolKind::Interface)
+        );
+    }
+
+    #[test]
+    fn assignments() {
+        assert_eq!(
+            detect_symbol_kind("VERSION: str = \"1.0\""),
+            Some(SymbolKind::Constant)
+        );
+        assert_eq!(
+            detect_symbol_kind("DEFAULT_TIMEOUT = 5"),
+            Some(SymbolKind::Constant)
+        );
+        assert_eq!(
+            detect_symbol_kind("Vector: TypeAlias = list[float]"),
+            Some(SymbolKind::TypeAlias)
+        );
+    }
+
+    #[test]
+    fn unrecognised_statements() {
+        assert_eq!(detect_symbol_kind("print()"), None);
+        assert_eq!(detect_symbol_kind(""), None);
+    }
+}
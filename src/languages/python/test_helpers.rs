@@ -0,0 +1,8 @@
+use daipendency_extractor::{get_parser, Extractor};
+use tree_sitter::Parser;
+
+use super::PythonExtractor;
+
+pub fn setup_parser() -> Parser {
+    get_parser(&PythonExtractor.get_parser_language()).unwrap()
+}
@@ -0,0 +1,598 @@
+use super::symbol_kind::detect_symbol_kind;
+use super::target::Target;
+use crate::symbol_kind::SymbolKind;
+use std::collections::HashSet;
+use std::ops::Range;
+
+const CFG_ATTRIBUTE_NAME: &str = "cfg";
+/// Options that are never set when a library is compiled as a dependency in release mode.
+const UNSET_OPTION_NAMES: [&str; 2] = ["test", "debug_assertions"];
+
+/// A configuration predicate, as in `#[cfg(all(feature = "net", unix))]`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Predicate {
+    Option { name: String, value: Option<String> },
+    All(Vec<Predicate>),
+    Any(Vec<Predicate>),
+    Not(Box<Predicate>),
+}
+
+/// What is known about the configuration that a library is compiled with.
+#[derive(Debug, Default)]
+pub struct Configuration {
+    /// The enabled features, or `None` if any feature may be enabled.
+    pub features: Option<HashSet<String>>,
+    /// The target platform, or `None` if any platform may be targeted.
+    pub target: Option<Target>,
+}
+
+impl Configuration {
+    /// Evaluate `predicate`, or return `None` if its outcome depends on unknown options.
+    pub fn evaluate(&self, predicate: &Predicate) -> Option<bool> {
+        match predicate {
+            Predicate::Option { name, value } => self.evaluate_option(name, value.as_deref()),
+            Predicate::All(predicates) => {
+                let outcomes = predicates
+                    .iter()
+                    .map(|p| self.evaluate(p))
+                    .collect::<Vec<_>>();
+                if outcomes.contains(&Some(false)) {
+                    Some(false)
+                } else if outcomes.iter().all(Option::is_some) {
+                    Some(true)
+                } else {
+                    None
+                }
+            }
+            Predicate::Any(predicates) => {
+                let outcomes = predicates
+                    .iter()
+                    .map(|p| self.evaluate(p))
+                    .collect::<Vec<_>>();
+                if outcomes.contains(&Some(true)) {
+                    Some(true)
+                } else if outcomes.iter().all(Option::is_some) {
+                    Some(false)
+                } else {
+                    None
+                }
+            }
+            Predicate::Not(predicate) => self.evaluate(predicate).map(|outcome| !outcome),
+        }
+    }
+
+    fn evaluate_option(&self, name: &str, value: Option<&str>) -> Option<bool> {
+        match (name, value) {
+            ("feature", Some(feature)) => Some(self.features.as_ref()?.contains(feature)),
+            (name, None) if UNSET_OPTION_NAMES.contains(&name) => Some(false),
+            _ => self.target.as_ref()?.evaluate_option(name, value),
+        }
+    }
+
+    /// Whether the item with the `#[cfg(...)]` attributes at the start of `source_code` may be
+    /// compiled, which is the case unless one of its predicates is known to be false.
+    pub fn is_item_enabled(&self, source_code: &str) -> bool {
+        let (predicates, _) = split_item_predicates(source_code);
+        self.are_predicates_satisfiable(&predicates)
+    }
+
+    /// Remove the fields or variants of the struct or enum in `source_code` whose `#[cfg(...)]`
+    /// attributes are known to be false.
+    pub fn filter_members(&self, source_code: &str) -> String {
+        if !matches!(
+            detect_symbol_kind(source_code),
+            Some(SymbolKind::Struct | SymbolKind::Enum)
+        ) {
+            return source_code.to_string();
+        }
+        let (_, declaration) = split_item_predicates(source_code);
+        let Some(body_start) = declaration.find('{') else {
+            return source_code.to_string();
+        };
+        let body_offset = source_code.len() - declaration.len() + body_start + 1;
+
+        let mut filtered_source_code = source_code.to_string();
+        for member in split_members(&source_code[body_offset..]).into_iter().rev() {
+            let member = member.start + body_offset..member.end + body_offset;
+            let (predicates, _) = split_item_predicates(&source_code[member.clone()]);
+            if !self.are_predicates_satisfiable(&predicates) {
+                filtered_source_code.replace_range(member, "");
+            }
+        }
+        filtered_source_code
+    }
+
+    /// Whether none of `predicates` is known to be false.
+    pub fn are_predicates_satisfiable(&self, predicates: &[Predicate]) -> bool {
+        predicates
+            .iter()
+            .all(|predicate| self.evaluate(predicate) != Some(false))
+    }
+}
+
+/// Get the predicates of the `#[cfg(...)]` attributes amongst the comments and attributes at the
+/// start of an item, along with the source code that follows them.
+pub fn split_item_predicates(source_code: &str) -> (Vec<Predicate>, &str) {
+    let mut predicates = Vec::new();
+    let mut rest = source_code.trim_start();
+    loop {
+        if rest.starts_with("//") {
+            rest = rest.split_once('\n').map_or("", |(_, rest)| rest);
+        } else if rest.starts_with("/*") {
+            let Some(end) = rest.find("*/") else {
+                break;
+            };
+            rest = &rest[end + 2..];
+        } else if let Some(attribute) = rest.strip_prefix("#[") {
+            let Some(end) = find_closing_bracket(attribute) else {
+                break;
+            };
+            predicates.extend(parse_cfg_attribute(&attribute[..end]));
+            rest = &attribute[end + 1..];
+        } else {
+            break;
+        }
+        rest = rest.trim_start();
+    }
+    (predicates, rest)
+}
+
+/// Split the body of a struct or enum, which starts after its `{`, into the ranges of its members.
+///
+/// Each member spans from the end of the previous one to its trailing comma, so that removing it
+/// removes its attributes and the line it's on.
+fn split_members(body: &str) -> Vec<Range<usize>> {
+    let mut members = Vec::new();
+    let mut member_start = 0;
+    let mut depth = 0_usize;
+    let mut previous_character = ' ';
+    let mut characters = body.char_indices().peekable();
+    while let Some((index, character)) = characters.next() {
+        match character {
+            '/' if characters.peek().is_some_and(|(_, next)| *next == '/') => {
+                characters.find(|(_, character)| *character == '\n');
+            }
+            '/' if characters.peek().is_some_and(|(_, next)| *next == '*') => {
+                characters.next();
+                let mut previous = ' ';
+                characters.find(|(_, character)| {
+                    let is_end = previous == '*' && *character == '/';
+                    previous = *character;
+                    is_end
+                });
+            }
+            '"' => {
+                while let Some((_, character)) = characters.next() {
+                    match character {
+                        '"' => break,
+                        '\\' => {
+                            characters.next();
+                        }
+                        _ => {}
+                    }
+                }
+            }
+            '(' | '[' | '{' | '<' => depth += 1,
+            '>' if previous_character == '-' || previous_character == '=' => {}
+            ')' | ']' | '>' => depth = depth.saturating_sub(1),
+            '}' if depth == 0 => {
+                let member = body[member_start..index].trim_end();
+                if !member.trim_start().is_empty() {
+                    members.push(member_start..member_start + member.len());
+                }
+                break;
+            }
+            '}' => depth -= 1,
+            ',' if depth == 0 => {
+                members.push(member_start..index + 1);
+                member_start = index + 1;
+            }
+            _ => {}
+        }
+        previous_character = character;
+    }
+    members
+}
+
+/// Find the `]` that closes an attribute, skipping nested brackets and string literals.
+fn find_closing_bracket(attribute: &str) -> Option<usize> {
+    let mut depth = 0;
+    let mut characters = attribute.char_indices();
+    while let Some((index, character)) = characters.next() {
+        match character {
+            '"' => loop {
+                match characters.next()?.1 {
+                    '"' => break,
+                    '\\' => {
+                        characters.next();
+                    }
+                    _ => {}
+                }
+            },
+            '[' => depth += 1,
+            ']' if depth == 0 => return Some(index),
+            ']' => depth -= 1,
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Parse the predicate of an attribute such as `cfg(unix)`, ignoring other attributes.
+fn parse_cfg_attribute(attribute: &str) -> Option<Predicate> {
+    let arguments = attribute
+        .trim()
+        .strip_prefix(CFG_ATTRIBUTE_NAME)?
+        .trim_start()
+        .strip_prefix('(')?
+        .strip_suffix(')')?;
+    parse_predicate(arguments)
+}
+
+/// Parse a configuration predicate such as `any(unix, feature = "fs")`.
+pub fn parse_predicate(input: &str) -> Option<Predicate> {
+    let tokens = tokenise(input)?;
+    let mut parser = Parser {
+        tokens,
+        position: 0,
+    };
+    let predicate = parser.parse_predicate()?;
+    (parser.position == parser.tokens.len()).then_some(predicate)
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Token {
+    Identifier(String),
+    String(String),
+    Equals,
+    Comma,
+    OpeningParenthesis,
+    ClosingParenthesis,
+}
+
+fn tokenise(input: &str) -> Option<Vec<Token>> {
+    let mut tokens = Vec::new();
+    let mut characters = input.chars().peekable();
+    while let Some(character) = characters.next() {
+        match character {
+            '=' => tokens.push(Token::Equals),
+            ',' => tokens.push(Token::Comma),
+            '(' => tokens.push(Token::OpeningParenthesis),
+            ')' => tokens.push(Token::ClosingParenthesis),
+            '"' => {
+                let mut string = String::new();
+                loop {
+                    match characters.next()? {
+                        '"' => break,
+                        '\\' => string.push(characters.next()?),
+                        character => string.push(character),
+                    }
+                }
+                tokens.push(Token::String(string));
+            }
+            character if character.is_whitespace() => {}
+            character if character.is_alphanumeric() || character == '_' => {
+                let mut identifier = character.to_string();
+                while let Some(&next) = characters.peek() {
+                    if !(next.is_alphanumeric() || next == '_') {
+                        break;
+                    }
+                    identifier.push(next);
+                    characters.next();
+                }
+                tokens.push(Token::Identifier(identifier));
+            }
+            _ => return None,
+        }
+    }
+    Some(tokens)
+}
+
+struct Parser {
+    tokens: Vec<Token>,
+    position: usize,
+}
+
+impl Parser {
+    fn next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.position).cloned();
+        self.position += 1;
+        token
+    }
+
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.position)
+    }
+
+    fn parse_predicate(&mut self) -> Option<Predicate> {
+        let Some(Token::Identifier(name)) = self.next() else {
+            return None;
+        };
+        match (name.as_str(), self.peek()) {
+            ("all", Some(Token::OpeningParenthesis)) => Some(Predicate::All(self.parse_list()?)),
+            ("any", Some(Token::OpeningParenthesis)) => Some(Predicate::Any(self.parse_list()?)),
+            ("not", Some(Token::OpeningParenthesis)) => {
+                let mut predicates = self.parse_list()?;
+                (predicates.len() == 1).then(|| Predicate::Not(Box::new(predicates.remove(0))))
+            }
+            (_, Some(Token::Equals)) => {
+                self.next();
+                let Some(Token::String(value)) = self.next() else {
+                    return None;
+                };
+                Some(Predicate::Option {
+                    name,
+                    value: Some(value),
+                })
+            }
+            _ => Some(Predicate::Option { name, value: None }),
+        }
+    }
+
+    fn parse_list(&mut self) -> Option<Vec<Predicate>> {
+        self.next();
+        let mut predicates = Vec::new();
+        loop {
+            if self.peek() == Some(&Token::ClosingParenthesis) {
+                self.next();
+                return Some(predicates);
+            }
+            predicates.push(self.parse_predicate()?);
+            match self.next()? {
+                Token::Comma => {}
+                Token::ClosingParenthesis => return Some(predicates),
+                _ => return None,
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn feature(name: &str) -> Predicate {
+        Predicate::Option {
+            name: "feature".to_string(),
+            value: Some(name.to_string()),
+        }
+    }
+
+    fn configure(features: &[&str]) -> Configuration {
+        Configuration {
+            features: Some(features.iter().map(|f| f.to_string()).collect()),
+            target: None,
+        }
+    }
+
+    mod parsing {
+        use super::*;
+
+        #[test]
+        fn option_with_value() {
+            assert_eq!(parse_predicate(r#"feature = "net""#), Some(feature("net")));
+        }
+
+        #[test]
+        fn option_without_value() {
+            assert_eq!(
+                parse_predicate("unix"),
+                Some(Predicate::Option {
+                    name: "unix".to_string(),
+                    value: None
+                })
+            );
+        }
+
+        #[test]
+        fn nested_predicates() {
+            let predicate =
+                parse_predicate(r#"all(feature = "net", not(any(feature = "a", unix)),)"#);
+
+            assert_eq!(
+                predicate,
+                Some(Predicate::All(vec![
+                    feature("net"),
+                    Predicate::Not(Box::new(Predicate::Any(vec![
+                        feature("a"),
+                        Predicate::Option {
+                            name: "unix".to_string(),
+                            value: None
+                        },
+                    ]))),
+                ]))
+            );
+        }
+
+        #[test]
+        fn malformed_predicate() {
+            assert_eq!(parse_predicate(r#"all(feature = "net""#), None);
+            assert_eq!(parse_predicate(r#"feature = "net" unix"#), None);
+        }
+    }
+
+    mod evaluation {
+        use super::*;
+
+        #[test]
+        fn enabled_feature() {
+            assert_eq!(configure(&["net"]).evaluate(&feature("net")), Some(true));
+            assert_eq!(configure(&["net"]).evaluate(&feature("fs")), Some(false));
+        }
+
+        #[test]
+        fn unknown_features() {
+            let configuration = Configuration::default();
+
+            assert_eq!(configuration.evaluate(&feature("net")), None);
+        }
+
+        #[test]
+        fn unknown_option() {
+            let configuration = configure(&["net"]);
+
+            let predicate = parse_predicate(r#"all(unix, feature = "fs")"#).unwrap();
+            assert_eq!(configuration.evaluate(&predicate), Some(false));
+            let predicate = parse_predicate(r#"any(unix, feature = "fs")"#).unwrap();
+            assert_eq!(configuration.evaluate(&predicate), None);
+            let predicate = parse_predicate(r#"any(unix, feature = "net")"#).unwrap();
+            assert_eq!(configuration.evaluate(&predicate), Some(true));
+        }
+
+        #[test]
+        fn target_options() {
+            let configuration = Configuration {
+                features: None,
+                target: Target::parse("x86_64-pc-windows-msvc"),
+            };
+
+            let predicate = parse_predicate(r#"all(windows, feature = "fs")"#).unwrap();
+            assert_eq!(configuration.evaluate(&predicate), None);
+            let predicate = parse_predicate(r#"any(unix, target_os = "macos")"#).unwrap();
+            assert_eq!(configuration.evaluate(&predicate), Some(false));
+        }
+
+        #[test]
+        fn unset_options() {
+            let configuration = configure(&["net"]);
+
+            let predicate = parse_predicate("test").unwrap();
+            assert_eq!(configuration.evaluate(&predicate), Some(false));
+            let predicate = parse_predicate("not(debug_assertions)").unwrap();
+            assert_eq!(configuration.evaluate(&predicate), Some(true));
+        }
+
+        #[test]
+        fn negation() {
+            let predicate = parse_predicate(r#"not(feature = "net")"#).unwrap();
+
+            assert_eq!(configure(&[]).evaluate(&predicate), Some(true));
+        }
+    }
+
+    mod members {
+        use super::*;
+
+        #[test]
+        fn disabled_field() {
+            let source_code = "pub struct Config {\n    /// Port, in host order\n    #[cfg(feature = \"net\")]\n    pub port: u16,\n    pub name: String,\n}";
+
+            assert_eq!(
+                configure(&[]).filter_members(source_code),
+                "pub struct Config {\n    pub name: String,\n}"
+            );
+            assert_eq!(configure(&["net"]).filter_members(source_code), source_code);
+        }
+
+        #[test]
+        fn disabled_last_field() {
+            let source_code = "pub struct Config {\n    pub name: String,\n    #[cfg(test)]\n    pub mock: bool\n}";
+
+            assert_eq!(
+                configure(&[]).filter_members(source_code),
+                "pub struct Config {\n    pub name: String,\n}"
+            );
+        }
+
+        #[test]
+        fn disabled_variant() {
+            let source_code = "pub enum Transport {\n    Memory(Vec<u8>),\n    #[cfg(feature = \"net\")]\n    Tcp { host: String, port: u16 },\n    File(HashMap<String, fn(u8) -> u8>),\n}";
+
+            assert_eq!(
+                configure(&[]).filter_members(source_code),
+                "pub enum Transport {\n    Memory(Vec<u8>),\n    File(HashMap<String, fn(u8) -> u8>),\n}"
+            );
+        }
+
+        #[test]
+        fn gated_struct() {
+            let source_code = "#[cfg(feature = \"net\")]\npub struct Config {\n    #[cfg(debug_assertions)]\n    pub trace: bool,\n}";
+
+            assert_eq!(
+                configure(&["net"]).filter_members(source_code),
+                "#[cfg(feature = \"net\")]\npub struct Config {\n}"
+            );
+        }
+
+        #[test]
+        fn unknown_predicate_kept() {
+            let source_code =
+                "pub struct Config {\n    #[cfg(feature = \"net\")]\n    pub port: u16,\n}";
+
+            assert_eq!(
+                Configuration::default().filter_members(source_code),
+                source_code
+            );
+        }
+
+        #[test]
+        fn other_items_untouched() {
+            let source_code = "pub fn connect() {\n    #[cfg(test)]\n    let x = 1;\n}";
+
+            assert_eq!(configure(&[]).filter_members(source_code), source_code);
+        }
+    }
+
+    mod items {
+        use super::*;
+
+        #[test]
+        fn gated_item() {
+            let source_code =
+                "/// Docs\n#[cfg(feature = \"net\")]\n#[derive(Debug)]\npub struct Socket;";
+
+            assert!(configure(&["net"]).is_item_enabled(source_code));
+            assert!(!configure(&[]).is_item_enabled(source_code));
+        }
+
+        #[test]
+        fn attribute_after_item_start() {
+            let source_code =
+                "pub struct Config {\n    #[cfg(feature = \"net\")]\n    pub port: u16,\n}";
+
+            assert!(configure(&[]).is_item_enabled(source_code));
+        }
+
+        #[test]
+        fn multi_line_attribute() {
+            let source_code = "#[cfg(all(\n    feature = \"net\",\n    feature = \"fs\"\n))]\n#[cfg(unix)]\npub struct Socket;";
+
+            let (predicates, rest) = split_item_predicates(source_code);
+
+            assert_eq!(
+                predicates,
+                vec![
+                    Predicate::All(vec![feature("net"), feature("fs")]),
+                    Predicate::Option {
+                        name: "unix".to_string(),
+                        value: None
+                    },
+                ]
+            );
+            assert_eq!(rest, "pub struct Socket;");
+            assert!(!configure(&["net"]).is_item_enabled(source_code));
+        }
+
+        #[test]
+        fn attribute_after_multi_line_attribute() {
+            let source_code = "#[doc(alias = \"[\",\n    alias = \"open\")]\n#[cfg(feature = \"net\")]\npub fn connect() {}";
+
+            assert!(!configure(&[]).is_item_enabled(source_code));
+        }
+
+        #[test]
+        fn block_doc_comment() {
+            let source_code = "/**\n * Docs\n */\n#[cfg(feature = \"net\")]\npub struct Socket;";
+
+            assert!(!configure(&[]).is_item_enabled(source_code));
+        }
+
+        #[test]
+        fn conditional_attribute() {
+            let source_code =
+                "#[cfg_attr(feature = \"serde\", derive(Serialize))]\npub struct Config;";
+
+            assert!(configure(&[]).is_item_enabled(source_code));
+        }
+    }
+}
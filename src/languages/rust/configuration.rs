@@ -0,0 +1,360 @@
+use super::cfg::{split_item_predicates, Configuration};
+use super::features::resolve_features;
+use super::target::Target;
+use crate::error::LibraryError;
+use crate::languages::{Language, LanguageConfig};
+use crate::loading_options::FeatureSelection;
+use daipendency_extractor::{LibraryMetadata, Namespace};
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Keep the namespaces, symbols, fields and variants of the crate at `path` that may be compiled
+/// with the features enabled by `selection` and for the `target` triple. Either may be omitted to keep the items
+/// gated behind any feature or platform, respectively.
+///
+/// # Returns
+///
+/// Returns a Result containing the namespaces that are kept and the enabled features, if any
+/// were selected.
+pub fn select_configuration(
+    path: &Path,
+    metadata: &LibraryMetadata,
+    selection: Option<&FeatureSelection>,
+    target: Option<&str>,
+    namespaces: Vec<Namespace>,
+) -> Result<(Vec<Namespace>, Option<Vec<String>>), LibraryError> {
+    let features = selection
+        .map(|selection| resolve_features(path, selection))
+        .transpose()?;
+    let target = target
+        .map(|triple| {
+            Target::load(triple).ok_or_else(|| LibraryError::UnknownTarget(triple.to_string()))
+        })
+        .transpose()?;
+    let configuration = Configuration {
+        features: features
+            .as_ref()
+            .map(|features| features.iter().cloned().collect()),
+        target,
+    };
+    let source_directory = metadata.entry_point.parent().unwrap_or(path).to_path_buf();
+    let separator = LanguageConfig::get_from_language(Language::Rust).namespace_separator;
+
+    let mut disabled_namespaces = HashSet::new();
+    for namespace in &namespaces {
+        let segments = namespace.name.split(separator).collect::<Vec<_>>();
+        for depth in 1..segments.len() {
+            let module_path = &segments[1..=depth];
+            if !is_module_enabled(
+                &metadata.entry_point,
+                &source_directory,
+                module_path,
+                &configuration,
+            ) {
+                disabled_namespaces.insert(segments[..=depth].join(separator));
+            }
+        }
+    }
+
+    let namespaces = namespaces
+        .into_iter()
+        .filter(|namespace| {
+            !disabled_namespaces.iter().any(|disabled| {
+                namespace.name == *disabled
+                    || namespace
+                        .name
+                        .starts_with(&format!("{}{}", disabled, separator))
+            })
+        })
+        .map(|mut namespace| {
+            namespace
+                .symbols
+                .retain(|symbol| configuration.is_item_enabled(&symbol.source_code));
+            for symbol in &mut namespace.symbols {
+                symbol.source_code = configuration.filter_members(&symbol.source_code);
+            }
+            namespace
+        })
+        .collect();
+    Ok((namespaces, features))
+}
+
+/// Whether the module at `module_path` may be compiled, judging by the `#[cfg(...)]` attributes
+/// of its declaration in its parent module.
+fn is_module_enabled(
+    entry_point: &Path,
+    source_directory: &Path,
+    module_path: &[&str],
+    configuration: &Configuration,
+) -> bool {
+    let (name, parent_path) = module_path
+        .split_last()
+        .expect("Module paths are not empty");
+    let Some(parent_source) = read_module_source(entry_point, source_directory, parent_path) else {
+        return true;
+    };
+    let mut remaining_source = parent_source.as_str();
+    loop {
+        let (predicates, item) = split_item_predicates(remaining_source);
+        let (declaration, next_lines) = item.split_once('\n').unwrap_or((item, ""));
+        if get_declared_module_name(declaration) == Some(name) {
+            return configuration.are_predicates_satisfiable(&predicates);
+        }
+        if next_lines.is_empty() {
+            return true;
+        }
+        remaining_source = next_lines;
+    }
+}
+
+fn read_module_source(
+    entry_point: &Path,
+    source_directory: &Path,
+    module_path: &[&str],
+) -> Option<String> {
+    if module_path.is_empty() {
+        return fs::read_to_string(entry_point).ok();
+    }
+    let module_directory: PathBuf = module_path.iter().collect();
+    [
+        source_directory
+            .join(&module_directory)
+            .with_extension("rs"),
+        source_directory.join(&module_directory).join("mod.rs"),
+    ]
+    .iter()
+    .find_map(|path| fs::read_to_string(path).ok())
+}
+
+fn get_declared_module_name(line: &str) -> Option<&str> {
+    let line = line.trim_start();
+    let line = match line.strip_prefix("pub") {
+        Some(rest) if rest.starts_with('(') => rest[rest.find(')')? + 1..].trim_start(),
+        Some(rest) if rest.starts_with(' ') => rest.trim_start(),
+        _ => line,
+    };
+    let rest = line.strip_prefix("mod ")?.trim_start();
+    let name_length = rest
+        .find(|character: char| !(character.is_alphanumeric() || character == '_'))
+        .unwrap_or(rest.len());
+    let (name, rest) = rest.split_at(name_length);
+    let rest = rest.trim_start();
+    (!name.is_empty() && (rest.starts_with(';') || rest.starts_with('{'))).then_some(name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::languages::rust::MANIFEST_PATH;
+    use assertables::assert_matches;
+    use daipendency_extractor::Symbol;
+    use daipendency_testing::tempdir::TempDir;
+
+    mod selection {
+        use super::*;
+
+        fn create_namespace(name: &str, symbols: &[(&str, &str)]) -> Namespace {
+            Namespace {
+                name: name.to_string(),
+                symbols: symbols
+                    .iter()
+                    .map(|(name, source_code)| Symbol {
+                        name: name.to_string(),
+                        source_code: source_code.to_string(),
+                    })
+                    .collect(),
+                doc_comment: None,
+            }
+        }
+
+        fn select_namespaces(
+            features: Option<&[&str]>,
+            target: Option<&str>,
+        ) -> Result<Vec<(String, Vec<String>)>, LibraryError> {
+            let temp_dir = TempDir::new();
+            temp_dir
+                .create_file(
+                    MANIFEST_PATH,
+                    "[package]\nname = \"net-lib\"\n\n[features]\nnet = []\n",
+                )
+                .unwrap();
+            temp_dir
+                .create_file(
+                    "src/lib.rs",
+                    "pub mod fs;\n\n/// Networking\n#[cfg(feature = \"net\")]\npub mod net;\n",
+                )
+                .unwrap();
+            temp_dir
+                .create_file("src/fs.rs", "#[cfg(windows)]\npub mod windows;\n")
+                .unwrap();
+            temp_dir
+                .create_file("src/net/mod.rs", "pub mod tcp;\n")
+                .unwrap();
+            let metadata = LibraryMetadata {
+                name: "net-lib".to_string(),
+                version: None,
+                documentation: String::new(),
+                entry_point: temp_dir.path.join("src/lib.rs"),
+            };
+            let namespaces = vec![
+                create_namespace(
+                    "net_lib",
+                    &[
+                        ("open", "pub fn open() {}"),
+                        ("connect", "#[cfg(feature = \"net\")]\npub fn connect() {}"),
+                    ],
+                ),
+                create_namespace(
+                    "net_lib::fs",
+                    &[
+                        ("File", "pub struct File;"),
+                        ("Permissions", "#[cfg(unix)]\npub struct Permissions;"),
+                    ],
+                ),
+                create_namespace("net_lib::fs::windows", &[("Handle", "pub struct Handle;")]),
+                create_namespace("net_lib::net", &[("Socket", "pub struct Socket;")]),
+                create_namespace(
+                    "net_lib::net::tcp",
+                    &[("TcpStream", "pub struct TcpStream;")],
+                ),
+            ];
+            let selection = features.map(|features| FeatureSelection {
+                features: features.iter().map(|f| f.to_string()).collect(),
+                ..Default::default()
+            });
+
+            let (namespaces, _) = select_configuration(
+                &temp_dir.path,
+                &metadata,
+                selection.as_ref(),
+                target,
+                namespaces,
+            )?;
+
+            Ok(namespaces
+                .into_iter()
+                .map(|namespace| {
+                    (
+                        namespace.name,
+                        namespace.symbols.into_iter().map(|s| s.name).collect(),
+                    )
+                })
+                .collect())
+        }
+
+        fn get_namespace_names(namespaces: &[(String, Vec<String>)]) -> Vec<&str> {
+            namespaces.iter().map(|(name, _)| name.as_str()).collect()
+        }
+
+        #[test]
+        fn disabled_feature() {
+            let namespaces = select_namespaces(Some(&[]), None).unwrap();
+
+            assert_eq!(
+                get_namespace_names(&namespaces),
+                vec!["net_lib", "net_lib::fs", "net_lib::fs::windows"]
+            );
+            assert_eq!(namespaces[0].1, vec!["open"]);
+        }
+
+        #[test]
+        fn enabled_feature() {
+            let namespaces = select_namespaces(Some(&["net"]), None).unwrap();
+
+            assert_eq!(namespaces.len(), 5);
+            assert_eq!(namespaces[0].1, vec!["open", "connect"]);
+        }
+
+        #[test]
+        fn unix_target() {
+            let namespaces = select_namespaces(None, Some("x86_64-unknown-linux-gnu")).unwrap();
+
+            assert_eq!(
+                get_namespace_names(&namespaces),
+                vec![
+                    "net_lib",
+                    "net_lib::fs",
+                    "net_lib::net",
+                    "net_lib::net::tcp"
+                ]
+            );
+            assert_eq!(namespaces[0].1, vec!["open", "connect"]);
+            assert_eq!(namespaces[1].1, vec!["File", "Permissions"]);
+        }
+
+        #[test]
+        fn windows_target() {
+            let namespaces = select_namespaces(None, Some("x86_64-pc-windows-msvc")).unwrap();
+
+            assert_eq!(namespaces.len(), 5);
+            assert_eq!(namespaces[1].1, vec!["File"]);
+        }
+
+        #[test]
+        fn unknown_target() {
+            let result = select_namespaces(None, Some("x86_64-unknown-plan9"));
+
+            assert_matches!(
+                result,
+                Err(LibraryError::UnknownTarget(triple)) if triple == "x86_64-unknown-plan9"
+            );
+        }
+    }
+
+    mod module_gating {
+        use super::*;
+
+        fn is_net_module_enabled(features: &[&str]) -> bool {
+            let temp_dir = TempDir::new();
+            temp_dir
+                .create_file(
+                    "src/lib.rs",
+                    "pub mod fs;\n\n/// Networking\n#[cfg(all(\n    feature = \"net\",\n    feature = \"fs\"\n))]\npub mod net;\n",
+                )
+                .unwrap();
+            let configuration = Configuration {
+                features: Some(features.iter().map(|f| f.to_string()).collect()),
+                target: None,
+            };
+
+            is_module_enabled(
+                &temp_dir.path.join("src/lib.rs"),
+                &temp_dir.path.join("src"),
+                &["net"],
+                &configuration,
+            )
+        }
+
+        #[test]
+        fn multi_line_attribute_satisfied() {
+            assert!(is_net_module_enabled(&["net", "fs"]));
+        }
+
+        #[test]
+        fn multi_line_attribute_unsatisfied() {
+            assert!(!is_net_module_enabled(&["net"]));
+        }
+    }
+
+    mod module_declarations {
+        use super::*;
+
+        #[test]
+        fn declarations() {
+            assert_eq!(get_declared_module_name("mod net;"), Some("net"));
+            assert_eq!(get_declared_module_name("    pub mod net {"), Some("net"));
+            assert_eq!(
+                get_declared_module_name("pub(crate) mod net ;"),
+                Some("net")
+            );
+        }
+
+        #[test]
+        fn other_items() {
+            assert_eq!(get_declared_module_name("pub fn module() {}"), None);
+            assert_eq!(get_declared_module_name("// mod net;"), None);
+            assert_eq!(get_declared_module_name("mod net_lib::x;"), None);
+        }
+    }
+}
@@ -0,0 +1,132 @@
+use super::MANIFEST_PATH;
+use crate::dependency_graph::DependencyGraph;
+use cargo_metadata::{DependencyKind, MetadataCommand, Package, PackageId};
+use daipendency_extractor::DependencyResolutionError;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+/// Resolve the dependency tree of the crate at `path` with a single `cargo metadata` run.
+///
+/// Each crate's dependencies are resolved to the exact packages Cargo selected for it, so
+/// different versions of the same crate are told apart. Only normal dependencies are recorded.
+pub fn load_dependency_graph(path: &Path) -> Result<DependencyGraph, DependencyResolutionError> {
+    let metadata = MetadataCommand::new()
+        .manifest_path(path.join(MANIFEST_PATH))
+        .exec()
+        .map_err(|e| DependencyResolutionError::RetrievalFailure(e.to_string()))?;
+    let resolve = metadata.resolve.ok_or_else(|| {
+        DependencyResolutionError::RetrievalFailure("Cargo did not resolve the dependencies".into())
+    })?;
+    let packages: HashMap<&PackageId, &Package> = metadata
+        .packages
+        .iter()
+        .map(|package| (&package.id, package))
+        .collect();
+
+    let mut graph = DependencyGraph::default();
+    for node in &resolve.nodes {
+        let Some(declarer) = packages.get(&node.id) else {
+            continue;
+        };
+        let declarer_path = get_package_path(declarer);
+        let normal_dependencies = node.deps.iter().filter(|dependency| {
+            dependency.dep_kinds.is_empty()
+                || dependency
+                    .dep_kinds
+                    .iter()
+                    .any(|info| info.kind == DependencyKind::Normal)
+        });
+        for dependency in normal_dependencies {
+            if let Some(package) = packages.get(&dependency.pkg) {
+                graph.insert(&declarer_path, &package.name, get_package_path(package));
+            }
+        }
+    }
+    Ok(graph)
+}
+
+fn get_package_path(package: &Package) -> PathBuf {
+    package
+        .manifest_path
+        .parent()
+        .map_or_else(PathBuf::new, |path| path.as_std_path().to_path_buf())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use assertables::assert_matches;
+    use daipendency_testing::tempdir::TempDir;
+
+    fn create_crate(temp_dir: &TempDir, directory: &str, name: &str, dependencies: &str) {
+        temp_dir
+            .create_file(
+                &format!("{directory}/{MANIFEST_PATH}"),
+                &format!(
+                    "[package]\nname = \"{name}\"\nversion = \"0.1.0\"\nedition = \"2021\"\n\n[dependencies]\n{dependencies}"
+                ),
+            )
+            .unwrap();
+        temp_dir
+            .create_file(&format!("{directory}/src/lib.rs"), "")
+            .unwrap();
+    }
+
+    #[test]
+    fn transitive_dependencies() {
+        let temp_dir = TempDir::new();
+        create_crate(&temp_dir, "app", "app", "a = { path = \"../a\" }\n");
+        create_crate(&temp_dir, "a", "a", "b = { path = \"../b\" }\n");
+        create_crate(&temp_dir, "b", "b", "");
+
+        let graph = load_dependency_graph(&temp_dir.path.join("app")).unwrap();
+
+        let a_path = graph.resolve("a", &temp_dir.path.join("app")).unwrap();
+        assert_eq!(
+            a_path.canonicalize().unwrap(),
+            temp_dir.path.join("a").canonicalize().unwrap()
+        );
+        let b_path = graph.resolve("b", &a_path).unwrap();
+        assert_eq!(
+            b_path.canonicalize().unwrap(),
+            temp_dir.path.join("b").canonicalize().unwrap()
+        );
+    }
+
+    #[test]
+    fn dependencies_resolved_per_declarer() {
+        let temp_dir = TempDir::new();
+        create_crate(
+            &temp_dir,
+            "app",
+            "app",
+            "a = { path = \"../a\" }\nshared = { path = \"../shared-2\" }\n",
+        );
+        create_crate(&temp_dir, "a", "a", "shared = { path = \"../shared-1\" }\n");
+        create_crate(&temp_dir, "shared-1", "shared", "");
+        temp_dir
+            .create_file(
+                &format!("shared-2/{MANIFEST_PATH}"),
+                "[package]\nname = \"shared\"\nversion = \"2.0.0\"\nedition = \"2021\"\n",
+            )
+            .unwrap();
+        temp_dir.create_file("shared-2/src/lib.rs", "").unwrap();
+
+        let graph = load_dependency_graph(&temp_dir.path.join("app")).unwrap();
+
+        let direct_path = graph.resolve("shared", &temp_dir.path.join("app")).unwrap();
+        let a_path = graph.resolve("a", &temp_dir.path.join("app")).unwrap();
+        let transitive_path = graph.resolve("shared", &a_path).unwrap();
+        assert!(direct_path.ends_with("shared-2"));
+        assert!(transitive_path.ends_with("shared-1"));
+    }
+
+    #[test]
+    fn missing_manifest() {
+        let temp_dir = TempDir::new();
+
+        let result = load_dependency_graph(&temp_dir.path);
+
+        assert_matches!(result, Err(DependencyResolutionError::RetrievalFailure(_)));
+    }
+}
@@ -0,0 +1,182 @@
+use super::MANIFEST_PATH;
+use crate::error::LibraryError;
+use crate::languages::Language;
+use crate::loading_options::FeatureSelection;
+use std::collections::{BTreeSet, HashSet};
+use std::fs;
+use std::path::Path;
+
+const DEFAULT_FEATURE: &str = "default";
+const DEPENDENCY_PREFIX: &str = "dep:";
+
+/// Resolve the features of the crate at `path` that `selection` enables, directly or through
+/// other features.
+pub fn resolve_features(
+    path: &Path,
+    selection: &FeatureSelection,
+) -> Result<Vec<String>, LibraryError> {
+    let content =
+        fs::read_to_string(path.join(MANIFEST_PATH)).map_err(LibraryError::MissingManifest)?;
+    let manifest: toml::Table =
+        toml::from_str(&content).map_err(|e| LibraryError::MalformedManifest {
+            language: Language::Rust,
+            error: e.to_string(),
+        })?;
+    let feature_table = manifest
+        .get("features")
+        .and_then(toml::Value::as_table)
+        .cloned()
+        .unwrap_or_default();
+    let optional_dependencies = get_implicit_features(&manifest, &feature_table);
+    let is_feature =
+        |name: &str| feature_table.contains_key(name) || optional_dependencies.contains(name);
+
+    let mut pending = Vec::new();
+    if selection.all_features {
+        pending.extend(feature_table.keys().cloned());
+        pending.extend(optional_dependencies.iter().cloned());
+    }
+    if !selection.no_default_features && feature_table.contains_key(DEFAULT_FEATURE) {
+        pending.push(DEFAULT_FEATURE.to_string());
+    }
+    for feature in &selection.features {
+        if !is_feature(feature) {
+            return Err(LibraryError::UnknownFeature(feature.clone()));
+        }
+        pending.push(feature.clone());
+    }
+
+    let mut features = BTreeSet::new();
+    while let Some(feature) = pending.pop() {
+        if !features.insert(feature.clone()) {
+            continue;
+        }
+        let enabled = feature_table
+            .get(&feature)
+            .and_then(toml::Value::as_array)
+            .into_iter()
+            .flatten()
+            .filter_map(toml::Value::as_str);
+        for entry in enabled {
+            // `dependency/feature` enables the optional dependency, unlike `dependency?/feature`.
+            let feature = match entry.split_once('/') {
+                Some((dependency, _)) if !dependency.ends_with('?') => dependency,
+                Some(_) => continue,
+                None => entry,
+            };
+            if !feature.starts_with(DEPENDENCY_PREFIX) && is_feature(feature) {
+                pending.push(feature.to_string());
+            }
+        }
+    }
+    Ok(features.into_iter().collect())
+}
+
+/// Get the features that optional dependencies implicitly define, which they do unless a feature
+/// refers to them with `dep:`.
+fn get_implicit_features(manifest: &toml::Table, feature_table: &toml::Table) -> HashSet<String> {
+    let explicit_dependencies = feature_table
+        .values()
+        .filter_map(toml::Value::as_array)
+        .flatten()
+        .filter_map(toml::Value::as_str)
+        .filter_map(|entry| entry.strip_prefix(DEPENDENCY_PREFIX))
+        .collect::<HashSet<_>>();
+    manifest
+        .get("dependencies")
+        .and_then(toml::Value::as_table)
+        .into_iter()
+        .flatten()
+        .filter(|(_, specification)| {
+            specification
+                .get("optional")
+                .and_then(toml::Value::as_bool)
+                .unwrap_or(false)
+        })
+        .map(|(name, _)| name)
+        .filter(|name| !explicit_dependencies.contains(name.as_str()))
+        .cloned()
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use assertables::assert_matches;
+    use daipendency_testing::tempdir::TempDir;
+
+    const STUB_MANIFEST: &str = r#"[package]
+name = "net-lib"
+
+[dependencies]
+serde = { version = "1", optional = true }
+tokio = { version = "1", optional = true }
+
+[features]
+default = ["fs"]
+fs = []
+net = ["io", "tokio?/net"]
+io = []
+full = ["fs", "net", "dep:tokio", "serde/derive"]
+"#;
+
+    fn select(features: &[&str], all_features: bool, no_default_features: bool) -> Vec<String> {
+        let temp_dir = TempDir::new();
+        temp_dir.create_file(MANIFEST_PATH, STUB_MANIFEST).unwrap();
+        let selection = FeatureSelection {
+            features: features.iter().map(|f| f.to_string()).collect(),
+            all_features,
+            no_default_features,
+        };
+        resolve_features(&temp_dir.path, &selection).unwrap()
+    }
+
+    mod resolution {
+        use super::*;
+
+        #[test]
+        fn default_features() {
+            assert_eq!(select(&[], false, false), vec!["default", "fs"]);
+        }
+
+        #[test]
+        fn no_default_features() {
+            assert!(select(&[], false, true).is_empty());
+        }
+
+        #[test]
+        fn enabled_features() {
+            assert_eq!(select(&["net"], false, true), vec!["io", "net"]);
+        }
+
+        #[test]
+        fn optional_dependencies() {
+            assert_eq!(
+                select(&["full"], false, true),
+                vec!["fs", "full", "io", "net", "serde"]
+            );
+        }
+
+        #[test]
+        fn all_features() {
+            assert_eq!(
+                select(&[], true, false),
+                vec!["default", "fs", "full", "io", "net", "serde"]
+            );
+        }
+
+        #[test]
+        fn unknown_feature() {
+            let temp_dir = TempDir::new();
+            temp_dir.create_file(MANIFEST_PATH, STUB_MANIFEST).unwrap();
+            let selection = FeatureSelection {
+                features: vec!["tokio".to_string()],
+                ..Default::default()
+            };
+
+            let result = resolve_features(&temp_dir.path, &selection);
+
+            assert_matches!(result, Err(LibraryError::UnknownFeature(name)) if name == "tokio");
+        }
+    }
+}
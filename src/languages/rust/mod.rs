@@ -0,0 +1,192 @@
+use daipendency_extractor::{
+    DependencyResolutionError, ExtractionError, Extractor, LibraryMetadata, LibraryMetadataError,
+    Namespace,
+};
+use std::fs;
+use std::path::{Path, PathBuf};
+use tree_sitter::{Language, Parser};
+
+mod cfg;
+mod configuration;
+mod dependency_graph;
+mod features;
+mod semver;
+mod symbol_kind;
+mod target;
+mod traits;
+mod workspace;
+
+pub use configuration::select_configuration;
+pub use dependency_graph::load_dependency_graph;
+pub use semver::classify_change;
+pub use symbol_kind::detect_symbol_kind;
+pub use workspace::list_members as list_workspace_members;
+
+const MANIFEST_PATH: &str = "Cargo.toml";
+const DEPENDENCIES_TABLE: &str = "dependencies";
+
+/// The Rust extractor, with traits rendered from the source code so that required and provided
+/// methods can be told apart.
+pub struct RustExtractor(daipendency_extractor_rust::RustExtractor);
+
+impl RustExtractor {
+    pub fn new() -> Self {
+        Self(daipendency_extractor_rust::RustExtractor::new())
+    }
+}
+
+impl Extractor for RustExtractor {
+    fn get_parser_language(&self) -> Language {
+        self.0.get_parser_language()
+    }
+
+    fn get_library_metadata(&self, path: &Path) -> Result<LibraryMetadata, LibraryMetadataError> {
+        self.0.get_library_metadata(path)
+    }
+
+    fn extract_public_api(
+        &self,
+        metadata: &LibraryMetadata,
+        parser: &mut Parser,
+    ) -> Result<Vec<Namespace>, ExtractionError> {
+        let mut namespaces = self.0.extract_public_api(metadata, parser)?;
+        traits::render_traits(metadata, parser, &mut namespaces)?;
+        Ok(namespaces)
+    }
+
+    fn resolve_dependency_path(
+        &self,
+        dependency_name: &str,
+        dependant_path: &Path,
+    ) -> Result<PathBuf, DependencyResolutionError> {
+        self.0
+            .resolve_dependency_path(dependency_name, dependant_path)
+    }
+}
+
+/// List the normal dependencies of the crate at `path`, including target-specific ones.
+///
+/// Renamed dependencies are listed by their package name, since that is how they are resolved,
+/// including those renamed in the workspace that they are inherited from.
+pub fn list_dependencies(path: &Path) -> Result<Vec<String>, DependencyResolutionError> {
+    let content = fs::read_to_string(path.join(MANIFEST_PATH))
+        .map_err(|e| DependencyResolutionError::RetrievalFailure(e.to_string()))?;
+    let manifest: toml::Table = toml::from_str(&content)
+        .map_err(|e| DependencyResolutionError::RetrievalFailure(e.to_string()))?;
+
+    let target_tables = manifest
+        .get("target")
+        .and_then(toml::Value::as_table)
+        .into_iter()
+        .flat_map(|targets| targets.values());
+    let dependency_tables = std::iter::once(&manifest)
+        .chain(target_tables.filter_map(toml::Value::as_table))
+        .filter_map(|table| table.get(DEPENDENCIES_TABLE))
+        .filter_map(toml::Value::as_table);
+
+    let mut workspace_dependencies = None;
+    let mut names = Vec::new();
+    for dependencies in dependency_tables {
+        for (key, specification) in dependencies {
+            let is_inherited = specification
+                .get("workspace")
+                .and_then(toml::Value::as_bool)
+                .unwrap_or(false);
+            let specification = match is_inherited {
+                true => workspace_dependencies
+                    .get_or_insert_with(|| get_workspace_dependencies(path))
+                    .get(key)
+                    .unwrap_or(specification),
+                false => specification,
+            };
+            let name = specification
+                .get("package")
+                .and_then(toml::Value::as_str)
+                .unwrap_or(key)
+                .to_string();
+            if !names.contains(&name) {
+                names.push(name);
+            }
+        }
+    }
+    Ok(names)
+}
+
+fn get_workspace_dependencies(path: &Path) -> toml::Table {
+    workspace::find_workspace_table(path)
+        .and_then(|mut workspace| workspace.remove(DEPENDENCIES_TABLE))
+        .and_then(|dependencies| match dependencies {
+            toml::Value::Table(dependencies) => Some(dependencies),
+            _ => None,
+        })
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use assertables::assert_matches;
+    use daipendency_testing::tempdir::TempDir;
+
+    fn list(manifest: &str) -> Vec<String> {
+        let temp_dir = TempDir::new();
+        temp_dir.create_file(MANIFEST_PATH, manifest).unwrap();
+        list_dependencies(&temp_dir.path).unwrap()
+    }
+
+    #[test]
+    fn normal_dependencies() {
+        let names = list(
+            "[package]\nname = \"app\"\n\n[dependencies]\nserde = \"1\"\ntokio = { version = \"1\" }\n\n[dev-dependencies]\ntempfile = \"3\"\n",
+        );
+
+        assert_eq!(names, vec!["serde", "tokio"]);
+    }
+
+    #[test]
+    fn renamed_dependency() {
+        let names =
+            list("[dependencies]\nfutures03 = { package = \"futures\", version = \"0.3\" }\n");
+
+        assert_eq!(names, vec!["futures"]);
+    }
+
+    #[test]
+    fn target_dependencies() {
+        let names = list(
+            "[dependencies]\nlibc = \"0.2\"\n\n[target.'cfg(windows)'.dependencies]\nwinapi = \"0.3\"\nlibc = \"0.2\"\n",
+        );
+
+        assert_eq!(names, vec!["libc", "winapi"]);
+    }
+
+    #[test]
+    fn inherited_dependencies() {
+        let temp_dir = TempDir::new();
+        temp_dir
+            .create_file(
+                MANIFEST_PATH,
+                "[workspace]\nmembers = [\"app\"]\n\n[workspace.dependencies]\nserde = \"1\"\nfutures03 = { package = \"futures\", version = \"0.3\" }\n",
+            )
+            .unwrap();
+        temp_dir
+            .create_file(
+                &format!("app/{MANIFEST_PATH}"),
+                "[package]\nname = \"app\"\n\n[dependencies]\nserde = { workspace = true }\nfutures03 = { workspace = true, features = [\"std\"] }\n",
+            )
+            .unwrap();
+
+        let names = list_dependencies(&temp_dir.path.join("app")).unwrap();
+
+        assert_eq!(names, vec!["futures", "serde"]);
+    }
+
+    #[test]
+    fn missing_manifest() {
+        let temp_dir = TempDir::new();
+
+        let result = list_dependencies(&temp_dir.path);
+
+        assert_matches!(result, Err(DependencyResolutionError::RetrievalFailure(_)));
+    }
+}
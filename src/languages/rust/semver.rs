@@ -0,0 +1,592 @@
+use crate::comments::strip_c_style_comments;
+use crate::diff::ChangeKind;
+use crate::semver::SemverImpact;
+use std::collections::{BTreeMap, BTreeSet};
+
+const NON_EXHAUSTIVE_ATTRIBUTE: &str = "#[non_exhaustive]";
+const DERIVE_ATTRIBUTE_PREFIX: &str = "#[derive(";
+const VISIBILITY_PREFIX: &str = "pub";
+
+/// Classify a change to an item following the Rust semver rules in the Cargo book.
+///
+/// Provided trait methods are told apart from required ones by the placeholder body that they
+/// are rendered with, so only adding a required method to a trait is considered breaking.
+pub fn classify_change(
+    kind: ChangeKind,
+    old_source_code: Option<&str>,
+    new_source_code: Option<&str>,
+) -> SemverImpact {
+    match (kind, old_source_code, new_source_code) {
+        (ChangeKind::Added, ..) => SemverImpact::Minor,
+        (ChangeKind::Changed, Some(old), Some(new)) => {
+            classify_item_change(&Item::parse(old), &Item::parse(new))
+        }
+        _ => SemverImpact::Major,
+    }
+}
+
+/// An item split into the parts that semver rules tell apart.
+#[derive(Debug, PartialEq, Eq)]
+struct Item {
+    attributes: Vec<String>,
+    /// The declaration up to the body, with whitespace normalised (e.g. `pub struct Config`).
+    header: String,
+    /// The fields, variants or associated items in the body, keyed by name.
+    members: BTreeMap<String, String>,
+}
+
+impl Item {
+    fn parse(source_code: &str) -> Self {
+        // Brackets in comments mustn't be mistaken for those of the item.
+        let source_code = strip_c_style_comments(source_code);
+        let mut attributes = Vec::new();
+        let mut declaration = Vec::new();
+        for line in source_code.lines().map(str::trim) {
+            if line.is_empty() || line.starts_with("//") {
+                continue;
+            }
+            if declaration.is_empty() && line.starts_with("#[") {
+                attributes.push(normalise_whitespace(line));
+            } else {
+                declaration.push(line);
+            }
+        }
+        let declaration = declaration.join("\n");
+        let (header, members) = match (declaration.find('{'), declaration.rfind('}')) {
+            (Some(start), Some(end)) if start < end => (
+                &declaration[..start],
+                split_members(&declaration[start + 1..end])
+                    .into_iter()
+                    .map(|member| (get_member_name(&member).to_string(), member))
+                    .collect(),
+            ),
+            _ => (declaration.as_str(), BTreeMap::new()),
+        };
+        Self {
+            attributes,
+            header: normalise_whitespace(header),
+            members,
+        }
+    }
+
+    fn get_kind(&self) -> Option<&str> {
+        let keywords = ["struct", "enum", "union", "trait"];
+        self.header
+            .split([' ', '<', '('])
+            .find(|word| keywords.contains(word))
+    }
+
+    fn is_non_exhaustive(&self) -> bool {
+        self.attributes
+            .iter()
+            .any(|attribute| attribute == NON_EXHAUSTIVE_ATTRIBUTE)
+    }
+
+    fn get_derived_traits(&self) -> BTreeSet<&str> {
+        self.attributes
+            .iter()
+            .filter_map(|attribute| {
+                attribute
+                    .strip_prefix(DERIVE_ATTRIBUTE_PREFIX)?
+                    .strip_suffix(")]")
+            })
+            .flat_map(|traits| traits.split(','))
+            .map(str::trim)
+            .collect()
+    }
+
+    /// Whether the item has fields that code outside the crate cannot set.
+    fn has_private_fields(&self) -> bool {
+        self.members.values().any(|member| !is_public(member))
+    }
+}
+
+fn classify_item_change(old: &Item, new: &Item) -> SemverImpact {
+    if old.header != new.header {
+        return SemverImpact::Major;
+    }
+    let attribute_impact = classify_attribute_change(old, new);
+    let member_impact = match old.get_kind() {
+        Some("enum") => classify_variant_changes(old, new),
+        Some("struct" | "union") => classify_field_changes(old, new),
+        Some("trait") => classify_associated_item_changes(old, new),
+        _ if old.members != new.members => SemverImpact::Major,
+        _ => SemverImpact::Patch,
+    };
+    attribute_impact.max(member_impact)
+}
+
+fn classify_attribute_change(old: &Item, new: &Item) -> SemverImpact {
+    let old_traits = old.get_derived_traits();
+    let new_traits = new.get_derived_traits();
+    if (!old.is_non_exhaustive() && new.is_non_exhaustive()) || !old_traits.is_subset(&new_traits) {
+        SemverImpact::Major
+    } else if old.is_non_exhaustive() != new.is_non_exhaustive() || old_traits != new_traits {
+        SemverImpact::Minor
+    } else {
+        SemverImpact::Patch
+    }
+}
+
+fn classify_variant_changes(old: &Item, new: &Item) -> SemverImpact {
+    classify_member_changes(old, new, |_| {
+        if old.is_non_exhaustive() {
+            SemverImpact::Minor
+        } else {
+            SemverImpact::Major
+        }
+    })
+}
+
+fn classify_field_changes(old: &Item, new: &Item) -> SemverImpact {
+    // Structs with private fields cannot be built or exhaustively matched outside the crate.
+    let is_closed = old.has_private_fields() || old.is_non_exhaustive();
+    let mut impact = SemverImpact::Patch;
+    for (name, old_field) in &old.members {
+        let new_field = new.members.get(name);
+        let field_impact = if is_public(old_field) {
+            if new_field == Some(old_field) {
+                SemverImpact::Patch
+            } else {
+                SemverImpact::Major
+            }
+        } else if new_field.is_some_and(|field| is_public(field)) {
+            SemverImpact::Minor
+        } else {
+            SemverImpact::Patch
+        };
+        impact = impact.max(field_impact);
+    }
+    for (name, new_field) in &new.members {
+        if !old.members.contains_key(name) {
+            let field_impact = match (is_closed, is_public(new_field)) {
+                (true, true) => SemverImpact::Minor,
+                (true, false) => SemverImpact::Patch,
+                (false, _) => SemverImpact::Major,
+            };
+            impact = impact.max(field_impact);
+        }
+    }
+    impact
+}
+
+fn classify_associated_item_changes(old: &Item, new: &Item) -> SemverImpact {
+    classify_member_changes(old, new, |item| {
+        if has_default(item) {
+            SemverImpact::Minor
+        } else {
+            SemverImpact::Major
+        }
+    })
+}
+
+/// Classify changes to members that are breaking unless they are additions, whose impact is
+/// determined by `classify_addition`.
+fn classify_member_changes(
+    old: &Item,
+    new: &Item,
+    classify_addition: impl Fn(&str) -> SemverImpact,
+) -> SemverImpact {
+    let is_member_removed_or_changed = old
+        .members
+        .iter()
+        .any(|(name, old_member)| new.members.get(name) != Some(old_member));
+    if is_member_removed_or_changed {
+        return SemverImpact::Major;
+    }
+    new.members
+        .iter()
+        .filter(|(name, _)| !old.members.contains_key(*name))
+        .map(|(_, member)| classify_addition(member))
+        .max()
+        .unwrap_or(SemverImpact::Patch)
+}
+
+/// Split the body of an item into its members, which are separated by commas or semicolons
+/// outside of any brackets, or end with a block.
+fn split_members(body: &str) -> Vec<String> {
+    let mut members = Vec::new();
+    let mut member = String::new();
+    let mut depth = 0usize;
+    for character in body.chars() {
+        match character {
+            '(' | '[' | '{' => depth += 1,
+            ')' | ']' | '}' => depth = depth.saturating_sub(1),
+            _ => {}
+        }
+        let is_separator = depth == 0 && matches!(character, ',' | ';');
+        let is_end_of_block = depth == 0 && character == '}';
+        if !is_separator {
+            member.push(character);
+        }
+        if is_separator || is_end_of_block {
+            push_member(&mut members, &member, character == ';');
+            member.clear();
+        }
+    }
+    push_member(&mut members, &member, false);
+    members
+}
+
+fn push_member(members: &mut Vec<String>, member: &str, ends_with_semicolon: bool) {
+    let mut member = normalise_whitespace(member);
+    if member.is_empty() {
+        return;
+    }
+    if ends_with_semicolon {
+        member.push(';');
+    }
+    members.push(member);
+}
+
+/// Get the name of a field (`pub port: u16`), variant (`B(u8)`) or associated item
+/// (`fn name(&self) -> &str;`).
+fn get_member_name(member: &str) -> &str {
+    let mut rest = member.trim_start();
+    while rest.starts_with("#[") {
+        let end = rest.find(']').map_or(rest.len(), |index| index + 1);
+        rest = rest[end..].trim_start();
+    }
+    if let Some(visibility) = rest.strip_prefix(VISIBILITY_PREFIX) {
+        rest = match visibility.strip_prefix('(') {
+            Some(restriction) => restriction
+                .find(')')
+                .map_or("", |index| &restriction[index + 1..]),
+            None => visibility,
+        };
+    }
+    let keywords = ["fn", "type", "const", "unsafe", "async", "extern"];
+    let mut words =
+        rest.split(|character: char| !(character.is_alphanumeric() || character == '_'));
+    words
+        .find(|word| !word.is_empty() && !keywords.contains(word))
+        .unwrap_or(rest)
+}
+
+fn is_public(member: &str) -> bool {
+    member
+        .split_whitespace()
+        .find(|word| !word.starts_with("#["))
+        .is_some_and(|word| word == VISIBILITY_PREFIX)
+}
+
+/// Whether an associated item has a default (e.g. `const ID: u32 = 1;` or a method with a body).
+fn has_default(item: &str) -> bool {
+    item.ends_with('}') || (!item.starts_with("fn ") && item.contains('='))
+}
+
+fn normalise_whitespace(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn classify(old: &str, new: &str) -> SemverImpact {
+        classify_change(ChangeKind::Changed, Some(old), Some(new))
+    }
+
+    mod items {
+        use super::*;
+
+        #[test]
+        fn added_item() {
+            assert_eq!(
+                classify_change(ChangeKind::Added, None, Some("pub fn f();")),
+                SemverImpact::Minor
+            );
+        }
+
+        #[test]
+        fn removed_item() {
+            assert_eq!(
+                classify_change(ChangeKind::Removed, Some("pub fn f();"), None),
+                SemverImpact::Major
+            );
+        }
+
+        #[test]
+        fn changed_function_signature() {
+            assert_eq!(
+                classify("pub fn f();", "pub fn f(x: u8);"),
+                SemverImpact::Major
+            );
+        }
+
+        #[test]
+        fn changed_item_kind() {
+            assert_eq!(
+                classify(
+                    "pub struct S {\n    pub x: u8,\n}",
+                    "pub enum S {\n    X,\n}"
+                ),
+                SemverImpact::Major
+            );
+        }
+    }
+
+    mod attributes {
+        use super::*;
+
+        #[test]
+        fn added_derive() {
+            assert_eq!(
+                classify(
+                    "#[derive(Debug)]\npub struct S;",
+                    "#[derive(Debug, Clone)]\npub struct S;"
+                ),
+                SemverImpact::Minor
+            );
+        }
+
+        #[test]
+        fn removed_derive() {
+            assert_eq!(
+                classify(
+                    "#[derive(Debug, Clone)]\npub struct S;",
+                    "#[derive(Debug)]\npub struct S;"
+                ),
+                SemverImpact::Major
+            );
+        }
+
+        #[test]
+        fn added_non_exhaustive() {
+            assert_eq!(
+                classify(
+                    "pub enum E {\n    A,\n}",
+                    "#[non_exhaustive]\npub enum E {\n    A,\n}"
+                ),
+                SemverImpact::Major
+            );
+        }
+
+        #[test]
+        fn other_attribute() {
+            assert_eq!(
+                classify("pub fn f();", "#[must_use]\npub fn f();"),
+                SemverImpact::Patch
+            );
+        }
+    }
+
+    mod enums {
+        use super::*;
+
+        #[test]
+        fn added_variant() {
+            assert_eq!(
+                classify(
+                    "pub enum E {\n    A,\n}",
+                    "pub enum E {\n    A,\n    B(u8),\n}"
+                ),
+                SemverImpact::Major
+            );
+        }
+
+        #[test]
+        fn added_variant_to_non_exhaustive_enum() {
+            assert_eq!(
+                classify(
+                    "#[non_exhaustive]\npub enum E {\n    A,\n}",
+                    "#[non_exhaustive]\npub enum E {\n    A,\n    /// Docs\n    B { x: u8 },\n}"
+                ),
+                SemverImpact::Minor
+            );
+        }
+
+        #[test]
+        fn changed_variant() {
+            assert_eq!(
+                classify(
+                    "#[non_exhaustive]\npub enum E {\n    A(u8),\n}",
+                    "#[non_exhaustive]\npub enum E {\n    A(u16),\n}"
+                ),
+                SemverImpact::Major
+            );
+        }
+    }
+
+    mod structs {
+        use super::*;
+
+        #[test]
+        fn added_public_field_to_exhaustive_struct() {
+            assert_eq!(
+                classify(
+                    "pub struct S {\n    pub x: u8,\n}",
+                    "pub struct S {\n    pub x: u8,\n    pub y: u8,\n}"
+                ),
+                SemverImpact::Major
+            );
+        }
+
+        #[test]
+        fn added_public_field_to_struct_with_private_fields() {
+            assert_eq!(
+                classify(
+                    "pub struct S {\n    pub x: u8,\n    y: u8,\n}",
+                    "pub struct S {\n    pub x: u8,\n    y: u8,\n    pub z: u8,\n}"
+                ),
+                SemverImpact::Minor
+            );
+        }
+
+        #[test]
+        fn changed_private_field() {
+            assert_eq!(
+                classify(
+                    "pub struct S {\n    pub x: u8,\n    y: u8,\n}",
+                    "pub struct S {\n    pub x: u8,\n    y: Vec<u8>,\n    z: bool,\n}"
+                ),
+                SemverImpact::Patch
+            );
+        }
+
+        #[test]
+        fn removed_public_field() {
+            assert_eq!(
+                classify(
+                    "pub struct S {\n    pub x: u8,\n    y: u8,\n}",
+                    "pub struct S {\n    y: u8,\n}"
+                ),
+                SemverImpact::Major
+            );
+        }
+
+        #[test]
+        fn added_private_field_to_exhaustive_struct() {
+            assert_eq!(
+                classify(
+                    "pub struct S {\n    pub x: u8,\n}",
+                    "pub struct S {\n    pub x: u8,\n    y: u8,\n}"
+                ),
+                SemverImpact::Major
+            );
+        }
+
+        #[test]
+        fn changed_tuple_struct() {
+            assert_eq!(
+                classify("pub struct S(pub u8);", "pub struct S(pub u8, u8);"),
+                SemverImpact::Major
+            );
+        }
+    }
+
+    mod traits {
+        use super::*;
+
+        #[test]
+        fn added_method() {
+            assert_eq!(
+                classify(
+                    "pub trait T {\n    fn a(&self);\n}",
+                    "pub trait T {\n    fn a(&self);\n    fn b(&self) -> u8;\n}"
+                ),
+                SemverImpact::Major
+            );
+        }
+
+        #[test]
+        fn added_provided_method() {
+            assert_eq!(
+                classify(
+                    "pub trait T {\n    fn a(&self);\n}",
+                    "pub trait T {\n    fn a(&self);\n    fn b(&self) -> u8 {\n        0\n    }\n}"
+                ),
+                SemverImpact::Minor
+            );
+        }
+
+        #[test]
+        fn added_rendered_provided_method() {
+            assert_eq!(
+                classify(
+                    "pub trait T {\n    fn a(&self);\n}",
+                    "pub trait T {\n    fn a(&self);\n    /// Docs\n    fn b(&self) -> u8 { ... }\n}"
+                ),
+                SemverImpact::Minor
+            );
+        }
+
+        #[test]
+        fn added_constant_with_default() {
+            assert_eq!(
+                classify(
+                    "pub trait T {\n    fn a(&self);\n}",
+                    "pub trait T {\n    fn a(&self);\n    const ID: u32 = 1;\n}"
+                ),
+                SemverImpact::Minor
+            );
+        }
+
+        #[test]
+        fn changed_method() {
+            assert_eq!(
+                classify(
+                    "pub trait T {\n    fn a(&self);\n}",
+                    "pub trait T {\n    fn a(&mut self);\n}"
+                ),
+                SemverImpact::Major
+            );
+        }
+    }
+
+    mod comments {
+        use super::*;
+
+        #[test]
+        fn block_doc_comment_with_brackets() {
+            assert_eq!(
+                classify(
+                    "/** Like `{ x }` */\npub struct S {\n    pub x: u8,\n}",
+                    "/** Like `{ x }`, but changed */\npub struct S {\n    pub x: u8,\n}"
+                ),
+                SemverImpact::Patch
+            );
+        }
+
+        #[test]
+        fn block_doc_comment_before_attributes() {
+            assert_eq!(
+                classify(
+                    "/**\n * Docs\n */\n#[non_exhaustive]\npub enum E {\n    A,\n}",
+                    "/**\n * Docs\n */\n#[non_exhaustive]\npub enum E {\n    A,\n    B,\n}"
+                ),
+                SemverImpact::Minor
+            );
+        }
+    }
+
+    mod members {
+        use super::*;
+
+        #[test]
+        fn member_names() {
+            assert_eq!(get_member_name("pub port: u16"), "port");
+            assert_eq!(get_member_name("pub(crate) host: String"), "host");
+            assert_eq!(get_member_name("#[serde(default)] pub port: u16"), "port");
+            assert_eq!(get_member_name("B(u8)"), "B");
+            assert_eq!(get_member_name("fn name(&self) -> &str;"), "name");
+            assert_eq!(get_member_name("unsafe fn f();"), "f");
+        }
+
+        #[test]
+        fn nested_brackets() {
+            assert_eq!(
+                split_members("\n    A,\n    B { x: u8, y: u8 },\n    C(u8, u8)\n"),
+                vec!["A", "B { x: u8, y: u8 }", "C(u8, u8)"]
+            );
+        }
+
+        #[test]
+        fn blocks() {
+            assert_eq!(
+                split_members("fn a(&self);\n    fn b(&self) { 0 }\n    type X;"),
+                vec!["fn a(&self);", "fn b(&self) { 0 }", "type X;"]
+            );
+        }
+    }
+}
@@ -0,0 +1,130 @@
+use super::cfg::split_item_predicates;
+use crate::symbol_kind::SymbolKind;
+
+/// Words that may precede the keyword of an item (e.g. `pub(crate) unsafe extern "C" fn`).
+const QUALIFIERS: [&str; 6] = ["pub", "in", "unsafe", "async", "extern", "default"];
+
+/// Detect the kind of the item declared by `source_code`, skipping its doc comments and attributes.
+pub fn detect_symbol_kind(source_code: &str) -> Option<SymbolKind> {
+    let (_, declaration) = split_item_predicates(source_code);
+    let mut words = declaration
+        .split(|character: char| character.is_whitespace() || character == '(')
+        .filter(|word| !word.is_empty())
+        .skip_while(|word| is_qualifier(word));
+    match words.next()? {
+        "fn" => Some(SymbolKind::Function),
+        "const" if words.find(|word| !is_qualifier(word)) == Some("fn") => {
+            Some(SymbolKind::Function)
+        }
+        "const" | "static" => Some(SymbolKind::Constant),
+        "struct" | "union" => Some(SymbolKind::Struct),
+        "enum" => Some(SymbolKind::Enum),
+        "trait" => Some(SymbolKind::Trait),
+        "type" => Some(SymbolKind::TypeAlias),
+        "macro_rules!" | "macro" => Some(SymbolKind::Macro),
+        "mod" => Some(SymbolKind::Module),
+        _ => None,
+    }
+}
+
+fn is_qualifier(word: &str) -> bool {
+    QUALIFIERS.contains(&word) || word.starts_with('"') || word.ends_with(')')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn functions() {
+        for source_code in [
+            "pub fn f();",
+            "pub const fn f();",
+            "pub const unsafe fn f();",
+            "pub const unsafe extern \"C\" fn f();",
+            "pub async fn f();",
+            "pub unsafe extern \"C\" fn f();",
+            "pub(crate) fn f();",
+            "pub(in crate::a) fn f();",
+        ] {
+            assert_eq!(
+                detect_symbol_kind(source_code),
+                Some(SymbolKind::Function),
+                "{source_code}"
+            );
+        }
+    }
+
+    #[test]
+    fn types() {
+        assert_eq!(
+            detect_symbol_kind("pub struct S;"),
+            Some(SymbolKind::Struct)
+        );
+        assert_eq!(
+            detect_symbol_kind("pub union U {\n    a: u8,\n}"),
+            Some(SymbolKind::Struct)
+        );
+        assert_eq!(
+            detect_symbol_kind("pub enum E { A }"),
+            Some(SymbolKind::Enum)
+        );
+        assert_eq!(
+            detect_symbol_kind("pub unsafe trait T {}"),
+            Some(SymbolKind::Trait)
+        );
+        assert_eq!(
+            detect_symbol_kind("pub type A = u8;"),
+            Some(SymbolKind::TypeAlias)
+        );
+    }
+
+    #[test]
+    fn constants() {
+        assert_eq!(
+            detect_symbol_kind("pub const C: u8;"),
+            Some(SymbolKind::Constant)
+        );
+        assert_eq!(
+            detect_symbol_kind("pub static S: u8;"),
+            Some(SymbolKind::Constant)
+        );
+    }
+
+    #[test]
+    fn macros() {
+        assert_eq!(
+            detect_symbol_kind("#[macro_export]\nmacro_rules! m;"),
+            Some(SymbolKind::Macro)
+        );
+    }
+
+    #[test]
+    fn modules() {
+        assert_eq!(detect_symbol_kind("pub mod m;"), Some(SymbolKind::Module));
+    }
+
+    #[test]
+    fn doc_comments_and_attributes() {
+        assert_eq!(
+            detect_symbol_kind("/// Docs\n#[derive(Debug)]\npub struct S;"),
+            Some(SymbolKind::Struct)
+        );
+    }
+
+    #[test]
+    fn block_doc_comments_and_multi_line_attributes() {
+        assert_eq!(
+            detect_symbol_kind(
+                "/**\n * Docs\n */\n#[cfg(all(\n    unix,\n    feature = \"net\"\n))]\npub struct S;"
+            ),
+            Some(SymbolKind::Struct)
+        );
+    }
+
+    #[test]
+    fn unrecognised_items() {
+        assert_eq!(detect_symbol_kind("impl S {}"), None);
+        assert_eq!(detect_symbol_kind(""), None);
+    }
+}
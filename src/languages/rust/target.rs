@@ -0,0 +1,379 @@
+use std::env;
+use std::ffi::OsString;
+use std::process::Command;
+
+const RUSTC_VARIABLE: &str = "RUSTC";
+const DEFAULT_RUSTC: &str = "rustc";
+const VENDORS: [&str; 7] = ["unknown", "pc", "apple", "uwp", "fortanix", "nvidia", "sun"];
+const UNIX_OPERATING_SYSTEMS: [&str; 16] = [
+    "linux",
+    "android",
+    "macos",
+    "ios",
+    "tvos",
+    "watchos",
+    "visionos",
+    "freebsd",
+    "netbsd",
+    "openbsd",
+    "dragonfly",
+    "solaris",
+    "illumos",
+    "fuchsia",
+    "haiku",
+    "aix",
+];
+const OTHER_OPERATING_SYSTEMS: [&str; 7] = [
+    "windows",
+    "wasi",
+    "emscripten",
+    "redox",
+    "uefi",
+    "none",
+    "unknown",
+];
+
+/// The configuration options that a target triple such as `x86_64-unknown-linux-gnu` implies,
+/// as in `rustc --print cfg --target <triple>`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Target {
+    pub arch: String,
+    pub vendor: String,
+    pub os: String,
+    pub env: String,
+    pub families: Vec<String>,
+    pub pointer_width: String,
+    pub endian: String,
+}
+
+impl Target {
+    /// Get the options of a target triple from `rustc` (or `$RUSTC`), or infer them from the
+    /// triple itself if `rustc` can't be run.
+    ///
+    /// Returns `None` if the target is not recognised.
+    pub fn load(triple: &str) -> Option<Self> {
+        let rustc = env::var_os(RUSTC_VARIABLE).unwrap_or_else(|| OsString::from(DEFAULT_RUSTC));
+        let Ok(output) = Command::new(rustc)
+            .args(["--print", "cfg", "--target", triple])
+            .output()
+        else {
+            return Self::parse(triple);
+        };
+        if !output.status.success() {
+            return None;
+        }
+        Self::parse_cfg(&String::from_utf8_lossy(&output.stdout))
+    }
+
+    /// Parse the output of `rustc --print cfg`, such as `target_os="linux"` on each line.
+    pub fn parse_cfg(output: &str) -> Option<Self> {
+        let options = output
+            .lines()
+            .filter_map(|line| {
+                let (name, value) = line.split_once('=')?;
+                Some((name, value.trim_matches('"')))
+            })
+            .collect::<Vec<_>>();
+        let get_option = |name: &str| {
+            options
+                .iter()
+                .find(|(option_name, _)| *option_name == name)
+                .map(|(_, value)| value.to_string())
+        };
+        Some(Self {
+            arch: get_option("target_arch")?,
+            vendor: get_option("target_vendor").unwrap_or_else(|| "unknown".to_string()),
+            os: get_option("target_os")?,
+            env: get_option("target_env").unwrap_or_default(),
+            families: options
+                .iter()
+                .filter(|(name, _)| *name == "target_family")
+                .map(|(_, family)| family.to_string())
+                .collect(),
+            pointer_width: get_option("target_pointer_width")?,
+            endian: get_option("target_endian")?,
+        })
+    }
+
+    /// Parse a target triple, or return `None` if its operating system is not recognised.
+    pub fn parse(triple: &str) -> Option<Self> {
+        let components = triple.split('-').collect::<Vec<_>>();
+        let (raw_arch, rest) = components.split_first()?;
+        let (vendor, rest) = match rest {
+            [vendor, rest @ ..] if !rest.is_empty() && VENDORS.contains(vendor) => (*vendor, rest),
+            _ => ("unknown", rest),
+        };
+        let (raw_os, raw_env) = match rest {
+            [os] => (*os, ""),
+            [os, env] => (*os, *env),
+            _ => return None,
+        };
+        let (os, env) = match (normalise_os(raw_os)?, raw_env) {
+            ("linux", env) if env.starts_with("android") => ("android", ""),
+            (os, env) => (os, normalise_env(env)),
+        };
+
+        let arch = normalise_arch(raw_arch);
+        let mut families = Vec::new();
+        if UNIX_OPERATING_SYSTEMS.contains(&os) || os == "emscripten" || os == "redox" {
+            families.push("unix".to_string());
+        }
+        if os == "windows" {
+            families.push("windows".to_string());
+        }
+        if arch.starts_with("wasm") {
+            families.push("wasm".to_string());
+        }
+        Some(Self {
+            pointer_width: get_pointer_width(&arch).to_string(),
+            endian: get_endian(raw_arch).to_string(),
+            arch,
+            vendor: vendor.to_string(),
+            os: os.to_string(),
+            env: env.to_string(),
+            families,
+        })
+    }
+
+    /// Evaluate the option `name` (with its `value`, if any), or return `None` if the option
+    /// does not depend on the target or cannot be inferred from the triple.
+    pub fn evaluate_option(&self, name: &str, value: Option<&str>) -> Option<bool> {
+        match (name, value) {
+            ("unix" | "windows", None) => Some(self.has_family(name)),
+            ("target_family", Some(family)) => Some(self.has_family(family)),
+            ("target_os", Some(os)) => Some(self.os == os),
+            ("target_arch", Some(arch)) => Some(self.arch == arch),
+            ("target_vendor", Some(vendor)) => Some(self.vendor == vendor),
+            ("target_env", Some(env)) => Some(self.env == env),
+            ("target_pointer_width", Some(width)) => Some(self.pointer_width == width),
+            ("target_endian", Some(endian)) => Some(self.endian == endian),
+            _ => None,
+        }
+    }
+
+    fn has_family(&self, family: &str) -> bool {
+        self.families.iter().any(|known| known == family)
+    }
+}
+
+fn normalise_os(os: &str) -> Option<&'static str> {
+    match os {
+        "darwin" => Some("macos"),
+        "wasip1" | "wasip2" => Some("wasi"),
+        _ => UNIX_OPERATING_SYSTEMS
+            .into_iter()
+            .chain(OTHER_OPERATING_SYSTEMS)
+            .find(|known| *known == os),
+    }
+}
+
+fn normalise_env(env: &str) -> &'static str {
+    ["gnu", "musl", "msvc", "uclibc", "sgx"]
+        .into_iter()
+        .find(|known| env.starts_with(known))
+        .unwrap_or("")
+}
+
+fn normalise_arch(arch: &str) -> String {
+    let normalised = match arch {
+        "i386" | "i586" | "i686" => "x86",
+        "powerpc64le" => "powerpc64",
+        "mipsel" => "mips",
+        "mips64el" => "mips64",
+        _ if arch.starts_with("arm") || arch.starts_with("thumb") => "arm",
+        _ if arch.starts_with("riscv64") => "riscv64",
+        _ if arch.starts_with("riscv32") => "riscv32",
+        _ => arch,
+    };
+    normalised.to_string()
+}
+
+fn get_pointer_width(arch: &str) -> &'static str {
+    if arch.contains("64") || arch == "s390x" {
+        "64"
+    } else {
+        "32"
+    }
+}
+
+fn get_endian(arch: &str) -> &'static str {
+    let is_big_endian = [
+        "s390x",
+        "powerpc",
+        "powerpc64",
+        "sparc",
+        "sparc64",
+        "mips",
+        "mips64",
+    ]
+    .contains(&arch)
+        || arch.ends_with("_be");
+    if is_big_endian {
+        "big"
+    } else {
+        "little"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    mod parsing {
+        use super::*;
+
+        #[test]
+        fn linux() {
+            let target = Target::parse("x86_64-unknown-linux-gnu").unwrap();
+
+            assert_eq!(
+                target,
+                Target {
+                    arch: "x86_64".to_string(),
+                    vendor: "unknown".to_string(),
+                    os: "linux".to_string(),
+                    env: "gnu".to_string(),
+                    families: vec!["unix".to_string()],
+                    pointer_width: "64".to_string(),
+                    endian: "little".to_string(),
+                }
+            );
+        }
+
+        #[test]
+        fn windows() {
+            let target = Target::parse("i686-pc-windows-msvc").unwrap();
+
+            assert_eq!(target.arch, "x86");
+            assert_eq!(target.os, "windows");
+            assert_eq!(target.env, "msvc");
+            assert_eq!(target.families, vec!["windows"]);
+            assert_eq!(target.pointer_width, "32");
+        }
+
+        #[test]
+        fn apple() {
+            let target = Target::parse("aarch64-apple-darwin").unwrap();
+
+            assert_eq!(target.vendor, "apple");
+            assert_eq!(target.os, "macos");
+            assert_eq!(target.env, "");
+        }
+
+        #[test]
+        fn without_vendor() {
+            let target = Target::parse("aarch64-linux-android").unwrap();
+
+            assert_eq!(target.vendor, "unknown");
+            assert_eq!(target.os, "android");
+            assert_eq!(target.env, "");
+        }
+
+        #[test]
+        fn webassembly() {
+            let target = Target::parse("wasm32-unknown-unknown").unwrap();
+
+            assert_eq!(target.os, "unknown");
+            assert_eq!(target.families, vec!["wasm"]);
+        }
+
+        #[test]
+        fn embedded() {
+            let target = Target::parse("thumbv7em-none-eabihf").unwrap();
+
+            assert_eq!(target.arch, "arm");
+            assert_eq!(target.os, "none");
+            assert!(target.families.is_empty());
+        }
+
+        #[test]
+        fn unknown_operating_system() {
+            assert_eq!(Target::parse("x86_64-unknown-plan9"), None);
+            assert_eq!(Target::parse("linux"), None);
+        }
+    }
+
+    mod rustc_configuration {
+        use super::*;
+
+        const STUB_CFG_OUTPUT: &str = "debug_assertions\npanic=\"unwind\"\ntarget_arch=\"x86_64\"\ntarget_endian=\"little\"\ntarget_env=\"gnu\"\ntarget_family=\"unix\"\ntarget_feature=\"sse2\"\ntarget_os=\"linux\"\ntarget_pointer_width=\"64\"\ntarget_vendor=\"unknown\"\nunix\n";
+
+        #[test]
+        fn cfg_output() {
+            let target = Target::parse_cfg(STUB_CFG_OUTPUT).unwrap();
+
+            assert_eq!(target, Target::parse("x86_64-unknown-linux-gnu").unwrap());
+        }
+
+        #[test]
+        fn several_families() {
+            let output = "target_arch=\"wasm32\"\ntarget_endian=\"little\"\ntarget_family=\"unix\"\ntarget_family=\"wasm\"\ntarget_os=\"emscripten\"\ntarget_pointer_width=\"32\"\n";
+
+            let target = Target::parse_cfg(output).unwrap();
+
+            assert_eq!(target.families, vec!["unix", "wasm"]);
+            assert_eq!(target.vendor, "unknown");
+            assert_eq!(target.env, "");
+        }
+
+        #[test]
+        fn incomplete_cfg_output() {
+            assert_eq!(Target::parse_cfg("unix\ntarget_os=\"linux\"\n"), None);
+        }
+
+        #[test]
+        fn known_target() {
+            let target = Target::load("aarch64-apple-darwin").unwrap();
+
+            assert_eq!(target.arch, "aarch64");
+            assert_eq!(target.os, "macos");
+        }
+
+        #[test]
+        fn unknown_target() {
+            assert_eq!(Target::load("x86_64-unknown-plan9"), None);
+        }
+    }
+
+    mod evaluation {
+        use super::*;
+
+        #[test]
+        fn families() {
+            let target = Target::parse("x86_64-unknown-linux-gnu").unwrap();
+
+            assert_eq!(target.evaluate_option("unix", None), Some(true));
+            assert_eq!(target.evaluate_option("windows", None), Some(false));
+            assert_eq!(
+                target.evaluate_option("target_family", Some("unix")),
+                Some(true)
+            );
+        }
+
+        #[test]
+        fn target_options() {
+            let target = Target::parse("x86_64-pc-windows-msvc").unwrap();
+
+            assert_eq!(
+                target.evaluate_option("target_os", Some("windows")),
+                Some(true)
+            );
+            assert_eq!(
+                target.evaluate_option("target_arch", Some("aarch64")),
+                Some(false)
+            );
+            assert_eq!(
+                target.evaluate_option("target_pointer_width", Some("64")),
+                Some(true)
+            );
+        }
+
+        #[test]
+        fn other_options() {
+            let target = Target::parse("x86_64-unknown-linux-gnu").unwrap();
+
+            assert_eq!(target.evaluate_option("test", None), None);
+            assert_eq!(target.evaluate_option("target_feature", Some("avx2")), None);
+        }
+    }
+}
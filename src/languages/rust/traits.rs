@@ -0,0 +1,302 @@
+use daipendency_extractor::{ExtractionError, LibraryMetadata, Namespace};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use tree_sitter::{Node, Parser};
+
+const SOURCE_EXTENSION: &str = "rs";
+const INDENTATION: &str = "    ";
+const PROVIDED_METHOD_BODY: &str = "{ ... }";
+
+/// A trait as declared in the source code.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct TraitDeclaration {
+    /// The declaration up to the body, as in `pub trait Greeter: Display `.
+    header: String,
+    /// The declaration with every associated item, where provided methods have a placeholder body.
+    source_code: String,
+}
+
+/// Render the traits in `namespaces` with all their associated items from the crate's sources.
+///
+/// The extractor drops required methods and the bodies of provided methods, which makes it
+/// impossible to tell whether adding a method to a trait is breaking.
+pub fn render_traits(
+    metadata: &LibraryMetadata,
+    parser: &mut Parser,
+    namespaces: &mut [Namespace],
+) -> Result<(), ExtractionError> {
+    let Some(source_directory) = metadata.entry_point.parent() else {
+        return Ok(());
+    };
+    let mut declarations: HashMap<String, Vec<TraitDeclaration>> = HashMap::new();
+    for path in list_source_files(source_directory)? {
+        let source_code = fs::read_to_string(&path)?;
+        let tree = parser.parse(&source_code, None).ok_or_else(|| {
+            ExtractionError::Malformed(format!("Failed to parse {}", path.display()))
+        })?;
+        collect_traits(tree.root_node(), &source_code, &mut declarations);
+    }
+
+    for symbol in namespaces
+        .iter_mut()
+        .flat_map(|namespace| namespace.symbols.iter_mut())
+    {
+        let Some(candidates) = declarations.get(&symbol.name) else {
+            continue;
+        };
+        let mut matches = candidates.iter().filter_map(|declaration| {
+            let position = symbol
+                .source_code
+                .find(&format!("{}{{", declaration.header))?;
+            Some(format!(
+                "{}{}",
+                &symbol.source_code[..position],
+                declaration.source_code
+            ))
+        });
+        // Traits that are declared several times with the same header are ambiguous.
+        if let (Some(source_code), None) = (matches.next(), matches.next()) {
+            symbol.source_code = source_code;
+        }
+    }
+    Ok(())
+}
+
+fn list_source_files(directory: &Path) -> Result<Vec<PathBuf>, ExtractionError> {
+    let mut entries = fs::read_dir(directory)?
+        .filter_map(Result::ok)
+        .map(|entry| entry.path())
+        .collect::<Vec<_>>();
+    entries.sort();
+
+    let mut source_files = Vec::new();
+    for path in entries {
+        if path.is_dir() {
+            source_files.extend(list_source_files(&path)?);
+        } else if path.extension() == Some(SOURCE_EXTENSION.as_ref()) {
+            source_files.push(path);
+        }
+    }
+    Ok(source_files)
+}
+
+fn collect_traits(
+    node: Node,
+    source_code: &str,
+    declarations: &mut HashMap<String, Vec<TraitDeclaration>>,
+) {
+    if node.kind() == "trait_item" {
+        if let Some((name, declaration)) = render_trait(node, source_code) {
+            let candidates = declarations.entry(name).or_default();
+            if !candidates.contains(&declaration) {
+                candidates.push(declaration);
+            }
+        }
+        return;
+    }
+    let mut cursor = node.walk();
+    for child in node.named_children(&mut cursor) {
+        collect_traits(child, source_code, declarations);
+    }
+}
+
+fn render_trait(node: Node, source_code: &str) -> Option<(String, TraitDeclaration)> {
+    let name = &source_code[node.child_by_field_name("name")?.byte_range()];
+    let body = node.child_by_field_name("body")?;
+    let header = &source_code[node.start_byte()..body.start_byte()];
+
+    let mut rendered = format!("{header}{{\n");
+    // The doc comments and attributes of the next associated item.
+    let mut preamble = Vec::new();
+    let mut cursor = body.walk();
+    for member in body.named_children(&mut cursor) {
+        let text = source_code[member.byte_range()].trim_end();
+        match member.kind() {
+            "line_comment" | "block_comment" => {
+                if is_doc_comment(text) {
+                    preamble.push(text.to_string());
+                }
+            }
+            "attribute_item" => preamble.push(text.to_string()),
+            kind => {
+                let declaration = match (kind, member.child_by_field_name("body")) {
+                    ("function_item", Some(block)) => format!(
+                        "{} {PROVIDED_METHOD_BODY}",
+                        source_code[member.start_byte()..block.start_byte()].trim_end()
+                    ),
+                    _ => text.to_string(),
+                };
+                preamble.push(declaration);
+                for part in preamble.drain(..) {
+                    push_indented(&mut rendered, &part, member.start_position().column);
+                }
+            }
+        }
+    }
+    rendered.push('}');
+
+    let declaration = TraitDeclaration {
+        header: header.to_string(),
+        source_code: rendered,
+    };
+    Some((name.to_string(), declaration))
+}
+
+fn is_doc_comment(comment: &str) -> bool {
+    (comment.starts_with("///") && !comment.starts_with("////"))
+        || (comment.starts_with("/**") && !comment.starts_with("/***") && comment != "/**/")
+}
+
+/// Push `text` onto `output` with one level of indentation, removing the `column` that it was
+/// originally indented by from all but its first line.
+fn push_indented(output: &mut String, text: &str, column: usize) {
+    for (index, line) in text.lines().enumerate() {
+        let line = match index {
+            0 => line,
+            _ => {
+                let indentation = line.len() - line.trim_start().len();
+                &line[indentation.min(column)..]
+            }
+        };
+        output.push_str(INDENTATION);
+        output.push_str(line);
+        output.push('\n');
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use daipendency_extractor::{get_parser, Extractor, Symbol};
+    use daipendency_testing::tempdir::TempDir;
+
+    const STUB_EXTRACTED_TRAIT: &str =
+        "/// Greets people\npub trait Greeter {\n    fn greet(&self) -> String;\n}";
+
+    fn render(files: &[(&str, &str)], source_code: &str) -> String {
+        let temp_dir = TempDir::new();
+        for (path, content) in files {
+            temp_dir.create_file(path, content).unwrap();
+        }
+        let metadata = LibraryMetadata {
+            name: "greeter".to_string(),
+            version: None,
+            documentation: String::new(),
+            entry_point: temp_dir.path.join("src/lib.rs"),
+        };
+        let mut namespaces = vec![Namespace {
+            name: "greeter".to_string(),
+            symbols: vec![Symbol {
+                name: "Greeter".to_string(),
+                source_code: source_code.to_string(),
+            }],
+            doc_comment: None,
+        }];
+        let language = daipendency_extractor_rust::RustExtractor::new().get_parser_language();
+
+        render_traits(
+            &metadata,
+            &mut get_parser(&language).unwrap(),
+            &mut namespaces,
+        )
+        .unwrap();
+
+        namespaces.remove(0).symbols.remove(0).source_code
+    }
+
+    #[test]
+    fn required_and_provided_methods() {
+        let source_code = render(
+            &[(
+                "src/lib.rs",
+                "/// Greets people\npub trait Greeter {\n    /// The name\n    fn name(&self) -> String;\n\n    // Not documentation\n    #[must_use]\n    fn greet(&self) -> String {\n        format!(\"Hello {}\", self.name())\n    }\n}\n",
+            )],
+            STUB_EXTRACTED_TRAIT,
+        );
+
+        assert_eq!(
+            source_code,
+            "/// Greets people\npub trait Greeter {\n    /// The name\n    fn name(&self) -> String;\n    #[must_use]\n    fn greet(&self) -> String { ... }\n}"
+        );
+    }
+
+    #[test]
+    fn associated_items() {
+        let source_code = render(
+            &[(
+                "src/lib.rs",
+                "pub trait Greeter {\n    type Name;\n    const ID: u32 = 1;\n}\n",
+            )],
+            "pub trait Greeter {\n}",
+        );
+
+        assert_eq!(
+            source_code,
+            "pub trait Greeter {\n    type Name;\n    const ID: u32 = 1;\n}"
+        );
+    }
+
+    #[test]
+    fn multi_line_method() {
+        let source_code = render(
+            &[(
+                "src/lib.rs",
+                "pub trait Greeter {\n    fn greet(\n        &self,\n        name: &str,\n    ) -> String;\n}\n",
+            )],
+            "pub trait Greeter {\n}",
+        );
+
+        assert_eq!(
+            source_code,
+            "pub trait Greeter {\n    fn greet(\n        &self,\n        name: &str,\n    ) -> String;\n}"
+        );
+    }
+
+    #[test]
+    fn trait_in_submodule() {
+        let source_code = render(
+            &[
+                ("src/lib.rs", "mod greeting;\npub use greeting::Greeter;\n"),
+                (
+                    "src/greeting/mod.rs",
+                    "pub trait Greeter {\n    fn greet(&self) -> String {\n        String::new()\n    }\n}\n",
+                ),
+            ],
+            STUB_EXTRACTED_TRAIT,
+        );
+
+        assert_eq!(
+            source_code,
+            "/// Greets people\npub trait Greeter {\n    fn greet(&self) -> String { ... }\n}"
+        );
+    }
+
+    #[test]
+    fn ambiguous_trait() {
+        let source_code = render(
+            &[
+                (
+                    "src/lib.rs",
+                    "pub mod a {\n    pub trait Greeter {\n        fn a(&self);\n    }\n}\npub mod b {\n    pub trait Greeter {\n        fn b(&self);\n    }\n}\n",
+                ),
+            ],
+            "pub trait Greeter {\n}",
+        );
+
+        assert_eq!(source_code, "pub trait Greeter {\n}");
+    }
+
+    #[test]
+    fn other_header() {
+        let source_code = render(
+            &[(
+                "src/lib.rs",
+                "pub trait Greeter: Clone {\n    fn greet(&self);\n}\n",
+            )],
+            STUB_EXTRACTED_TRAIT,
+        );
+
+        assert_eq!(source_code, STUB_EXTRACTED_TRAIT);
+    }
+}
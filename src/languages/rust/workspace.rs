@@ -0,0 +1,216 @@
+use super::MANIFEST_PATH;
+use crate::workspaces::WorkspaceMember;
+use daipendency_extractor::LibraryMetadataError;
+use std::fs;
+use std::path::Path;
+
+const WORKSPACE_TABLE: &str = "workspace";
+
+/// List the member crates of the Cargo workspace whose root is at `path`.
+///
+/// Crates that are not workspace roots have no members.
+pub fn list_members(path: &Path) -> Result<Vec<WorkspaceMember>, LibraryMetadataError> {
+    let manifest = read_manifest(path)?;
+    let Some(workspace) = manifest
+        .get(WORKSPACE_TABLE)
+        .and_then(toml::Value::as_table)
+    else {
+        return Ok(Vec::new());
+    };
+    let excluded_paths = get_strings(workspace, "exclude")
+        .map(|exclude| path.join(exclude))
+        .collect::<Vec<_>>();
+
+    let mut members = Vec::new();
+    if let Some(name) = get_package_name(&manifest) {
+        members.push(WorkspaceMember {
+            name: name.to_string(),
+            path: path.to_path_buf(),
+        });
+    }
+    for pattern in get_strings(workspace, "members") {
+        let pattern = path.join(pattern).to_string_lossy().into_owned();
+        let member_paths = glob::glob(&pattern)
+            .map_err(|e| LibraryMetadataError::MalformedManifest(e.to_string()))?
+            .filter_map(Result::ok)
+            .filter(|member_path| !excluded_paths.contains(member_path));
+        for member_path in member_paths {
+            // Globs may match directories that are not crates, which Cargo ignores too.
+            let Ok(member_manifest) = read_manifest(&member_path) else {
+                continue;
+            };
+            if let Some(name) = get_package_name(&member_manifest) {
+                members.push(WorkspaceMember {
+                    name: name.to_string(),
+                    path: member_path,
+                });
+            }
+        }
+    }
+    members.sort_by(|a, b| a.name.cmp(&b.name));
+    members.dedup_by(|a, b| a.path == b.path);
+    Ok(members)
+}
+
+/// Find the `[workspace]` table of the workspace that the crate at `path` belongs to, if any.
+pub fn find_workspace_table(path: &Path) -> Option<toml::Table> {
+    path.ancestors().find_map(|ancestor| {
+        let manifest = read_manifest(ancestor).ok()?;
+        match manifest.get(WORKSPACE_TABLE)? {
+            toml::Value::Table(workspace) => Some(workspace.clone()),
+            _ => None,
+        }
+    })
+}
+
+fn read_manifest(path: &Path) -> Result<toml::Table, LibraryMetadataError> {
+    let content = fs::read_to_string(path.join(MANIFEST_PATH))
+        .map_err(LibraryMetadataError::MissingManifest)?;
+    toml::from_str(&content).map_err(|e| LibraryMetadataError::MalformedManifest(e.to_string()))
+}
+
+fn get_package_name(manifest: &toml::Table) -> Option<&str> {
+    manifest.get("package")?.get("name")?.as_str()
+}
+
+fn get_strings<'a>(table: &'a toml::Table, key: &str) -> impl Iterator<Item = &'a str> {
+    table
+        .get(key)
+        .and_then(toml::Value::as_array)
+        .into_iter()
+        .flatten()
+        .filter_map(toml::Value::as_str)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use assertables::assert_matches;
+    use daipendency_testing::tempdir::TempDir;
+
+    fn create_crate(temp_dir: &TempDir, directory: &str, name: &str) {
+        temp_dir
+            .create_file(
+                &format!("{directory}/{MANIFEST_PATH}"),
+                &format!("[package]\nname = \"{name}\"\n"),
+            )
+            .unwrap();
+    }
+
+    fn get_names(members: &[WorkspaceMember]) -> Vec<&str> {
+        members.iter().map(|member| member.name.as_str()).collect()
+    }
+
+    mod members {
+        use super::*;
+
+        #[test]
+        fn virtual_workspace() {
+            let temp_dir = TempDir::new();
+            temp_dir
+                .create_file(MANIFEST_PATH, "[workspace]\nmembers = [\"crates/*\"]\n")
+                .unwrap();
+            create_crate(&temp_dir, "crates/server", "app-server");
+            create_crate(&temp_dir, "crates/core", "app-core");
+
+            let members = list_members(&temp_dir.path).unwrap();
+
+            assert_eq!(get_names(&members), vec!["app-core", "app-server"]);
+            assert_eq!(members[0].path, temp_dir.path.join("crates/core"));
+        }
+
+        #[test]
+        fn root_package() {
+            let temp_dir = TempDir::new();
+            temp_dir
+                .create_file(
+                    MANIFEST_PATH,
+                    "[package]\nname = \"app\"\n\n[workspace]\nmembers = [\"macros\"]\n",
+                )
+                .unwrap();
+            create_crate(&temp_dir, "macros", "app-macros");
+
+            let members = list_members(&temp_dir.path).unwrap();
+
+            assert_eq!(get_names(&members), vec!["app", "app-macros"]);
+        }
+
+        #[test]
+        fn excluded_member() {
+            let temp_dir = TempDir::new();
+            temp_dir
+                .create_file(
+                    MANIFEST_PATH,
+                    "[workspace]\nmembers = [\"crates/*\"]\nexclude = [\"crates/legacy\"]\n",
+                )
+                .unwrap();
+            create_crate(&temp_dir, "crates/core", "app-core");
+            create_crate(&temp_dir, "crates/legacy", "app-legacy");
+
+            let members = list_members(&temp_dir.path).unwrap();
+
+            assert_eq!(get_names(&members), vec!["app-core"]);
+        }
+
+        #[test]
+        fn directory_without_crate() {
+            let temp_dir = TempDir::new();
+            temp_dir
+                .create_file(MANIFEST_PATH, "[workspace]\nmembers = [\"crates/*\"]\n")
+                .unwrap();
+            create_crate(&temp_dir, "crates/core", "app-core");
+            temp_dir.create_file("crates/README.md", "").unwrap();
+            temp_dir.create_file("crates/docs/index.md", "").unwrap();
+
+            let members = list_members(&temp_dir.path).unwrap();
+
+            assert_eq!(get_names(&members), vec!["app-core"]);
+        }
+
+        #[test]
+        fn not_a_workspace() {
+            let temp_dir = TempDir::new();
+            create_crate(&temp_dir, ".", "app");
+
+            let members = list_members(&temp_dir.path).unwrap();
+
+            assert!(members.is_empty());
+        }
+
+        #[test]
+        fn missing_manifest() {
+            let temp_dir = TempDir::new();
+
+            let result = list_members(&temp_dir.path);
+
+            assert_matches!(result, Err(LibraryMetadataError::MissingManifest(_)));
+        }
+    }
+
+    mod workspace_table {
+        use super::*;
+
+        #[test]
+        fn member_of_workspace() {
+            let temp_dir = TempDir::new();
+            temp_dir
+                .create_file(MANIFEST_PATH, "[workspace]\nmembers = [\"core\"]\n")
+                .unwrap();
+            create_crate(&temp_dir, "core", "app-core");
+
+            let workspace = find_workspace_table(&temp_dir.path.join("core")).unwrap();
+
+            assert!(workspace.contains_key("members"));
+        }
+
+        #[test]
+        fn standalone_crate() {
+            let temp_dir = TempDir::new();
+            create_crate(&temp_dir, "app", "app");
+
+            let workspace = find_workspace_table(&temp_dir.path.join("app"));
+
+            assert!(workspace.is_none());
+        }
+    }
+}
@@ -1,10 +1,40 @@
+mod cache;
+mod comments;
+mod dependency_graph;
+mod diff;
+mod error;
 mod extractors;
+mod filtering;
+mod formatting_options;
+mod json_formatting;
 mod languages;
 mod library;
+mod loading_options;
 mod markdown_formatting;
+mod registry;
+mod semver;
+pub mod serialisation;
+mod symbol_kind;
+mod token_budget;
+mod workspaces;
 
-pub use library::Library;
-pub use markdown_formatting::generate_markdown_documentation;
+pub use cache::Cache;
+pub use diff::{ChangeKind, LibraryDiff, SymbolChange};
+pub use error::LibraryError;
+pub use filtering::SymbolFilter;
+pub use formatting_options::FormattingOptions;
+pub use json_formatting::{
+    generate_json_diff, generate_json_documentation, generate_json_documentation_with_options,
+};
+pub use library::{Library, LoadedDependency};
+pub use loading_options::{FeatureSelection, LoadingOptions};
+pub use markdown_formatting::{
+    generate_markdown_diff, generate_markdown_documentation,
+    generate_markdown_documentation_with_options,
+};
+pub use semver::{SemverImpact, SemverVerdict};
+pub use symbol_kind::SymbolKind;
+pub use workspaces::WorkspaceMember;
 
 pub use languages::Language;
 
@@ -1,16 +1,38 @@
-use crate::extractors::{discover_extractor, get_extractor};
-use crate::languages::Language;
-use daipendency_extractor::{get_parser, Extractor, Namespace};
-use std::path::Path;
+use crate::cache::{compute_checksum, CacheKey};
+use crate::dependency_graph::DependencyGraph;
+use crate::error::LibraryError;
+use crate::extractors::{detect_dialect, discover_extractor, get_extractor, list_dependencies};
+use crate::languages::{Language, LanguageConfig};
+use crate::loading_options::LoadingOptions;
+use crate::workspaces::explain_workspace_root;
+use daipendency_extractor::{
+    get_parser, DependencyResolutionError, Extractor, LibraryMetadata, Namespace,
+};
+use std::collections::{BTreeMap, HashSet, VecDeque};
+use std::path::{Path, PathBuf};
 
 pub type BoxedExtractor = Box<dyn Extractor + Send + Sync>;
 
+/// A dependency loaded as part of a dependency tree.
+pub struct LoadedDependency {
+    pub name: String,
+    /// The distance from the dependant, where direct dependencies have a depth of 1.
+    pub depth: usize,
+    pub library: Result<Library, LibraryError>,
+}
+
+#[derive(serde::Serialize, serde::Deserialize)]
 pub struct Library {
     pub name: String,
     pub version: Option<String>,
     pub documentation: String,
+    #[serde(with = "crate::serialisation::namespaces")]
     pub namespaces: Vec<Namespace>,
     pub language: Language,
+    /// The features enabled in the API, if a selection of features was applied.
+    pub features: Option<Vec<String>>,
+    /// The target triple that the API was filtered for, if any.
+    pub target: Option<String>,
 }
 
 impl Library {
@@ -21,13 +43,29 @@ impl Library {
     /// # Returns
     ///
     /// Returns a Result containing the loaded library, or an error if something went wrong.
-    pub fn load(path: &Path, language: Option<Language>) -> anyhow::Result<Self> {
+    pub fn load(path: &Path, language: Option<Language>) -> Result<Self, LibraryError> {
+        Self::load_with_options(path, language, &LoadingOptions::default())
+    }
+
+    /// Load a library from a `path` with the given `options`.
+    ///
+    /// # Returns
+    ///
+    /// Returns a Result containing the loaded library, or an error if something went wrong.
+    pub fn load_with_options(
+        path: &Path,
+        language: Option<Language>,
+        options: &LoadingOptions,
+    ) -> Result<Self, LibraryError> {
         let (extractor, metadata, language) = if let Some(lang) = language {
             let extractor = get_extractor(lang);
-            let metadata = extractor.get_library_metadata(path)?;
+            let metadata = extractor.get_library_metadata(path).map_err(|e| {
+                explain_workspace_root(path, LibraryError::from_metadata_error(e, lang))
+            })?;
             (extractor, metadata, lang)
         } else {
-            let discovery = discover_extractor(path).map_err(|e| anyhow::anyhow!(e))?;
+            let discovery =
+                discover_extractor(path).map_err(|e| explain_workspace_root(path, e))?;
             (
                 discovery.extractor,
                 discovery.library_metadata,
@@ -35,20 +73,72 @@ impl Library {
             )
         };
 
-        let mut parser = get_parser(&extractor.get_parser_language())?;
-        let namespaces = match extractor.extract_public_api(&metadata, &mut parser) {
-            Ok(namespaces) => namespaces,
-            Err(e) => {
-                return Err(anyhow::anyhow!("Failed to extract public API: {}", e));
+        // Libraries whose sources cannot be read are simply not cached.
+        let cache = options.cache.as_ref();
+        let checksum = cache.and_then(|_| compute_checksum(path, &metadata.entry_point).ok());
+        let cache_entry = cache.zip(checksum).map(|(cache, checksum)| {
+            let key = CacheKey {
+                language,
+                name: &metadata.name,
+                version: metadata.version.as_deref(),
+                checksum,
+            };
+            (cache, key)
+        });
+        let cached_library = cache_entry.as_ref().and_then(|(cache, key)| cache.get(key));
+        let library = match cached_library {
+            Some(library) => library,
+            None => {
+                let mut parser = get_parser(&extractor.get_parser_language())?;
+                let namespaces = extractor.extract_public_api(&metadata, &mut parser)?;
+                let library = Self {
+                    name: metadata.name.clone(),
+                    version: metadata.version.clone(),
+                    documentation: metadata.documentation.clone(),
+                    namespaces,
+                    language,
+                    features: None,
+                    target: None,
+                };
+                if let Some((cache, key)) = cache_entry {
+                    // The cache is an optimisation, so failing to write to it isn't an error.
+                    let _ = cache.put(&key, &library);
+                }
+                library
             }
         };
 
+        if options.features.is_none() && options.target.is_none() {
+            return Ok(library);
+        }
+        library.select_configuration(path, &metadata, options)
+    }
+
+    /// Keep the parts of the API compiled with the features and for the target in `options`, if
+    /// the language of the library supports conditional compilation.
+    fn select_configuration(
+        self,
+        path: &Path,
+        metadata: &LibraryMetadata,
+        options: &LoadingOptions,
+    ) -> Result<Self, LibraryError> {
+        let Some(selector) =
+            LanguageConfig::get_from_language(self.language).configuration_selector
+        else {
+            return Ok(self);
+        };
+        let (namespaces, features) = selector(
+            path,
+            metadata,
+            options.features.as_ref(),
+            options.target.as_deref(),
+            self.namespaces,
+        )?;
         Ok(Self {
-            name: metadata.name,
-            version: metadata.version,
-            documentation: metadata.documentation,
             namespaces,
-            language,
+            features,
+            target: options.target.clone(),
+            ..self
         })
     }
 
@@ -61,24 +151,262 @@ impl Library {
         name: &str,
         dependant_path: &Path,
         language: Option<Language>,
-    ) -> anyhow::Result<Self> {
+    ) -> Result<Self, LibraryError> {
+        Self::load_dependency_with_options(
+            name,
+            dependant_path,
+            language,
+            &LoadingOptions::default(),
+        )
+    }
+
+    /// Load a dependency of a crate with the given `options`, which apply to the dependency.
+    ///
+    /// # Returns
+    ///
+    /// Returns a Result containing the loaded dependency, or an error if something went wrong.
+    pub fn load_dependency_with_options(
+        name: &str,
+        dependant_path: &Path,
+        language: Option<Language>,
+        options: &LoadingOptions,
+    ) -> Result<Self, LibraryError> {
         let (extractor, language) = if let Some(lang) = language {
             let extractor = get_extractor(lang);
             (extractor, lang)
         } else {
-            let discovery = discover_extractor(dependant_path).map_err(|e| anyhow::anyhow!(e))?;
+            let discovery = discover_extractor(dependant_path)
+                .map_err(|e| explain_workspace_root(dependant_path, e))?;
             (discovery.extractor, discovery.language)
         };
-        let dependency_path = extractor
-            .resolve_dependency_path(name, dependant_path)
-            .map_err(|e| anyhow::anyhow!(e))?;
-        Self::load(&dependency_path, Some(language))
+        let dependency_path = extractor.resolve_dependency_path(name, dependant_path)?;
+        let dependency_language = detect_dialect(language, &dependency_path);
+        Self::load_with_options(&dependency_path, Some(dependency_language), options)
+    }
+
+    /// List the names of the direct dependencies declared in the manifest of the library at
+    /// `dependant_path`, without loading them.
+    ///
+    /// # Returns
+    ///
+    /// Returns a Result containing the dependency names, or an error if they could not be listed.
+    pub fn list_dependencies(
+        dependant_path: &Path,
+        language: Option<Language>,
+    ) -> Result<Vec<String>, LibraryError> {
+        let language = get_dependant_language(dependant_path, language)?;
+        Ok(list_dependencies(language, dependant_path)?)
+    }
+
+    /// Load every direct dependency declared in the manifest of the library at `dependant_path`.
+    ///
+    /// # Returns
+    ///
+    /// Returns a Result containing the outcome of loading each dependency, keyed by its name,
+    /// or an error if the dependencies could not be listed.
+    pub fn load_dependencies(
+        dependant_path: &Path,
+        language: Option<Language>,
+    ) -> Result<BTreeMap<String, Result<Self, LibraryError>>, LibraryError> {
+        let language = get_dependant_language(dependant_path, language)?;
+        let dependency_names = list_dependencies(language, dependant_path)?;
+        let resolver = DependencyResolver::new(dependant_path, language)?;
+
+        Ok(dependency_names
+            .into_iter()
+            .map(|name| {
+                let dependency = resolver
+                    .resolve(&name, dependant_path)
+                    .map_err(LibraryError::from)
+                    .and_then(|path| Self::load(&path, Some(detect_dialect(language, &path))));
+                (name, dependency)
+            })
+            .collect())
+    }
+
+    /// Load the dependencies of the library at `dependant_path` up to `max_depth` levels deep.
+    ///
+    /// Dependencies are loaded breadth-first, and each version of a library is loaded once even if
+    /// it is required at several places in the tree. Transitive dependencies are resolved from the
+    /// library that declares them in languages where several versions of a package can coexist,
+    /// and otherwise from the dependant first, so that its pinned versions are honoured.
+    ///
+    /// Transitive dependencies that weren't installed because they're optional (e.g. disabled
+    /// Rust features or npm peer dependencies) are skipped in every language, whereas those that
+    /// should have been installed are reported as errors.
+    ///
+    /// # Returns
+    ///
+    /// Returns a Result containing the outcome of loading each dependency,
+    /// or an error if the direct dependencies could not be listed.
+    pub fn load_dependency_tree(
+        dependant_path: &Path,
+        language: Option<Language>,
+        max_depth: usize,
+    ) -> Result<Vec<LoadedDependency>, LibraryError> {
+        Self::load_dependency_tree_with_options(
+            dependant_path,
+            language,
+            max_depth,
+            &LoadingOptions::default(),
+        )
+    }
+
+    /// Load the dependencies of the library at `dependant_path` up to `max_depth` levels deep
+    /// with the given `options`, which apply to every dependency except for the feature selection,
+    /// since features are specific to each crate.
+    ///
+    /// # Returns
+    ///
+    /// Returns a Result containing the outcome of loading each dependency,
+    /// or an error if the direct dependencies could not be listed.
+    pub fn load_dependency_tree_with_options(
+        dependant_path: &Path,
+        language: Option<Language>,
+        max_depth: usize,
+        options: &LoadingOptions,
+    ) -> Result<Vec<LoadedDependency>, LibraryError> {
+        let language = get_dependant_language(dependant_path, language)?;
+        if max_depth == 0 {
+            return Ok(Vec::new());
+        }
+        let direct_dependency_names = list_dependencies(language, dependant_path)?;
+        let resolver = DependencyResolver::new(dependant_path, language)?;
+        let options = LoadingOptions {
+            features: None,
+            ..options.clone()
+        };
+
+        let mut pending: VecDeque<(String, PathBuf, usize)> = direct_dependency_names
+            .into_iter()
+            .map(|name| (name, dependant_path.to_path_buf(), 1))
+            .collect();
+        let mut loaded_paths = HashSet::from([dependant_path.to_path_buf()]);
+        let mut loaded_versions = HashSet::new();
+        let mut failed_names = HashSet::new();
+        let mut dependencies = Vec::new();
+        while let Some((name, declarer_path, depth)) = pending.pop_front() {
+            let resolution = resolver.resolve_transitive(&name, &declarer_path, dependant_path);
+            let dependency_path = match resolution {
+                Ok(dependency_path) => dependency_path,
+                // Transitive dependencies may be optional or platform-specific, so they may
+                // legitimately be absent.
+                Err(DependencyResolutionError::MissingDependency(_)) if depth > 1 => continue,
+                Err(error) => {
+                    if failed_names.insert(name.clone()) {
+                        dependencies.push(LoadedDependency {
+                            name,
+                            depth,
+                            library: Err(error.into()),
+                        });
+                    }
+                    continue;
+                }
+            };
+            if !loaded_paths.insert(dependency_path.clone()) {
+                continue;
+            }
+
+            let dependency_language = detect_dialect(language, &dependency_path);
+            let library =
+                Self::load_with_options(&dependency_path, Some(dependency_language), &options);
+            // The same version may be installed at several paths (e.g. in nested `node_modules`).
+            if let Ok(Library {
+                name,
+                version: Some(version),
+                ..
+            }) = &library
+            {
+                if !loaded_versions.insert((name.clone(), version.clone())) {
+                    continue;
+                }
+            }
+            if library.is_ok() && depth < max_depth {
+                let transitive_names =
+                    list_dependencies(language, &dependency_path).unwrap_or_default();
+                pending.extend(
+                    transitive_names
+                        .into_iter()
+                        .map(|name| (name, dependency_path.clone(), depth + 1)),
+                );
+            }
+            dependencies.push(LoadedDependency {
+                name,
+                depth,
+                library,
+            });
+        }
+        Ok(dependencies)
+    }
+}
+
+/// Get the language of the library at `dependant_path`, discovering it if it isn't given.
+fn get_dependant_language(
+    dependant_path: &Path,
+    language: Option<Language>,
+) -> Result<Language, LibraryError> {
+    match language {
+        Some(language) => Ok(language),
+        None => Ok(discover_extractor(dependant_path)
+            .map_err(|e| explain_workspace_root(dependant_path, e))?
+            .language),
+    }
+}
+
+/// Resolves the paths of the dependencies in the tree of a dependant.
+struct DependencyResolver {
+    extractor: BoxedExtractor,
+    /// The whole tree, resolved upfront if the language supports it.
+    graph: Option<DependencyGraph>,
+    has_nested_dependencies: bool,
+}
+
+impl DependencyResolver {
+    fn new(dependant_path: &Path, language: Language) -> Result<Self, LibraryError> {
+        let config = LanguageConfig::get_from_language(language);
+        let graph = config
+            .dependency_graph_loader
+            .map(|loader| loader(dependant_path))
+            .transpose()?;
+        Ok(Self {
+            extractor: get_extractor(language),
+            graph,
+            has_nested_dependencies: config.has_nested_dependencies,
+        })
+    }
+
+    /// Resolve the dependency called `name` of the library at `declarer_path`.
+    fn resolve(
+        &self,
+        name: &str,
+        declarer_path: &Path,
+    ) -> Result<PathBuf, DependencyResolutionError> {
+        match &self.graph {
+            Some(graph) => graph.resolve(name, declarer_path),
+            None => self.extractor.resolve_dependency_path(name, declarer_path),
+        }
+    }
+
+    /// Resolve the dependency called `name` of the library at `declarer_path`, in the tree of
+    /// the library at `dependant_path`.
+    fn resolve_transitive(
+        &self,
+        name: &str,
+        declarer_path: &Path,
+        dependant_path: &Path,
+    ) -> Result<PathBuf, DependencyResolutionError> {
+        if self.has_nested_dependencies || declarer_path == dependant_path {
+            return self.resolve(name, declarer_path);
+        }
+        self.resolve(name, dependant_path)
+            .or_else(|_| self.resolve(name, declarer_path))
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use assertables::assert_matches;
 
     mod load {
         use super::*;
@@ -187,6 +515,582 @@ mod tests {
 
             let result = Library::load(&temp_dir.path, None);
 
+            assert_matches!(result, Err(LibraryError::UnknownLanguage));
+        }
+
+        #[test]
+        fn missing_manifest() {
+            let temp_dir = TempDir::new();
+
+            let result = Library::load(&temp_dir.path, Some(Language::Rust));
+
+            assert_matches!(result, Err(LibraryError::MissingManifest(_)));
+        }
+
+        #[test]
+        fn malformed_manifest() {
+            let temp_dir = TempDir::new();
+            temp_dir.create_file("Cargo.toml", "invalid toml").unwrap();
+
+            let result = Library::load(&temp_dir.path, Some(Language::Rust));
+
+            assert_matches!(
+                result,
+                Err(LibraryError::MalformedManifest {
+                    language: Language::Rust,
+                    ..
+                })
+            );
+        }
+    }
+
+    mod load_dependency {
+        use super::*;
+        use daipendency_testing::tempdir::TempDir;
+
+        #[test]
+        fn undeclared_dependency() {
+            let temp_dir = TempDir::new();
+            temp_dir
+                .create_file("package.json", r#"{"name": "app", "dependencies": {}}"#)
+                .unwrap();
+
+            let result =
+                Library::load_dependency("left-pad", &temp_dir.path, Some(Language::JavaScript));
+
+            assert_matches!(result, Err(LibraryError::UnknownDependency(name)) if name == "left-pad");
+        }
+
+        #[test]
+        fn dependency_in_other_dialect() {
+            let temp_dir = TempDir::new();
+            temp_dir
+                .create_file(
+                    "package.json",
+                    r#"{"name": "app", "dependencies": {"left-pad": "^1"}}"#,
+                )
+                .unwrap();
+            temp_dir
+                .create_file(
+                    "node_modules/left-pad/package.json",
+                    r#"{"name": "left-pad"}"#,
+                )
+                .unwrap();
+            temp_dir
+                .create_file(
+                    "node_modules/left-pad/index.d.ts",
+                    "export declare function leftPad(text: string): string;\n",
+                )
+                .unwrap();
+
+            let library =
+                Library::load_dependency("left-pad", &temp_dir.path, Some(Language::JavaScript))
+                    .unwrap();
+
+            assert_eq!(library.language, Language::TypeScript);
+            assert_eq!(library.namespaces[0].symbols[0].name, "leftPad");
+        }
+    }
+
+    mod load_dependency_tree {
+        use super::*;
+        use daipendency_testing::tempdir::TempDir;
+
+        fn create_package(temp_dir: &TempDir, directory: &str, name: &str, dependencies: &[&str]) {
+            let dependencies = dependencies
+                .iter()
+                .map(|dependency| format!(r#""{dependency}": "^1""#))
+                .collect::<Vec<_>>()
+                .join(", ");
+            temp_dir
+                .create_file(
+                    &format!("{directory}package.json"),
+                    &format!(r#"{{"name": "{name}", "dependencies": {{{dependencies}}}}}"#),
+                )
+                .unwrap();
+            temp_dir
+                .create_file(&format!("{directory}index.js"), "export const x = 1;\n")
+                .unwrap();
+        }
+
+        /// Create `app -> {a, c}`, `a -> b` and `c -> b`.
+        fn create_tree(temp_dir: &TempDir) {
+            create_package(temp_dir, "", "app", &["a", "c"]);
+            create_package(temp_dir, "node_modules/a/", "a", &["b"]);
+            create_package(temp_dir, "node_modules/b/", "b", &[]);
+            create_package(temp_dir, "node_modules/c/", "c", &["b"]);
+        }
+
+        fn get_names_and_depths(dependencies: &[LoadedDependency]) -> Vec<(&str, usize)> {
+            dependencies
+                .iter()
+                .map(|dependency| (dependency.name.as_str(), dependency.depth))
+                .collect()
+        }
+
+        #[test]
+        fn direct_dependencies_only() {
+            let temp_dir = TempDir::new();
+            create_tree(&temp_dir);
+
+            let dependencies =
+                Library::load_dependency_tree(&temp_dir.path, Some(Language::JavaScript), 1)
+                    .unwrap();
+
+            assert_eq!(
+                get_names_and_depths(&dependencies),
+                vec![("a", 1), ("c", 1)]
+            );
+        }
+
+        #[test]
+        fn cached_dependencies() {
+            let temp_dir = TempDir::new();
+            create_tree(&temp_dir);
+            let cache = crate::cache::Cache::new(temp_dir.path.join("cache"));
+            let options = LoadingOptions {
+                cache: Some(cache.clone()),
+                ..LoadingOptions::default()
+            };
+
+            Library::load_dependency_tree_with_options(
+                &temp_dir.path,
+                Some(Language::JavaScript),
+                1,
+                &options,
+            )
+            .unwrap();
+
+            assert!(cache.get_directory().is_dir());
+        }
+
+        #[test]
+        fn transitive_dependencies_deduplicated() {
+            let temp_dir = TempDir::new();
+            create_tree(&temp_dir);
+
+            let dependencies =
+                Library::load_dependency_tree(&temp_dir.path, Some(Language::JavaScript), 2)
+                    .unwrap();
+
+            assert_eq!(
+                get_names_and_depths(&dependencies),
+                vec![("a", 1), ("c", 1), ("b", 2)]
+            );
+            assert!(dependencies.iter().all(|d| d.library.is_ok()));
+        }
+
+        #[test]
+        fn nested_versions() {
+            let temp_dir = TempDir::new();
+            create_tree(&temp_dir);
+            create_package(&temp_dir, "node_modules/c/node_modules/b/", "b", &[]);
+
+            let dependencies =
+                Library::load_dependency_tree(&temp_dir.path, Some(Language::JavaScript), 2)
+                    .unwrap();
+
+            assert_eq!(
+                get_names_and_depths(&dependencies),
+                vec![("a", 1), ("c", 1), ("b", 2), ("b", 2)]
+            );
+        }
+
+        #[test]
+        fn uninstalled_transitive_dependency() {
+            let temp_dir = TempDir::new();
+            create_package(&temp_dir, "", "app", &["a"]);
+            create_package(&temp_dir, "node_modules/a/", "a", &["b"]);
+
+            let dependencies =
+                Library::load_dependency_tree(&temp_dir.path, Some(Language::JavaScript), 2)
+                    .unwrap();
+
+            assert_eq!(
+                get_names_and_depths(&dependencies),
+                vec![("a", 1), ("b", 2)]
+            );
+            assert!(dependencies[1].library.is_err());
+        }
+
+        #[test]
+        fn uninstalled_optional_transitive_dependency() {
+            let temp_dir = TempDir::new();
+            create_package(&temp_dir, "", "app", &["a"]);
+            temp_dir
+                .create_file(
+                    "node_modules/a/package.json",
+                    r#"{"name": "a", "optionalDependencies": {"b": "^1"}}"#,
+                )
+                .unwrap();
+            temp_dir
+                .create_file("node_modules/a/index.js", "export const x = 1;\n")
+                .unwrap();
+
+            let dependencies =
+                Library::load_dependency_tree(&temp_dir.path, Some(Language::JavaScript), 2)
+                    .unwrap();
+
+            assert_eq!(get_names_and_depths(&dependencies), vec![("a", 1)]);
+        }
+
+        #[test]
+        fn transitive_dependency_resolved_from_declarer() {
+            let temp_dir = TempDir::new();
+            create_package(&temp_dir, "", "app", &["c", "b"]);
+            create_package(&temp_dir, "node_modules/b/", "b", &[]);
+            create_package(&temp_dir, "node_modules/c/", "c", &["b"]);
+            create_package(&temp_dir, "node_modules/c/node_modules/b/", "b", &[]);
+
+            let dependencies =
+                Library::load_dependency_tree(&temp_dir.path, Some(Language::JavaScript), 2)
+                    .unwrap();
+
+            assert_eq!(
+                get_names_and_depths(&dependencies),
+                vec![("b", 1), ("c", 1), ("b", 2)]
+            );
+        }
+
+        #[test]
+        fn same_version_installed_twice() {
+            let temp_dir = TempDir::new();
+            create_tree(&temp_dir);
+            create_package(&temp_dir, "node_modules/c/node_modules/b/", "b", &[]);
+            for directory in ["node_modules/b/", "node_modules/c/node_modules/b/"] {
+                temp_dir
+                    .create_file(
+                        &format!("{directory}package.json"),
+                        r#"{"name": "b", "version": "1.0.0"}"#,
+                    )
+                    .unwrap();
+            }
+
+            let dependencies =
+                Library::load_dependency_tree(&temp_dir.path, Some(Language::JavaScript), 2)
+                    .unwrap();
+
+            assert_eq!(
+                get_names_and_depths(&dependencies),
+                vec![("a", 1), ("c", 1), ("b", 2)]
+            );
+        }
+
+        #[test]
+        fn rust_crate_versions() {
+            let temp_dir = TempDir::new();
+            let create_crate = |directory: &str, name: &str, version: &str, dependencies: &str| {
+                temp_dir
+                    .create_file(
+                        &format!("{directory}/Cargo.toml"),
+                        &format!(
+                            "[package]\nname = \"{name}\"\nversion = \"{version}\"\n\n[dependencies]\n{dependencies}"
+                        ),
+                    )
+                    .unwrap();
+                temp_dir
+                    .create_file(&format!("{directory}/src/lib.rs"), "pub fn run() {}\n")
+                    .unwrap();
+            };
+            create_crate(
+                "app",
+                "app",
+                "0.1.0",
+                "a = { path = \"../a\" }\nshared = { path = \"../shared-2\" }\n",
+            );
+            create_crate("a", "a", "0.1.0", "shared = { path = \"../shared-1\" }\n");
+            create_crate("shared-1", "shared", "1.0.0", "");
+            create_crate("shared-2", "shared", "2.0.0", "");
+
+            let dependencies =
+                Library::load_dependency_tree(&temp_dir.path.join("app"), Some(Language::Rust), 2)
+                    .unwrap();
+
+            let versions = dependencies
+                .iter()
+                .map(|dependency| {
+                    let library = dependency.library.as_ref().unwrap();
+                    (library.name.as_str(), library.version.as_deref().unwrap())
+                })
+                .collect::<Vec<_>>();
+            assert_eq!(
+                versions,
+                vec![("a", "0.1.0"), ("shared", "2.0.0"), ("shared", "1.0.0")]
+            );
+        }
+
+        #[test]
+        fn zero_depth() {
+            let temp_dir = TempDir::new();
+            create_tree(&temp_dir);
+
+            let dependencies =
+                Library::load_dependency_tree(&temp_dir.path, Some(Language::JavaScript), 0)
+                    .unwrap();
+
+            assert!(dependencies.is_empty());
+        }
+    }
+
+    mod caching {
+        use super::*;
+        use crate::cache::Cache;
+        use daipendency_testing::tempdir::TempDir;
+
+        fn create_library(temp_dir: &TempDir, source_code: &str) -> PathBuf {
+            temp_dir
+                .create_file("lib/package.json", r#"{"name": "lib", "version": "1.0.0"}"#)
+                .unwrap();
+            temp_dir.create_file("lib/index.js", source_code).unwrap();
+            temp_dir.path.join("lib")
+        }
+
+        fn get_source_code(library: &Library) -> &str {
+            &library.namespaces[0].symbols[0].source_code
+        }
+
+        fn load_cached(path: &Path, cache: &Cache) -> Library {
+            let options = LoadingOptions {
+                cache: Some(cache.clone()),
+                ..LoadingOptions::default()
+            };
+            Library::load_with_options(path, Some(Language::JavaScript), &options).unwrap()
+        }
+
+        fn find_cache_entry(directory: &Path) -> PathBuf {
+            let path = std::fs::read_dir(directory)
+                .unwrap()
+                .next()
+                .unwrap()
+                .unwrap()
+                .path();
+            if path.is_dir() {
+                find_cache_entry(&path)
+            } else {
+                path
+            }
+        }
+
+        #[test]
+        fn cache_hit() {
+            let temp_dir = TempDir::new();
+            let cache = Cache::new(temp_dir.path.join("cache"));
+            let library_path = create_library(&temp_dir, "export function f() {}\n");
+            load_cached(&library_path, &cache);
+            let entry_path = find_cache_entry(cache.get_directory());
+            let entry = std::fs::read_to_string(&entry_path).unwrap();
+            std::fs::write(&entry_path, entry.replace("f()", "cached()")).unwrap();
+
+            let library = load_cached(&library_path, &cache);
+
+            assert_eq!(get_source_code(&library), "export function cached();");
+        }
+
+        #[test]
+        fn changed_sources() {
+            let temp_dir = TempDir::new();
+            let cache = Cache::new(temp_dir.path.join("cache"));
+            let library_path = create_library(&temp_dir, "export function f() {}\n");
+            load_cached(&library_path, &cache);
+            create_library(&temp_dir, "export function g() {}\n");
+
+            let library = load_cached(&library_path, &cache);
+
+            assert_eq!(get_source_code(&library), "export function g();");
+        }
+    }
+
+    mod load_with_options {
+        use super::*;
+        use crate::loading_options::FeatureSelection;
+        use daipendency_testing::tempdir::TempDir;
+
+        fn create_crate(temp_dir: &TempDir) {
+            temp_dir
+                .create_file(
+                    "Cargo.toml",
+                    "[package]\nname = \"app\"\nversion = \"1.0.0\"\n\n[features]\nnet = []\n",
+                )
+                .unwrap();
+            temp_dir
+                .create_file(
+                    "src/lib.rs",
+                    "pub fn run() {}\n\n#[cfg(feature = \"net\")]\npub fn connect() {}\n",
+                )
+                .unwrap();
+        }
+
+        fn get_symbol_names(library: &Library) -> Vec<&str> {
+            library.namespaces[0]
+                .symbols
+                .iter()
+                .map(|symbol| symbol.name.as_str())
+                .collect()
+        }
+
+        #[test]
+        fn no_feature_selection() {
+            let temp_dir = TempDir::new();
+            create_crate(&temp_dir);
+
+            let library =
+                Library::load_with_options(&temp_dir.path, None, &LoadingOptions::default())
+                    .unwrap();
+
+            assert_eq!(library.features, None);
+            assert_eq!(get_symbol_names(&library).len(), 2);
+        }
+
+        #[test]
+        fn feature_selection() {
+            let temp_dir = TempDir::new();
+            create_crate(&temp_dir);
+            let options = LoadingOptions {
+                features: Some(FeatureSelection::default()),
+                ..LoadingOptions::default()
+            };
+
+            let library = Library::load_with_options(&temp_dir.path, None, &options).unwrap();
+
+            assert_eq!(library.features, Some(Vec::new()));
+            assert_eq!(get_symbol_names(&library), vec!["run"]);
+        }
+
+        #[test]
+        fn language_without_features() {
+            let temp_dir = TempDir::new();
+            temp_dir
+                .create_file("package.json", r#"{"name": "lib", "version": "1.0.0"}"#)
+                .unwrap();
+            temp_dir
+                .create_file("index.js", "export function f() {}\n")
+                .unwrap();
+            let options = LoadingOptions {
+                features: Some(FeatureSelection::default()),
+                ..LoadingOptions::default()
+            };
+
+            let library = Library::load_with_options(&temp_dir.path, None, &options).unwrap();
+
+            assert_eq!(library.features, None);
+        }
+    }
+
+    mod list_dependencies {
+        use super::*;
+        use daipendency_testing::tempdir::TempDir;
+
+        #[test]
+        fn declared_dependencies() {
+            let temp_dir = TempDir::new();
+            temp_dir
+                .create_file(
+                    "package.json",
+                    r#"{"name": "app", "dependencies": {"left-pad": "^1"}}"#,
+                )
+                .unwrap();
+
+            let dependency_names = Library::list_dependencies(&temp_dir.path, None).unwrap();
+
+            assert_eq!(dependency_names, vec!["left-pad"]);
+        }
+    }
+
+    mod load_dependencies {
+        use super::*;
+        use daipendency_testing::tempdir::TempDir;
+
+        const STUB_DEPENDENCY: &str = "left-pad";
+        const STUB_MISSING_DEPENDENCY: &str = "right-pad";
+
+        fn create_dependant(temp_dir: &TempDir) {
+            temp_dir
+                .create_file(
+                    "package.json",
+                    &format!(
+                        r#"{{"name": "app", "dependencies": {{"{STUB_DEPENDENCY}": "^1", "{STUB_MISSING_DEPENDENCY}": "^1"}}}}"#
+                    ),
+                )
+                .unwrap();
+            temp_dir
+                .create_file(
+                    &format!("node_modules/{STUB_DEPENDENCY}/package.json"),
+                    &format!(r#"{{"name": "{STUB_DEPENDENCY}", "version": "1.3.0"}}"#),
+                )
+                .unwrap();
+            temp_dir
+                .create_file(
+                    &format!("node_modules/{STUB_DEPENDENCY}/index.js"),
+                    "export function leftPad(text) {}\n",
+                )
+                .unwrap();
+        }
+
+        #[test]
+        fn declared_dependencies() {
+            let temp_dir = TempDir::new();
+            create_dependant(&temp_dir);
+
+            let dependencies =
+                Library::load_dependencies(&temp_dir.path, Some(Language::JavaScript)).unwrap();
+
+            assert_eq!(
+                dependencies.keys().collect::<Vec<_>>(),
+                vec![STUB_DEPENDENCY, STUB_MISSING_DEPENDENCY]
+            );
+            let dependency = dependencies[STUB_DEPENDENCY].as_ref().unwrap();
+            assert_eq!(dependency.name, STUB_DEPENDENCY);
+            assert_eq!(dependency.version, Some("1.3.0".to_string()));
+        }
+
+        #[test]
+        fn resolved_in_one_pass() {
+            let temp_dir = TempDir::new();
+            temp_dir
+                .create_file(
+                    "app/Cargo.toml",
+                    "[package]\nname = \"app\"\nversion = \"0.1.0\"\n\n[dependencies]\nutil = { path = \"../util\" }\n",
+                )
+                .unwrap();
+            temp_dir.create_file("app/src/lib.rs", "").unwrap();
+            temp_dir
+                .create_file(
+                    "util/Cargo.toml",
+                    "[package]\nname = \"util\"\nversion = \"0.2.0\"\n",
+                )
+                .unwrap();
+            temp_dir
+                .create_file("util/src/lib.rs", "pub fn run() {}\n")
+                .unwrap();
+
+            let dependencies =
+                Library::load_dependencies(&temp_dir.path.join("app"), Some(Language::Rust))
+                    .unwrap();
+
+            let dependency = dependencies["util"].as_ref().unwrap();
+            assert_eq!(dependency.version, Some("0.2.0".to_string()));
+        }
+
+        #[test]
+        fn failed_dependency() {
+            let temp_dir = TempDir::new();
+            create_dependant(&temp_dir);
+
+            let dependencies =
+                Library::load_dependencies(&temp_dir.path, Some(Language::JavaScript)).unwrap();
+
+            assert_matches!(
+                dependencies[STUB_MISSING_DEPENDENCY],
+                Err(LibraryError::DependencyRetrieval(_))
+            );
+        }
+
+        #[test]
+        fn missing_manifest() {
+            let temp_dir = TempDir::new();
+
+            let result = Library::load_dependencies(&temp_dir.path, Some(Language::JavaScript));
+
             assert!(result.is_err());
         }
     }
@@ -0,0 +1,22 @@
+use crate::cache::Cache;
+
+/// Options for loading libraries.
+#[derive(Debug, Clone, Default)]
+pub struct LoadingOptions {
+    /// The cache to reuse the results of previous extractions from.
+    pub cache: Option<Cache>,
+    /// The features to enable, or `None` to keep the items gated behind any feature.
+    /// Only Rust crates have features.
+    pub features: Option<FeatureSelection>,
+    /// The target triple to compile for (e.g. `x86_64-unknown-linux-gnu`), or `None` to keep the
+    /// items for any platform. Only Rust crates are filtered by target.
+    pub target: Option<String>,
+}
+
+/// The Cargo features to enable in a crate, like the options of `cargo build`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FeatureSelection {
+    pub features: Vec<String>,
+    pub all_features: bool,
+    pub no_default_features: bool,
+}
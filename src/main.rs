@@ -1,27 +1,274 @@
-use daipendency::{generate_markdown_documentation, Library};
+use bpaf::{Args, ParseFailure};
+use daipendency::{
+    generate_json_diff, generate_json_documentation_with_options, generate_markdown_diff,
+    generate_markdown_documentation_with_options, Cache, FormattingOptions, Language, Library,
+    LibraryError, LoadingOptions,
+};
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
 mod cli;
-use cli::{make_command_parser, Command};
+mod cli_error;
+mod mcp;
+use cli::{
+    get_error_format, hoist_error_format, make_command_parser, Command, DependencySource,
+    ErrorFormat, LibrarySource, OutputFormat,
+};
+use cli_error::{CliError, USAGE_EXIT_CODE};
 
-fn main() -> Result<(), String> {
-    let command = make_command_parser().run();
+const HELP_WIDTH: usize = 100;
+
+fn main() -> ExitCode {
+    let args = hoist_error_format(env::args_os().skip(1).collect());
+    let error_format = get_error_format(&args);
+    let parsing_result = make_command_parser()
+        .run_inner(Args::from(args.as_slice()).set_name(env!("CARGO_BIN_NAME")));
+    let cli = match parsing_result {
+        Ok(cli) => cli,
+        Err(ParseFailure::Stderr(message)) if error_format == ErrorFormat::Json => {
+            return report_error(CliError::Usage(message.monochrome(true)), error_format);
+        }
+        Err(failure) => {
+            failure.print_message(HELP_WIDTH);
+            return match failure.exit_code() {
+                0 => ExitCode::SUCCESS,
+                _ => ExitCode::from(USAGE_EXIT_CODE),
+            };
+        }
+    };
+    match run(cli.command) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(error) => report_error(error, cli.error_format),
+    }
+}
+
+fn report_error(error: CliError, error_format: ErrorFormat) -> ExitCode {
+    match error_format {
+        ErrorFormat::Text => eprintln!("Error: {}", error),
+        ErrorFormat::Json => eprintln!("{}", error.to_json()),
+    }
+    ExitCode::from(error.get_exit_code())
+}
+
+fn run(command: Command) -> Result<(), CliError> {
     match command {
-        Command::Extract { path, language } => {
-            let library = Library::load(path.as_path(), language).map_err(|e| e.to_string())?;
-            println!("{}", generate_markdown_documentation(&library));
+        Command::Extract {
+            path,
+            language,
+            format,
+            formatting,
+            no_cache,
+            filter,
+            features,
+            target,
+            package,
+        } => {
+            let path = resolve_workspace_member(path, package, language)?;
+            let options = LoadingOptions {
+                cache: get_cache(no_cache),
+                features,
+                target,
+            };
+            let library = Library::load_with_options(&path, language, &options)?.filter(&filter);
+            println!("{}", generate_documentation(&library, format, &formatting));
         }
         Command::ExtractDep {
             dependency,
+            source,
+            format,
+            formatting,
+            no_cache,
+            filter,
+            features,
+            target,
+        } => {
+            let options = LoadingOptions {
+                cache: get_cache(no_cache),
+                features,
+                target,
+            };
+            let dependency = match source {
+                DependencySource::Registry { version } => {
+                    Library::load_registry_crate(&dependency, &version, &options)
+                }
+                DependencySource::Dependant {
+                    path,
+                    package,
+                    language,
+                } => {
+                    let dependant = resolve_workspace_member(path, package, language)?;
+                    Library::load_dependency_with_options(
+                        &dependency,
+                        &dependant,
+                        language,
+                        &options,
+                    )
+                }
+            }?
+            .filter(&filter);
+            println!(
+                "{}",
+                generate_documentation(&dependency, format, &formatting)
+            );
+        }
+        Command::ExtractAll {
             dependant,
             language,
+            format,
+            formatting,
+            no_cache,
+            output_dir,
+            depth,
+            package,
+        } => {
+            let dependant = resolve_workspace_member(dependant, package, language)?;
+            let options = LoadingOptions {
+                cache: get_cache(no_cache),
+                ..LoadingOptions::default()
+            };
+            let dependencies =
+                Library::load_dependency_tree_with_options(&dependant, language, depth, &options)?;
+            let mut documents: Vec<(String, String)> = Vec::new();
+            let mut failures = Vec::new();
+            for dependency in dependencies {
+                match dependency.library {
+                    Ok(library) => {
+                        let mut name = dependency.name;
+                        if documents.iter().any(|(other_name, _)| *other_name == name) {
+                            if let Some(version) = &library.version {
+                                name = format!("{}@{}", name, version);
+                            }
+                        }
+                        documents
+                            .push((name, generate_documentation(&library, format, &formatting)))
+                    }
+                    Err(error) => failures.push((dependency.name, error)),
+                }
+            }
+            match output_dir {
+                Some(output_dir) => write_documents(&output_dir, &documents, format)?,
+                None => println!("{}", combine_documents(&documents, format)),
+            }
+            if !failures.is_empty() {
+                return Err(CliError::PartialFailure(failures));
+            }
+        }
+        Command::Diff {
+            old,
+            new,
+            language,
+            format,
+            no_cache,
+            filter,
+            features,
+            target,
+            check,
         } => {
-            let dependency = Library::load_dependency(&dependency, &dependant, language)
-                .map_err(|e| e.to_string())?;
-            println!("{}", generate_markdown_documentation(&dependency));
+            let options = LoadingOptions {
+                cache: get_cache(no_cache),
+                features,
+                target,
+            };
+            let old = load_library_source(old, language, &options)?.filter(&filter);
+            let new = load_library_source(new, language, &options)?.filter(&filter);
+            let diff = old.diff(&new);
+            let document = match format {
+                OutputFormat::Markdown => generate_markdown_diff(&diff),
+                OutputFormat::Json => generate_json_diff(&diff),
+            };
+            println!("{}", document);
+            let verdict = diff.get_verdict();
+            if check && verdict.is_version_bump_sufficient() == Some(false) {
+                return Err(CliError::InsufficientVersionBump(verdict));
+            }
+        }
+        Command::ServeMcp { no_cache } => mcp::serve(
+            std::io::stdin().lock(),
+            std::io::stdout().lock(),
+            get_cache(no_cache).as_ref(),
+        )?,
+        Command::ClearCache => Cache::get_default()
+            .ok_or(CliError::CacheUnavailable)?
+            .clear()?,
+    }
+    Ok(())
+}
+
+/// Get the path to the workspace member called `package`, if one was requested.
+fn resolve_workspace_member(
+    path: PathBuf,
+    package: Option<String>,
+    language: Option<Language>,
+) -> Result<PathBuf, LibraryError> {
+    match package {
+        Some(package) => Library::get_workspace_member_path(&path, &package, language),
+        None => Ok(path),
+    }
+}
+
+/// Get the default cache, unless the user opted out of it.
+fn get_cache(no_cache: bool) -> Option<Cache> {
+    if no_cache {
+        None
+    } else {
+        Cache::get_default()
+    }
+}
+
+fn load_library_source(
+    source: LibrarySource,
+    language: Option<Language>,
+    options: &LoadingOptions,
+) -> Result<Library, LibraryError> {
+    match source {
+        LibrarySource::Path(path) => Library::load_with_options(&path, language, options),
+        LibrarySource::Registry { name, version } => {
+            Library::load_registry_crate(&name, &version, options)
         }
     }
+}
+
+fn generate_documentation(
+    library: &Library,
+    format: OutputFormat,
+    options: &FormattingOptions,
+) -> String {
+    match format {
+        OutputFormat::Markdown => generate_markdown_documentation_with_options(library, options),
+        OutputFormat::Json => generate_json_documentation_with_options(library, options),
+    }
+}
+
+/// Write each document to a file named after its dependency.
+fn write_documents(
+    output_dir: &Path,
+    documents: &[(String, String)],
+    format: OutputFormat,
+) -> std::io::Result<()> {
+    fs::create_dir_all(output_dir)?;
+    for (name, document) in documents {
+        let file_name = format!(
+            "{}.{}",
+            name.replace(['/', '\\', ':'], "_"),
+            format.get_file_extension()
+        );
+        fs::write(output_dir.join(file_name), document)?;
+    }
     Ok(())
 }
 
+fn combine_documents(documents: &[(String, String)], format: OutputFormat) -> String {
+    let documents = documents
+        .iter()
+        .map(|(_, document)| document.as_str())
+        .collect::<Vec<_>>();
+    match format {
+        OutputFormat::Markdown => documents.join("\n"),
+        OutputFormat::Json => format!("[{}]", documents.join(",")),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -35,6 +282,28 @@ mod tests {
         assert!(result.is_ok());
     }
 
+    #[test]
+    fn test_extract_all_command_execution() {
+        let parser = make_command_parser();
+
+        let result = parser.run_inner(&["extract-all"]);
+
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn test_combine_json_documents() {
+        let documents = vec![
+            ("a".to_string(), "{\"name\": \"a\"}".to_string()),
+            ("b".to_string(), "{\"name\": \"b\"}".to_string()),
+        ];
+
+        let combined = combine_documents(&documents, OutputFormat::Json);
+
+        let value: serde_json::Value = serde_json::from_str(&combined).unwrap();
+        assert_eq!(value, serde_json::json!([{"name": "a"}, {"name": "b"}]));
+    }
+
     #[test]
     fn test_extract_dep_command_execution() {
         let parser = make_command_parser();
@@ -1,35 +1,319 @@
+use crate::diff::{ChangeKind, LibraryDiff, SymbolChange};
+use crate::formatting_options::FormattingOptions;
+use crate::languages::LanguageConfig;
 use crate::library::Library;
-use daipendency_extractor::Namespace;
+use crate::semver::SemverVerdict;
+use crate::symbol_kind::SymbolKind;
+use crate::token_budget::{estimate_tokens, fit_to_budget, Omissions};
+use daipendency_extractor::{Namespace, Symbol};
+use std::collections::{BTreeMap, HashMap};
+
+/// Room left in the token budget for the note about omitted content.
+const OMISSION_NOTE_TOKENS: usize = 100;
+const MAX_LISTED_OMITTED_NAMESPACES: usize = 5;
+const API_HEADING: &str = "API";
+const CONTENTS_HEADING: &str = "Contents";
+const SYMBOL_INDEX_HEADING: &str = "Symbol index";
+const CODE_FENCES: [&str; 2] = ["```", "~~~"];
+/// The order of the sections of a namespace when grouping its symbols by kind.
+const KIND_SECTION_ORDER: [SymbolKind; 10] = [
+    SymbolKind::Struct,
+    SymbolKind::Class,
+    SymbolKind::Enum,
+    SymbolKind::Trait,
+    SymbolKind::Interface,
+    SymbolKind::TypeAlias,
+    SymbolKind::Function,
+    SymbolKind::Constant,
+    SymbolKind::Macro,
+    SymbolKind::Module,
+];
 
 pub fn generate_markdown_documentation(library: &Library) -> String {
-    let api_content = format_namespaces_content(
-        &library.namespaces,
-        &format!("{:?}", library.language).to_lowercase(),
-    );
+    generate_markdown_documentation_with_options(library, &FormattingOptions::default())
+}
 
-    format!(
-        r#"---
-library_name: {name}
-library_version: {version}
----
+pub fn generate_markdown_documentation_with_options(
+    library: &Library,
+    options: &FormattingOptions,
+) -> String {
+    let Some(max_tokens) = options.max_tokens else {
+        return format_document(library, options, None);
+    };
+    let available_tokens = max_tokens
+        .saturating_sub(estimate_tokens(&format_front_matter(library)) + OMISSION_NOTE_TOKENS);
+    if !options.table_of_contents {
+        let (library, omissions) = fit_to_budget(library, available_tokens, options.group_by_kind);
+        return format_fitted_document(&library, options, &omissions, max_tokens);
+    }
+    // The contents list the symbols that survive truncation, so they shrink along with them: look
+    // for the largest budget for the symbols that leaves room for their contents.
+    let fit_with_contents = |content_tokens| {
+        let (fitted_library, omissions) =
+            fit_to_budget(library, content_tokens, options.group_by_kind);
+        let contents_tokens = estimate_tokens(&format_table_of_contents(
+            &fitted_library,
+            options.group_by_kind,
+        ));
+        let fits = content_tokens + contents_tokens <= available_tokens;
+        (fitted_library, omissions, fits)
+    };
+    let (mut lowest_tokens, mut highest_tokens) = (0, available_tokens);
+    while lowest_tokens < highest_tokens {
+        let content_tokens = (lowest_tokens + highest_tokens).div_ceil(2);
+        if fit_with_contents(content_tokens).2 {
+            lowest_tokens = content_tokens;
+        } else {
+            highest_tokens = content_tokens - 1;
+        }
+    }
+    let (library, omissions, _) = fit_with_contents(lowest_tokens);
+    format_fitted_document(&library, options, &omissions, max_tokens)
+}
+
+fn format_fitted_document(
+    library: &Library,
+    options: &FormattingOptions,
+    omissions: &Omissions,
+    max_tokens: usize,
+) -> String {
+    let omission_note =
+        (!omissions.is_empty()).then(|| format_omission_note(omissions, max_tokens));
+    format_document(library, options, omission_note.as_deref())
+}
 
-{documentation}
+fn format_document(
+    library: &Library,
+    options: &FormattingOptions,
+    omission_note: Option<&str>,
+) -> String {
+    let api_content = format_namespaces_content(library, options.group_by_kind);
+    let api_content = match omission_note {
+        Some(note) => format!("{api_content}\n{note}\n"),
+        None => api_content,
+    };
+    let has_symbols = library.namespaces.iter().any(|n| !n.symbols.is_empty());
+    let api_content = if options.table_of_contents && has_symbols {
+        format!(
+            "{}\n{}",
+            format_table_of_contents(library, options.group_by_kind),
+            api_content
+        )
+    } else {
+        api_content
+    };
 
-# API
+    let documentation = match library.documentation.trim() {
+        "" => String::new(),
+        documentation => format!("{documentation}\n\n"),
+    };
+    format!(
+        r#"{front_matter}
+
+{documentation}# {API_HEADING}
 
 {api_content}"#,
+        front_matter = format_front_matter(library),
+        documentation = documentation,
+        api_content = api_content
+    )
+}
+
+/// Format a list of links to the namespaces in the output, followed by an alphabetical index of
+/// the symbols that links each one to the namespaces declaring it.
+fn format_table_of_contents(library: &Library, group_by_kind: bool) -> String {
+    let namespaces = library
+        .namespaces
+        .iter()
+        .filter(|n| !n.symbols.is_empty())
+        .collect::<Vec<_>>();
+
+    // Anchors depend on every heading before them, so go through the headings in document order.
+    let mut anchors = HeadingAnchors::default();
+    let preceding_headings = list_headings(library.documentation.trim()).chain([
+        API_HEADING,
+        CONTENTS_HEADING,
+        SYMBOL_INDEX_HEADING,
+    ]);
+    for heading in preceding_headings {
+        anchors.add(heading);
+    }
+    let mut namespace_anchors = HashMap::new();
+    for namespace in &namespaces {
+        let anchor = anchors.add(&namespace.name);
+        namespace_anchors
+            .entry(namespace.name.as_str())
+            .or_insert(anchor);
+        if group_by_kind {
+            for (kind, _) in group_symbols_by_kind(namespace, library) {
+                anchors.add(get_kind_heading(kind));
+            }
+        }
+    }
+    let format_namespace_link = |name: &str| format!("[`{}`](#{})", name, namespace_anchors[name]);
+
+    let mut contents = format!("## {CONTENTS_HEADING}\n\n");
+    for namespace in &namespaces {
+        contents.push_str(&format!("- {}\n", format_namespace_link(&namespace.name)));
+    }
+
+    let mut index = BTreeMap::<(String, &str), Vec<&str>>::new();
+    for namespace in &namespaces {
+        for symbol in &namespace.symbols {
+            let namespaces = index
+                .entry((symbol.name.to_lowercase(), symbol.name.as_str()))
+                .or_default();
+            if !namespaces.contains(&namespace.name.as_str()) {
+                namespaces.push(&namespace.name);
+            }
+        }
+    }
+    contents.push_str(&format!("\n## {SYMBOL_INDEX_HEADING}\n\n"));
+    for ((_, name), namespaces) in index {
+        let links = namespaces
+            .into_iter()
+            .map(format_namespace_link)
+            .collect::<Vec<_>>();
+        contents.push_str(&format!("- `{}`: {}\n", name, links.join(", ")));
+    }
+    contents
+}
+
+/// The anchors of the headings in a document, which GitHub makes unique by suffixing repeated
+/// ones with a counter (e.g. the second `Contents` heading gets `contents-1`).
+#[derive(Debug, Default)]
+struct HeadingAnchors {
+    occurrences: HashMap<String, usize>,
+}
+
+impl HeadingAnchors {
+    /// Get the anchor of the next heading in the document.
+    fn add(&mut self, heading: &str) -> String {
+        let base_anchor = get_heading_anchor(heading);
+        let mut anchor = base_anchor.clone();
+        while self.occurrences.contains_key(&anchor) {
+            let count = self.occurrences.entry(base_anchor.clone()).or_default();
+            *count += 1;
+            anchor = format!("{base_anchor}-{count}");
+        }
+        self.occurrences.insert(anchor.clone(), 0);
+        anchor
+    }
+}
+
+/// List the text of the ATX headings (e.g. `## Usage`) in `markdown`, outside code blocks.
+fn list_headings(markdown: &str) -> impl Iterator<Item = &str> {
+    let mut is_in_code_block = false;
+    markdown.lines().filter_map(move |line| {
+        let line = line.trim();
+        if CODE_FENCES.iter().any(|fence| line.starts_with(fence)) {
+            is_in_code_block = !is_in_code_block;
+            return None;
+        }
+        let text = line.trim_start_matches('#');
+        let level = line.len() - text.len();
+        let is_heading = !is_in_code_block
+            && (1..=6).contains(&level)
+            && (text.is_empty() || text.starts_with(' '));
+        is_heading.then(|| text.trim().trim_end_matches('#').trim_end())
+    })
+}
+
+/// Get the anchor of a heading as generated by GitHub, which lowercases it, drops punctuation
+/// and replaces spaces with hyphens (e.g. `tokio::sync` becomes `tokiosync`).
+fn get_heading_anchor(heading: &str) -> String {
+    heading
+        .to_lowercase()
+        .chars()
+        .filter_map(|character| match character {
+            ' ' => Some('-'),
+            '-' | '_' => Some(character),
+            _ if character.is_alphanumeric() => Some(character),
+            _ => None,
+        })
+        .collect()
+}
+
+fn format_front_matter(library: &Library) -> String {
+    let features = match &library.features {
+        Some(features) => format!("library_features: [{}]\n", features.join(", ")),
+        None => String::new(),
+    };
+    let target = match &library.target {
+        Some(target) => format!("library_target: {}\n", target),
+        None => String::new(),
+    };
+    format!(
+        r#"---
+library_name: {name}
+library_version: {version}
+{features}{target}---"#,
         name = library.name,
         version = library.version.as_deref().unwrap_or("null"),
-        documentation = library.documentation.trim(),
-        api_content = api_content
     )
 }
 
-fn format_namespaces_content(namespaces: &[Namespace], language: &str) -> String {
-    namespaces
+fn format_omission_note(omissions: &Omissions, max_tokens: usize) -> String {
+    let mut omitted_content = Vec::new();
+    if omissions.is_documentation_shortened {
+        omitted_content.push("the end of the documentation".to_string());
+    } else if omissions.is_documentation_omitted {
+        omitted_content.push("the documentation".to_string());
+    }
+    if omissions.omitted_symbol_count > 0 {
+        omitted_content.push(count_noun(omissions.omitted_symbol_count, "symbol"));
+    }
+    match omissions.omitted_doc_comment_count {
+        0 => {}
+        1 => omitted_content.push("the doc comment of 1 namespace".to_string()),
+        count => omitted_content.push(format!("the doc comments of {count} namespaces")),
+    }
+    let mut note = format!(
+        "> Omitted {} to fit within {} tokens.",
+        join_list(&omitted_content),
+        max_tokens
+    );
+    if !omissions.omitted_namespaces.is_empty() {
+        let mut namespaces = omissions
+            .omitted_namespaces
+            .iter()
+            .take(MAX_LISTED_OMITTED_NAMESPACES)
+            .map(|name| format!("`{name}`"))
+            .collect::<Vec<_>>();
+        let unlisted_count = omissions
+            .omitted_namespaces
+            .len()
+            .saturating_sub(MAX_LISTED_OMITTED_NAMESPACES);
+        if unlisted_count > 0 {
+            namespaces.push(format!("{unlisted_count} more"));
+        }
+        note.push_str(&format!(
+            " Namespaces left out entirely: {}.",
+            namespaces.join(", ")
+        ));
+    }
+    note
+}
+
+fn format_namespaces_content(library: &Library, group_by_kind: bool) -> String {
+    let config = LanguageConfig::get_from_language(library.language);
+    library
+        .namespaces
         .iter()
         .filter(|n| !n.symbols.is_empty())
-        .map(|n| format_namespace_content(n, language))
+        .map(|n| {
+            let source_codes = n
+                .symbols
+                .iter()
+                .map(|symbol| symbol.source_code.as_str())
+                .collect::<Vec<_>>();
+            let language = config.get_code_block_language(&source_codes);
+            if group_by_kind {
+                format_grouped_namespace_content(n, library, language)
+            } else {
+                format_namespace_content(n, language)
+            }
+        })
         .collect::<Vec<_>>()
         .join("\n")
 }
@@ -43,20 +327,202 @@ fn format_namespace_content(namespace: &Namespace, language: &str) -> String {
             code_block.push_str(doc);
             code_block.push('\n');
         }
-        code_block.push_str(
-            &namespace
-                .symbols
-                .iter()
-                .map(|s| s.source_code.as_str())
-                .collect::<Vec<_>>()
-                .join("\n\n"),
-        );
+        code_block.push_str(&join_source_code(&namespace.symbols));
         content.push_str(&format!("```{}\n{}\n```\n", language, code_block));
     }
 
     content
 }
 
+/// Format a namespace with a section per kind of symbol, followed by the symbols of unknown kinds.
+fn format_grouped_namespace_content(
+    namespace: &Namespace,
+    library: &Library,
+    language: &str,
+) -> String {
+    let mut content = format!("## {}\n", namespace.name);
+    if let Some(doc) = &namespace.doc_comment {
+        content.push_str(&format!("\n```{}\n{}\n```\n", language, doc.trim_end()));
+    }
+    for (kind, symbols) in group_symbols_by_kind(namespace, library) {
+        content.push_str(&format!(
+            "\n### {}\n\n```{}\n{}\n```\n",
+            get_kind_heading(kind),
+            language,
+            join_source_code(symbols)
+        ));
+    }
+    content
+}
+
+/// Group the symbols of `namespace` by kind in the order of their sections, leaving out kinds
+/// without symbols.
+fn group_symbols_by_kind<'a>(
+    namespace: &'a Namespace,
+    library: &Library,
+) -> Vec<(Option<SymbolKind>, Vec<&'a Symbol>)> {
+    KIND_SECTION_ORDER
+        .into_iter()
+        .map(Some)
+        .chain([None])
+        .map(|kind| {
+            let symbols = namespace
+                .symbols
+                .iter()
+                .filter(|symbol| library.get_symbol_kind(symbol) == kind)
+                .collect::<Vec<_>>();
+            (kind, symbols)
+        })
+        .filter(|(_, symbols)| !symbols.is_empty())
+        .collect()
+}
+
+fn get_kind_heading(kind: Option<SymbolKind>) -> &'static str {
+    match kind {
+        Some(SymbolKind::Function) => "Functions",
+        Some(SymbolKind::Struct) => "Structs",
+        Some(SymbolKind::Enum) => "Enums",
+        Some(SymbolKind::Trait) => "Traits",
+        Some(SymbolKind::TypeAlias) => "Type aliases",
+        Some(SymbolKind::Constant) => "Constants",
+        Some(SymbolKind::Macro) => "Macros",
+        Some(SymbolKind::Module) => "Modules",
+        Some(SymbolKind::Class) => "Classes",
+        Some(SymbolKind::Interface) => "Interfaces",
+        None => "Other symbols",
+    }
+}
+
+fn join_source_code<'a>(symbols: impl IntoIterator<Item = &'a Symbol>) -> String {
+    symbols
+        .into_iter()
+        .map(|s| s.source_code.as_str())
+        .collect::<Vec<_>>()
+        .join("\n\n")
+}
+
+/// Generate a Markdown document listing the changes to the public API in `diff`.
+pub fn generate_markdown_diff(diff: &LibraryDiff) -> String {
+    let config = LanguageConfig::get_from_language(diff.language);
+    let mut content = String::new();
+    if diff.is_empty() {
+        content.push_str("No changes to the public API.\n");
+    }
+    for (label, namespaces) in [
+        ("Added namespaces", &diff.added_namespaces),
+        ("Removed namespaces", &diff.removed_namespaces),
+    ] {
+        if !namespaces.is_empty() {
+            let namespaces = namespaces
+                .iter()
+                .map(|name| format!("`{name}`"))
+                .collect::<Vec<_>>();
+            content.push_str(&format!("{}: {}.\n", label, namespaces.join(", ")));
+        }
+    }
+    let mut current_namespace = None;
+    for change in &diff.changes {
+        if current_namespace != Some(&change.namespace) {
+            if !content.is_empty() {
+                content.push('\n');
+            }
+            content.push_str(&format!("## {}\n", change.namespace));
+            current_namespace = Some(&change.namespace);
+        }
+        let source_codes = [&change.old_source_code, &change.new_source_code]
+            .into_iter()
+            .flatten()
+            .map(String::as_str)
+            .collect::<Vec<_>>();
+        let language = config.get_code_block_language(&source_codes);
+        content.push_str(&format_symbol_change(change, language));
+    }
+
+    format!(
+        r#"---
+library_name: {name}
+old_version: {old_version}
+new_version: {new_version}
+---
+
+# API changes
+
+{verdict}
+
+{content}"#,
+        name = diff.name,
+        old_version = diff.old_version.as_deref().unwrap_or("null"),
+        new_version = diff.new_version.as_deref().unwrap_or("null"),
+        verdict = format_verdict(&diff.get_verdict()),
+    )
+}
+
+fn format_verdict(verdict: &SemverVerdict) -> String {
+    let required_bump = format!("Required version bump: {}.", verdict.required_bump);
+    match (verdict.actual_bump, verdict.is_version_bump_sufficient()) {
+        (Some(actual_bump), Some(is_sufficient)) => format!(
+            "{} Actual version bump: {}, which is {}.",
+            required_bump,
+            actual_bump,
+            if is_sufficient {
+                "sufficient"
+            } else {
+                "insufficient"
+            }
+        ),
+        _ => required_bump,
+    }
+}
+
+fn format_symbol_change(change: &SymbolChange, language: &str) -> String {
+    let format_code = |source_code: &Option<String>| {
+        format!(
+            "```{}\n{}\n```\n",
+            language,
+            source_code.as_deref().unwrap_or_default()
+        )
+    };
+    let heading = match change.kind {
+        ChangeKind::Added => "Added",
+        ChangeKind::Removed => "Removed",
+        ChangeKind::Changed => "Changed",
+    };
+    let body = match change.kind {
+        ChangeKind::Added => format_code(&change.new_source_code),
+        ChangeKind::Removed => format_code(&change.old_source_code),
+        ChangeKind::Changed => format!(
+            "Before:\n\n{}\nAfter:\n\n{}",
+            format_code(&change.old_source_code),
+            format_code(&change.new_source_code)
+        ),
+    };
+    let name = match change.symbol_kind {
+        Some(symbol_kind) => format!("{} `{}`", symbol_kind, change.name),
+        None => format!("`{}`", change.name),
+    };
+    format!(
+        "\n### {}: {} ({})\n\n{}",
+        heading, name, change.impact, body
+    )
+}
+
+/// Join `items` as in "a, b and c".
+/// Format `count` followed by `noun`, pluralised unless `count` is 1.
+fn count_noun(count: usize, noun: &str) -> String {
+    match count {
+        1 => format!("1 {noun}"),
+        _ => format!("{count} {noun}s"),
+    }
+}
+
+fn join_list(items: &[String]) -> String {
+    match items {
+        [] => String::new(),
+        [item] => item.clone(),
+        [init @ .., last] => format!("{} and {}", init.join(", "), last),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -80,6 +546,8 @@ mod tests {
             documentation: STUB_DOCUMENTATION.to_string(),
             namespaces,
             language: STUB_LANGUAGE,
+            features: None,
+            target: None,
         }
     }
 
@@ -130,6 +598,24 @@ mod tests {
             assert_contains!(frontmatter_lines, &"library_version: null".to_string());
         }
 
+        #[test]
+        fn with_configuration() {
+            let mut library = create_library(vec![]);
+            library.features = Some(vec!["fs".to_string(), "net".to_string()]);
+            library.target = Some("x86_64-unknown-linux-gnu".to_string());
+            let documentation = generate_markdown_documentation(&library);
+            let frontmatter_lines = get_frontmatter_lines(documentation).unwrap();
+
+            assert_contains!(
+                frontmatter_lines,
+                &"library_features: [fs, net]".to_string()
+            );
+            assert_contains!(
+                frontmatter_lines,
+                &"library_target: x86_64-unknown-linux-gnu".to_string()
+            );
+        }
+
         #[test]
         fn library_documentation() {
             let library = create_library(vec![]);
@@ -142,6 +628,131 @@ mod tests {
         }
     }
 
+    mod token_budget {
+        use super::*;
+
+        fn create_large_library() -> Library {
+            let mut library = create_library(vec![Namespace {
+                name: STUB_LIBRARY_NAME.to_string(),
+                symbols: (0..100)
+                    .map(|index| Symbol {
+                        name: format!("symbol{index}"),
+                        source_code: format!("pub fn symbol{index}() {{}}"),
+                    })
+                    .collect(),
+                doc_comment: None,
+            }]);
+            library.documentation = "Paragraph.\n\n".repeat(200);
+            library
+        }
+
+        fn generate(library: &Library, max_tokens: Option<usize>) -> String {
+            let options = FormattingOptions {
+                max_tokens,
+                ..FormattingOptions::default()
+            };
+            generate_markdown_documentation_with_options(library, &options)
+        }
+
+        #[test]
+        fn unlimited() {
+            let library = create_large_library();
+
+            let documentation = generate(&library, None);
+
+            assert_eq!(documentation, generate_markdown_documentation(&library));
+            assert!(!documentation.contains("> Omitted"));
+        }
+
+        #[test]
+        fn within_budget() {
+            let library = create_library(vec![]);
+
+            let documentation = generate(&library, Some(1000));
+
+            assert_eq!(documentation, generate_markdown_documentation(&library));
+        }
+
+        #[test]
+        fn over_budget() {
+            let max_tokens = 500;
+            let library = create_large_library();
+
+            let documentation = generate(&library, Some(max_tokens));
+
+            assert!(estimate_tokens(&documentation) <= max_tokens);
+            assert_contains!(documentation, "library_name: test-lib");
+            assert_contains!(documentation, "pub fn symbol0() {}");
+            assert_contains!(documentation, "> Omitted the end of the documentation and ");
+            assert_contains!(documentation, " symbols to fit within 500 tokens.");
+        }
+
+        #[test]
+        fn documentation_omitted() {
+            let mut library = create_large_library();
+            library.documentation = "Paragraph. ".repeat(1000);
+
+            let documentation = generate(&library, Some(500));
+
+            assert_contains!(documentation, "---\n\n# API\n\n");
+            assert_contains!(documentation, "> Omitted the documentation and ");
+        }
+
+        #[test]
+        fn omitted_content_listed() {
+            let omissions = Omissions {
+                is_documentation_shortened: true,
+                is_documentation_omitted: false,
+                omitted_symbol_count: 3,
+                omitted_namespaces: vec![],
+                omitted_doc_comment_count: 2,
+            };
+
+            let note = format_omission_note(&omissions, 100);
+
+            assert_eq!(
+                note,
+                "> Omitted the end of the documentation, 3 symbols and the doc comments of 2 namespaces to fit within 100 tokens."
+            );
+        }
+
+        #[test]
+        fn singular_omissions() {
+            let omissions = Omissions {
+                is_documentation_shortened: false,
+                is_documentation_omitted: false,
+                omitted_symbol_count: 1,
+                omitted_namespaces: vec![],
+                omitted_doc_comment_count: 1,
+            };
+
+            let note = format_omission_note(&omissions, 100);
+
+            assert_eq!(
+                note,
+                "> Omitted 1 symbol and the doc comment of 1 namespace to fit within 100 tokens."
+            );
+        }
+
+        #[test]
+        fn omitted_namespaces_listed() {
+            let omissions = Omissions {
+                is_documentation_shortened: false,
+                is_documentation_omitted: false,
+                omitted_symbol_count: 7,
+                omitted_namespaces: (1..=7).map(|index| format!("ns{index}")).collect(),
+                omitted_doc_comment_count: 0,
+            };
+
+            let note = format_omission_note(&omissions, 100);
+
+            assert_eq!(
+                note,
+                "> Omitted 7 symbols to fit within 100 tokens. Namespaces left out entirely: `ns1`, `ns2`, `ns3`, `ns4`, `ns5`, 2 more."
+            );
+        }
+    }
+
     mod api {
         use super::*;
 
@@ -177,6 +788,23 @@ mod tests {
             assert_api_is_empty(&documentation);
         }
 
+        #[test]
+        fn kotlin_namespace() {
+            let mut library = create_library(vec![create_namespace(
+                "com.example",
+                vec![create_symbol("greet", "fun greet(): String")],
+                None,
+            )]);
+            library.language = Language::Java;
+
+            let documentation = generate_markdown_documentation(&library);
+
+            assert_contains!(
+                documentation,
+                "## com.example\n\n```kotlin\nfun greet(): String\n```\n"
+            );
+        }
+
         #[test]
         fn single_namespace() {
             let namespace = create_namespace(
@@ -334,4 +962,398 @@ mod tests {
             }
         }
     }
+
+    mod grouped_by_kind {
+        use super::*;
+
+        fn create_namespace(symbols: &[(&str, &str)], doc_comment: Option<&str>) -> Namespace {
+            Namespace {
+                name: "test".to_string(),
+                symbols: symbols
+                    .iter()
+                    .map(|(name, source_code)| Symbol {
+                        name: name.to_string(),
+                        source_code: source_code.to_string(),
+                    })
+                    .collect(),
+                doc_comment: doc_comment.map(String::from),
+            }
+        }
+
+        fn generate(library: &Library) -> String {
+            let options = FormattingOptions {
+                group_by_kind: true,
+                ..FormattingOptions::default()
+            };
+            generate_markdown_documentation_with_options(library, &options)
+        }
+
+        #[test]
+        fn sections() {
+            let library = create_library(vec![create_namespace(
+                &[
+                    ("f", "pub fn f();"),
+                    ("S", "pub struct S;"),
+                    ("g", "pub fn g();"),
+                    ("T", "pub trait T {}"),
+                ],
+                None,
+            )]);
+
+            let documentation = generate(&library);
+
+            assert_contains!(
+                documentation,
+                "## test\n\n### Structs\n\n```rust\npub struct S;\n```\n\n\
+                 ### Traits\n\n```rust\npub trait T {}\n```\n\n\
+                 ### Functions\n\n```rust\npub fn f();\n\npub fn g();\n```\n"
+            );
+        }
+
+        #[test]
+        fn doc_comment() {
+            let library = create_library(vec![create_namespace(
+                &[("f", "pub fn f();")],
+                Some("//! Test module\n"),
+            )]);
+
+            let documentation = generate(&library);
+
+            assert_contains!(
+                documentation,
+                "## test\n\n```rust\n//! Test module\n```\n\n### Functions\n"
+            );
+        }
+
+        #[test]
+        fn over_budget() {
+            let max_tokens = 300;
+            let source_codes = [
+                "pub fn f();",
+                "pub struct S;",
+                "pub enum E {}",
+                "pub trait T {}",
+                "pub type A = u8;",
+                "pub const C: u8;",
+                "macro_rules! m;",
+            ];
+            let library = create_library(
+                (0..10)
+                    .map(|index| Namespace {
+                        name: format!("test::module{index}"),
+                        symbols: source_codes
+                            .iter()
+                            .map(|source_code| Symbol {
+                                name: format!("symbol{index}"),
+                                source_code: source_code.to_string(),
+                            })
+                            .collect(),
+                        doc_comment: None,
+                    })
+                    .collect(),
+            );
+            let options = FormattingOptions {
+                max_tokens: Some(max_tokens),
+                group_by_kind: true,
+                ..FormattingOptions::default()
+            };
+
+            let documentation = generate_markdown_documentation_with_options(&library, &options);
+
+            assert!(estimate_tokens(&documentation) <= max_tokens);
+            assert_contains!(documentation, "### Structs\n");
+        }
+
+        #[test]
+        fn unknown_kind() {
+            let library = create_library(vec![create_namespace(
+                &[("f", "pub fn f();"), ("S", "impl S {}")],
+                None,
+            )]);
+
+            let documentation = generate(&library);
+
+            assert_contains!(
+                documentation,
+                "### Functions\n\n```rust\npub fn f();\n```\n\n### Other symbols\n\n```rust\nimpl S {}\n```\n"
+            );
+        }
+    }
+
+    mod table_of_contents {
+        use super::*;
+
+        fn create_namespace(name: &str, symbol_names: &[&str]) -> Namespace {
+            Namespace {
+                name: name.to_string(),
+                symbols: symbol_names
+                    .iter()
+                    .map(|symbol_name| Symbol {
+                        name: symbol_name.to_string(),
+                        source_code: format!("pub fn {symbol_name}();"),
+                    })
+                    .collect(),
+                doc_comment: None,
+            }
+        }
+
+        fn generate(library: &Library) -> String {
+            let options = FormattingOptions {
+                table_of_contents: true,
+                ..FormattingOptions::default()
+            };
+            generate_markdown_documentation_with_options(library, &options)
+        }
+
+        #[test]
+        fn contents() {
+            let library = create_library(vec![
+                create_namespace("test", &["run"]),
+                create_namespace("test::empty", &[]),
+                create_namespace("test::sync_io", &["Mutex"]),
+            ]);
+
+            let documentation = generate(&library);
+
+            assert_contains!(
+                documentation,
+                "# API\n\n## Contents\n\n- [`test`](#test)\n- [`test::sync_io`](#testsync_io)\n\n"
+            );
+        }
+
+        #[test]
+        fn symbol_index() {
+            let library = create_library(vec![
+                create_namespace("test", &["run", "Error", "Error"]),
+                create_namespace("test::sync", &["Error", "acquire"]),
+            ]);
+
+            let documentation = generate(&library);
+
+            assert_contains!(
+                documentation,
+                "## Symbol index\n\n\
+                 - `acquire`: [`test::sync`](#testsync)\n\
+                 - `Error`: [`test`](#test), [`test::sync`](#testsync)\n\
+                 - `run`: [`test`](#test)\n\n## test\n"
+            );
+        }
+
+        #[test]
+        fn namespace_named_like_heading() {
+            let library = create_library(vec![
+                create_namespace("contents", &["run"]),
+                create_namespace("Symbol index", &["stop"]),
+            ]);
+
+            let documentation = generate(&library);
+
+            assert_contains!(
+                documentation,
+                "- [`contents`](#contents-1)\n- [`Symbol index`](#symbol-index-1)\n"
+            );
+        }
+
+        #[test]
+        fn namespace_named_like_documentation_heading() {
+            let mut library = create_library(vec![create_namespace("test", &["run"])]);
+            library.documentation = "# Test\n\n```sh\n# Not a heading\n```".to_string();
+
+            let documentation = generate(&library);
+
+            assert_contains!(documentation, "- [`test`](#test-1)\n");
+        }
+
+        #[test]
+        fn namespace_named_like_kind_section() {
+            let library = create_library(vec![
+                create_namespace("test", &["run"]),
+                create_namespace("functions", &["stop"]),
+            ]);
+            let options = FormattingOptions {
+                group_by_kind: true,
+                table_of_contents: true,
+                ..FormattingOptions::default()
+            };
+
+            let documentation = generate_markdown_documentation_with_options(&library, &options);
+
+            assert_contains!(documentation, "- [`functions`](#functions-1)\n");
+        }
+
+        #[test]
+        fn no_symbols() {
+            let library = create_library(vec![create_namespace("test", &[])]);
+
+            let documentation = generate(&library);
+
+            assert!(!documentation.contains("## Contents"));
+        }
+
+        #[test]
+        fn within_budget() {
+            let library = create_library(vec![create_namespace("test", &["run"])]);
+            let options = FormattingOptions {
+                max_tokens: Some(1000),
+                table_of_contents: true,
+                ..FormattingOptions::default()
+            };
+
+            let documentation = generate_markdown_documentation_with_options(&library, &options);
+
+            assert_eq!(documentation, generate(&library));
+        }
+
+        #[test]
+        fn small_budget() {
+            let max_tokens = 400;
+            let library = create_library(
+                (0..20)
+                    .map(|index| {
+                        create_namespace(
+                            &format!("test::module{index}"),
+                            &["run", "stop", "restart", "pause", "resume"],
+                        )
+                    })
+                    .collect(),
+            );
+            let options = FormattingOptions {
+                max_tokens: Some(max_tokens),
+                table_of_contents: true,
+                ..FormattingOptions::default()
+            };
+
+            let documentation = generate_markdown_documentation_with_options(&library, &options);
+
+            assert!(estimate_tokens(&documentation) <= max_tokens);
+            assert_contains!(documentation, "## Contents\n\n- [`test::module0`]");
+            assert_contains!(documentation, "pub fn run()");
+        }
+    }
+
+    mod diff {
+        use super::*;
+        use crate::diff::LibraryDiff;
+        use crate::semver::SemverImpact;
+        use crate::symbol_kind::SymbolKind;
+
+        fn create_diff(changes: Vec<SymbolChange>) -> LibraryDiff {
+            LibraryDiff {
+                name: STUB_LIBRARY_NAME.to_string(),
+                language: STUB_LANGUAGE,
+                old_version: Some(STUB_LIBRARY_VERSION.to_string()),
+                new_version: None,
+                added_namespaces: vec!["test::net".to_string()],
+                removed_namespaces: vec![],
+                changes,
+            }
+        }
+
+        fn create_change(
+            kind: ChangeKind,
+            old_source_code: Option<&str>,
+            new_source_code: Option<&str>,
+        ) -> SymbolChange {
+            SymbolChange {
+                namespace: "test".to_string(),
+                name: "symbol".to_string(),
+                kind,
+                symbol_kind: None,
+                impact: SemverImpact::Major,
+                old_source_code: old_source_code.map(String::from),
+                new_source_code: new_source_code.map(String::from),
+            }
+        }
+
+        #[test]
+        fn front_matter() {
+            let documentation = generate_markdown_diff(&create_diff(vec![]));
+
+            assert!(documentation.starts_with(&format!(
+                "---\nlibrary_name: {STUB_LIBRARY_NAME}\nold_version: {STUB_LIBRARY_VERSION}\nnew_version: null\n---\n"
+            )));
+        }
+
+        #[test]
+        fn no_changes() {
+            let mut diff = create_diff(vec![]);
+            diff.added_namespaces.clear();
+
+            let documentation = generate_markdown_diff(&diff);
+
+            assert_contains!(documentation, "No changes to the public API.");
+        }
+
+        #[test]
+        fn verdict() {
+            let mut diff = create_diff(vec![create_change(
+                ChangeKind::Removed,
+                Some(STUB_SOURCE_CODE),
+                None,
+            )]);
+            diff.new_version = Some("1.1.0".to_string());
+
+            let documentation = generate_markdown_diff(&diff);
+
+            assert_contains!(
+                documentation,
+                "# API changes\n\nRequired version bump: major. Actual version bump: minor, which is insufficient.\n"
+            );
+        }
+
+        #[test]
+        fn verdict_without_version() {
+            let documentation = generate_markdown_diff(&create_diff(vec![]));
+
+            assert_contains!(
+                documentation,
+                "# API changes\n\nRequired version bump: minor.\n\n"
+            );
+        }
+
+        #[test]
+        fn namespace_changes() {
+            let documentation = generate_markdown_diff(&create_diff(vec![]));
+
+            assert_contains!(documentation, "Added namespaces: `test::net`.");
+        }
+
+        #[test]
+        fn added_symbol() {
+            let change = create_change(ChangeKind::Added, None, Some(STUB_SOURCE_CODE));
+
+            let documentation = generate_markdown_diff(&create_diff(vec![change]));
+
+            assert_contains!(
+                documentation,
+                &format!(
+                    "## test\n\n### Added: `symbol` (major)\n\n```{STUB_LANGUAGE_STR}\n{STUB_SOURCE_CODE}\n```\n"
+                )
+            );
+        }
+
+        #[test]
+        fn symbol_kind() {
+            let mut change = create_change(ChangeKind::Removed, Some(STUB_SOURCE_CODE), None);
+            change.symbol_kind = Some(SymbolKind::Function);
+
+            let documentation = generate_markdown_diff(&create_diff(vec![change]));
+
+            assert_contains!(documentation, "### Removed: function `symbol` (major)\n");
+        }
+
+        #[test]
+        fn changed_symbol() {
+            let change = create_change(ChangeKind::Changed, Some("OLD"), Some("NEW"));
+
+            let documentation = generate_markdown_diff(&create_diff(vec![change]));
+
+            assert_contains!(
+                documentation,
+                &format!(
+                    "### Changed: `symbol` (major)\n\nBefore:\n\n```{STUB_LANGUAGE_STR}\nOLD\n```\n\nAfter:\n\n```{STUB_LANGUAGE_STR}\nNEW\n```\n"
+                )
+            );
+        }
+    }
 }
@@ -0,0 +1,189 @@
+//! A Model Context Protocol server exposing Daipendency as tools over stdio.
+//!
+//! Messages are JSON-RPC 2.0 objects delimited by newlines.
+
+use daipendency::Cache;
+use serde_json::{json, Value};
+use std::io::{BufRead, Write};
+
+mod tools;
+
+const JSONRPC_VERSION: &str = "2.0";
+const PROTOCOL_VERSION: &str = "2024-11-05";
+const SERVER_NAME: &str = "daipendency";
+
+const PARSE_ERROR: i64 = -32700;
+const INVALID_REQUEST: i64 = -32600;
+const METHOD_NOT_FOUND: i64 = -32601;
+const INVALID_PARAMS: i64 = -32602;
+
+/// An error to report to the client in place of a result.
+#[derive(Debug, PartialEq)]
+struct RpcError {
+    code: i64,
+    message: String,
+}
+
+impl RpcError {
+    fn new(code: i64, message: impl Into<String>) -> Self {
+        Self {
+            code,
+            message: message.into(),
+        }
+    }
+}
+
+/// Serve requests from `input` until it is closed, writing responses to `output`.
+pub fn serve(
+    input: impl BufRead,
+    mut output: impl Write,
+    cache: Option<&Cache>,
+) -> std::io::Result<()> {
+    for line in input.lines() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        if let Some(response) = handle_message(&line, cache) {
+            writeln!(output, "{}", response)?;
+            output.flush()?;
+        }
+    }
+    Ok(())
+}
+
+/// Handle a JSON-RPC message, returning the response unless the message is a notification.
+fn handle_message(message: &str, cache: Option<&Cache>) -> Option<Value> {
+    let message: Value = match serde_json::from_str(message) {
+        Ok(message) => message,
+        Err(error) => {
+            return Some(format_error(
+                Value::Null,
+                RpcError::new(PARSE_ERROR, error.to_string()),
+            ))
+        }
+    };
+    let id = message.get("id").cloned();
+    let Some(method) = message.get("method").and_then(Value::as_str) else {
+        return Some(format_error(
+            id.unwrap_or(Value::Null),
+            RpcError::new(INVALID_REQUEST, "Missing method"),
+        ));
+    };
+    let params = message.get("params").cloned().unwrap_or(json!({}));
+
+    let result = handle_request(method, &params, cache);
+    let id = id?;
+    Some(match result {
+        Ok(result) => json!({"jsonrpc": JSONRPC_VERSION, "id": id, "result": result}),
+        Err(error) => format_error(id, error),
+    })
+}
+
+fn handle_request(method: &str, params: &Value, cache: Option<&Cache>) -> Result<Value, RpcError> {
+    match method {
+        "initialize" => Ok(json!({
+            "protocolVersion": PROTOCOL_VERSION,
+            "capabilities": {"tools": {}},
+            "serverInfo": {"name": SERVER_NAME, "version": env!("CARGO_PKG_VERSION")},
+        })),
+        "ping" => Ok(json!({})),
+        "tools/list" => Ok(json!({"tools": tools::get_tool_definitions()})),
+        "tools/call" => {
+            let name = params
+                .get("name")
+                .and_then(Value::as_str)
+                .ok_or_else(|| RpcError::new(INVALID_PARAMS, "Missing tool name"))?;
+            let arguments = params.get("arguments").cloned().unwrap_or(json!({}));
+            tools::call_tool(name, &arguments, cache)
+        }
+        method if method.starts_with("notifications/") => Ok(Value::Null),
+        method => Err(RpcError::new(
+            METHOD_NOT_FOUND,
+            format!("Unknown method '{}'", method),
+        )),
+    }
+}
+
+fn format_error(id: Value, error: RpcError) -> Value {
+    json!({
+        "jsonrpc": JSONRPC_VERSION,
+        "id": id,
+        "error": {"code": error.code, "message": error.message},
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request(method: &str, params: Value) -> Value {
+        let message = json!({"jsonrpc": "2.0", "id": 1, "method": method, "params": params});
+        handle_message(&message.to_string(), None).unwrap()
+    }
+
+    #[test]
+    fn test_initialize() {
+        let response = request("initialize", json!({"protocolVersion": PROTOCOL_VERSION}));
+
+        assert_eq!(response["id"], 1);
+        assert_eq!(response["result"]["protocolVersion"], PROTOCOL_VERSION);
+        assert_eq!(response["result"]["serverInfo"]["name"], SERVER_NAME);
+        assert_eq!(response["result"]["capabilities"]["tools"], json!({}));
+    }
+
+    #[test]
+    fn test_notification_without_response() {
+        let message = json!({"jsonrpc": "2.0", "method": "notifications/initialized"});
+
+        let response = handle_message(&message.to_string(), None);
+
+        assert_eq!(response, None);
+    }
+
+    #[test]
+    fn test_tools_list() {
+        let response = request("tools/list", json!({}));
+
+        let tool_names = response["result"]["tools"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|tool| tool["name"].as_str().unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(tool_names, vec!["extract_dependency", "list_dependencies"]);
+    }
+
+    #[test]
+    fn test_unknown_method() {
+        let response = request("resources/list", json!({}));
+
+        assert_eq!(response["error"]["code"], METHOD_NOT_FOUND);
+    }
+
+    #[test]
+    fn test_malformed_message() {
+        let response = handle_message("{not json", None).unwrap();
+
+        assert_eq!(response["id"], Value::Null);
+        assert_eq!(response["error"]["code"], PARSE_ERROR);
+    }
+
+    #[test]
+    fn test_serve() {
+        let input = format!(
+            "{}\n\n{}\n",
+            json!({"jsonrpc": "2.0", "method": "notifications/initialized"}),
+            json!({"jsonrpc": "2.0", "id": "a", "method": "ping"})
+        );
+        let mut output = Vec::new();
+
+        serve(input.as_bytes(), &mut output, None).unwrap();
+
+        let output = String::from_utf8(output).unwrap();
+        assert_eq!(
+            output.lines().collect::<Vec<_>>(),
+            vec![r#"{"id":"a","jsonrpc":"2.0","result":{}}"#]
+        );
+    }
+}
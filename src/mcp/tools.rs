@@ -0,0 +1,254 @@
+use super::{RpcError, INVALID_PARAMS};
+use daipendency::{
+    generate_markdown_documentation_with_options, Cache, FormattingOptions, Language, Library,
+    LibraryError, LoadingOptions,
+};
+use serde_json::{json, Value};
+use std::path::Path;
+
+const EXTRACT_DEPENDENCY_TOOL: &str = "extract_dependency";
+const LIST_DEPENDENCIES_TOOL: &str = "list_dependencies";
+
+pub fn get_tool_definitions() -> Value {
+    json!([
+        {
+            "name": EXTRACT_DEPENDENCY_TOOL,
+            "description": "Extract the documentation and public API of a dependency of a project as Markdown, honouring the version in the project's manifest",
+            "inputSchema": {
+                "type": "object",
+                "properties": {
+                    "name": {"type": "string", "description": "Name of the dependency"},
+                    "dependant": {"type": "string", "description": "Absolute path to the dependant project"},
+                    "language": {"type": "string", "description": "Programming language of the project, detected if omitted"},
+                    "max_tokens": {"type": "integer", "description": "Approximate maximum number of tokens in the output"},
+                    "group_by_kind": {"type": "boolean", "description": "Whether to split the symbols in each namespace into a section per kind, such as structs and functions"},
+                    "table_of_contents": {"type": "boolean", "description": "Whether to start the API with links to each namespace and an alphabetical index of the symbols"},
+                },
+                "required": ["name", "dependant"],
+            },
+        },
+        {
+            "name": LIST_DEPENDENCIES_TOOL,
+            "description": "List the direct dependencies declared in the manifest of a project",
+            "inputSchema": {
+                "type": "object",
+                "properties": {
+                    "dependant": {"type": "string", "description": "Absolute path to the dependant project"},
+                    "language": {"type": "string", "description": "Programming language of the project, detected if omitted"},
+                },
+                "required": ["dependant"],
+            },
+        },
+    ])
+}
+
+/// Call a tool, reporting failures to run it as tool results so that the model can see them.
+pub fn call_tool(name: &str, arguments: &Value, cache: Option<&Cache>) -> Result<Value, RpcError> {
+    let output = match name {
+        EXTRACT_DEPENDENCY_TOOL => extract_dependency(arguments, cache)?,
+        LIST_DEPENDENCIES_TOOL => list_dependencies(arguments)?,
+        _ => {
+            return Err(RpcError::new(
+                INVALID_PARAMS,
+                format!("Unknown tool '{}'", name),
+            ))
+        }
+    };
+    let (text, is_error) = match output {
+        Ok(text) => (text, false),
+        Err(error) => (error.to_string(), true),
+    };
+    Ok(json!({
+        "content": [{"type": "text", "text": text}],
+        "isError": is_error,
+    }))
+}
+
+fn extract_dependency(
+    arguments: &Value,
+    cache: Option<&Cache>,
+) -> Result<Result<String, LibraryError>, RpcError> {
+    let name = get_string_argument(arguments, "name")?;
+    let dependant = get_string_argument(arguments, "dependant")?;
+    let language = get_language_argument(arguments)?;
+    let options = FormattingOptions {
+        max_tokens: get_optional_argument(arguments, "max_tokens", Value::as_u64)?
+            .map(|max_tokens| max_tokens as usize),
+        group_by_kind: get_optional_argument(arguments, "group_by_kind", Value::as_bool)?
+            .unwrap_or_default(),
+        table_of_contents: get_optional_argument(arguments, "table_of_contents", Value::as_bool)?
+            .unwrap_or_default(),
+    };
+    let dependant = Path::new(dependant);
+    let loading_options = LoadingOptions {
+        cache: cache.cloned(),
+        ..LoadingOptions::default()
+    };
+    let library =
+        Library::load_dependency_with_options(name, dependant, language, &loading_options);
+    Ok(library.map(|library| generate_markdown_documentation_with_options(&library, &options)))
+}
+
+fn list_dependencies(arguments: &Value) -> Result<Result<String, LibraryError>, RpcError> {
+    let dependant = get_string_argument(arguments, "dependant")?;
+    let language = get_language_argument(arguments)?;
+    Ok(Library::list_dependencies(Path::new(dependant), language).map(|names| names.join("\n")))
+}
+
+fn get_string_argument<'a>(arguments: &'a Value, name: &str) -> Result<&'a str, RpcError> {
+    get_optional_argument(arguments, name, Value::as_str)?
+        .ok_or_else(|| RpcError::new(INVALID_PARAMS, format!("Missing argument '{}'", name)))
+}
+
+fn get_language_argument(arguments: &Value) -> Result<Option<Language>, RpcError> {
+    get_optional_argument(arguments, "language", Value::as_str)?
+        .map(|language| {
+            language
+                .parse()
+                .map_err(|error: anyhow::Error| RpcError::new(INVALID_PARAMS, error.to_string()))
+        })
+        .transpose()
+}
+
+fn get_optional_argument<'a, T>(
+    arguments: &'a Value,
+    name: &str,
+    convert: impl Fn(&'a Value) -> Option<T>,
+) -> Result<Option<T>, RpcError> {
+    match arguments.get(name) {
+        None | Some(Value::Null) => Ok(None),
+        Some(value) => convert(value)
+            .map(Some)
+            .ok_or_else(|| RpcError::new(INVALID_PARAMS, format!("Invalid argument '{}'", name))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use daipendency_testing::tempdir::TempDir;
+
+    const STUB_DEPENDENCY: &str = "left-pad";
+
+    fn create_dependant(temp_dir: &TempDir) {
+        temp_dir
+            .create_file(
+                "package.json",
+                &format!(r#"{{"name": "app", "dependencies": {{"{STUB_DEPENDENCY}": "^1"}}}}"#),
+            )
+            .unwrap();
+        temp_dir
+            .create_file(
+                &format!("node_modules/{STUB_DEPENDENCY}/package.json"),
+                &format!(r#"{{"name": "{STUB_DEPENDENCY}", "version": "1.3.0"}}"#),
+            )
+            .unwrap();
+        temp_dir
+            .create_file(
+                &format!("node_modules/{STUB_DEPENDENCY}/index.js"),
+                "export function leftPad(text) {}\n",
+            )
+            .unwrap();
+    }
+
+    fn get_text(result: &Value) -> &str {
+        result["content"][0]["text"].as_str().unwrap()
+    }
+
+    #[test]
+    fn test_extract_dependency() {
+        let temp_dir = TempDir::new();
+        create_dependant(&temp_dir);
+
+        let result = call_tool(
+            EXTRACT_DEPENDENCY_TOOL,
+            &json!({"name": STUB_DEPENDENCY, "dependant": temp_dir.path, "language": "javascript"}),
+            None,
+        )
+        .unwrap();
+
+        assert_eq!(result["isError"], false);
+        assert!(get_text(&result).contains("export function leftPad(text)"));
+    }
+
+    #[test]
+    fn test_extract_dependency_grouped_by_kind() {
+        let temp_dir = TempDir::new();
+        create_dependant(&temp_dir);
+
+        let result = call_tool(
+            EXTRACT_DEPENDENCY_TOOL,
+            &json!({
+                "name": STUB_DEPENDENCY,
+                "dependant": temp_dir.path,
+                "language": "javascript",
+                "group_by_kind": true,
+            }),
+            None,
+        )
+        .unwrap();
+
+        assert_eq!(result["isError"], false);
+        assert!(get_text(&result).contains("### Functions"));
+    }
+
+    #[test]
+    fn test_extract_missing_dependency() {
+        let temp_dir = TempDir::new();
+        create_dependant(&temp_dir);
+
+        let result = call_tool(
+            EXTRACT_DEPENDENCY_TOOL,
+            &json!({"name": "right-pad", "dependant": temp_dir.path, "language": "javascript"}),
+            None,
+        )
+        .unwrap();
+
+        assert_eq!(result["isError"], true);
+    }
+
+    #[test]
+    fn test_extract_dependency_without_name() {
+        let result = call_tool(
+            EXTRACT_DEPENDENCY_TOOL,
+            &json!({"dependant": "/some/path"}),
+            None,
+        );
+
+        assert_eq!(result.unwrap_err().code, INVALID_PARAMS);
+    }
+
+    #[test]
+    fn test_invalid_language() {
+        let result = call_tool(
+            LIST_DEPENDENCIES_TOOL,
+            &json!({"dependant": "/some/path", "language": "cobol"}),
+            None,
+        );
+
+        assert_eq!(result.unwrap_err().code, INVALID_PARAMS);
+    }
+
+    #[test]
+    fn test_list_dependencies() {
+        let temp_dir = TempDir::new();
+        create_dependant(&temp_dir);
+
+        let result = call_tool(
+            LIST_DEPENDENCIES_TOOL,
+            &json!({"dependant": temp_dir.path}),
+            None,
+        )
+        .unwrap();
+
+        assert_eq!(result["isError"], false);
+        assert_eq!(get_text(&result), STUB_DEPENDENCY);
+    }
+
+    #[test]
+    fn test_unknown_tool() {
+        let result = call_tool("delete_everything", &json!({}), None);
+
+        assert_eq!(result.unwrap_err().code, INVALID_PARAMS);
+    }
+}
@@ -0,0 +1,135 @@
+use crate::error::LibraryError;
+use crate::languages::Language;
+use crate::library::Library;
+use crate::loading_options::LoadingOptions;
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const REGISTRY_SOURCE_PATH: &str = "registry/src";
+
+impl Library {
+    /// Load version `version` of the crate `name` from the local Cargo registry source cache
+    /// (`$CARGO_HOME/registry/src`) with the given `options`, without a dependant project.
+    ///
+    /// # Returns
+    ///
+    /// Returns a Result containing the loaded crate, or an error if that version of the crate
+    /// has not been downloaded.
+    pub fn load_registry_crate(
+        name: &str,
+        version: &str,
+        options: &LoadingOptions,
+    ) -> Result<Self, LibraryError> {
+        let cargo_home = get_cargo_home().ok_or_else(|| {
+            LibraryError::DependencyRetrieval(
+                "Could not determine the Cargo home directory".to_string(),
+            )
+        })?;
+        let path = find_registry_crate(&cargo_home.join(REGISTRY_SOURCE_PATH), name, version)?;
+        Self::load_with_options(&path, Some(Language::Rust), options)
+    }
+}
+
+fn get_cargo_home() -> Option<PathBuf> {
+    env::var_os("CARGO_HOME")
+        .filter(|path| !path.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| env::var_os("HOME").map(|home| PathBuf::from(home).join(".cargo")))
+}
+
+/// Find the sources of version `version` of the crate `name` in any of the registries whose
+/// sources are under `source_directory`.
+fn find_registry_crate(
+    source_directory: &Path,
+    name: &str,
+    version: &str,
+) -> Result<PathBuf, LibraryError> {
+    let crate_directory_name = format!("{name}-{version}");
+    let mut registry_directories = fs::read_dir(source_directory)
+        .into_iter()
+        .flatten()
+        .filter_map(Result::ok)
+        .map(|entry| entry.path())
+        .collect::<Vec<_>>();
+    registry_directories.sort();
+    registry_directories
+        .into_iter()
+        .map(|registry_directory| registry_directory.join(&crate_directory_name))
+        .find(|path| path.is_dir())
+        .ok_or_else(|| {
+            LibraryError::DependencyRetrieval(format!(
+                "{} {} is not in the Cargo registry cache at {}",
+                name,
+                version,
+                source_directory.display()
+            ))
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use assertables::assert_matches;
+    use daipendency_testing::tempdir::TempDir;
+
+    const STUB_REGISTRY: &str = "index.crates.io-1949cf8c6b5b557f";
+
+    #[test]
+    fn downloaded_version() {
+        let temp_dir = TempDir::new();
+        temp_dir
+            .create_file(&format!("{STUB_REGISTRY}/serde-1.0.0/Cargo.toml"), "")
+            .unwrap();
+        temp_dir
+            .create_file(&format!("{STUB_REGISTRY}/serde-1.0.1/Cargo.toml"), "")
+            .unwrap();
+
+        let path = find_registry_crate(&temp_dir.path, "serde", "1.0.1").unwrap();
+
+        assert_eq!(path, temp_dir.path.join(STUB_REGISTRY).join("serde-1.0.1"));
+    }
+
+    #[test]
+    fn several_registries() {
+        let temp_dir = TempDir::new();
+        temp_dir
+            .create_file("github.com-1ecc6299db9ec823/log-0.4.0/Cargo.toml", "")
+            .unwrap();
+        temp_dir
+            .create_file(&format!("{STUB_REGISTRY}/serde-1.0.0/Cargo.toml"), "")
+            .unwrap();
+
+        let path = find_registry_crate(&temp_dir.path, "log", "0.4.0").unwrap();
+
+        assert_eq!(
+            path,
+            temp_dir.path.join("github.com-1ecc6299db9ec823/log-0.4.0")
+        );
+    }
+
+    #[test]
+    fn missing_version() {
+        let temp_dir = TempDir::new();
+        temp_dir
+            .create_file(&format!("{STUB_REGISTRY}/serde-1.0.0/Cargo.toml"), "")
+            .unwrap();
+
+        let result = find_registry_crate(&temp_dir.path, "serde", "2.0.0");
+
+        assert_matches!(
+            result,
+            Err(LibraryError::DependencyRetrieval(message))
+                if message.starts_with("serde 2.0.0 is not in the Cargo registry cache")
+        );
+    }
+
+    #[test]
+    fn missing_registry() {
+        let temp_dir = TempDir::new();
+
+        let result = find_registry_crate(&temp_dir.path.join("registry/src"), "serde", "1.0.0");
+
+        assert_matches!(result, Err(LibraryError::DependencyRetrieval(_)));
+    }
+}
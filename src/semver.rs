@@ -0,0 +1,344 @@
+use crate::diff::ChangeKind;
+use std::fmt;
+
+/// The version bump that a change requires under Semantic Versioning.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum SemverImpact {
+    Patch,
+    Minor,
+    Major,
+}
+
+impl SemverImpact {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Patch => "patch",
+            Self::Minor => "minor",
+            Self::Major => "major",
+        }
+    }
+}
+
+impl fmt::Display for SemverImpact {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+/// Whether the version bump between two versions of a library suits the changes to its API.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SemverVerdict {
+    /// The smallest version bump that the changes require.
+    pub required_bump: SemverImpact,
+    /// The bump between the old and new versions, if both are valid semantic versions.
+    /// Identical versions count as a patch bump.
+    pub actual_bump: Option<SemverImpact>,
+}
+
+impl SemverVerdict {
+    /// Whether the actual version bump is at least the required one, or `None` if the versions
+    /// are unknown.
+    pub fn is_version_bump_sufficient(&self) -> Option<bool> {
+        self.actual_bump
+            .map(|actual_bump| actual_bump >= self.required_bump)
+    }
+}
+
+/// Classify a change to a symbol in a language without specific rules by comparing its
+/// declarations, with comments already stripped.
+///
+/// The declaration is split into a header, up to the line that opens its body (with `{` or `:`),
+/// and the members in that body. Adding members to a type whose header is unchanged is a minor
+/// change, unless implementers must provide them (as in interfaces and abstract classes); any
+/// other change to an existing symbol is breaking.
+pub(crate) fn classify_change(
+    kind: ChangeKind,
+    old_source_code: Option<&str>,
+    new_source_code: Option<&str>,
+) -> SemverImpact {
+    let (Some(old_source_code), Some(new_source_code)) = (old_source_code, new_source_code) else {
+        return match kind {
+            ChangeKind::Added => SemverImpact::Minor,
+            ChangeKind::Removed | ChangeKind::Changed => SemverImpact::Major,
+        };
+    };
+    let (old_header, old_members) = split_declaration(old_source_code);
+    let (new_header, new_members) = split_declaration(new_source_code);
+    if old_header == new_header && old_members == new_members {
+        SemverImpact::Patch
+    } else if old_header != new_header || is_implemented_by_dependants(&old_header) {
+        SemverImpact::Major
+    } else if is_subsequence(&old_members, &new_members) {
+        SemverImpact::Minor
+    } else {
+        SemverImpact::Major
+    }
+}
+
+/// Split a declaration into its header and the lines of its body, ignoring indentation and blank
+/// lines. Declarations without a body are all header.
+fn split_declaration(source_code: &str) -> (Vec<&str>, Vec<&str>) {
+    let mut lines: Vec<&str> = source_code
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .collect();
+    let header_length = lines
+        .iter()
+        .position(|line| line.ends_with('{') || line.ends_with(':'))
+        .map_or(lines.len(), |index| index + 1);
+    let members = lines.split_off(header_length);
+    (lines, members)
+}
+
+fn is_implemented_by_dependants(header: &[&str]) -> bool {
+    header
+        .iter()
+        .flat_map(|line| line.split(|c: char| !c.is_alphanumeric() && c != '_'))
+        .any(|word| word == "interface" || word == "abstract")
+}
+
+/// Whether all the `lines` appear in `other_lines`, in the same order.
+fn is_subsequence(lines: &[&str], other_lines: &[&str]) -> bool {
+    let mut other_lines = other_lines.iter();
+    lines
+        .iter()
+        .all(|line| other_lines.any(|other_line| other_line == line))
+}
+
+/// Get the bump between two semantic versions, following Cargo's convention that the first
+/// non-zero component is the major one (e.g. `0.2.0` to `0.3.0` is a major bump).
+pub(crate) fn get_version_bump(old_version: &str, new_version: &str) -> Option<SemverImpact> {
+    let old_version = parse_version(old_version)?;
+    let new_version = parse_version(new_version)?;
+    let major_index = old_version
+        .iter()
+        .position(|&component| component != 0)
+        .unwrap_or(old_version.len() - 1);
+    let changed_index = old_version
+        .iter()
+        .zip(new_version.iter())
+        .position(|(old, new)| old != new);
+    Some(match changed_index {
+        None => SemverImpact::Patch,
+        Some(index) if index <= major_index => SemverImpact::Major,
+        Some(index) if index == major_index + 1 => SemverImpact::Minor,
+        Some(_) => SemverImpact::Patch,
+    })
+}
+
+/// Parse the major, minor and patch components of `version`, ignoring any pre-release or build
+/// metadata.
+fn parse_version(version: &str) -> Option<[u64; 3]> {
+    let core = version.split(['-', '+']).next()?;
+    let components = core
+        .split('.')
+        .map(|component| component.parse().ok())
+        .collect::<Option<Vec<u64>>>()?;
+    components.try_into().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    mod version_bump {
+        use super::*;
+
+        #[test]
+        fn stable_versions() {
+            assert_eq!(
+                get_version_bump("1.2.3", "2.0.0"),
+                Some(SemverImpact::Major)
+            );
+            assert_eq!(
+                get_version_bump("1.2.3", "1.3.0"),
+                Some(SemverImpact::Minor)
+            );
+            assert_eq!(
+                get_version_bump("1.2.3", "1.2.4"),
+                Some(SemverImpact::Patch)
+            );
+        }
+
+        #[test]
+        fn initial_development_versions() {
+            assert_eq!(
+                get_version_bump("0.2.3", "0.3.0"),
+                Some(SemverImpact::Major)
+            );
+            assert_eq!(
+                get_version_bump("0.2.3", "0.2.4"),
+                Some(SemverImpact::Minor)
+            );
+            assert_eq!(
+                get_version_bump("0.0.3", "0.0.4"),
+                Some(SemverImpact::Major)
+            );
+            assert_eq!(
+                get_version_bump("0.0.0", "0.0.1"),
+                Some(SemverImpact::Major)
+            );
+        }
+
+        #[test]
+        fn identical_versions() {
+            assert_eq!(
+                get_version_bump("1.2.3", "1.2.3"),
+                Some(SemverImpact::Patch)
+            );
+        }
+
+        #[test]
+        fn pre_release_versions() {
+            assert_eq!(
+                get_version_bump("1.2.3", "2.0.0-rc.1"),
+                Some(SemverImpact::Major)
+            );
+        }
+
+        #[test]
+        fn invalid_versions() {
+            assert_eq!(get_version_bump("1.2", "1.3"), None);
+            assert_eq!(get_version_bump("1.2.3", "latest"), None);
+        }
+    }
+
+    mod verdict {
+        use super::*;
+
+        #[test]
+        fn sufficient_bump() {
+            let verdict = SemverVerdict {
+                required_bump: SemverImpact::Minor,
+                actual_bump: Some(SemverImpact::Major),
+            };
+
+            assert_eq!(verdict.is_version_bump_sufficient(), Some(true));
+        }
+
+        #[test]
+        fn insufficient_bump() {
+            let verdict = SemverVerdict {
+                required_bump: SemverImpact::Major,
+                actual_bump: Some(SemverImpact::Minor),
+            };
+
+            assert_eq!(verdict.is_version_bump_sufficient(), Some(false));
+        }
+
+        #[test]
+        fn unknown_bump() {
+            let verdict = SemverVerdict {
+                required_bump: SemverImpact::Major,
+                actual_bump: None,
+            };
+
+            assert_eq!(verdict.is_version_bump_sufficient(), None);
+        }
+    }
+
+    mod generic_classification {
+        use super::*;
+
+        #[test]
+        fn added_symbol() {
+            assert_eq!(
+                classify_change(ChangeKind::Added, None, Some("def f(): ...")),
+                SemverImpact::Minor
+            );
+        }
+
+        #[test]
+        fn removed_symbol() {
+            assert_eq!(
+                classify_change(ChangeKind::Removed, Some("def f(): ..."), None),
+                SemverImpact::Major
+            );
+        }
+
+        #[test]
+        fn changed_signature() {
+            assert_eq!(
+                classify_change(
+                    ChangeKind::Changed,
+                    Some("def f(): ..."),
+                    Some("def f(x): ...")
+                ),
+                SemverImpact::Major
+            );
+        }
+
+        #[test]
+        fn reformatted_declaration() {
+            assert_eq!(
+                classify_change(
+                    ChangeKind::Changed,
+                    Some("public class C {\n    public void f();\n}"),
+                    Some("public class C {\n\n  public void f();\n}")
+                ),
+                SemverImpact::Patch
+            );
+        }
+
+        #[test]
+        fn added_member() {
+            assert_eq!(
+                classify_change(
+                    ChangeKind::Changed,
+                    Some("class C:\n    def f(self): ..."),
+                    Some("class C:\n    def f(self): ...\n    def g(self): ...")
+                ),
+                SemverImpact::Minor
+            );
+        }
+
+        #[test]
+        fn removed_member() {
+            assert_eq!(
+                classify_change(
+                    ChangeKind::Changed,
+                    Some("type S struct {\n    A int\n    B int\n}"),
+                    Some("type S struct {\n    A int\n}")
+                ),
+                SemverImpact::Major
+            );
+        }
+
+        #[test]
+        fn added_interface_member() {
+            assert_eq!(
+                classify_change(
+                    ChangeKind::Changed,
+                    Some("type I interface {\n    A()\n}"),
+                    Some("type I interface {\n    A()\n    B()\n}")
+                ),
+                SemverImpact::Major
+            );
+        }
+
+        #[test]
+        fn added_abstract_class_member() {
+            assert_eq!(
+                classify_change(
+                    ChangeKind::Changed,
+                    Some("public abstract class C {\n    void a();\n}"),
+                    Some("public abstract class C {\n    void a();\n    void b();\n}")
+                ),
+                SemverImpact::Major
+            );
+        }
+
+        #[test]
+        fn changed_header() {
+            assert_eq!(
+                classify_change(
+                    ChangeKind::Changed,
+                    Some("public class C {\n    void a();\n}"),
+                    Some("public final class C {\n    void a();\n    void b();\n}")
+                ),
+                SemverImpact::Major
+            );
+        }
+    }
+}
@@ -0,0 +1,157 @@
+//! Serde definitions for the types re-exported from `daipendency-extractor`.
+//!
+//! [`Namespace`] and [`Symbol`] are foreign types, so they are (de)serialised through
+//! [remote derives](https://serde.rs/remote-derive.html). For example:
+//!
+//! ```
+//! use daipendency::serialisation::NamespaceDef;
+//! use daipendency::Namespace;
+//! use serde::Serialize;
+//!
+//! #[derive(Serialize)]
+//! struct Cached {
+//!     #[serde(with = "NamespaceDef")]
+//!     namespace: Namespace,
+//! }
+//! ```
+
+use daipendency_extractor::{Namespace, Symbol};
+use serde::{Deserialize, Serialize};
+
+/// Remote definition of [`Symbol`].
+#[derive(Serialize, Deserialize)]
+#[serde(remote = "Symbol")]
+pub struct SymbolDef {
+    pub name: String,
+    pub source_code: String,
+}
+
+/// Remote definition of [`Namespace`].
+#[derive(Serialize, Deserialize)]
+#[serde(remote = "Namespace")]
+pub struct NamespaceDef {
+    pub name: String,
+    #[serde(with = "symbols")]
+    pub symbols: Vec<Symbol>,
+    pub doc_comment: Option<String>,
+}
+
+/// Define a module to (de)serialise a `Vec` of a foreign type through its remote definition.
+macro_rules! remote_vec {
+    ($module:ident, $type:ty, $definition:ident) => {
+        #[doc = concat!("(De)serialisation of `Vec<", stringify!($type), ">`, for use with `#[serde(with = \"...\")]`.")]
+        pub mod $module {
+            use super::*;
+            use serde::{Deserializer, Serializer};
+
+            pub fn serialize<S: Serializer>(
+                values: &[$type],
+                serializer: S,
+            ) -> Result<S::Ok, S::Error> {
+                struct Wrapper<'a>(&'a $type);
+
+                impl Serialize for Wrapper<'_> {
+                    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                        $definition::serialize(self.0, serializer)
+                    }
+                }
+
+                serializer.collect_seq(values.iter().map(Wrapper))
+            }
+
+            pub fn deserialize<'de, D: Deserializer<'de>>(
+                deserializer: D,
+            ) -> Result<Vec<$type>, D::Error> {
+                struct Wrapper($type);
+
+                impl<'de> Deserialize<'de> for Wrapper {
+                    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                        $definition::deserialize(deserializer).map(Wrapper)
+                    }
+                }
+
+                let values = Vec::<Wrapper>::deserialize(deserializer)?;
+                Ok(values.into_iter().map(|Wrapper(value)| value).collect())
+            }
+        }
+    };
+}
+
+remote_vec!(symbols, Symbol, SymbolDef);
+remote_vec!(namespaces, Namespace, NamespaceDef);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::languages::Language;
+    use crate::library::Library;
+
+    const STUB_SOURCE_CODE: &str = "pub fn test() {}";
+
+    fn create_library() -> Library {
+        Library {
+            name: "test-lib".to_string(),
+            version: Some("1.0.0".to_string()),
+            documentation: "Test documentation".to_string(),
+            namespaces: vec![Namespace {
+                name: "test".to_string(),
+                symbols: vec![Symbol {
+                    name: "test".to_string(),
+                    source_code: STUB_SOURCE_CODE.to_string(),
+                }],
+                doc_comment: Some("//! Docs".to_string()),
+            }],
+            language: Language::TypeScript,
+            features: None,
+            target: None,
+        }
+    }
+
+    #[test]
+    fn language() {
+        let json = serde_json::to_string(&Language::TypeScript).unwrap();
+
+        assert_eq!(json, "\"typescript\"");
+        assert_eq!(
+            serde_json::from_str::<Language>(&json).unwrap(),
+            Language::TypeScript
+        );
+    }
+
+    #[test]
+    fn library_schema() {
+        let value = serde_json::to_value(create_library()).unwrap();
+
+        assert_eq!(value["name"], "test-lib");
+        assert_eq!(value["language"], "typescript");
+        assert_eq!(value["namespaces"][0]["name"], "test");
+        assert_eq!(value["namespaces"][0]["doc_comment"], "//! Docs");
+        assert_eq!(
+            value["namespaces"][0]["symbols"][0]["source_code"],
+            STUB_SOURCE_CODE
+        );
+    }
+
+    #[test]
+    fn library_round_trip() {
+        let library = create_library();
+
+        let json = serde_json::to_string(&library).unwrap();
+        let deserialised: Library = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(deserialised.name, library.name);
+        assert_eq!(deserialised.version, library.version);
+        assert_eq!(deserialised.documentation, library.documentation);
+        assert_eq!(deserialised.language, library.language);
+        assert_eq!(deserialised.namespaces.len(), 1);
+        assert_eq!(deserialised.namespaces[0].name, "test");
+        assert_eq!(
+            deserialised.namespaces[0].doc_comment,
+            library.namespaces[0].doc_comment
+        );
+        assert_eq!(
+            deserialised.namespaces[0].symbols,
+            library.namespaces[0].symbols
+        );
+    }
+}
@@ -0,0 +1,257 @@
+use crate::languages::LanguageConfig;
+use crate::library::Library;
+use daipendency_extractor::Symbol;
+use std::fmt;
+use std::str::FromStr;
+
+/// The kind of item that a symbol declares.
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, serde::Serialize, serde::Deserialize,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum SymbolKind {
+    Function,
+    Struct,
+    Enum,
+    Trait,
+    TypeAlias,
+    Constant,
+    Macro,
+    Module,
+    Class,
+    Interface,
+}
+
+impl SymbolKind {
+    pub const ALL: [SymbolKind; 10] = [
+        Self::Function,
+        Self::Struct,
+        Self::Enum,
+        Self::Trait,
+        Self::TypeAlias,
+        Self::Constant,
+        Self::Macro,
+        Self::Module,
+        Self::Class,
+        Self::Interface,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Function => "function",
+            Self::Struct => "struct",
+            Self::Enum => "enum",
+            Self::Trait => "trait",
+            Self::TypeAlias => "type_alias",
+            Self::Constant => "constant",
+            Self::Macro => "macro",
+            Self::Module => "module",
+            Self::Class => "class",
+            Self::Interface => "interface",
+        }
+    }
+}
+
+impl fmt::Display for SymbolKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+impl FromStr for SymbolKind {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|kind| kind.as_str() == s)
+            .ok_or_else(|| anyhow::anyhow!("Unknown symbol kind '{}'", s))
+    }
+}
+
+impl Library {
+    /// Get the kind of `symbol`, if it declares a recognised kind of item.
+    pub fn get_symbol_kind(&self, symbol: &Symbol) -> Option<SymbolKind> {
+        let detector = LanguageConfig::get_from_language(self.language).symbol_kind_detector;
+        detector(&symbol.source_code)
+    }
+}
+
+/// Skip the comments and annotations (e.g. `@Deprecated(since = "9")`) before the declaration in
+/// `source_code`, where line comments start with `line_comment`.
+pub fn skip_comments_and_annotations<'a>(source_code: &'a str, line_comment: &str) -> &'a str {
+    let mut rest = source_code.trim_start();
+    loop {
+        if rest.starts_with(line_comment) {
+            rest = rest.split_once('\n').map_or("", |(_, next)| next);
+        } else if let Some(comment) = rest.strip_prefix("/*") {
+            rest = comment.split_once("*/").map_or("", |(_, next)| next);
+        } else if let Some(annotation) = rest.strip_prefix('@') {
+            let name_end = annotation
+                .find(|character: char| {
+                    !(character.is_alphanumeric() || matches!(character, '_' | '.' | ':'))
+                })
+                .unwrap_or(annotation.len());
+            // Java annotation types are declared with `@interface`.
+            if &annotation[..name_end] == "interface" {
+                return rest;
+            }
+            rest = &annotation[name_end..];
+            if rest.starts_with('(') {
+                rest = find_closing_parenthesis(rest).map_or("", |end| &rest[end + 1..]);
+            }
+        } else {
+            return rest;
+        }
+        rest = rest.trim_start();
+    }
+}
+
+/// Find the parenthesis that closes the one at the start of `code`, skipping string literals.
+fn find_closing_parenthesis(code: &str) -> Option<usize> {
+    let mut depth = 0usize;
+    let mut quote = None;
+    let mut characters = code.char_indices();
+    while let Some((index, character)) = characters.next() {
+        match (quote, character) {
+            (Some(_), '\\') => {
+                characters.next();
+            }
+            (Some(open), _) if character == open => quote = None,
+            (Some(_), _) => {}
+            (None, '"' | '\'' | '`') => quote = Some(character),
+            (None, '(') => depth += 1,
+            (None, ')') => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(index);
+                }
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::languages::Language;
+
+    fn create_library(language: Language) -> Library {
+        Library {
+            name: "test-lib".to_string(),
+            version: None,
+            documentation: String::new(),
+            namespaces: vec![],
+            language,
+            features: None,
+            target: None,
+        }
+    }
+
+    fn create_symbol(source_code: &str) -> Symbol {
+        Symbol {
+            name: "test".to_string(),
+            source_code: source_code.to_string(),
+        }
+    }
+
+    #[test]
+    fn from_str() {
+        for kind in SymbolKind::ALL {
+            assert_eq!(kind.as_str().parse::<SymbolKind>().unwrap(), kind);
+        }
+        assert!("record".parse::<SymbolKind>().is_err());
+    }
+
+    #[test]
+    fn supported_language() {
+        let library = create_library(Language::Rust);
+
+        let kind = library.get_symbol_kind(&create_symbol("pub trait Test {}"));
+
+        assert_eq!(kind, Some(SymbolKind::Trait));
+    }
+
+    #[test]
+    fn every_language_detects_kinds() {
+        for (language, source_code) in [
+            (Language::Python, "def test(): ..."),
+            (Language::TypeScript, "export function test(): void;"),
+            (Language::JavaScript, "export function test();"),
+            (Language::Go, "func Test()"),
+            (Language::Java, "fun test()"),
+        ] {
+            let library = create_library(language);
+
+            let kind = library.get_symbol_kind(&create_symbol(source_code));
+
+            assert_eq!(kind, Some(SymbolKind::Function), "{source_code}");
+        }
+    }
+
+    #[test]
+    fn unrecognised_symbol() {
+        let library = create_library(Language::Rust);
+
+        let kind = library.get_symbol_kind(&create_symbol("impl Test {}"));
+
+        assert_eq!(kind, None);
+    }
+
+    mod skipping_comments_and_annotations {
+        use super::*;
+
+        #[test]
+        fn line_comments() {
+            assert_eq!(
+                skip_comments_and_annotations("// Docs\n// More docs\nfunc F()", "//"),
+                "func F()"
+            );
+        }
+
+        #[test]
+        fn block_comments() {
+            assert_eq!(
+                skip_comments_and_annotations("/**\n * Docs\n */\nexport class C {}", "//"),
+                "export class C {}"
+            );
+        }
+
+        #[test]
+        fn annotations_with_arguments() {
+            assert_eq!(
+                skip_comments_and_annotations(
+                    "@Deprecated(since = \"9 (or so)\")\n@Override\npublic void f();",
+                    "//"
+                ),
+                "public void f();"
+            );
+        }
+
+        #[test]
+        fn multi_line_decorators() {
+            assert_eq!(
+                skip_comments_and_annotations(
+                    "# Comment\n@app.route(\n    \"/\",\n)\ndef index(): ...",
+                    "#"
+                ),
+                "def index(): ..."
+            );
+        }
+
+        #[test]
+        fn annotation_type() {
+            assert_eq!(
+                skip_comments_and_annotations("@interface Marker {}", "//"),
+                "@interface Marker {}"
+            );
+        }
+
+        #[test]
+        fn unclosed_annotation() {
+            assert_eq!(skip_comments_and_annotations("@Foo(\"a\"", "//"), "");
+        }
+    }
+}
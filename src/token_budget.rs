@@ -0,0 +1,520 @@
+use crate::languages::LanguageConfig;
+use crate::library::Library;
+use crate::symbol_kind::SymbolKind;
+use daipendency_extractor::{Namespace, Symbol};
+use std::collections::{HashMap, HashSet};
+
+/// A rough average for source code and English prose across common tokenisers.
+const CHARACTERS_PER_TOKEN: usize = 4;
+/// The heading and code fence around the symbols of a namespace.
+const NAMESPACE_OVERHEAD_TOKENS: usize = 8;
+/// The heading and code fence of a section of symbols of the same kind, when grouping by kind.
+const KIND_SECTION_OVERHEAD_TOKENS: usize = 10;
+/// The blank line between symbols.
+const SYMBOL_OVERHEAD_TOKENS: usize = 1;
+/// The documentation can take up to half of the budget, leaving the rest for the API.
+const DOCUMENTATION_BUDGET_DIVISOR: usize = 2;
+const PARAGRAPH_SEPARATOR: &str = "\n\n";
+
+/// Estimate the number of tokens in `text`.
+pub fn estimate_tokens(text: &str) -> usize {
+    text.chars().count().div_ceil(CHARACTERS_PER_TOKEN)
+}
+
+/// The content left out of a library to fit it within a token budget.
+#[derive(Debug, Default, PartialEq)]
+pub struct Omissions {
+    pub is_documentation_shortened: bool,
+    /// Whether the documentation was left out entirely, as not even its first paragraph fit.
+    pub is_documentation_omitted: bool,
+    pub omitted_symbol_count: usize,
+    /// The namespaces none of whose symbols were kept.
+    pub omitted_namespaces: Vec<String>,
+    /// The number of kept namespaces whose doc comment was left out.
+    pub omitted_doc_comment_count: usize,
+}
+
+impl Omissions {
+    pub fn is_empty(&self) -> bool {
+        !self.is_documentation_shortened
+            && !self.is_documentation_omitted
+            && self.omitted_symbol_count == 0
+            && self.omitted_doc_comment_count == 0
+    }
+}
+
+/// Select the content of `library` that fits within `max_tokens`.
+///
+/// The beginning of the documentation comes first, followed by the symbols in the root namespace
+/// and then the symbols most referenced by other symbols. When `group_by_kind` is set, the
+/// section of each kind of symbol in a namespace counts towards the budget too.
+pub fn fit_to_budget(
+    library: &Library,
+    max_tokens: usize,
+    group_by_kind: bool,
+) -> (Library, Omissions) {
+    let get_section = |namespace_index: usize, symbol: &Symbol| {
+        group_by_kind.then(|| (namespace_index, library.get_symbol_kind(symbol)))
+    };
+    let total_tokens = estimate_tokens(&library.documentation)
+        + library
+            .namespaces
+            .iter()
+            .enumerate()
+            .filter(|(_, namespace)| !namespace.symbols.is_empty())
+            .map(|(namespace_index, namespace)| {
+                let sections = namespace
+                    .symbols
+                    .iter()
+                    .filter_map(|symbol| get_section(namespace_index, symbol))
+                    .collect::<HashSet<_>>();
+                estimate_namespace_tokens(namespace)
+                    + namespace.doc_comment.as_deref().map_or(0, estimate_tokens)
+                    + sections.len() * KIND_SECTION_OVERHEAD_TOKENS
+                    + namespace
+                        .symbols
+                        .iter()
+                        .map(estimate_symbol_tokens)
+                        .sum::<usize>()
+            })
+            .sum::<usize>();
+    if total_tokens <= max_tokens {
+        return (
+            copy_library(
+                library,
+                library.documentation.clone(),
+                |_, _| true,
+                |_| true,
+            ),
+            Omissions::default(),
+        );
+    }
+
+    let documentation_budget = max_tokens / DOCUMENTATION_BUDGET_DIVISOR;
+    let (documentation, is_documentation_shortened) =
+        shorten_documentation(&library.documentation, documentation_budget);
+    let is_documentation_omitted = is_documentation_shortened && documentation.is_empty();
+    let mut remaining_tokens = max_tokens.saturating_sub(estimate_tokens(&documentation));
+
+    let total_symbol_count = library
+        .namespaces
+        .iter()
+        .map(|namespace| namespace.symbols.len())
+        .sum::<usize>();
+    let separator = LanguageConfig::get_from_language(library.language).namespace_separator;
+    let mut selected_symbols = HashSet::new();
+    let mut selected_sections: HashSet<(usize, Option<SymbolKind>)> = HashSet::new();
+    // Whether each selected namespace keeps its doc comment, which is dropped if it doesn't fit.
+    let mut selected_namespaces = HashMap::new();
+    for (namespace_index, symbol_index) in prioritise_symbols(library, separator) {
+        let namespace = &library.namespaces[namespace_index];
+        let symbol = &namespace.symbols[symbol_index];
+        let section = get_section(namespace_index, symbol);
+        let section_tokens = match section {
+            Some(section) if !selected_sections.contains(&section) => KIND_SECTION_OVERHEAD_TOKENS,
+            _ => 0,
+        };
+        let symbol_tokens = estimate_symbol_tokens(symbol) + section_tokens;
+        let doc_comment_tokens = namespace.doc_comment.as_deref().map_or(0, estimate_tokens);
+        let (cost, keeps_doc_comment) = match selected_namespaces.get(&namespace_index) {
+            Some(_) => (symbol_tokens, None),
+            None => {
+                let cost = symbol_tokens + estimate_namespace_tokens(namespace);
+                if cost + doc_comment_tokens <= remaining_tokens {
+                    (cost + doc_comment_tokens, Some(true))
+                } else {
+                    (cost, Some(false))
+                }
+            }
+        };
+        if cost <= remaining_tokens {
+            remaining_tokens -= cost;
+            if let Some(keeps_doc_comment) = keeps_doc_comment {
+                selected_namespaces.insert(namespace_index, keeps_doc_comment);
+            }
+            selected_symbols.insert((namespace_index, symbol_index));
+            selected_sections.extend(section);
+        }
+    }
+
+    let omitted_namespaces = library
+        .namespaces
+        .iter()
+        .enumerate()
+        .filter(|(index, namespace)| {
+            !namespace.symbols.is_empty() && !selected_namespaces.contains_key(index)
+        })
+        .map(|(_, namespace)| namespace.name.clone())
+        .collect();
+    let omitted_doc_comment_count = selected_namespaces
+        .iter()
+        .filter(|(index, keeps_doc_comment)| {
+            !**keeps_doc_comment && library.namespaces[**index].doc_comment.is_some()
+        })
+        .count();
+    let library = copy_library(
+        library,
+        documentation,
+        |namespace_index, symbol_index| selected_symbols.contains(&(namespace_index, symbol_index)),
+        |namespace_index| selected_namespaces.get(&namespace_index) != Some(&false),
+    );
+    let omissions = Omissions {
+        is_documentation_shortened: is_documentation_shortened && !is_documentation_omitted,
+        is_documentation_omitted,
+        omitted_symbol_count: total_symbol_count - selected_symbols.len(),
+        omitted_namespaces,
+        omitted_doc_comment_count,
+    };
+    (library, omissions)
+}
+
+/// Copy `library` with the given documentation, keeping the symbols selected by `is_selected`
+/// and dropping the namespaces whose symbols were all left out.
+fn copy_library(
+    library: &Library,
+    documentation: String,
+    is_selected: impl Fn(usize, usize) -> bool,
+    keeps_doc_comment: impl Fn(usize) -> bool,
+) -> Library {
+    let namespaces = library
+        .namespaces
+        .iter()
+        .enumerate()
+        .filter_map(|(namespace_index, namespace)| {
+            let symbols: Vec<Symbol> = namespace
+                .symbols
+                .iter()
+                .enumerate()
+                .filter(|(symbol_index, _)| is_selected(namespace_index, *symbol_index))
+                .map(|(_, symbol)| symbol.clone())
+                .collect();
+            (symbols.len() == namespace.symbols.len() || !symbols.is_empty()).then(|| Namespace {
+                name: namespace.name.clone(),
+                symbols,
+                doc_comment: namespace
+                    .doc_comment
+                    .clone()
+                    .filter(|_| keeps_doc_comment(namespace_index)),
+            })
+        })
+        .collect();
+    Library {
+        name: library.name.clone(),
+        version: library.version.clone(),
+        documentation,
+        namespaces,
+        language: library.language,
+        features: library.features.clone(),
+        target: library.target.clone(),
+    }
+}
+
+/// Keep the whole `documentation` if it fits within `max_tokens`, or else its summary:
+/// the leading paragraphs up to the first section heading that fit, which may be none.
+fn shorten_documentation(documentation: &str, max_tokens: usize) -> (String, bool) {
+    let documentation = documentation.trim();
+    if estimate_tokens(documentation) <= max_tokens {
+        return (documentation.to_string(), false);
+    }
+    let mut paragraphs = Vec::new();
+    let mut tokens = 0;
+    for paragraph in documentation.split(PARAGRAPH_SEPARATOR) {
+        tokens += estimate_tokens(paragraph) + 1;
+        let is_section_heading = !paragraphs.is_empty() && paragraph.starts_with('#');
+        if tokens > max_tokens || is_section_heading {
+            break;
+        }
+        paragraphs.push(paragraph);
+    }
+    (paragraphs.join(PARAGRAPH_SEPARATOR), true)
+}
+
+/// Estimate the tokens in the heading and code fence of a namespace, excluding its doc comment.
+fn estimate_namespace_tokens(namespace: &Namespace) -> usize {
+    NAMESPACE_OVERHEAD_TOKENS + estimate_tokens(&namespace.name)
+}
+
+fn estimate_symbol_tokens(symbol: &Symbol) -> usize {
+    SYMBOL_OVERHEAD_TOKENS + estimate_tokens(&symbol.source_code)
+}
+
+/// Order the `(namespace index, symbol index)` pairs of `library` from most to least important.
+fn prioritise_symbols(library: &Library, separator: &str) -> Vec<(usize, usize)> {
+    let root_namespace_index = find_root_namespace(library, separator);
+    let identifier_counts = count_identifiers(
+        library
+            .namespaces
+            .iter()
+            .flat_map(|namespace| &namespace.symbols),
+    );
+
+    let mut symbols: Vec<(usize, usize, bool, usize)> = library
+        .namespaces
+        .iter()
+        .enumerate()
+        .flat_map(|(namespace_index, namespace)| {
+            let identifier_counts = &identifier_counts;
+            namespace
+                .symbols
+                .iter()
+                .enumerate()
+                .map(move |(symbol_index, symbol)| {
+                    let own_count = count_identifiers([symbol])
+                        .get(symbol.name.as_str())
+                        .copied()
+                        .unwrap_or_default();
+                    let total_count = identifier_counts
+                        .get(symbol.name.as_str())
+                        .copied()
+                        .unwrap_or_default();
+                    (
+                        namespace_index,
+                        symbol_index,
+                        Some(namespace_index) == root_namespace_index,
+                        total_count - own_count,
+                    )
+                })
+        })
+        .collect();
+    symbols.sort_by(|a, b| b.2.cmp(&a.2).then(b.3.cmp(&a.3)));
+    symbols
+        .into_iter()
+        .map(|(namespace_index, symbol_index, _, _)| (namespace_index, symbol_index))
+        .collect()
+}
+
+/// Find the namespace named after the library or, failing that, the least nested one.
+fn find_root_namespace(library: &Library, separator: &str) -> Option<usize> {
+    let library_name = library.name.replace('-', "_");
+    library
+        .namespaces
+        .iter()
+        .position(|namespace| namespace.name.replace('-', "_") == library_name)
+        .or_else(|| {
+            library
+                .namespaces
+                .iter()
+                .enumerate()
+                .min_by_key(|(_, namespace)| namespace.name.matches(separator).count())
+                .map(|(index, _)| index)
+        })
+}
+
+fn count_identifiers<'a>(symbols: impl IntoIterator<Item = &'a Symbol>) -> HashMap<&'a str, usize> {
+    let mut counts = HashMap::new();
+    for symbol in symbols {
+        for identifier in symbol
+            .source_code
+            .split(|c: char| !(c.is_alphanumeric() || c == '_'))
+            .filter(|identifier| !identifier.is_empty())
+        {
+            *counts.entry(identifier).or_default() += 1;
+        }
+    }
+    counts
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::languages::Language;
+
+    const STUB_LIBRARY_NAME: &str = "test_lib";
+
+    fn create_symbol(name: &str, source_code: &str) -> Symbol {
+        Symbol {
+            name: name.to_string(),
+            source_code: source_code.to_string(),
+        }
+    }
+
+    fn create_namespace(name: &str, symbols: Vec<Symbol>) -> Namespace {
+        Namespace {
+            name: name.to_string(),
+            symbols,
+            doc_comment: None,
+        }
+    }
+
+    fn create_library(documentation: &str, namespaces: Vec<Namespace>) -> Library {
+        Library {
+            name: STUB_LIBRARY_NAME.to_string(),
+            version: None,
+            documentation: documentation.to_string(),
+            namespaces,
+            language: Language::Rust,
+            features: None,
+            target: None,
+        }
+    }
+
+    fn get_symbol_names(library: &Library) -> Vec<&str> {
+        library
+            .namespaces
+            .iter()
+            .flat_map(|namespace| &namespace.symbols)
+            .map(|symbol| symbol.name.as_str())
+            .collect()
+    }
+
+    #[test]
+    fn estimate() {
+        assert_eq!(estimate_tokens(""), 0);
+        assert_eq!(estimate_tokens("abcd"), 1);
+        assert_eq!(estimate_tokens("abcde"), 2);
+    }
+
+    #[test]
+    fn within_budget() {
+        let library = create_library(
+            "Docs",
+            vec![create_namespace(
+                STUB_LIBRARY_NAME,
+                vec![create_symbol("a", "pub fn a() {}")],
+            )],
+        );
+
+        let (fitted_library, omissions) = fit_to_budget(&library, 1000, false);
+
+        assert_eq!(fitted_library.documentation, "Docs");
+        assert_eq!(get_symbol_names(&fitted_library), vec!["a"]);
+        assert!(omissions.is_empty());
+    }
+
+    #[test]
+    fn documentation_shortened_by_paragraph() {
+        let first_paragraph = "Summary.";
+        let documentation = format!("{first_paragraph}\n\n{}", "Details. ".repeat(100));
+        let library = create_library(&documentation, vec![]);
+
+        let (fitted_library, omissions) = fit_to_budget(&library, 40, false);
+
+        assert_eq!(fitted_library.documentation, first_paragraph);
+        assert!(omissions.is_documentation_shortened);
+    }
+
+    #[test]
+    fn documentation_omitted_if_first_paragraph_too_long() {
+        let documentation = format!("{}\n\nSummary.", "Details. ".repeat(100));
+        let library = create_library(&documentation, vec![]);
+
+        let (fitted_library, omissions) = fit_to_budget(&library, 40, false);
+
+        assert_eq!(fitted_library.documentation, "");
+        assert!(omissions.is_documentation_omitted);
+        assert!(!omissions.is_documentation_shortened);
+    }
+
+    #[test]
+    fn root_namespace_prioritised() {
+        let library = create_library(
+            "",
+            vec![
+                create_namespace(
+                    "test_lib::other",
+                    vec![create_symbol("other", "pub fn other() {}")],
+                ),
+                create_namespace(
+                    STUB_LIBRARY_NAME,
+                    vec![create_symbol("root", "pub fn root() {}")],
+                ),
+            ],
+        );
+
+        let (fitted_library, omissions) = fit_to_budget(&library, 20, false);
+
+        assert_eq!(get_symbol_names(&fitted_library), vec!["root"]);
+        assert_eq!(omissions.omitted_symbol_count, 1);
+        assert_eq!(omissions.omitted_namespaces, vec!["test_lib::other"]);
+    }
+
+    #[test]
+    fn referenced_symbols_prioritised() {
+        let library = create_library(
+            "",
+            vec![
+                create_namespace(STUB_LIBRARY_NAME, vec![]),
+                create_namespace(
+                    "test_lib::types",
+                    vec![
+                        create_symbol("Unused", "pub struct Unused;"),
+                        create_symbol("Used", "pub struct Used;"),
+                    ],
+                ),
+                create_namespace(
+                    "test_lib::functions",
+                    vec![create_symbol("make", "pub fn make() -> Used {}")],
+                ),
+            ],
+        );
+
+        let (fitted_library, omissions) = fit_to_budget(&library, 20, false);
+
+        assert_eq!(get_symbol_names(&fitted_library), vec!["Used"]);
+        assert_eq!(omissions.omitted_symbol_count, 2);
+    }
+
+    #[test]
+    fn documentation_summary() {
+        let summary = "# Title\n\nSummary.";
+        let documentation = format!("{summary}\n\n## Usage\n\n{}", "Details. ".repeat(100));
+        let library = create_library(&documentation, vec![]);
+
+        let (fitted_library, _) = fit_to_budget(&library, 200, false);
+
+        assert_eq!(fitted_library.documentation, summary);
+    }
+
+    #[test]
+    fn oversized_doc_comment_dropped() {
+        let mut namespace =
+            create_namespace(STUB_LIBRARY_NAME, vec![create_symbol("a", "pub fn a() {}")]);
+        namespace.doc_comment = Some("//! Docs.\n".repeat(100));
+        let library = create_library("", vec![namespace]);
+
+        let (fitted_library, omissions) = fit_to_budget(&library, 30, false);
+
+        assert_eq!(get_symbol_names(&fitted_library), vec!["a"]);
+        assert_eq!(fitted_library.namespaces[0].doc_comment, None);
+        assert_eq!(omissions.omitted_doc_comment_count, 1);
+    }
+
+    #[test]
+    fn kind_sections_counted() {
+        let library = create_library(
+            "",
+            vec![create_namespace(
+                STUB_LIBRARY_NAME,
+                vec![
+                    create_symbol("a", "pub fn a() {}"),
+                    create_symbol("B", "pub struct B;"),
+                ],
+            )],
+        );
+
+        let (ungrouped_library, _) = fit_to_budget(&library, 30, false);
+        let (grouped_library, omissions) = fit_to_budget(&library, 30, true);
+
+        assert_eq!(get_symbol_names(&ungrouped_library), vec!["a", "B"]);
+        assert_eq!(get_symbol_names(&grouped_library), vec!["a"]);
+        assert_eq!(omissions.omitted_symbol_count, 1);
+    }
+
+    #[test]
+    fn original_order_preserved() {
+        let library = create_library(
+            "",
+            vec![create_namespace(
+                STUB_LIBRARY_NAME,
+                vec![
+                    create_symbol("First", "pub struct First;"),
+                    create_symbol("Second", "pub struct Second(First);"),
+                ],
+            )],
+        );
+
+        let (fitted_library, _) = fit_to_budget(&library, 1000, false);
+
+        assert_eq!(get_symbol_names(&fitted_library), vec!["First", "Second"]);
+    }
+}
@@ -0,0 +1,255 @@
+use crate::error::LibraryError;
+use crate::languages::{Language, LanguageConfig, DISCOVERY_ORDER};
+use crate::library::Library;
+use std::path::{Path, PathBuf};
+
+/// A library in a workspace, such as a member crate of a Cargo workspace.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WorkspaceMember {
+    pub name: String,
+    pub path: PathBuf,
+}
+
+impl Library {
+    /// List the members of the workspace whose root is at `path`.
+    ///
+    /// Libraries that are not workspace roots have no members.
+    ///
+    /// # Returns
+    ///
+    /// Returns a Result containing the members sorted by name, or an error if the workspace
+    /// manifest could not be read.
+    pub fn list_workspace_members(
+        path: &Path,
+        language: Option<Language>,
+    ) -> Result<Vec<WorkspaceMember>, LibraryError> {
+        let languages = match language {
+            Some(language) => vec![language],
+            None => DISCOVERY_ORDER
+                .into_iter()
+                .filter(|language| LanguageConfig::get_from_language(*language).has_manifest(path))
+                .collect(),
+        };
+        for language in languages {
+            let Some(lister) = LanguageConfig::get_from_language(language).workspace_member_lister
+            else {
+                continue;
+            };
+            let members =
+                lister(path).map_err(|e| LibraryError::from_metadata_error(e, language))?;
+            if !members.is_empty() {
+                return Ok(members);
+            }
+        }
+        Ok(Vec::new())
+    }
+
+    /// Get the path to the member called `name` of the workspace whose root is at `path`.
+    ///
+    /// # Returns
+    ///
+    /// Returns a Result containing the path to the member, or an error if there is no such member.
+    pub fn get_workspace_member_path(
+        path: &Path,
+        name: &str,
+        language: Option<Language>,
+    ) -> Result<PathBuf, LibraryError> {
+        let members = Self::list_workspace_members(path, language)?;
+        match members.iter().find(|member| member.name == name) {
+            Some(member) => Ok(member.path.clone()),
+            None => Err(LibraryError::UnknownWorkspaceMember {
+                name: name.to_string(),
+                members: get_names(members),
+            }),
+        }
+    }
+}
+
+/// Explain that the manifest at `path` could not be read because it belongs to a workspace root
+/// without a library of its own, if that is the case.
+pub(crate) fn explain_workspace_root(path: &Path, error: LibraryError) -> LibraryError {
+    let LibraryError::MalformedManifest { language, .. } = error else {
+        return error;
+    };
+    match Library::list_workspace_members(path, Some(language)) {
+        Ok(members) if !members.is_empty() => LibraryError::WorkspaceRoot {
+            members: get_names(members),
+        },
+        _ => error,
+    }
+}
+
+fn get_names(members: Vec<WorkspaceMember>) -> Vec<String> {
+    members.into_iter().map(|member| member.name).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use assertables::assert_matches;
+    use daipendency_testing::tempdir::TempDir;
+
+    fn create_workspace() -> TempDir {
+        let temp_dir = TempDir::new();
+        temp_dir
+            .create_file("Cargo.toml", "[workspace]\nmembers = [\"crates/*\"]\n")
+            .unwrap();
+        for name in ["core", "server"] {
+            temp_dir
+                .create_file(
+                    &format!("crates/{name}/Cargo.toml"),
+                    &format!("[package]\nname = \"app-{name}\"\nversion = \"1.0.0\"\n"),
+                )
+                .unwrap();
+            temp_dir
+                .create_file(&format!("crates/{name}/src/lib.rs"), "pub fn run() {}\n")
+                .unwrap();
+        }
+        temp_dir
+    }
+
+    mod members {
+        use super::*;
+
+        #[test]
+        fn discovered_language() {
+            let temp_dir = create_workspace();
+
+            let members = Library::list_workspace_members(&temp_dir.path, None).unwrap();
+
+            assert_eq!(
+                members,
+                vec![
+                    WorkspaceMember {
+                        name: "app-core".to_string(),
+                        path: temp_dir.path.join("crates/core"),
+                    },
+                    WorkspaceMember {
+                        name: "app-server".to_string(),
+                        path: temp_dir.path.join("crates/server"),
+                    },
+                ]
+            );
+        }
+
+        #[test]
+        fn language_without_workspaces() {
+            let temp_dir = create_workspace();
+
+            let members =
+                Library::list_workspace_members(&temp_dir.path, Some(Language::Python)).unwrap();
+
+            assert!(members.is_empty());
+        }
+
+        #[test]
+        fn not_a_library() {
+            let temp_dir = TempDir::new();
+
+            let members = Library::list_workspace_members(&temp_dir.path, None).unwrap();
+
+            assert!(members.is_empty());
+        }
+    }
+
+    mod member_path {
+        use super::*;
+
+        #[test]
+        fn existing_member() {
+            let temp_dir = create_workspace();
+
+            let path =
+                Library::get_workspace_member_path(&temp_dir.path, "app-server", None).unwrap();
+
+            assert_eq!(path, temp_dir.path.join("crates/server"));
+        }
+
+        #[test]
+        fn unknown_member() {
+            let temp_dir = create_workspace();
+
+            let result = Library::get_workspace_member_path(&temp_dir.path, "app-client", None);
+
+            assert_matches!(
+                result,
+                Err(LibraryError::UnknownWorkspaceMember { name, members })
+                    if name == "app-client" && members == vec!["app-core", "app-server"]
+            );
+        }
+    }
+
+    mod workspace_root {
+        use super::*;
+
+        #[test]
+        fn load_virtual_workspace() {
+            let temp_dir = create_workspace();
+
+            let result = Library::load(&temp_dir.path, None);
+
+            assert_matches!(
+                result,
+                Err(LibraryError::WorkspaceRoot { members })
+                    if members == vec!["app-core", "app-server"]
+            );
+        }
+
+        #[test]
+        fn load_virtual_workspace_with_language() {
+            let temp_dir = create_workspace();
+
+            let result = Library::load(&temp_dir.path, Some(Language::Rust));
+
+            assert_matches!(result, Err(LibraryError::WorkspaceRoot { .. }));
+        }
+
+        #[test]
+        fn load_member() {
+            let temp_dir = create_workspace();
+            let path =
+                Library::get_workspace_member_path(&temp_dir.path, "app-core", None).unwrap();
+
+            let library = Library::load(&path, None).unwrap();
+
+            assert_eq!(library.name, "app-core");
+        }
+
+        #[test]
+        fn inherited_dependency() {
+            let temp_dir = create_workspace();
+            temp_dir
+                .create_file(
+                    "Cargo.toml",
+                    "[workspace]\nmembers = [\"crates/*\"]\n\n[workspace.dependencies]\napp-core = { path = \"crates/core\" }\n",
+                )
+                .unwrap();
+            temp_dir
+                .create_file(
+                    "crates/server/Cargo.toml",
+                    "[package]\nname = \"app-server\"\nversion = \"1.0.0\"\n\n[dependencies]\napp-core = { workspace = true }\n",
+                )
+                .unwrap();
+            let dependant_path =
+                Library::get_workspace_member_path(&temp_dir.path, "app-server", None).unwrap();
+
+            let dependency = Library::load_dependency("app-core", &dependant_path, None).unwrap();
+
+            assert_eq!(dependency.name, "app-core");
+            assert_eq!(
+                Library::list_dependencies(&dependant_path, None).unwrap(),
+                vec!["app-core"]
+            );
+        }
+
+        #[test]
+        fn malformed_manifest() {
+            let temp_dir = TempDir::new();
+            temp_dir.create_file("Cargo.toml", "invalid toml").unwrap();
+
+            let result = Library::load(&temp_dir.path, None);
+
+            assert_matches!(result, Err(LibraryError::MalformedManifest { .. }));
+        }
+    }
+}